@@ -42,45 +42,87 @@ complete -c tabor -n "__fish_tabor_needs_command" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_needs_command" -s V -l version -d 'Print version'
 complete -c tabor -n "__fish_tabor_needs_command" -f -a "msg" -d 'Send a message to the Tabor socket'
 complete -c tabor -n "__fish_tabor_needs_command" -f -a "migrate" -d 'Migrate the configuration file'
+complete -c tabor -n "__fish_tabor_needs_command" -f -a "check-config" -d 'Validate the configuration file'
 complete -c tabor -n "__fish_tabor_needs_command" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -s s -l socket -d 'IPC socket connection path override' -r -F
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -s h -l help -d 'Print help'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "config" -d 'Update the Tabor configuration'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "get-config" -d 'Read runtime Tabor configuration'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "ping" -d 'Ping the IPC socket'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "get-capabilities" -d 'List IPC capabilities'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "list-tabs" -d 'List all tabs'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "get-tab-state" -d 'Get a single tab state'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "create-tab" -d 'Create a new tab'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "create-group" -d 'Create a new tab group'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "close-tab" -d 'Close a tab (defaults to active)'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "select-tab" -d 'Select a tab'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "move-tab" -d 'Move a tab within or across groups'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "set-tab-title" -d 'Set or clear a tab title'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "set-group-name" -d 'Set or clear a tab group name'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "restore-closed-tab" -d 'Restore the most recently closed tab'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "open-url" -d 'Open a URL in a tab'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "set-web-url" -d 'Set the URL for a web tab'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "reload-web" -d 'Reload a web tab'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "open-inspector" -d 'Open the Web Inspector for a web tab'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "get-tab-panel" -d 'Get tab panel state'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "set-tab-panel" -d 'Set tab panel state'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "dispatch-action" -d 'Dispatch a configured action'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "send-input" -d 'Send literal input to a tab'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "run-command-bar" -d 'Run a command in the command bar'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "inspector" -d 'Web Inspector commands'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "send" -d 'Send raw JSON IPC message'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "list-requests" -d 'List available IPC request types'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities list-tabs get-tab-state create-tab create-group close-tab select-tab move-tab set-tab-title set-group-name restore-closed-tab open-url set-web-url reload-web open-inspector get-tab-panel set-tab-panel dispatch-action send-input run-command-bar inspector send list-requests help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -s s -l socket -d 'IPC socket connection path override' -r -F
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "config" -d 'Update the Tabor configuration'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "get-config" -d 'Read runtime Tabor configuration'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "ping" -d 'Ping the IPC socket'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "get-capabilities" -d 'List IPC capabilities'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "subscribe" -d 'Print tab events as they happen until interrupted'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "list-tabs" -d 'List all tabs'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "export-session" -d 'Print a shell script recreating the current groups and tabs'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "list-windows" -d 'List all windows with their tabs'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "get-tab-state" -d 'Get a single tab state'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "create-tab" -d 'Create a new tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "create-group" -d 'Create a new tab group'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "create-window" -d 'Open a new window'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "focus-window" -d 'Raise and focus a window'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "get-render-stats" -d 'Get frame draw times of a window'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "duplicate-tab" -d 'Open a copy of a tab in its group (defaults to active)'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "close-tab" -d 'Close a tab (defaults to active)'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "select-tab" -d 'Select a tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "move-tab" -d 'Move a tab within or across groups'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-tab-title" -d 'Set or clear a tab title'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-tab-title-template" -d 'Set or clear the title template of a terminal tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-group-name" -d 'Set or clear a tab group name'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-group-color" -d 'Set or clear a tab group color'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-group-defaults" -d 'Set or clear the defaults of new tabs in a tab group'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "restore-closed-tab" -d 'Restore the most recently closed tabs'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "list-closed-tabs" -d 'List closed tabs which can be restored'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "open-url" -d 'Open a URL in a tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-web-url" -d 'Set the URL for a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "reload-web" -d 'Reload a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "open-external" -d 'Open a web tab\'s URL in the default browser'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "run-bookmarklet" -d 'Run a configured bookmarklet on a web tab\'s page'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-web-zoom" -d 'Set the page zoom for a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-web-muted" -d 'Mute or unmute audio in a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-group-muted" -d 'Mute or unmute audio in every web tab of a group'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "hibernate-group" -d 'Unload every background web tab of a group'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-web-headers" -d 'Override the user agent and add request headers for a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "clear-web-data" -d 'Clear cookies, caches and storage of a web tab\'s site or all sites'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "get-web-history" -d 'List the back-forward history of a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "get-web-scroll" -d 'Print the scroll position of a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-web-scroll" -d 'Scroll a web tab to a position'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "go-to-history-index" -d 'Navigate a web tab to a back-forward history entry'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "snapshot-tab" -d 'Save a PNG snapshot of a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "open-inspector" -d 'Open the Web Inspector for a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-inspector-dock" -d 'Dock the Web Inspector of a web tab to a side, or detach it'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "get-tab-panel" -d 'Get tab panel state'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "set-tab-panel" -d 'Set tab panel state'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "dispatch-action" -d 'Dispatch a configured action'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "send-input" -d 'Send literal input to a tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "get-tab-contents" -d 'Print the text of a terminal tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "get-selection" -d 'Print the selected text of a terminal or web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "wait-tab" -d 'Wait for the command of a terminal tab to exit and print its exit status'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "run-command-bar" -d 'Run a command in the command bar'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "inspector" -d 'Web Inspector commands'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "send" -d 'Send raw JSON IPC message'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "list-requests" -d 'List available IPC request types'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and not __fish_seen_subcommand_from config get-config ping get-capabilities subscribe list-tabs export-session list-windows get-tab-state create-tab create-group create-window focus-window get-render-stats duplicate-tab close-tab select-tab move-tab set-tab-title set-tab-title-template set-group-name set-group-color set-group-defaults restore-closed-tab list-closed-tabs open-url set-web-url reload-web open-external run-bookmarklet set-web-zoom set-web-muted set-group-muted hibernate-group set-web-headers clear-web-data get-web-history get-web-scroll set-web-scroll go-to-history-index snapshot-tab open-inspector set-inspector-dock get-tab-panel set-tab-panel dispatch-action send-input get-tab-contents get-selection wait-tab run-command-bar inspector send list-requests help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from config" -s w -l window-id -d 'Window ID for the new config' -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from config" -s r -l reset -d 'Clear all runtime configuration changes'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from config" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from config" -s h -l help -d 'Print help (see more with \'--help\')'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-config" -s w -l window-id -d 'Window ID for the config request' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-config" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-config" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from ping" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from ping" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-capabilities" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-capabilities" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from subscribe" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from subscribe" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from list-tabs" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from list-tabs" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from export-session" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from export-session" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from list-windows" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from list-windows" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-tab-state" -l tab-id -d 'Tab id formatted as <index>:<generation>' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-tab-state" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-tab-state" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-tab" -l web -d 'Create a web tab with the provided URL' -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-tab" -l group-id -d 'Target group id for the new tab' -r
@@ -89,11 +131,35 @@ complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcomm
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-tab" -s e -l command -d 'Command and args to execute in the default shell (must be last argument)' -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-tab" -s T -l title -d 'Defines the window title [default: Tabor]' -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-tab" -l class -d 'Defines window class/app_id on X11/Wayland [default: Tabor]' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-tab" -l private -d 'Keep cookies and storage of the web tab out of other tabs'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-tab" -l background -d 'Open the tab without focusing it'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-tab" -l hold -d 'Remain open after child process exit'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-tab" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-tab" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-group" -l name -d 'Optional name for the new group' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-group" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-group" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-window" -l web -d 'Open a web window with the provided URL' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-window" -l working-directory -d 'Start the shell in the specified working directory' -r -F
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-window" -s e -l command -d 'Command and args to execute in the default shell (must be last argument)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-window" -s T -l title -d 'Defines the window title [default: Tabor]' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-window" -l class -d 'Defines window class/app_id on X11/Wayland [default: Tabor]' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-window" -s o -l option -d 'Override configuration file options [example: \'cursor.style="Beam"\']' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-window" -l private -d 'Keep cookies and storage of the web window out of other tabs'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-window" -l hold -d 'Remain open after child process exit'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-window" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from create-window" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from focus-window" -s w -l window-id -d 'Window ID of the window to focus' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from focus-window" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from focus-window" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-render-stats" -s w -l window-id -d 'Window ID of the window to inspect' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-render-stats" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-render-stats" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from duplicate-tab" -l tab-id -d 'Tab id formatted as <index>:<generation>' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from duplicate-tab" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from duplicate-tab" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from close-tab" -l tab-id -d 'Tab id formatted as <index>:<generation>' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from close-tab" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from close-tab" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from select-tab" -l index -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from select-tab" -l tab-id -d 'Tab id formatted as <index>:<generation>' -r
@@ -101,33 +167,115 @@ complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcomm
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from select-tab" -l next
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from select-tab" -l previous
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from select-tab" -l last
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from select-tab" -l no-wrap -d 'Stop at the first or last tab instead of cycling around'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from select-tab" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from select-tab" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from move-tab" -l tab-id -d 'Tab id formatted as <index>:<generation>' -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from move-tab" -l target-group-id -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from move-tab" -l target-index -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from move-tab" -l before -d 'Place the tab right before this tab, in its group' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from move-tab" -l after -d 'Place the tab right after this tab, in its group' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from move-tab" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from move-tab" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-title" -l tab-id -d 'Tab id formatted as <index>:<generation>' -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-title" -l title -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-title" -l clear
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-title" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-title" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-title-template" -l tab-id -d 'Tab id formatted as <index>:<generation>' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-title-template" -l template -d 'Template with {title}, {program}, {cwd} and {cwd_basename} placeholders' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-title-template" -l clear
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-title-template" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-title-template" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-name" -l group-id -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-name" -l name -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-name" -l clear
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-name" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-name" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-color" -l group-id -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-color" -l color -d 'Color in `#RRGGBB` or `0xRRGGBB` format' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-color" -l clear
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-color" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-color" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-defaults" -l group-id -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-defaults" -l cwd -d 'Working directory of new terminal tabs, cleared when omitted' -r -f -a "(__fish_complete_directories)"
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-defaults" -l homepage -d 'Page loaded by new blank web tabs, cleared when omitted' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-defaults" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-defaults" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from restore-closed-tab" -s c -l count -d 'Number of tabs to restore, most recently closed first' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from restore-closed-tab" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from restore-closed-tab" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from list-closed-tabs" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from list-closed-tabs" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from open-url" -l tab-id -d 'Target tab id formatted as <index>:<generation>' -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from open-url" -l new-tab
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from open-url" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from open-url" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-url" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-url" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-url" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from reload-web" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from reload-web" -l ignore-cache -d 'Revalidate the page and its resources instead of loading them from the cache'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from reload-web" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from reload-web" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from open-external" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from open-external" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from open-external" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from run-bookmarklet" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from run-bookmarklet" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from run-bookmarklet" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-zoom" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-zoom" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-zoom" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-muted" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-muted" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-muted" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-muted" -l group-id -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-muted" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-group-muted" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from hibernate-group" -l group-id -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from hibernate-group" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from hibernate-group" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-headers" -l user-agent -d 'User agent for the tab, an empty agent restores the default' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-headers" -l header -d 'Header added when loading pages, replacing all previously set headers' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-headers" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-headers" -l clear-headers -d 'Remove all previously set headers'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-headers" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-headers" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from clear-web-data" -l since -d 'Only clear data modified within the last number of seconds' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from clear-web-data" -l tab-id -d 'Only clear data of this tab\'s site, formatted as <index>:<generation>' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from clear-web-data" -l cookies -d 'Clear cookies'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from clear-web-data" -l cache -d 'Clear memory and disk caches'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from clear-web-data" -l storage -d 'Clear local storage, IndexedDB databases and service workers'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from clear-web-data" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from clear-web-data" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-web-history" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-web-history" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-web-history" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-web-scroll" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-web-scroll" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-web-scroll" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-scroll" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-scroll" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-web-scroll" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from go-to-history-index" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from go-to-history-index" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from go-to-history-index" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from snapshot-tab" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from snapshot-tab" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from snapshot-tab" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from open-inspector" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from open-inspector" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from open-inspector" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-inspector-dock" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-inspector-dock" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-inspector-dock" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-tab-panel" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-tab-panel" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-panel" -l width -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-panel" -l enable
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-panel" -l disable
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-panel" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from set-tab-panel" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from dispatch-action" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from dispatch-action" -l action -r
@@ -137,11 +285,30 @@ complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcomm
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from dispatch-action" -l mouse-action -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from dispatch-action" -l esc -r
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from dispatch-action" -l command -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from dispatch-action" -l scroll -d 'Scroll the scrollback by a number of lines, positive values scroll up' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from dispatch-action" -l scroll-to-top -d 'Scroll to the top of the scrollback'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from dispatch-action" -l scroll-to-bottom -d 'Scroll to the bottom of the scrollback'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from dispatch-action" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from dispatch-action" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from send-input" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from send-input" -l escapes -d 'Interpret escapes like `\\n`, `\\e`, `\\x03` and `\\u{...}` in the text'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from send-input" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from send-input" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-tab-contents" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-tab-contents" -l scrollback -d 'Include the scrollback history instead of only the visible lines'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-tab-contents" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-tab-contents" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-selection" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-selection" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from get-selection" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from wait-tab" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from wait-tab" -l timeout -d 'Fail if the command didn\'t exit within this number of milliseconds' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from wait-tab" -l json -d 'Print every reply as a single JSON object'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from wait-tab" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from run-command-bar" -l tab-id -d 'Tab id formatted as <index>:<generation> (defaults to active tab)' -r
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from run-command-bar" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from run-command-bar" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from inspector" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from inspector" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from inspector" -f -a "list-targets" -d 'List Web Inspector targets'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from inspector" -f -a "attach" -d 'Attach to a Web Inspector target'
@@ -149,30 +316,60 @@ complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcomm
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from inspector" -f -a "send" -d 'Send a Web Inspector message'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from inspector" -f -a "poll" -d 'Poll for Web Inspector messages'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from inspector" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from send" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from send" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from list-requests" -l json -d 'Print every reply as a single JSON object'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from list-requests" -s h -l help -d 'Print help'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "config" -d 'Update the Tabor configuration'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "get-config" -d 'Read runtime Tabor configuration'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "ping" -d 'Ping the IPC socket'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "get-capabilities" -d 'List IPC capabilities'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "subscribe" -d 'Print tab events as they happen until interrupted'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "list-tabs" -d 'List all tabs'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "export-session" -d 'Print a shell script recreating the current groups and tabs'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "list-windows" -d 'List all windows with their tabs'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "get-tab-state" -d 'Get a single tab state'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "create-tab" -d 'Create a new tab'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "create-group" -d 'Create a new tab group'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "create-window" -d 'Open a new window'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "focus-window" -d 'Raise and focus a window'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "get-render-stats" -d 'Get frame draw times of a window'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "duplicate-tab" -d 'Open a copy of a tab in its group (defaults to active)'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "close-tab" -d 'Close a tab (defaults to active)'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "select-tab" -d 'Select a tab'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "move-tab" -d 'Move a tab within or across groups'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-tab-title" -d 'Set or clear a tab title'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-tab-title-template" -d 'Set or clear the title template of a terminal tab'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-group-name" -d 'Set or clear a tab group name'
-complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "restore-closed-tab" -d 'Restore the most recently closed tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-group-color" -d 'Set or clear a tab group color'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-group-defaults" -d 'Set or clear the defaults of new tabs in a tab group'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "restore-closed-tab" -d 'Restore the most recently closed tabs'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "list-closed-tabs" -d 'List closed tabs which can be restored'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "open-url" -d 'Open a URL in a tab'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-web-url" -d 'Set the URL for a web tab'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "reload-web" -d 'Reload a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "open-external" -d 'Open a web tab\'s URL in the default browser'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "run-bookmarklet" -d 'Run a configured bookmarklet on a web tab\'s page'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-web-zoom" -d 'Set the page zoom for a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-web-muted" -d 'Mute or unmute audio in a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-group-muted" -d 'Mute or unmute audio in every web tab of a group'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "hibernate-group" -d 'Unload every background web tab of a group'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-web-headers" -d 'Override the user agent and add request headers for a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "clear-web-data" -d 'Clear cookies, caches and storage of a web tab\'s site or all sites'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "get-web-history" -d 'List the back-forward history of a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "get-web-scroll" -d 'Print the scroll position of a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-web-scroll" -d 'Scroll a web tab to a position'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "go-to-history-index" -d 'Navigate a web tab to a back-forward history entry'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "snapshot-tab" -d 'Save a PNG snapshot of a web tab'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "open-inspector" -d 'Open the Web Inspector for a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-inspector-dock" -d 'Dock the Web Inspector of a web tab to a side, or detach it'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "get-tab-panel" -d 'Get tab panel state'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "set-tab-panel" -d 'Set tab panel state'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "dispatch-action" -d 'Dispatch a configured action'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "send-input" -d 'Send literal input to a tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "get-tab-contents" -d 'Print the text of a terminal tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "get-selection" -d 'Print the selected text of a terminal or web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "wait-tab" -d 'Wait for the command of a terminal tab to exit and print its exit status'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "run-command-bar" -d 'Run a command in the command bar'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "inspector" -d 'Web Inspector commands'
 complete -c tabor -n "__fish_tabor_using_subcommand msg; and __fish_seen_subcommand_from help" -f -a "send" -d 'Send raw JSON IPC message'
@@ -184,31 +381,62 @@ complete -c tabor -n "__fish_tabor_using_subcommand migrate" -s i -l skip-import
 complete -c tabor -n "__fish_tabor_using_subcommand migrate" -l skip-renames -d 'Do not move renamed fields to their new location'
 complete -c tabor -n "__fish_tabor_using_subcommand migrate" -s s -l silent -d 'Do not output to STDOUT'
 complete -c tabor -n "__fish_tabor_using_subcommand migrate" -s h -l help -d 'Print help'
-complete -c tabor -n "__fish_tabor_using_subcommand help; and not __fish_seen_subcommand_from msg migrate help" -f -a "msg" -d 'Send a message to the Tabor socket'
-complete -c tabor -n "__fish_tabor_using_subcommand help; and not __fish_seen_subcommand_from msg migrate help" -f -a "migrate" -d 'Migrate the configuration file'
-complete -c tabor -n "__fish_tabor_using_subcommand help; and not __fish_seen_subcommand_from msg migrate help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c tabor -n "__fish_tabor_using_subcommand check-config" -s c -l config-file -d 'Path to the configuration file' -r -F
+complete -c tabor -n "__fish_tabor_using_subcommand check-config" -s h -l help -d 'Print help'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and not __fish_seen_subcommand_from msg migrate check-config help" -f -a "msg" -d 'Send a message to the Tabor socket'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and not __fish_seen_subcommand_from msg migrate check-config help" -f -a "migrate" -d 'Migrate the configuration file'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and not __fish_seen_subcommand_from msg migrate check-config help" -f -a "check-config" -d 'Validate the configuration file'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and not __fish_seen_subcommand_from msg migrate check-config help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "config" -d 'Update the Tabor configuration'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "get-config" -d 'Read runtime Tabor configuration'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "ping" -d 'Ping the IPC socket'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "get-capabilities" -d 'List IPC capabilities'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "subscribe" -d 'Print tab events as they happen until interrupted'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "list-tabs" -d 'List all tabs'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "export-session" -d 'Print a shell script recreating the current groups and tabs'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "list-windows" -d 'List all windows with their tabs'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "get-tab-state" -d 'Get a single tab state'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "create-tab" -d 'Create a new tab'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "create-group" -d 'Create a new tab group'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "create-window" -d 'Open a new window'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "focus-window" -d 'Raise and focus a window'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "get-render-stats" -d 'Get frame draw times of a window'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "duplicate-tab" -d 'Open a copy of a tab in its group (defaults to active)'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "close-tab" -d 'Close a tab (defaults to active)'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "select-tab" -d 'Select a tab'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "move-tab" -d 'Move a tab within or across groups'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-tab-title" -d 'Set or clear a tab title'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-tab-title-template" -d 'Set or clear the title template of a terminal tab'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-group-name" -d 'Set or clear a tab group name'
-complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "restore-closed-tab" -d 'Restore the most recently closed tab'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-group-color" -d 'Set or clear a tab group color'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-group-defaults" -d 'Set or clear the defaults of new tabs in a tab group'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "restore-closed-tab" -d 'Restore the most recently closed tabs'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "list-closed-tabs" -d 'List closed tabs which can be restored'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "open-url" -d 'Open a URL in a tab'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-web-url" -d 'Set the URL for a web tab'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "reload-web" -d 'Reload a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "open-external" -d 'Open a web tab\'s URL in the default browser'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "run-bookmarklet" -d 'Run a configured bookmarklet on a web tab\'s page'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-web-zoom" -d 'Set the page zoom for a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-web-muted" -d 'Mute or unmute audio in a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-group-muted" -d 'Mute or unmute audio in every web tab of a group'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "hibernate-group" -d 'Unload every background web tab of a group'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-web-headers" -d 'Override the user agent and add request headers for a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "clear-web-data" -d 'Clear cookies, caches and storage of a web tab\'s site or all sites'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "get-web-history" -d 'List the back-forward history of a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "get-web-scroll" -d 'Print the scroll position of a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-web-scroll" -d 'Scroll a web tab to a position'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "go-to-history-index" -d 'Navigate a web tab to a back-forward history entry'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "snapshot-tab" -d 'Save a PNG snapshot of a web tab'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "open-inspector" -d 'Open the Web Inspector for a web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-inspector-dock" -d 'Dock the Web Inspector of a web tab to a side, or detach it'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "get-tab-panel" -d 'Get tab panel state'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "set-tab-panel" -d 'Set tab panel state'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "dispatch-action" -d 'Dispatch a configured action'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "send-input" -d 'Send literal input to a tab'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "get-tab-contents" -d 'Print the text of a terminal tab'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "get-selection" -d 'Print the selected text of a terminal or web tab'
+complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "wait-tab" -d 'Wait for the command of a terminal tab to exit and print its exit status'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "run-command-bar" -d 'Run a command in the command bar'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "inspector" -d 'Web Inspector commands'
 complete -c tabor -n "__fish_tabor_using_subcommand help; and __fish_seen_subcommand_from msg" -f -a "send" -d 'Send raw JSON IPC message'
@@ -4,10 +4,10 @@ use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
 use std::rc::Rc;
 
-use tabor_config::SerdeReplace;
 use clap::{ArgAction, ArgGroup, Args, Parser, Subcommand, ValueHint};
 use log::{LevelFilter, error};
 use serde::{Deserialize, Serialize};
+use tabor_config::SerdeReplace;
 use toml::Value;
 
 use tabor_terminal::tty::Options as PtyOptions;
@@ -16,6 +16,8 @@ use tabor_terminal::vi_mode::ViMotion;
 
 use crate::config::UiConfig;
 use crate::config::window::{Class, Identity};
+#[cfg(unix)]
+use crate::display::color::Rgb;
 use crate::logging::LOG_TARGET_IPC_CONFIG;
 use crate::window_kind::WindowKind;
 
@@ -159,15 +161,24 @@ fn parse_tab_id(input: &str) -> Result<TabIdArg, String> {
         .split_once(':')
         .or_else(|| input.split_once(','))
         .ok_or_else(|| String::from("tab id must be <index>:<generation>"))?;
-    let index = index
-        .parse::<u32>()
-        .map_err(|_| String::from("tab id index must be a u32"))?;
-    let generation = generation
-        .parse::<u32>()
-        .map_err(|_| String::from("tab id generation must be a u32"))?;
+    let index = index.parse::<u32>().map_err(|_| String::from("tab id index must be a u32"))?;
+    let generation =
+        generation.parse::<u32>().map_err(|_| String::from("tab id generation must be a u32"))?;
     Ok(TabIdArg { index, generation })
 }
 
+#[cfg(unix)]
+fn parse_header(input: &str) -> Result<(String, String), String> {
+    let (name, value) =
+        input.split_once(':').ok_or_else(|| String::from("header must be <name>:<value>"))?;
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+#[cfg(unix)]
+fn parse_color(input: &str) -> Result<Rgb, String> {
+    input.parse().map_err(|_| String::from("color must be #RRGGBB or 0xRRGGBB"))
+}
+
 #[cfg(unix)]
 fn parse_vi_motion(input: &str) -> Result<ViMotion, String> {
     serde_json::from_str(&format!("\"{input}\"")).map_err(|err| err.to_string())
@@ -264,6 +275,18 @@ impl TerminalOptions {
         Some(parts.join(" "))
     }
 
+    /// Inherit the working directory reported by `lookup` when none was specified.
+    ///
+    /// Without a known directory, `general.working_directory` or the PTY default still apply.
+    pub fn inherit_working_directory<F>(&mut self, lookup: F)
+    where
+        F: FnOnce() -> Option<PathBuf>,
+    {
+        if self.working_directory.is_none() {
+            self.working_directory = lookup().filter(|directory| directory.is_dir());
+        }
+    }
+
     /// Override the [`PtyOptions`]'s fields with the [`TerminalOptions`].
     pub fn override_pty_config(&self, pty_config: &mut PtyOptions) {
         if let Some(working_directory) = &self.working_directory {
@@ -319,8 +342,9 @@ impl WindowIdentity {
 #[derive(Subcommand, Debug)]
 pub enum Subcommands {
     #[cfg(unix)]
-    Msg(MessageOptions),
+    Msg(Box<MessageOptions>),
     Migrate(MigrateOptions),
+    CheckConfig(CheckConfigOptions),
 }
 
 /// Send a message to the Tabor socket.
@@ -331,6 +355,10 @@ pub struct MessageOptions {
     #[clap(short, long, value_hint = ValueHint::FilePath)]
     pub socket: Option<PathBuf>,
 
+    /// Print every reply as a single JSON object.
+    #[clap(long, global = true)]
+    pub json: bool,
+
     /// Message which should be sent.
     #[clap(subcommand)]
     pub message: MessageCommand,
@@ -338,7 +366,7 @@ pub struct MessageOptions {
 
 /// Available socket messages.
 #[cfg(unix)]
-#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+#[derive(Subcommand, Debug, Clone, PartialEq)]
 pub enum MessageCommand {
     /// Update the Tabor configuration.
     Config(IpcConfig),
@@ -352,9 +380,18 @@ pub enum MessageCommand {
     /// List IPC capabilities.
     GetCapabilities,
 
+    /// Print tab events as they happen until interrupted.
+    Subscribe(MsgSubscribe),
+
     /// List all tabs.
     ListTabs,
 
+    /// Print a shell script recreating the current groups and tabs.
+    ExportSession,
+
+    /// List all windows with their tabs.
+    ListWindows,
+
     /// Get a single tab state.
     GetTabState(MsgGetTabState),
 
@@ -364,6 +401,18 @@ pub enum MessageCommand {
     /// Create a new tab group.
     CreateGroup(MsgCreateGroup),
 
+    /// Open a new window.
+    CreateWindow(MsgCreateWindow),
+
+    /// Raise and focus a window.
+    FocusWindow(MsgFocusWindow),
+
+    /// Get frame draw times of a window.
+    GetRenderStats(MsgGetRenderStats),
+
+    /// Open a copy of a tab in its group (defaults to active).
+    DuplicateTab(MsgDuplicateTab),
+
     /// Close a tab (defaults to active).
     CloseTab(MsgCloseTab),
 
@@ -376,11 +425,23 @@ pub enum MessageCommand {
     /// Set or clear a tab title.
     SetTabTitle(MsgSetTabTitle),
 
+    /// Set or clear the title template of a terminal tab.
+    SetTabTitleTemplate(MsgSetTabTitleTemplate),
+
     /// Set or clear a tab group name.
     SetGroupName(MsgSetGroupName),
 
-    /// Restore the most recently closed tab.
-    RestoreClosedTab,
+    /// Set or clear a tab group color.
+    SetGroupColor(MsgSetGroupColor),
+
+    /// Set or clear the defaults of new tabs in a tab group.
+    SetGroupDefaults(MsgSetGroupDefaults),
+
+    /// Restore the most recently closed tabs.
+    RestoreClosedTab(MsgRestoreClosedTab),
+
+    /// List closed tabs which can be restored.
+    ListClosedTabs,
 
     /// Open a URL in a tab.
     OpenUrl(MsgOpenUrl),
@@ -391,9 +452,51 @@ pub enum MessageCommand {
     /// Reload a web tab.
     ReloadWeb(MsgReloadWeb),
 
+    /// Open a web tab's URL in the default browser.
+    OpenExternal(MsgOpenExternal),
+
+    /// Run a configured bookmarklet on a web tab's page.
+    RunBookmarklet(MsgRunBookmarklet),
+
+    /// Set the page zoom for a web tab.
+    SetWebZoom(MsgSetWebZoom),
+
+    /// Mute or unmute audio in a web tab.
+    SetWebMuted(MsgSetWebMuted),
+
+    /// Mute or unmute audio in every web tab of a group.
+    SetGroupMuted(MsgSetGroupMuted),
+
+    /// Unload every background web tab of a group.
+    HibernateGroup(MsgHibernateGroup),
+
+    /// Override the user agent and add request headers for a web tab.
+    SetWebHeaders(MsgSetWebHeaders),
+
+    /// Clear cookies, caches and storage of a web tab's site or all sites.
+    ClearWebData(MsgClearWebData),
+
+    /// List the back-forward history of a web tab.
+    GetWebHistory(MsgGetWebHistory),
+
+    /// Print the scroll position of a web tab.
+    GetWebScroll(MsgGetWebScroll),
+
+    /// Scroll a web tab to a position.
+    SetWebScroll(MsgSetWebScroll),
+
+    /// Navigate a web tab to a back-forward history entry.
+    GoToHistoryIndex(MsgGoToHistoryIndex),
+
+    /// Save a PNG snapshot of a web tab.
+    SnapshotTab(MsgSnapshotTab),
+
     /// Open the Web Inspector for a web tab.
     OpenInspector(MsgOpenInspector),
 
+    /// Dock the Web Inspector of a web tab to a side, or detach it.
+    SetInspectorDock(MsgSetInspectorDock),
+
     /// Get tab panel state.
     GetTabPanel,
 
@@ -406,6 +509,15 @@ pub enum MessageCommand {
     /// Send literal input to a tab.
     SendInput(MsgSendInput),
 
+    /// Print the text of a terminal tab.
+    GetTabContents(MsgGetTabContents),
+
+    /// Print the selected text of a terminal or web tab.
+    GetSelection(MsgGetSelection),
+
+    /// Wait for the command of a terminal tab to exit and print its exit status.
+    WaitTab(MsgWaitTab),
+
     /// Run a command in the command bar.
     RunCommandBar(MsgRunCommandBar),
 
@@ -419,13 +531,21 @@ pub enum MessageCommand {
     Send {
         /// JSON payload to send.
         #[clap(value_name = "JSON")]
-        json: String,
+        message: String,
     },
 
     /// List available IPC request types.
     ListRequests,
 }
 
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgSubscribe {
+    /// Events to print (defaults to all events).
+    #[clap(value_parser = clap::builder::PossibleValuesParser::new(crate::ipc::IPC_EVENT_NAMES))]
+    pub events: Vec<String>,
+}
+
 #[cfg(unix)]
 #[derive(Args, Debug, Clone, PartialEq, Eq)]
 pub struct MsgGetTabState {
@@ -441,6 +561,10 @@ pub struct MsgCreateTab {
     #[clap(long, value_name = "URL")]
     pub web: Option<String>,
 
+    /// Keep cookies and storage of the web tab out of other tabs.
+    #[clap(long, requires = "web")]
+    pub private: bool,
+
     /// Target group id for the new tab.
     #[clap(long, value_name = "GROUP_ID", conflicts_with = "group_name")]
     pub group_id: Option<usize>,
@@ -449,6 +573,10 @@ pub struct MsgCreateTab {
     #[clap(long, value_name = "NAME", conflicts_with = "group_id")]
     pub group_name: Option<String>,
 
+    /// Open the tab without focusing it.
+    #[clap(long)]
+    pub background: bool,
+
     #[clap(flatten)]
     pub terminal_options: TerminalOptions,
 
@@ -456,6 +584,37 @@ pub struct MsgCreateTab {
     pub window_identity: WindowIdentity,
 }
 
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgCreateWindow {
+    /// Open a web window with the provided URL.
+    #[clap(long, value_name = "URL")]
+    pub web: Option<String>,
+
+    /// Keep cookies and storage of the web window out of other tabs.
+    #[clap(long, requires = "web")]
+    pub private: bool,
+
+    #[clap(flatten)]
+    pub window_options: WindowOptions,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgFocusWindow {
+    /// Window ID of the window to focus.
+    #[clap(short, long, env = "TABOR_WINDOW_ID")]
+    pub window_id: u64,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgGetRenderStats {
+    /// Window ID of the window to inspect.
+    #[clap(short, long, env = "TABOR_WINDOW_ID")]
+    pub window_id: u64,
+}
+
 #[cfg(unix)]
 #[derive(Args, Debug, Clone, PartialEq, Eq)]
 pub struct MsgCreateGroup {
@@ -464,6 +623,14 @@ pub struct MsgCreateGroup {
     pub name: Option<String>,
 }
 
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgDuplicateTab {
+    /// Tab id formatted as <index>:<generation>.
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
 #[cfg(unix)]
 #[derive(Args, Debug, Clone, PartialEq, Eq)]
 pub struct MsgCloseTab {
@@ -498,6 +665,10 @@ pub struct MsgSelectTab {
     /// Tab id formatted as <index>:<generation>.
     #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
     pub tab_id: Option<TabIdArg>,
+
+    /// Stop at the first or last tab instead of cycling around.
+    #[clap(long, conflicts_with_all = ["active", "last", "index", "tab_id"])]
+    pub no_wrap: bool,
 }
 
 #[cfg(unix)]
@@ -505,7 +676,7 @@ pub struct MsgSelectTab {
 #[clap(group(
     ArgGroup::new("target")
         .required(true)
-        .args(&["target_group_id", "target_index"])
+        .args(&["target_group_id", "target_index", "before", "after"])
 ))]
 pub struct MsgMoveTab {
     /// Tab id formatted as <index>:<generation>.
@@ -517,6 +688,14 @@ pub struct MsgMoveTab {
 
     #[clap(long, value_name = "INDEX")]
     pub target_index: Option<usize>,
+
+    /// Place the tab right before this tab, in its group.
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub before: Option<TabIdArg>,
+
+    /// Place the tab right after this tab, in its group.
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub after: Option<TabIdArg>,
 }
 
 #[cfg(unix)]
@@ -538,6 +717,26 @@ pub struct MsgSetTabTitle {
     pub clear: bool,
 }
 
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(group(
+    ArgGroup::new("title_template_choice")
+        .required(true)
+        .args(&["template", "clear"])
+))]
+pub struct MsgSetTabTitleTemplate {
+    /// Tab id formatted as <index>:<generation>.
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+
+    /// Template with {title}, {program}, {cwd} and {cwd_basename} placeholders.
+    #[clap(long)]
+    pub template: Option<String>,
+
+    #[clap(long, conflicts_with = "template")]
+    pub clear: bool,
+}
+
 #[cfg(unix)]
 #[derive(Args, Debug, Clone, PartialEq, Eq)]
 #[clap(group(
@@ -556,6 +755,48 @@ pub struct MsgSetGroupName {
     pub clear: bool,
 }
 
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(group(
+    ArgGroup::new("group_color_choice")
+        .required(true)
+        .args(&["color", "clear"])
+))]
+pub struct MsgSetGroupColor {
+    #[clap(long, value_name = "GROUP_ID")]
+    pub group_id: usize,
+
+    /// Color in `#RRGGBB` or `0xRRGGBB` format.
+    #[clap(long, value_parser = parse_color)]
+    pub color: Option<Rgb>,
+
+    #[clap(long, conflicts_with = "color")]
+    pub clear: bool,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgSetGroupDefaults {
+    #[clap(long, value_name = "GROUP_ID")]
+    pub group_id: usize,
+
+    /// Working directory of new terminal tabs, cleared when omitted.
+    #[clap(long, value_hint = ValueHint::DirPath)]
+    pub cwd: Option<PathBuf>,
+
+    /// Page loaded by new blank web tabs, cleared when omitted.
+    #[clap(long, value_name = "URL")]
+    pub homepage: Option<String>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgRestoreClosedTab {
+    /// Number of tabs to restore, most recently closed first.
+    #[clap(short, long, value_name = "COUNT", default_value_t = 1)]
+    pub count: usize,
+}
+
 #[cfg(unix)]
 #[derive(Args, Debug, Clone, PartialEq, Eq)]
 pub struct MsgOpenUrl {
@@ -585,6 +826,172 @@ pub struct MsgReloadWeb {
     /// Tab id formatted as <index>:<generation> (defaults to active tab).
     #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
     pub tab_id: Option<TabIdArg>,
+
+    /// Revalidate the page and its resources instead of loading them from the cache.
+    #[clap(long)]
+    pub ignore_cache: bool,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgOpenExternal {
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgRunBookmarklet {
+    /// Name of the bookmarklet in the `web.bookmarklets` config.
+    pub name: String,
+
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq)]
+pub struct MsgSetWebZoom {
+    /// Zoom factor, clamped to 0.25-5.0 (1.0 is the default size).
+    pub zoom: f64,
+
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgSetWebMuted {
+    /// Whether audio should be muted.
+    #[clap(action = ArgAction::Set)]
+    pub muted: bool,
+
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgSetGroupMuted {
+    /// Whether audio should be muted.
+    #[clap(action = ArgAction::Set)]
+    pub muted: bool,
+
+    #[clap(long, value_name = "GROUP_ID")]
+    pub group_id: usize,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgHibernateGroup {
+    #[clap(long, value_name = "GROUP_ID")]
+    pub group_id: usize,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(group(
+    ArgGroup::new("headers_choice")
+        .required(true)
+        .multiple(true)
+        .args(&["user_agent", "headers", "clear_headers"])
+))]
+pub struct MsgSetWebHeaders {
+    /// User agent for the tab, an empty agent restores the default.
+    #[clap(long, value_name = "AGENT")]
+    pub user_agent: Option<String>,
+
+    /// Header added when loading pages, replacing all previously set headers.
+    #[clap(long = "header", value_parser = parse_header, value_name = "NAME:VALUE")]
+    pub headers: Vec<(String, String)>,
+
+    /// Remove all previously set headers.
+    #[clap(long, conflicts_with = "headers")]
+    pub clear_headers: bool,
+
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgClearWebData {
+    /// Clear cookies.
+    #[clap(long)]
+    pub cookies: bool,
+
+    /// Clear memory and disk caches.
+    #[clap(long)]
+    pub cache: bool,
+
+    /// Clear local storage, IndexedDB databases and service workers.
+    #[clap(long)]
+    pub storage: bool,
+
+    /// Only clear data modified within the last number of seconds.
+    #[clap(long, value_name = "SECONDS", conflicts_with = "tab_id")]
+    pub since: Option<u64>,
+
+    /// Only clear data of this tab's site, formatted as <index>:<generation>.
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgGetWebHistory {
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgGetWebScroll {
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq)]
+pub struct MsgSetWebScroll {
+    /// Horizontal scroll offset in CSS pixels.
+    pub x: f64,
+
+    /// Vertical scroll offset in CSS pixels.
+    pub y: f64,
+
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgGoToHistoryIndex {
+    /// Index into the list returned by get-web-history.
+    pub index: usize,
+
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgSnapshotTab {
+    /// Output path for the PNG file.
+    pub path: PathBuf,
+
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
 }
 
 #[cfg(unix)]
@@ -595,6 +1002,18 @@ pub struct MsgOpenInspector {
     pub tab_id: Option<TabIdArg>,
 }
 
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgSetInspectorDock {
+    /// Side of the page the inspector is docked to.
+    #[clap(value_enum)]
+    pub side: crate::ipc::InspectorDock,
+
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
 #[cfg(unix)]
 #[derive(Args, Debug, Clone, PartialEq, Eq)]
 #[clap(group(
@@ -626,6 +1045,9 @@ pub struct MsgSetTabPanel {
             "mouse_action",
             "esc",
             "command",
+            "scroll",
+            "scroll_to_top",
+            "scroll_to_bottom",
         ])
 ))]
 pub struct MsgDispatchAction {
@@ -653,6 +1075,18 @@ pub struct MsgDispatchAction {
 
     #[clap(long, num_args = 1..)]
     pub command: Option<Vec<String>>,
+
+    /// Scroll the scrollback by a number of lines, positive values scroll up.
+    #[clap(long, value_name = "LINES", allow_hyphen_values = true)]
+    pub scroll: Option<i32>,
+
+    /// Scroll to the top of the scrollback.
+    #[clap(long)]
+    pub scroll_to_top: bool,
+
+    /// Scroll to the bottom of the scrollback.
+    #[clap(long)]
+    pub scroll_to_bottom: bool,
 }
 
 #[cfg(unix)]
@@ -660,11 +1094,47 @@ pub struct MsgDispatchAction {
 pub struct MsgSendInput {
     pub text: String,
 
+    /// Interpret escapes like `\n`, `\e`, `\x03` and `\u{...}` in the text.
+    #[clap(long)]
+    pub escapes: bool,
+
     /// Tab id formatted as <index>:<generation> (defaults to active tab).
     #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
     pub tab_id: Option<TabIdArg>,
 }
 
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgGetTabContents {
+    /// Include the scrollback history instead of only the visible lines.
+    #[clap(long)]
+    pub scrollback: bool,
+
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgGetSelection {
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgWaitTab {
+    /// Tab id formatted as <index>:<generation> (defaults to active tab).
+    #[clap(long, value_parser = parse_tab_id, value_name = "INDEX:GEN")]
+    pub tab_id: Option<TabIdArg>,
+
+    /// Fail if the command didn't exit within this number of milliseconds.
+    #[clap(long, value_name = "MILLISECONDS")]
+    pub timeout: Option<u64>,
+}
+
 #[cfg(unix)]
 #[derive(Args, Debug, Clone, PartialEq, Eq)]
 pub struct MsgRunCommandBar {
@@ -761,6 +1231,14 @@ pub struct MigrateOptions {
     pub silent: bool,
 }
 
+/// Validate the configuration file.
+#[derive(Args, Clone, Debug)]
+pub struct CheckConfigOptions {
+    /// Path to the configuration file.
+    #[clap(short, long, value_hint = ValueHint::FilePath)]
+    pub config_file: Option<PathBuf>,
+}
+
 /// Subset of window options that can be passed via IPC.
 #[derive(Serialize, Deserialize, Args, Default, Clone, Debug, PartialEq, Eq)]
 pub struct WindowOptions {
@@ -934,6 +1412,30 @@ mod tests {
         assert!(config.window.dynamic_title);
     }
 
+    #[test]
+    fn inherit_working_directory() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut options = TerminalOptions::default();
+        options.inherit_working_directory(|| Some(dir.path().to_path_buf()));
+        assert_eq!(options.working_directory.as_deref(), Some(dir.path()));
+
+        // Explicit directories are never replaced.
+        let explicit = PathBuf::from("/explicit");
+        let mut options =
+            TerminalOptions { working_directory: Some(explicit.clone()), ..Default::default() };
+        options.inherit_working_directory(|| Some(dir.path().to_path_buf()));
+        assert_eq!(options.working_directory, Some(explicit));
+
+        let mut options = TerminalOptions::default();
+        options.inherit_working_directory(|| None);
+        assert_eq!(options.working_directory, None);
+
+        let mut options = TerminalOptions::default();
+        options.inherit_working_directory(|| Some(PathBuf::from("/nonexistent/tabor/cwd")));
+        assert_eq!(options.working_directory, None);
+    }
+
     #[test]
     fn valid_option_as_value() {
         // Test with a single field.
@@ -1010,15 +1512,53 @@ mod tests {
     }
 
     #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[cfg(unix)]
+    #[test]
+    fn parse_private_web_tab() {
+        let options = Options::try_parse_from([
+            "tabor",
+            "msg",
+            "create-tab",
+            "--web",
+            "about:blank",
+            "--private",
+        ])
+        .unwrap();
+        let Some(Subcommands::Msg(message)) = options.subcommands else {
+            panic!("expected msg subcommand");
+        };
+        let MessageCommand::CreateTab(tab) = message.message else {
+            panic!("expected create-tab message");
+        };
+        assert!(tab.private);
+
+        assert!(Options::try_parse_from(["tabor", "msg", "create-tab", "--private"]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_create_window() {
+        let args = ["tabor", "msg", "create-window", "--working-directory", "/tmp", "-o", "a=1"];
+        let options = Options::try_parse_from(args).unwrap();
+        let Some(Subcommands::Msg(message)) = options.subcommands else {
+            panic!("expected msg subcommand");
+        };
+        let MessageCommand::CreateWindow(window) = message.message else {
+            panic!("expected create-window message");
+        };
+        assert_eq!(window.web, None);
+        let terminal_options = &window.window_options.terminal_options;
+        assert_eq!(terminal_options.working_directory, Some(PathBuf::from("/tmp")));
+        assert_eq!(window.window_options.config_overrides().config_options.len(), 1);
+    }
+
     #[test]
     fn completions() {
         let mut clap = Options::command();
 
-        for (shell, file) in &[
-            (Shell::Bash, "tabor.bash"),
-            (Shell::Fish, "tabor.fish"),
-            (Shell::Zsh, "_tabor"),
-        ] {
+        for (shell, file) in
+            &[(Shell::Bash, "tabor.bash"), (Shell::Fish, "tabor.fish"), (Shell::Zsh, "_tabor")]
+        {
             if std::env::var("TABOR_GEN_COMPLETIONS").is_ok() {
                 let mut file = File::create(format!("../extra/completions/{file}")).unwrap();
                 clap_complete::generate(*shell, &mut clap, "tabor", &mut file);
@@ -115,6 +115,10 @@ pub enum Action {
     #[config(skip)]
     Mouse(MouseAction),
 
+    /// Scroll the scrollback by a number of lines, positive values scroll up.
+    #[config(skip)]
+    ScrollLines(i32),
+
     /// Paste contents of system clipboard.
     Paste,
 
@@ -217,6 +221,9 @@ pub enum Action {
     /// Select the last tab.
     SelectLastTab,
 
+    /// Move keyboard focus between the tab panel and the active tab.
+    ToggleTabPanelFocus,
+
     /// Create a new Tabor window.
     CreateNewWindow,
 
@@ -602,6 +609,7 @@ pub fn platform_key_bindings() -> Vec<KeyBinding> {
         "7",    ModifiersState::SUPER;                                         Action::SelectTab7;
         "8",    ModifiersState::SUPER;                                         Action::SelectTab8;
         "9",    ModifiersState::SUPER;                                         Action::SelectLastTab;
+        "e",    ModifiersState::SUPER | ModifiersState::SHIFT;                 Action::ToggleTabPanelFocus;
         "0",    ModifiersState::SUPER;                                         Action::ResetFontSize;
         "=",    ModifiersState::SUPER;                                         Action::IncreaseFontSize;
         "+",    ModifiersState::SUPER;                                         Action::IncreaseFontSize;
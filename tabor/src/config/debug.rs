@@ -19,6 +19,9 @@ pub struct Debug {
     /// Highlight damage information produced by tabor.
     pub highlight_damage: bool,
 
+    /// Record frame draw times for the `get_render_stats` IPC request.
+    pub frame_stats: bool,
+
     /// The renderer tabor should be using.
     pub renderer: Option<RendererPreference>,
 
@@ -39,6 +42,7 @@ impl Default for Debug {
             persistent_logging: Default::default(),
             render_timer: Default::default(),
             highlight_damage: Default::default(),
+            frame_stats: Default::default(),
             ref_test: Default::default(),
             renderer: Default::default(),
             prefer_egl: Default::default(),
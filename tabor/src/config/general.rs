@@ -24,9 +24,28 @@ pub struct General {
     /// Live config reload.
     pub live_config_reload: bool,
 
+    /// Save tabs and groups on change and reopen them on launch.
+    pub restore_session: bool,
+
+    /// Number of closed tabs which can be restored.
+    pub closed_tab_history: usize,
+
     /// Offer IPC through a unix socket.
     #[allow(unused)]
     pub ipc_socket: bool,
+
+    /// Token required on every IPC request.
+    #[allow(unused)]
+    #[serde(skip_serializing)]
+    pub ipc_token: Option<String>,
+
+    /// Address of an additional TCP listener for IPC, like `127.0.0.1:7373` or a port.
+    #[allow(unused)]
+    pub ipc_tcp: Option<String>,
+
+    /// Accept TCP listener addresses other than loopback.
+    #[allow(unused)]
+    pub ipc_tcp_allow_remote: bool,
 }
 
 impl Default for General {
@@ -34,6 +53,11 @@ impl Default for General {
         Self {
             live_config_reload: true,
             ipc_socket: true,
+            restore_session: Default::default(),
+            closed_tab_history: 16,
+            ipc_token: Default::default(),
+            ipc_tcp: Default::default(),
+            ipc_tcp_allow_remote: Default::default(),
             working_directory: Default::default(),
             import: Default::default(),
         }
@@ -20,21 +20,23 @@ pub mod monitor;
 pub mod scrolling;
 pub mod selection;
 pub mod serde_utils;
+pub mod tabs;
 pub mod terminal;
 pub mod ui_config;
+pub mod web;
 pub mod window;
 
 mod bindings;
 mod mouse;
 
-use crate::cli::Options;
+use crate::cli::{CheckConfigOptions, Options};
 #[cfg(test)]
 pub use crate::config::bindings::Binding;
 pub use crate::config::bindings::{
     Action, BindingKey, BindingMode, KeyBinding, MouseAction, MouseEvent, SearchAction, ViAction,
 };
 pub use crate::config::ui_config::UiConfig;
-use crate::logging::LOG_TARGET_CONFIG;
+use crate::logging::{ConfigErrorLogger, LOG_TARGET_CONFIG};
 
 /// Maximum number of depth for the configuration file imports.
 pub const IMPORT_RECURSION_LIMIT: usize = 5;
@@ -158,10 +160,64 @@ pub fn reload(config_path: &Path, options: &mut Options) -> Result<UiConfig> {
     Ok(config)
 }
 
+/// Handle configuration validation.
+pub fn check_config(options: CheckConfigOptions) {
+    let config_path = options
+        .config_file
+        .or_else(|| installed_config("toml"))
+        .or_else(|| installed_config("yml"));
+
+    let Some(config_path) = config_path else {
+        eprintln!("No configuration file found");
+        std::process::exit(1);
+    };
+
+    let logger = match ConfigErrorLogger::install() {
+        Ok(logger) => logger,
+        Err(err) => {
+            eprintln!("Unable to collect configuration errors: {err}");
+            std::process::exit(1);
+        },
+    };
+
+    match check(&config_path, logger) {
+        Ok(config_paths) => {
+            for warning in logger.take_warnings() {
+                eprintln!("Warning: {warning}");
+            }
+
+            println!("OK");
+            for path in config_paths {
+                println!("    {}", path.display());
+            }
+        },
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        },
+    }
+}
+
+/// Load a configuration file like during startup, failing on the first error.
+///
+/// Errors which are only logged while loading are read from `logger`. Returns the paths of all
+/// loaded configuration files, starting with `path`.
+fn check(path: &Path, logger: &ConfigErrorLogger) -> StdResult<Vec<PathBuf>, String> {
+    let config = read_config(path).map_err(|err| format!("{}: {err}", path.display()))?;
+
+    match logger.take_errors().into_iter().next() {
+        Some(err) => Err(format!("{}: {err}", path.display())),
+        None => Ok(config.config_paths),
+    }
+}
+
 /// Modifications after the `UiConfig` object is created.
 fn after_loading(config: &mut UiConfig, options: &mut Options) {
     // Override config with CLI options.
     options.override_config(config);
+
+    // An empty token would lock out every client, since they never send one.
+    config.general.ipc_token = config.general.ipc_token.take().filter(|token| !token.is_empty());
 }
 
 /// Load configuration file and log errors.
@@ -219,9 +275,7 @@ pub fn deserialize_config(path: &Path, warn_pruned: bool) -> Result<Value> {
     // Convert YAML to TOML as a transitionary fallback mechanism.
     let extension = path.extension().unwrap_or_default();
     if (extension == "yaml" || extension == "yml") && !contents.trim().is_empty() {
-        warn!(
-            "YAML config {path:?} is deprecated, please migrate to TOML using `tabor migrate`"
-        );
+        warn!("YAML config {path:?} is deprecated, please migrate to TOML using `tabor migrate`");
 
         let mut value: serde_yaml::Value = serde_yaml::from_str(&contents)?;
         prune_yaml_nulls(&mut value, warn_pruned);
@@ -418,6 +472,17 @@ mod tests {
         toml::to_string(&value).unwrap()
     }
 
+    #[test]
+    fn empty_ipc_token_unset() {
+        let mut config: UiConfig = toml::from_str("general.ipc_token = \"\"").unwrap();
+        after_loading(&mut config, &mut Options::default());
+        assert_eq!(config.general.ipc_token, None);
+
+        let mut config: UiConfig = toml::from_str("general.ipc_token = \"secret\"").unwrap();
+        after_loading(&mut config, &mut Options::default());
+        assert_eq!(config.general.ipc_token.as_deref(), Some("secret"));
+    }
+
     #[test]
     fn yaml_with_nulls() {
         let contents = r#"
@@ -442,6 +507,42 @@ window = "Hello""#
         );
     }
 
+    #[test]
+    fn check_malformed_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tabor.toml");
+        fs::write(&path, "[window]\nopacity = 0.5\n[font\n").unwrap();
+
+        let err = check(&path, &ConfigErrorLogger::default()).unwrap_err();
+        assert!(err.starts_with(&path.display().to_string()), "{err}");
+        assert!(err.contains("line 3"), "{err}");
+    }
+
+    #[test]
+    fn check_config_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tabor.toml");
+        let import = dir.path().join("colors.toml");
+        fs::write(&path, "[general]\nimport = [\"colors.toml\"]\n").unwrap();
+        fs::write(&import, "[colors.primary]\nbackground = \"#101010\"\n").unwrap();
+
+        let config_paths = check(&path, &ConfigErrorLogger::default()).unwrap();
+        assert_eq!(config_paths, vec![path.clone(), import]);
+
+        // Errors which are only logged during startup fail the check.
+        let logger = ConfigErrorLogger::default();
+        log::Log::log(
+            &logger,
+            &log::Record::builder()
+                .level(log::Level::Error)
+                .target(LOG_TARGET_CONFIG)
+                .args(format_args!("Config error: font: invalid type"))
+                .build(),
+        );
+        let err = check(&path, &logger).unwrap_err();
+        assert!(err.ends_with("Config error: font: invalid type"), "{err}");
+    }
+
     #[test]
     fn empty_yaml_to_toml() {
         let contents = r#"
@@ -0,0 +1,21 @@
+use serde::Serialize;
+
+use tabor_config_derive::ConfigDeserialize;
+
+#[derive(ConfigDeserialize, Serialize, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TabsConfig {
+    /// Position of new tabs within their group.
+    pub new_tab_position: NewTabPosition,
+}
+
+/// Position of new tabs within their group.
+#[derive(ConfigDeserialize, Serialize, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NewTabPosition {
+    /// Append new tabs to the end of their group.
+    #[default]
+    End,
+    /// Insert new tabs right after the current tab, if it is in the same group.
+    AfterCurrent,
+    /// Insert new tabs at the start of their group.
+    Start,
+}
@@ -32,7 +32,9 @@ use crate::config::general::General;
 use crate::config::mouse::Mouse;
 use crate::config::scrolling::Scrolling;
 use crate::config::selection::Selection;
+use crate::config::tabs::TabsConfig;
 use crate::config::terminal::Terminal;
+use crate::config::web::WebConfig;
 use crate::config::window::WindowConfig;
 
 /// Regex used for the default URL hint.
@@ -86,6 +88,12 @@ pub struct UiConfig {
     /// Config for the tabor_terminal itself.
     pub terminal: Terminal,
 
+    /// Tab configuration.
+    pub tabs: TabsConfig,
+
+    /// Web tab configuration.
+    pub web: WebConfig,
+
     /// Keyboard configuration.
     keyboard: Keyboard,
 
@@ -169,6 +177,49 @@ impl UiConfig {
     }
 }
 
+/// Top-level config sections which differ between two configs.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ChangedSections {
+    pub colors: bool,
+    pub font: bool,
+    pub window: bool,
+
+    /// Keyboard and mouse bindings.
+    pub bindings: bool,
+
+    /// Any option outside of the other sections.
+    pub other: bool,
+}
+
+impl ChangedSections {
+    /// Whether no section has changed.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Compute which sections changed between `old` and `new`.
+///
+/// The paths the configs were loaded from are ignored.
+pub fn config_diff(old: &UiConfig, new: &UiConfig) -> ChangedSections {
+    // Mask all dedicated sections to check the remaining options at once.
+    let mut rest = new.clone();
+    rest.colors = old.colors.clone();
+    rest.font = old.font.clone();
+    rest.window = old.window.clone();
+    rest.keyboard = old.keyboard.clone();
+    rest.mouse.bindings = old.mouse.bindings.clone();
+    rest.config_paths = old.config_paths.clone();
+
+    ChangedSections {
+        colors: old.colors != new.colors,
+        font: old.font != new.font,
+        window: old.window != new.window,
+        bindings: old.keyboard != new.keyboard || old.mouse.bindings != new.mouse.bindings,
+        other: rest != *old,
+    }
+}
+
 /// Keyboard configuration.
 #[derive(ConfigDeserialize, Serialize, Default, Clone, Debug, PartialEq)]
 struct Keyboard {
@@ -684,6 +735,7 @@ mod tests {
 
     use tabor_terminal::term::test::mock_term;
 
+    use crate::display::color::Rgb;
     use crate::display::hint::visible_regex_match_iter;
 
     #[test]
@@ -734,4 +786,29 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn config_diff_only_colors() {
+        let old = UiConfig::default();
+        let mut new = old.clone();
+        new.colors.draw_bold_text_with_bright_colors = true;
+        new.colors.primary.background = Rgb::new(0x12, 0x34, 0x56);
+        new.config_paths.push(PathBuf::from("/tmp/tabor.toml"));
+
+        let changed = config_diff(&old, &new);
+        assert_eq!(changed, ChangedSections { colors: true, ..Default::default() });
+    }
+
+    #[test]
+    fn config_diff_nothing_changed() {
+        let config = UiConfig::default();
+        assert!(config_diff(&config, &config.clone()).is_empty());
+
+        let mut new = config.clone();
+        new.scrolling.multiplier = 7;
+        assert_eq!(config_diff(&config, &new), ChangedSections {
+            other: true,
+            ..Default::default()
+        });
+    }
 }
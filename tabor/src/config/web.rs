@@ -0,0 +1,552 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use tabor_config_derive::ConfigDeserialize;
+
+/// Search engine used when the default search URL isn't configured.
+const DEFAULT_SEARCH_URL: &str = "https://duckduckgo.com/?q={}";
+
+/// Scheme prefixed to the source of bookmarklets.
+const JAVASCRIPT_SCHEME: &str = "javascript:";
+
+/// Distance in points scrolled by web mode's `h`, `j`, `k` and `l`.
+const DEFAULT_SCROLL_STEP: f64 = 48.0;
+
+/// Fraction of the viewport height scrolled by web mode's `d` and `u`.
+const DEFAULT_HALF_PAGE_FRACTION: f64 = 0.5;
+
+/// Milliseconds to wait for the connection to a favicon's host.
+const DEFAULT_FAVICON_CONNECT_TIMEOUT: u32 = 3000;
+
+/// Milliseconds to wait for reading or writing favicon requests.
+const DEFAULT_FAVICON_IO_TIMEOUT: u32 = 5000;
+
+/// Bytes of decoded favicons kept in memory by every window.
+const DEFAULT_FAVICON_MEMORY_BUDGET: usize = 8 * 1024 * 1024;
+
+/// Web tab configuration.
+#[derive(ConfigDeserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct WebConfig {
+    /// Stylesheets and scripts injected into web pages.
+    pub user_scripts: Vec<UserScript>,
+
+    /// Named `javascript:` snippets run on the current page with web mode's `;b`.
+    pub bookmarklets: Vec<Bookmarklet>,
+
+    /// Load pages whose TLS certificate fails validation.
+    pub allow_invalid_certs: bool,
+
+    /// Seconds a background web tab stays idle before its page is unloaded.
+    hibernate_after: u32,
+
+    /// URL searching for text which isn't a URL, with `{}` replaced by the query.
+    pub search_url: String,
+
+    /// Command bar keywords searching with another engine, mapped to their search URL.
+    pub search_engines: HashMap<String, String>,
+
+    /// Distance in points scrolled by a single step of the web mode motions.
+    pub scroll_step: f64,
+
+    /// Fraction of the viewport height scrolled by the web mode half-page motions.
+    pub half_page_fraction: f64,
+
+    /// Animate the web mode half-page motions instead of jumping.
+    pub momentum: bool,
+
+    /// Color scheme pages are rendered with.
+    pub force_color_scheme: ColorScheme,
+
+    /// Network settings for fetching the favicons of web tabs.
+    pub favicon: FaviconConfig,
+
+    /// Web mode keys mapped to the action they run instead of their default command.
+    pub keybindings: HashMap<String, WebKeyAction>,
+}
+
+impl Default for WebConfig {
+    fn default() -> Self {
+        Self {
+            user_scripts: Default::default(),
+            bookmarklets: Default::default(),
+            allow_invalid_certs: Default::default(),
+            hibernate_after: Default::default(),
+            search_url: String::from(DEFAULT_SEARCH_URL),
+            search_engines: Default::default(),
+            scroll_step: DEFAULT_SCROLL_STEP,
+            half_page_fraction: DEFAULT_HALF_PAGE_FRACTION,
+            momentum: false,
+            force_color_scheme: Default::default(),
+            favicon: Default::default(),
+            keybindings: Default::default(),
+        }
+    }
+}
+
+impl WebConfig {
+    /// Idle time after which background web tabs are hibernated.
+    #[inline]
+    pub fn hibernate_after(&self) -> Option<Duration> {
+        match self.hibernate_after {
+            0 => None,
+            seconds => Some(Duration::from_secs(seconds as u64)),
+        }
+    }
+
+    /// Bookmarklet called `name`, falling back to a case-insensitive match.
+    pub fn bookmarklet(&self, name: &str) -> Option<&Bookmarklet> {
+        let name = name.trim();
+        self.bookmarklets
+            .iter()
+            .find(|bookmarklet| bookmarklet.name == name)
+            .or_else(|| self.bookmarklets.iter().find(|b| b.name.eq_ignore_ascii_case(name)))
+    }
+}
+
+/// Web mode command which can be bound to a key.
+#[derive(ConfigDeserialize, Serialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WebKeyAction {
+    ScrollDown,
+    ScrollUp,
+    ScrollLeft,
+    ScrollRight,
+    HalfPageDown,
+    HalfPageUp,
+    ScrollTop,
+    ScrollBottom,
+    ScrollFarLeft,
+    ScrollFarRight,
+    Back,
+    Forward,
+    Reload,
+    HardReload,
+    PreviousPage,
+    NextPage,
+    ParentUrl,
+    RootUrl,
+    FollowHint,
+    FollowHintNewTab,
+    FollowHintBackground,
+    QueueHints,
+    CopyHint,
+    CopyUrl,
+    CopyTitle,
+    CopyMarkdownLink,
+    CopyDomain,
+    CopyVisibleLinks,
+    Find,
+    FindNext,
+    FindPrevious,
+    Visual,
+    VisualLine,
+    Caret,
+    FocusInput,
+    NextInput,
+    PreviousInput,
+    NewTab,
+    CloseTab,
+    RestoreTab,
+    DuplicateTab,
+    PreviousTab,
+    NextTab,
+    FirstTab,
+    LastTab,
+    Open,
+    OpenNewTab,
+    OpenClipboard,
+    OpenClipboardNewTab,
+    HistorySearch,
+    ToggleMute,
+    ViewSource,
+    OpenExternal,
+    RunBookmarklet,
+    SetMark,
+    JumpToMark,
+    Help,
+}
+
+/// Favicon fetching configuration.
+#[derive(ConfigDeserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct FaviconConfig {
+    /// Milliseconds to wait for the connection to the favicon's host.
+    connect_timeout: u32,
+
+    /// Milliseconds to wait for reading the response.
+    read_timeout: u32,
+
+    /// Milliseconds to wait for writing the request.
+    write_timeout: u32,
+
+    /// Proxy URL favicons are fetched through, instead of the proxy environment variables.
+    pub proxy: Option<String>,
+
+    /// Bytes of decoded favicons kept in memory, past which the least recently drawn are dropped.
+    pub memory_budget: usize,
+}
+
+impl Default for FaviconConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: DEFAULT_FAVICON_CONNECT_TIMEOUT,
+            read_timeout: DEFAULT_FAVICON_IO_TIMEOUT,
+            write_timeout: DEFAULT_FAVICON_IO_TIMEOUT,
+            proxy: None,
+            memory_budget: DEFAULT_FAVICON_MEMORY_BUDGET,
+        }
+    }
+}
+
+impl FaviconConfig {
+    #[inline]
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_millis(self.connect_timeout as u64)
+    }
+
+    #[inline]
+    pub fn read_timeout(&self) -> Duration {
+        Duration::from_millis(self.read_timeout as u64)
+    }
+
+    #[inline]
+    pub fn write_timeout(&self) -> Duration {
+        Duration::from_millis(self.write_timeout as u64)
+    }
+
+    /// Proxy for fetching `url`.
+    ///
+    /// Without a configured proxy, `HTTPS_PROXY` is used for `https` URLs and `HTTP_PROXY`
+    /// for everything else, with the lowercase variables as fallback.
+    pub fn proxy_for<F>(&self, url: &str, var: F) -> Option<String>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if let Some(proxy) = self.proxy.as_ref().filter(|proxy| !proxy.is_empty()) {
+            return Some(proxy.clone());
+        }
+
+        let names = if url.get(..8).is_some_and(|scheme| scheme.eq_ignore_ascii_case("https://")) {
+            ["HTTPS_PROXY", "https_proxy"]
+        } else {
+            ["HTTP_PROXY", "http_proxy"]
+        };
+        names.into_iter().filter_map(var).find(|proxy| !proxy.is_empty())
+    }
+}
+
+/// Stylesheet or script injected into matching web pages.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct UserScript {
+    /// Path to the source file, `.css` files are injected as stylesheets.
+    pub path: PathBuf,
+
+    /// URL globs selecting the pages this script is injected into.
+    #[serde(default = "default_matches")]
+    pub matches: Vec<UrlGlob>,
+
+    /// When the script is injected.
+    #[serde(default)]
+    pub run_at: RunAt,
+}
+
+/// Named snippet run on the current page, like a `javascript:` bookmark.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Bookmarklet {
+    /// Name the bookmarklet is picked by in the command bar.
+    pub name: String,
+
+    /// Source of the snippet, usually a `javascript:` URL.
+    pub url: String,
+
+    /// URL globs of the pages this bookmarklet may run on.
+    #[serde(default = "default_matches")]
+    pub matches: Vec<UrlGlob>,
+}
+
+impl Bookmarklet {
+    /// JavaScript source of the snippet, without its `javascript:` prefix.
+    pub fn source(&self) -> &str {
+        let url = self.url.trim_start();
+        match url.get(..JAVASCRIPT_SCHEME.len()) {
+            Some(scheme) if scheme.eq_ignore_ascii_case(JAVASCRIPT_SCHEME) => {
+                &url[JAVASCRIPT_SCHEME.len()..]
+            },
+            _ => url,
+        }
+    }
+
+    /// Check whether the bookmarklet may run on the page at `url`.
+    pub fn allowed_on(&self, url: &str) -> bool {
+        self.matches.iter().any(|glob| glob.matches(url))
+    }
+}
+
+fn default_matches() -> Vec<UrlGlob> {
+    vec![UrlGlob(String::from("*"))]
+}
+
+#[derive(ConfigDeserialize, Serialize, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RunAt {
+    /// Before any page content is parsed.
+    #[default]
+    DocumentStart,
+    /// After the document is parsed, before subresources finish loading.
+    DocumentEnd,
+}
+
+/// Color scheme preferred by web pages.
+#[derive(ConfigDeserialize, Serialize, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorScheme {
+    /// Follow the system appearance.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    /// Name of the scheme in the CSS `color-scheme` property.
+    pub fn css_name(self) -> Option<&'static str> {
+        match self {
+            Self::System => None,
+            Self::Light => Some("light"),
+            Self::Dark => Some("dark"),
+        }
+    }
+
+    /// Document start script declaring the scheme as the only one supported by the page.
+    ///
+    /// This is a fallback for pages which don't follow the appearance of the web view.
+    pub fn injection_source(self) -> Option<String> {
+        let scheme = self.css_name()?;
+        Some(format!(
+            "(() => {{\n\
+             const root = document.documentElement;\n\
+             if (root) root.style.colorScheme = '{scheme}';\n\
+             document.addEventListener('DOMContentLoaded', () => {{\n\
+             let meta = document.querySelector('meta[name=\"color-scheme\"]');\n\
+             if (!meta) {{\n\
+             meta = document.createElement('meta');\n\
+             meta.name = 'color-scheme';\n\
+             (document.head || document.documentElement).appendChild(meta);\n\
+             }}\n\
+             meta.content = '{scheme}';\n\
+             }});\n\
+             }})();"
+        ))
+    }
+}
+
+/// URL pattern where `*` matches any sequence of characters and `?` a single character.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct UrlGlob(String);
+
+impl UrlGlob {
+    /// JavaScript regex source matching the same URLs as the glob.
+    pub fn to_js_regex(&self) -> String {
+        let mut regex = String::from("^");
+        for c in self.0.chars() {
+            match c {
+                '*' => regex.push_str(".*"),
+                '?' => regex.push('.'),
+                '\\' | '^' | '$' | '.' | '|' | '+' | '(' | ')' | '[' | ']' | '{' | '}' | '/' => {
+                    regex.push('\\');
+                    regex.push(c);
+                },
+                _ => regex.push(c),
+            }
+        }
+        regex.push('$');
+        regex
+    }
+
+    /// Check whether the glob matches all of `url`.
+    pub fn matches(&self, url: &str) -> bool {
+        let pattern: Vec<char> = self.0.chars().collect();
+        let text: Vec<char> = url.chars().collect();
+
+        // Position after the last `*` and the text index it is currently matched up to.
+        let mut star = None;
+        let (mut p, mut t) = (0, 0);
+        while t < text.len() {
+            match pattern.get(p) {
+                Some('*') => {
+                    star = Some((p + 1, t));
+                    p += 1;
+                },
+                Some(&c) if c == '?' || c == text[t] => {
+                    p += 1;
+                    t += 1;
+                },
+                _ => match star {
+                    Some((after_star, matched)) => {
+                        star = Some((after_star, matched + 1));
+                        p = after_star;
+                        t = matched + 1;
+                    },
+                    None => return false,
+                },
+            }
+        }
+
+        pattern[p..].iter().all(|&c| c == '*')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_glob_to_js_regex() {
+        let glob = UrlGlob(String::from("*"));
+        assert_eq!(glob.to_js_regex(), "^.*$");
+
+        let glob = UrlGlob(String::from("https://*.example.com/*"));
+        assert_eq!(glob.to_js_regex(), r"^https:\/\/.*\.example\.com\/.*$");
+
+        let glob = UrlGlob(String::from("http?://a.b/?q=(1+2)"));
+        assert_eq!(glob.to_js_regex(), r"^http.:\/\/a\.b\/.q=\(1\+2\)$");
+    }
+
+    #[test]
+    fn url_glob_matches() {
+        assert!(UrlGlob(String::from("*")).matches("https://example.com/"));
+        assert!(UrlGlob(String::from("*")).matches(""));
+
+        let glob = UrlGlob(String::from("https://*.example.com/*"));
+        assert!(glob.matches("https://www.example.com/"));
+        assert!(glob.matches("https://a.b.example.com/path?q=1"));
+        assert!(!glob.matches("https://example.com/"));
+        assert!(!glob.matches("http://www.example.com/"));
+
+        let glob = UrlGlob(String::from("http?://*/a*b"));
+        assert!(glob.matches("https://x/ab"));
+        assert!(glob.matches("https://x/a/b/ab"));
+        assert!(!glob.matches("https://x/a/b/abc"));
+        assert!(!glob.matches("http://x/ab"));
+    }
+
+    #[test]
+    fn bookmarklet_source() {
+        let mut bookmarklet = Bookmarklet {
+            name: String::from("top"),
+            url: String::from("javascript:window.scrollTo(0, 0)"),
+            matches: default_matches(),
+        };
+        assert_eq!(bookmarklet.source(), "window.scrollTo(0, 0)");
+
+        bookmarklet.url = String::from("  JavaScript:(() => alert(1))()");
+        assert_eq!(bookmarklet.source(), "(() => alert(1))()");
+
+        // Plain scripts are run as they are.
+        bookmarklet.url = String::from("alert('javascript:')");
+        assert_eq!(bookmarklet.source(), "alert('javascript:')");
+    }
+
+    #[test]
+    fn bookmarklet_lookup() {
+        let toml = "[[bookmarklets]]\nname = 'Readable'\nurl = 'javascript:read()'\n\
+                    [[bookmarklets]]\nname = 'readable'\nurl = 'javascript:lower()'\n\
+                    [[bookmarklets]]\nname = 'Print'\nurl = 'javascript:print()'\n\
+                    matches = ['https://docs.example.com/*']";
+        let config: WebConfig = toml::from_str(toml).unwrap();
+
+        // Exact names win over case-insensitive matches.
+        assert_eq!(config.bookmarklet("readable").unwrap().source(), "lower()");
+        assert_eq!(config.bookmarklet(" Readable ").unwrap().source(), "read()");
+        assert_eq!(config.bookmarklet("PRINT").unwrap().source(), "print()");
+        assert_eq!(config.bookmarklet("missing"), None);
+
+        let readable = config.bookmarklet("Readable").unwrap();
+        assert!(readable.allowed_on("https://bank.example.com/"));
+        let print = config.bookmarklet("Print").unwrap();
+        assert!(print.allowed_on("https://docs.example.com/guide"));
+        assert!(!print.allowed_on("https://bank.example.com/"));
+    }
+
+    #[test]
+    fn user_script_defaults() {
+        let script: UserScript = toml::from_str("path = '/tmp/dark.css'").unwrap();
+        assert_eq!(script.matches, default_matches());
+        assert_eq!(script.run_at, RunAt::DocumentStart);
+
+        let script: UserScript =
+            toml::from_str("path = '/tmp/a.js'\nmatches = ['https://*']\nrun_at = 'DocumentEnd'")
+                .unwrap();
+        assert_eq!(script.matches, vec![UrlGlob(String::from("https://*"))]);
+        assert_eq!(script.run_at, RunAt::DocumentEnd);
+    }
+
+    #[test]
+    fn force_color_scheme_config() {
+        let config: WebConfig = toml::from_str("").unwrap();
+        assert_eq!(config.force_color_scheme, ColorScheme::System);
+
+        let config: WebConfig = toml::from_str("force_color_scheme = 'Dark'").unwrap();
+        assert_eq!(config.force_color_scheme, ColorScheme::Dark);
+
+        let config: WebConfig = toml::from_str("force_color_scheme = 'light'").unwrap();
+        assert_eq!(config.force_color_scheme, ColorScheme::Light);
+    }
+
+    #[test]
+    fn favicon_config() {
+        let config: WebConfig = toml::from_str("").unwrap();
+        assert_eq!(config.favicon.connect_timeout(), Duration::from_secs(3));
+        assert_eq!(config.favicon.read_timeout(), Duration::from_secs(5));
+        assert_eq!(config.favicon.write_timeout(), Duration::from_secs(5));
+        assert_eq!(config.favicon.proxy, None);
+        assert_eq!(config.favicon.memory_budget, 8 * 1024 * 1024);
+
+        let toml = "[favicon]\nconnect_timeout = 500\nread_timeout = 1500\nwrite_timeout = 2000\n\
+                    proxy = 'http://proxy.corp:3128'\nmemory_budget = 65536";
+        let config: WebConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.favicon.connect_timeout(), Duration::from_millis(500));
+        assert_eq!(config.favicon.read_timeout(), Duration::from_millis(1500));
+        assert_eq!(config.favicon.write_timeout(), Duration::from_secs(2));
+        assert_eq!(config.favicon.memory_budget, 65536);
+
+        // The configured proxy wins over the environment.
+        let env = |_: &str| Some(String::from("http://env.proxy:8080"));
+        let proxy = config.favicon.proxy_for("https://example.com/favicon.ico", env);
+        assert_eq!(proxy.as_deref(), Some("http://proxy.corp:3128"));
+    }
+
+    #[test]
+    fn favicon_proxy_env_fallback() {
+        let config = FaviconConfig::default();
+        let env = |name: &str| match name {
+            "HTTPS_PROXY" => Some(String::from("http://secure:1")),
+            "http_proxy" => Some(String::from("http://plain:2")),
+            "HTTP_PROXY" => Some(String::new()),
+            _ => None,
+        };
+
+        let proxy = config.proxy_for("https://example.com/favicon.ico", env);
+        assert_eq!(proxy.as_deref(), Some("http://secure:1"));
+
+        // Empty variables are skipped in favor of their lowercase variant.
+        let proxy = config.proxy_for("http://example.com/favicon.ico", env);
+        assert_eq!(proxy.as_deref(), Some("http://plain:2"));
+
+        let proxy = config.proxy_for("https://example.com/favicon.ico", |_: &str| None);
+        assert_eq!(proxy, None);
+    }
+
+    #[test]
+    fn color_scheme_injection_source() {
+        assert_eq!(ColorScheme::System.injection_source(), None);
+
+        let light = ColorScheme::Light.injection_source().unwrap();
+        assert!(light.contains("root.style.colorScheme = 'light';"));
+        assert!(light.contains("meta.content = 'light';"));
+
+        let dark = ColorScheme::Dark.injection_source().unwrap();
+        assert!(dark.contains("root.style.colorScheme = 'dark';"));
+        assert!(dark.contains("meta.content = 'dark';"));
+    }
+}
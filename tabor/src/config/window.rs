@@ -12,6 +12,7 @@ use tabor_config_derive::{ConfigDeserialize, SerdeReplace};
 
 use crate::config::LOG_TARGET_CONFIG;
 use crate::config::ui_config::{Delta, Percentage};
+use crate::display::color::Rgb;
 
 /// Default app name, used for window title and class.
 pub const DEFAULT_NAME: &str = "Tabor";
@@ -204,13 +205,40 @@ pub struct Dimensions {
     pub lines: usize,
 }
 
-#[derive(ConfigDeserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[derive(ConfigDeserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct TabPanelConfig {
     /// Whether the tab panel is visible.
     pub enabled: bool,
 
     /// Panel width in logical pixels.
     pub width: usize,
+
+    /// Smallest panel width in logical pixels.
+    pub min_width: usize,
+
+    /// Largest panel width in logical pixels.
+    pub max_width: Option<usize>,
+
+    /// Widths in logical pixels the panel snaps to while it is resized.
+    pub snap_widths: Vec<usize>,
+
+    /// Template for terminal tab titles, like `{program}` or `{cwd_basename}`.
+    pub title_template: Option<String>,
+
+    /// Output activity indicator in front of tab titles.
+    pub activity: TabActivityConfig,
+
+    /// Position and visibility of the close button of tabs.
+    pub close_button: TabCloseButton,
+
+    /// Distance in logical pixels the mouse moves before a pressed tab is dragged.
+    pub drag_threshold: usize,
+
+    /// Pixels added to the height of every row.
+    pub row_padding: f32,
+
+    /// Favicon size in cell widths, which is also the smallest row height.
+    pub icon_scale: f32,
 }
 
 impl Default for TabPanelConfig {
@@ -218,6 +246,73 @@ impl Default for TabPanelConfig {
         Self {
             enabled: true,
             width: 250,
+            min_width: 120,
+            max_width: Default::default(),
+            snap_widths: Default::default(),
+            title_template: Default::default(),
+            activity: Default::default(),
+            close_button: Default::default(),
+            drag_threshold: 4,
+            row_padding: 4.,
+            icon_scale: 2.,
+        }
+    }
+}
+
+impl TabPanelConfig {
+    /// Clamp a panel width in logical pixels to the configured limits.
+    ///
+    /// The minimum width takes precedence over the maximum width.
+    pub fn clamp_width(&self, width: f32) -> f32 {
+        let width = self.max_width.map_or(width, |max_width| width.min(max_width as f32));
+        width.max(self.min_width as f32)
+    }
+}
+
+/// Close button of tabs in the tab panel.
+#[derive(ConfigDeserialize, Serialize, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TabCloseButton {
+    /// Never show a close button, leaving its column to the title.
+    Hidden,
+    /// Show the close button in the last column of hovered tabs.
+    #[default]
+    HoverRight,
+    /// Always show the close button in the last column.
+    AlwaysRight,
+    /// Show the close button in the first column of hovered tabs.
+    HoverLeft,
+}
+
+#[derive(ConfigDeserialize, Serialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TabActivityConfig {
+    /// Whether the activity indicator is shown.
+    pub enabled: bool,
+
+    /// Color of tabs with recent output.
+    pub active: Option<Rgb>,
+
+    /// Color of tabs with output which wasn't seen yet.
+    pub unseen: Option<Rgb>,
+
+    /// Color of idle tabs.
+    pub idle: Option<Rgb>,
+
+    /// Glyph of active and unseen tabs.
+    pub filled: Option<char>,
+
+    /// Glyph of idle tabs.
+    pub outline: Option<char>,
+}
+
+impl Default for TabActivityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            active: Default::default(),
+            unseen: Default::default(),
+            idle: Default::default(),
+            filled: Default::default(),
+            outline: Default::default(),
         }
     }
 }
@@ -166,6 +166,19 @@ pub fn foreground_process_path(
     }
 }
 
+/// Check whether a job other than the shell owns the terminal.
+#[cfg(not(windows))]
+pub fn foreground_process_busy(master_fd: RawFd, shell_pid: u32) -> bool {
+    let pgid = unsafe { libc::tcgetpgrp(master_fd) };
+    process_group_busy(pgid, shell_pid)
+}
+
+/// The shell leads its own process group, so any other foreground group is a running job.
+#[cfg(not(windows))]
+fn process_group_busy(foreground_pgid: pid_t, shell_pid: u32) -> bool {
+    foreground_pgid >= 0 && foreground_pgid as u32 != shell_pid
+}
+
 #[cfg(not(windows))]
 pub fn foreground_process_name(
     master_fd: RawFd,
@@ -219,3 +232,17 @@ pub fn foreground_process_name(
         Ok(name.to_string())
     }
 }
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn busy_when_job_owns_terminal() {
+        assert!(!process_group_busy(4200, 4200));
+        assert!(process_group_busy(4312, 4200));
+
+        // Unknown foreground groups are treated as idle.
+        assert!(!process_group_busy(-1, 4200));
+    }
+}
@@ -18,10 +18,14 @@
 //! println!("Average time: {}", meter.average());
 //! ```
 
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
 const NUM_SAMPLES: usize = 10;
 
+/// Number of frames tracked by [`FrameTimes`].
+const FRAME_SAMPLES: usize = 120;
+
 /// The meter.
 #[derive(Default)]
 pub struct Meter {
@@ -90,3 +94,92 @@ impl Meter {
         self.index = (self.index + 1) % NUM_SAMPLES;
     }
 }
+
+/// Draw time statistics of recent frames.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Draw time of the latest frame.
+    pub last_frame: Duration,
+
+    /// 99th percentile of the recent draw times.
+    pub p99: Duration,
+
+    /// Number of frames the percentile is based on.
+    pub frames: usize,
+}
+
+/// Rolling window of frame draw times.
+#[derive(Default, Debug)]
+pub struct FrameTimes {
+    samples: VecDeque<Duration>,
+}
+
+impl FrameTimes {
+    /// Add the draw time of a frame, dropping the oldest one once the window is full.
+    pub fn add_sample(&mut self, sample: Duration) {
+        if self.samples.len() == FRAME_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn stats(&self) -> FrameStats {
+        let last_frame = self.samples.back().copied().unwrap_or_default();
+
+        // Use the nearest-rank percentile, so it is always one of the recorded samples.
+        let mut sorted: Vec<_> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (sorted.len() * 99).div_ceil(100);
+        let p99 = rank.checked_sub(1).and_then(|index| sorted.get(index)).copied();
+
+        FrameStats { last_frame, p99: p99.unwrap_or_default(), frames: sorted.len() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(samples: impl IntoIterator<Item = u64>) -> FrameTimes {
+        let mut frame_times = FrameTimes::default();
+        for sample in samples {
+            frame_times.add_sample(Duration::from_millis(sample));
+        }
+        frame_times
+    }
+
+    #[test]
+    fn frame_stats_empty() {
+        assert_eq!(FrameTimes::default().stats(), FrameStats::default());
+    }
+
+    #[test]
+    fn frame_stats_percentile() {
+        // A single slow frame among 100 is the 99th percentile.
+        let stats = millis((1..=99).map(|_| 2).chain([40])).stats();
+        assert_eq!(stats.last_frame, Duration::from_millis(40));
+        assert_eq!(stats.p99, Duration::from_millis(2));
+        assert_eq!(stats.frames, 100);
+
+        // Two of them are above it.
+        let stats = millis((1..=98).map(|_| 2).chain([40, 3])).stats();
+        assert_eq!(stats.last_frame, Duration::from_millis(3));
+        assert_eq!(stats.p99, Duration::from_millis(3));
+
+        let stats = millis([5, 1, 9]).stats();
+        assert_eq!(stats.p99, Duration::from_millis(9));
+    }
+
+    #[test]
+    fn frame_stats_rolling_window() {
+        // Slow frames drop out of the statistics once enough new frames were drawn.
+        let mut frame_times = millis([50, 50]);
+        for _ in 0..FRAME_SAMPLES {
+            frame_times.add_sample(Duration::from_millis(4));
+        }
+
+        let stats = frame_times.stats();
+        assert_eq!(stats.frames, FRAME_SAMPLES);
+        assert_eq!(stats.p99, Duration::from_millis(4));
+    }
+}
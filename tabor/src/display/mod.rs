@@ -15,7 +15,7 @@ use glutin::error::ErrorKind;
 use glutin::prelude::*;
 use glutin::surface::{Surface, SwapInterval, WindowSurface};
 
-use log::{debug, info};
+use log::{debug, error, info};
 use parking_lot::MutexGuard;
 use serde::{Deserialize, Serialize};
 use winit::dpi::PhysicalSize;
@@ -48,10 +48,10 @@ use crate::display::content::{RenderableContent, RenderableCursor};
 use crate::display::cursor::IntoRects;
 use crate::display::damage::{DamageTracker, damage_y_to_viewport_y};
 use crate::display::hint::{HintMatch, HintState};
-use crate::display::meter::Meter;
+use crate::display::meter::{FrameTimes, Meter};
 use crate::display::window::Window;
 #[cfg(target_os = "macos")]
-use crate::display::tab_panel::{compute_panel_dimensions, TabPanel};
+use crate::display::tab_panel::{compute_panel_dimensions, PanelWidthLimits, TabPanel};
 use crate::event::{CommandState, Event, EventType, Mouse, SearchState};
 use crate::message_bar::{MessageBuffer, MessageType};
 use crate::renderer::rects::{RenderLine, RenderLines, RenderRect};
@@ -75,7 +75,7 @@ pub mod window;
 #[cfg(target_os = "macos")]
 mod tab_panel;
 #[cfg(target_os = "macos")]
-pub(crate) use tab_panel::{TabPanelEditOutcome, TabPanelEditTarget};
+pub(crate) use tab_panel::{TabPanelEditOutcome, TabPanelEditTarget, DRAG_SCROLL_INTERVAL};
 
 mod bell;
 mod damage;
@@ -292,6 +292,13 @@ impl SizeInfo<f32> {
         self.screen_lines = cmp::max(self.screen_lines.saturating_sub(count), MIN_SCREEN_LINES);
     }
 
+    /// Keep the number of lines and columns of `other`, while using this window size.
+    #[inline]
+    pub fn keep_grid(&mut self, other: &SizeInfo) {
+        self.screen_lines = other.screen_lines;
+        self.columns = other.columns;
+    }
+
     /// Check if coordinates are inside the terminal grid.
     ///
     /// The padding, message bar or search are not counted as part of the grid.
@@ -365,6 +372,33 @@ impl DisplayUpdate {
     }
 }
 
+/// Burst of resize events, holding back terminal and PTY resizes until the debounce timer fired.
+#[derive(Default, Debug)]
+pub struct PendingResize {
+    pending: bool,
+}
+
+impl PendingResize {
+    /// Record a window resize.
+    ///
+    /// Returns `true` for the first resize of a burst, which needs to start the debounce timer.
+    pub fn push(&mut self) -> bool {
+        !mem::replace(&mut self.pending, true)
+    }
+
+    /// Whether the terminal grid is kept at its size until the burst is over.
+    pub fn is_pending(&self) -> bool {
+        self.pending
+    }
+
+    /// End the burst once the debounce timer fired.
+    ///
+    /// Returns `true` if a burst was in progress.
+    pub fn finish(&mut self) -> bool {
+        mem::take(&mut self.pending)
+    }
+}
+
 /// The display wraps a window, font rasterizer, and GPU renderer.
 pub struct Display {
     pub window: Window,
@@ -400,6 +434,9 @@ pub struct Display {
     /// Unprocessed display updates.
     pub pending_update: DisplayUpdate,
 
+    /// Window resizes which aren't applied to the terminal and PTY yet.
+    pub pending_resize: PendingResize,
+
     /// The renderer update that takes place only once before the actual rendering.
     pub pending_renderer_update: Option<RendererUpdate>,
 
@@ -425,8 +462,14 @@ pub struct Display {
 
     context: ManuallyDrop<PossiblyCurrentContext>,
 
+    /// Whether the OpenGL context was lost and still has to be rebuilt.
+    context_lost: bool,
+
     glyph_cache: GlyphCache,
     meter: Meter,
+
+    /// Draw times of recent frames, recorded with `debug.frame_stats`.
+    frame_times: FrameTimes,
 }
 
 impl Display {
@@ -560,7 +603,15 @@ impl Display {
         #[cfg(target_os = "macos")]
         {
             tab_panel.set_enabled(config.window.tab_panel.enabled);
+            tab_panel.set_activity_enabled(config.window.tab_panel.activity.enabled);
+            tab_panel.set_close_button(config.window.tab_panel.close_button);
             tab_panel.set_dimensions(panel_dimensions);
+            let scale_factor = window.scale_factor as f32;
+            tab_panel.set_drag_threshold(&config.window.tab_panel, scale_factor);
+            tab_panel.set_row_metrics(&config.window.tab_panel);
+            tab_panel.set_favicon_budget(config.web.favicon.memory_budget);
+            let limits = PanelWidthLimits::new(&config.window.tab_panel, scale_factor);
+            tab_panel.set_width_limits(limits);
         }
 
         // Disable vsync.
@@ -570,6 +621,7 @@ impl Display {
 
         Ok(Self {
             context: ManuallyDrop::new(context),
+            context_lost: false,
             visual_bell: VisualBell::from(&config.bell),
             renderer: ManuallyDrop::new(renderer),
             renderer_preference: config.debug.renderer,
@@ -592,8 +644,10 @@ impl Display {
             highlighted_hint: Default::default(),
             hint_mouse_point: Default::default(),
             pending_update: Default::default(),
+            pending_resize: Default::default(),
             cursor_hidden: Default::default(),
             meter: Default::default(),
+            frame_times: Default::default(),
             ime: Default::default(),
         })
     }
@@ -609,58 +663,17 @@ impl Display {
         }
     }
 
-    pub fn make_current(&mut self) {
-        let is_current = self.context.is_current();
-
-        // Attempt to make the context current if it's not.
-        let context_loss = if is_current {
-            self.renderer.was_context_reset()
-        } else {
-            match self.context.make_current(&self.surface) {
-                Err(err) if err.error_kind() == ErrorKind::ContextLost => {
-                    info!("Context lost for window {:?}", self.window.id());
-                    true
-                },
-                _ => false,
-            }
-        };
-
-        if !context_loss {
-            return;
-        }
-
-        let gl_display = self.context.display();
-        let gl_config = self.context.config();
-        let raw_window_handle = Some(self.window.raw_window_handle());
-        let context = platform::create_gl_context(&gl_display, &gl_config, raw_window_handle)
-            .expect("failed to recreate context.");
-
-        // Drop the old context and renderer.
-        unsafe {
-            ManuallyDrop::drop(&mut self.renderer);
-            ManuallyDrop::drop(&mut self.context);
-        }
-
-        // Activate new context.
-        let context = context.treat_as_possibly_current();
-        self.context = ManuallyDrop::new(context);
-        self.context.make_current(&self.surface).expect("failed to reativate context after reset.");
-
-        // Recreate renderer.
-        let renderer = Renderer::new(&self.context, self.renderer_preference)
-            .expect("failed to recreate renderer after reset");
-        self.renderer = ManuallyDrop::new(renderer);
-
-        // Resize the renderer.
-        self.renderer.resize(&self.size_info);
-
-        self.reset_glyph_cache();
-        self.damage_tracker.frame().mark_fully_damaged();
-
-        debug!("Recovered window {:?} from gpu reset", self.window.id());
+    /// Make this window's OpenGL context current, rebuilding it if it was lost.
+    ///
+    /// Returns `false` while the context is lost and couldn't be rebuilt, in which case nothing
+    /// can be drawn until the rebuild is retried with the next frame.
+    pub fn make_current(&mut self) -> bool {
+        let lost = self.context_lost;
+        self.context_lost = activate_context(self, lost);
+        !self.context_lost
     }
 
-    fn swap_buffers(&self) {
+    fn swap_buffers(&mut self) {
         #[allow(clippy::single_match)]
         let res = match (self.surface.deref(), &self.context.deref()) {
             #[cfg(not(any(target_os = "macos", windows)))]
@@ -673,8 +686,14 @@ impl Display {
             },
             (surface, context) => surface.swap_buffers(context),
         };
-        if let Err(err) = res {
-            debug!("error calling swap_buffers: {err}");
+        match res {
+            Err(err) if err.error_kind() == ErrorKind::ContextLost => {
+                info!("Context lost for window {:?}", self.window.id());
+                self.context_lost = true;
+                self.window.request_redraw();
+            },
+            Err(err) => debug!("error calling swap_buffers: {err}"),
+            Ok(()) => (),
         }
     }
 
@@ -773,7 +792,15 @@ impl Display {
         #[cfg(target_os = "macos")]
         {
             self.tab_panel.set_enabled(config.window.tab_panel.enabled);
+            self.tab_panel.set_activity_enabled(config.window.tab_panel.activity.enabled);
+            self.tab_panel.set_close_button(config.window.tab_panel.close_button);
             self.tab_panel.set_dimensions(panel_dimensions);
+            let scale_factor = self.window.scale_factor as f32;
+            self.tab_panel.set_drag_threshold(&config.window.tab_panel, scale_factor);
+            self.tab_panel.set_row_metrics(&config.window.tab_panel);
+            self.tab_panel.set_favicon_budget(config.web.favicon.memory_budget);
+            let limits = PanelWidthLimits::new(&config.window.tab_panel, scale_factor);
+            self.tab_panel.set_width_limits(limits);
         }
 
         // Update number of column/lines in the viewport.
@@ -787,9 +814,12 @@ impl Display {
         }
 
         // Resize when terminal when its dimensions have changed.
-        if self.size_info.screen_lines() != new_size.screen_lines
-            || self.size_info.columns() != new_size.columns()
-        {
+        let grid_changed = self.size_info.screen_lines() != new_size.screen_lines
+            || self.size_info.columns() != new_size.columns();
+        if grid_changed && self.pending_resize.is_pending() {
+            // Follow the window immediately, but only reflow the terminal once resizing settled.
+            new_size.keep_grid(&self.size_info);
+        } else if grid_changed {
             // Resize PTY.
             pty_resize_handle.on_resize(new_size.into());
 
@@ -857,6 +887,8 @@ impl Display {
         search_state: &mut SearchState,
         command_state: &CommandState,
     ) {
+        let frame_start = config.debug.frame_stats.then(Instant::now);
+
         // Collect renderable content before the terminal is dropped.
         let mut content = RenderableContent::new(config, self, &terminal, search_state);
         let mut grid_cells = Vec::new();
@@ -912,7 +944,9 @@ impl Display {
         self.damage_tracker.damage_selection(selection_range, display_offset);
 
         // Make sure this window's OpenGL context is active.
-        self.make_current();
+        if !self.make_current() {
+            return;
+        }
 
         self.renderer.clear(background_color, config.window_opacity());
         let mut lines = RenderLines::new();
@@ -981,7 +1015,7 @@ impl Display {
                 &size_info,
                 0,
                 0,
-                self.tab_panel.width().round() as i32,
+                self.tab_panel.damage_width(&size_info).round() as i32,
                 size_info.height() as i32,
             );
         }
@@ -1167,6 +1201,10 @@ impl Display {
             self.renderer.draw_rects(&self.size_info, &metrics, rects);
         }
 
+        if let Some(frame_start) = frame_start {
+            self.frame_times.add_sample(frame_start.elapsed());
+        }
+
         // Clearing debug highlights from the previous frame requires full redraw.
         self.swap_buffers();
 
@@ -1192,7 +1230,10 @@ impl Display {
         message_buffer: &MessageBuffer,
         config: &UiConfig,
         command_state: &CommandState,
+        load_progress: Option<f64>,
+        hint_preview: Option<&str>,
     ) {
+        let frame_start = config.debug.frame_stats.then(Instant::now);
         let size_info = self.size_info;
         let metrics = self.glyph_cache.font_metrics();
         let background_color = config.colors.primary.background;
@@ -1200,7 +1241,9 @@ impl Display {
 
         self.damage_tracker.frame().mark_fully_damaged();
 
-        self.make_current();
+        if !self.make_current() {
+            return;
+        }
         self.renderer.clear(background_color, config.window_opacity());
 
         #[cfg(target_os = "macos")]
@@ -1215,12 +1258,30 @@ impl Display {
                 &size_info,
                 0,
                 0,
-                self.tab_panel.width().round() as i32,
+                self.tab_panel.damage_width(&size_info).round() as i32,
                 size_info.height() as i32,
             );
         }
 
-        let footer_offset = if command_active { self.footer_offset() } else { 0. };
+        // Draw the loading progress along the bottom edge of the page.
+        if let Some(progress) = load_progress {
+            let x = size_info.padding_x();
+            let page_height = size_info.cell_height() * size_info.screen_lines() as f32;
+            let y = size_info.padding_y() + page_height;
+            let page_width = size_info.width() - x - size_info.padding_right();
+            let height = (2. * self.window.scale_factor as f32).round();
+            let color = config.colors.normal.blue;
+            rects.push(RenderRect::new(x, y, page_width * progress as f32, height, color, 1.));
+        }
+
+        let footer_active = command_active || hint_preview.is_some();
+        let footer_offset = if footer_active { self.footer_offset() } else { 0. };
+
+        // Show the link of a unique hint label prefix while no command is typed.
+        if let Some(url) = hint_preview.filter(|_| !command_active) {
+            let preview_text = Self::format_command(url, size_info.columns());
+            self.draw_command_bar(config, &preview_text, footer_offset);
+        }
 
         let ime_position = if command_active {
             let command_text = Self::format_command(command_state.text(), size_info.columns());
@@ -1310,6 +1371,10 @@ impl Display {
             self.renderer.draw_rects(&self.size_info, &metrics, rects);
         }
 
+        if let Some(frame_start) = frame_start {
+            self.frame_times.add_sample(frame_start.elapsed());
+        }
+
         self.swap_buffers();
 
         if matches!(self.raw_window_handle, RawWindowHandle::Xcb(_) | RawWindowHandle::Xlib(_)) {
@@ -1323,13 +1388,23 @@ impl Display {
         self.damage_tracker.swap_damage();
     }
 
+    /// Draw time statistics of recent frames.
+    #[cfg(unix)]
+    pub fn frame_stats(&self) -> meter::FrameStats {
+        self.frame_times.stats()
+    }
+
     /// Update to a new configuration.
     pub fn update_config(&mut self, config: &UiConfig) {
         self.damage_tracker.debug = config.debug.highlight_damage;
         self.visual_bell.update_config(&config.bell);
         self.colors = List::from(&config.colors);
         #[cfg(target_os = "macos")]
-        self.tab_panel.set_enabled(config.window.tab_panel.enabled);
+        {
+            self.tab_panel.set_enabled(config.window.tab_panel.enabled);
+            self.tab_panel.set_activity_enabled(config.window.tab_panel.activity.enabled);
+            self.tab_panel.set_close_button(config.window.tab_panel.close_button);
+        }
     }
 
     #[cfg(target_os = "macos")]
@@ -1863,6 +1938,94 @@ impl Display {
     }
 }
 
+/// OpenGL state of a display, which is rebuilt when its context is lost.
+trait GpuContext {
+    /// Make the context current, returning whether it was lost.
+    fn activate(&mut self) -> bool;
+
+    /// Replace the context and renderer with new ones.
+    ///
+    /// The old context and renderer are kept when the new ones can't be created. Once they were
+    /// replaced, an error means the new context couldn't be made current again, in which case it's
+    /// rebuilt with the next attempt like a lost context.
+    fn rebuild(&mut self) -> Result<(), Error>;
+
+    /// Upload all glyphs again, after they were lost with the old context.
+    fn reload_glyphs(&mut self);
+}
+
+impl GpuContext for Display {
+    fn activate(&mut self) -> bool {
+        if self.context.is_current() {
+            return self.renderer.was_context_reset();
+        }
+
+        match self.context.make_current(&self.surface) {
+            Err(err) if err.error_kind() == ErrorKind::ContextLost => {
+                info!("Context lost for window {:?}", self.window.id());
+                true
+            },
+            _ => false,
+        }
+    }
+
+    fn rebuild(&mut self) -> Result<(), Error> {
+        let gl_display = self.context.display();
+        let gl_config = self.context.config();
+        let raw_window_handle = Some(self.window.raw_window_handle());
+        let context = platform::create_gl_context(&gl_display, &gl_config, raw_window_handle)?;
+        let context = context.make_current(&self.surface)?;
+        let renderer = Renderer::new(&context, self.renderer_preference)?;
+        let old_renderer = mem::replace(&mut *self.renderer, renderer);
+        let old_context = mem::replace(&mut *self.context, context);
+
+        // Drop the old renderer with its own context current, since deleting its objects in the
+        // new context would delete the new renderer's objects which reuse their names. If that
+        // context can't be made current anymore, its renderer has to be leaked instead.
+        if old_context.make_current(&self.surface).is_ok() {
+            drop(old_renderer);
+            self.context.make_current(&self.surface)?;
+        } else {
+            mem::forget(old_renderer);
+        }
+        drop(old_context);
+
+        self.renderer.resize(&self.size_info);
+        self.damage_tracker.frame().mark_fully_damaged();
+
+        debug!("Recovered window {:?} from gpu reset", self.window.id());
+
+        Ok(())
+    }
+
+    fn reload_glyphs(&mut self) {
+        // Favicons of the tab panel are rasterized again once they're missing from the cache.
+        self.reset_glyph_cache();
+    }
+}
+
+/// Make the context of a display current, rebuilding it if it was `lost`.
+///
+/// Returns whether the context is still lost, since failed rebuilds are retried with the next
+/// frame instead of aborting.
+fn activate_context(gpu: &mut impl GpuContext, lost: bool) -> bool {
+    let lost = gpu.activate() || lost;
+    if !lost {
+        return false;
+    }
+
+    match gpu.rebuild() {
+        Ok(()) => {
+            gpu.reload_glyphs();
+            false
+        },
+        Err(err) => {
+            error!("Unable to recover from context loss: {err}");
+            true
+        },
+    }
+}
+
 impl Drop for Display {
     fn drop(&mut self) {
         // Switch OpenGL context before dropping, otherwise objects (like programs) from other
@@ -2034,7 +2197,8 @@ fn window_size(
 
     #[cfg(target_os = "macos")]
     let panel_width = if config.window.tab_panel.enabled {
-        config.window.tab_panel.width as f32 * scale_factor
+        let tab_panel = &config.window.tab_panel;
+        tab_panel.clamp_width(tab_panel.width as f32) * scale_factor
     } else {
         0.
     };
@@ -2046,3 +2210,90 @@ fn window_size(
 
     PhysicalSize::new(width as u32, height as u32)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_resize_collapses_burst() {
+        let mut pending = PendingResize::default();
+        assert!(!pending.is_pending());
+        assert!(pending.push());
+        assert!(!pending.push());
+        assert!(pending.is_pending());
+
+        assert!(pending.finish());
+        assert!(!pending.finish());
+        assert!(!pending.is_pending());
+
+        // Resizing again starts a new burst.
+        assert!(pending.push());
+    }
+
+    #[test]
+    fn size_info_keeps_grid() {
+        let old = SizeInfo::new(800., 600., 10., 20., 0., 0., 0., false);
+        let mut new = SizeInfo::new(1000., 400., 10., 20., 0., 0., 0., false);
+        new.keep_grid(&old);
+
+        assert_eq!((new.width(), new.height()), (1000., 400.));
+        assert_eq!((new.columns(), new.screen_lines()), (old.columns(), old.screen_lines()));
+    }
+
+    /// Context which stays lost until one of its rebuilds succeeds.
+    #[derive(Default)]
+    struct MockGpuContext {
+        lost: bool,
+        failing_rebuilds: usize,
+        rebuilds: usize,
+        glyph_reloads: usize,
+    }
+
+    impl GpuContext for MockGpuContext {
+        fn activate(&mut self) -> bool {
+            self.lost
+        }
+
+        fn rebuild(&mut self) -> Result<(), Error> {
+            self.rebuilds += 1;
+            if self.failing_rebuilds > 0 {
+                self.failing_rebuilds -= 1;
+                return Err(Error::Context(ErrorKind::ContextLost.into()));
+            }
+
+            self.lost = false;
+            Ok(())
+        }
+
+        fn reload_glyphs(&mut self) {
+            self.glyph_reloads += 1;
+        }
+    }
+
+    #[test]
+    fn lost_context_rebuilt() {
+        let mut gpu = MockGpuContext::default();
+        assert!(!activate_context(&mut gpu, false));
+        assert_eq!((gpu.rebuilds, gpu.glyph_reloads), (0, 0));
+
+        gpu.lost = true;
+        assert!(!activate_context(&mut gpu, false));
+        assert_eq!((gpu.rebuilds, gpu.glyph_reloads), (1, 1));
+
+        // Losses noticed while swapping buffers are recovered from with the next frame.
+        assert!(!activate_context(&mut gpu, true));
+        assert_eq!((gpu.rebuilds, gpu.glyph_reloads), (2, 2));
+    }
+
+    #[test]
+    fn failed_rebuild_retried() {
+        let mut gpu = MockGpuContext { lost: true, failing_rebuilds: 1, ..Default::default() };
+        assert!(activate_context(&mut gpu, false));
+        assert_eq!((gpu.rebuilds, gpu.glyph_reloads), (1, 0));
+
+        assert!(!activate_context(&mut gpu, true));
+        assert_eq!((gpu.rebuilds, gpu.glyph_reloads), (2, 1));
+        assert!(!gpu.lost);
+    }
+}
@@ -1,8 +1,12 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
+#[cfg(target_os = "macos")]
+use std::mem;
+#[cfg(target_os = "macos")]
+use std::sync::Arc;
 
 use winit::dpi::PhysicalPosition;
-use winit::event::{ElementState, KeyEvent, MouseButton};
-use winit::keyboard::{Key, NamedKey};
+use winit::event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta};
+use winit::keyboard::{Key, ModifiersState, NamedKey};
 use winit::platform::modifier_supplement::KeyEventExtModifierSupplement;
 use winit::window::CursorIcon;
 
@@ -15,22 +19,52 @@ use tabor_terminal::grid::Dimensions;
 use tabor_terminal::index::{Column, Point};
 use tabor_terminal::term::MIN_COLUMNS;
 
+use crate::config::window::{TabCloseButton, TabPanelConfig};
 use crate::config::UiConfig;
 use crate::display::color::Rgb;
 use crate::display::SizeInfo;
+#[cfg(target_os = "macos")]
+use crate::macos::favicon::{FaviconCache, FaviconImage, FaviconRasterizer};
 use crate::renderer::rects::RenderRect;
 use crate::renderer::{GlyphCache, Renderer};
+#[cfg(target_os = "macos")]
+use crate::tab_panel::TabFavicon;
 use crate::tab_panel::{TabPanelCommand, TabPanelGroup, TabPanelTab};
 use crate::tabs::TabId;
+use crate::window_kind::TabKind;
 
 const RESIZE_HANDLE_WIDTH_PX: f64 = 6.0;
+/// Favicon size of panels without a configured icon scale.
 const PANEL_ICON_SCALE: f32 = 2.0;
+/// Row padding of panels without a configured padding.
 const PANEL_ROW_PADDING_PX: f32 = 4.0;
 const GROUP_HEADER_INDENT_COLS: usize = 1;
 const TAB_INDENT_COLS: usize = 1;
+const GROUP_DROP_INDICATOR_PX: f32 = 2.0;
+const GROUP_EDGE_WIDTH_PX: f32 = 3.0;
+const LOAD_PROGRESS_HEIGHT_PX: f32 = 2.0;
 const ACTIVITY_INDICATOR_COLS: usize = 2;
 const ACTIVITY_INDICATOR_FILLED: char = '\u{25CF}';
 const ACTIVITY_INDICATOR_OUTLINE: char = '\u{25CB}';
+const ACTIVITY_INDICATOR_BUSY: char = '\u{25D0}';
+const GROUP_MUTED_MARKER: &str = " (muted)";
+/// Button at the end of group headers, creating a tab in the group.
+const NEW_TAB_BUTTON: &str = "+";
+/// Button on the last panel line, creating a new group.
+const NEW_GROUP_BUTTON: &str = "++";
+const RESIZE_SNAP_DISTANCE_PX: f32 = 8.0;
+const FOCUS_RING_WIDTH_PX: f32 = 2.0;
+/// Maximum number of undo steps kept while renaming.
+const EDIT_UNDO_DEPTH: usize = 100;
+
+/// Time the cursor has to rest on a truncated tab title before its tooltip is shown.
+pub const TAB_TOOLTIP_DELAY: Duration = Duration::from_millis(600);
+
+/// Interval between scrolled lines while a dragged item is held near the panel's edge.
+pub const DRAG_SCROLL_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Distance from the panel's top and bottom edge which scrolls it during drags, in lines.
+const DRAG_SCROLL_MARGIN_LINES: f64 = 1.0;
 
 #[derive(Default, Clone, Copy)]
 pub struct PanelDimensions {
@@ -54,6 +88,8 @@ pub struct TabPanelEditCommit {
 pub enum TabPanelEditOutcome {
     None,
     Changed,
+    /// Clipboard content should be inserted with [`TabPanel::paste`].
+    Paste,
     Commit(TabPanelEditCommit),
     Cancelled,
 }
@@ -75,7 +111,8 @@ pub fn compute_panel_dimensions(
         return PanelDimensions::default();
     }
 
-    let requested_width = config.window.tab_panel.width as f32 * scale_factor;
+    let tab_panel = &config.window.tab_panel;
+    let requested_width = tab_panel.clamp_width(tab_panel.width as f32) * scale_factor;
     let max_width = max_panel_cols as f32 * cell_width;
     let width = requested_width.min(max_width);
     let columns = (width / cell_width).floor().min(max_panel_cols as f32) as usize;
@@ -87,6 +124,40 @@ pub fn compute_panel_dimensions(
     PanelDimensions { columns, width }
 }
 
+/// Width limits of an interactive panel resize, in physical pixels.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct PanelWidthLimits {
+    min: f32,
+    max: Option<f32>,
+    snap_widths: Vec<f32>,
+    snap_distance: f32,
+}
+
+impl PanelWidthLimits {
+    pub fn new(config: &TabPanelConfig, scale_factor: f32) -> Self {
+        Self {
+            min: config.min_width as f32 * scale_factor,
+            max: config.max_width.map(|max_width| max_width as f32 * scale_factor),
+            snap_widths: config.snap_widths.iter().map(|&w| w as f32 * scale_factor).collect(),
+            snap_distance: RESIZE_SNAP_DISTANCE_PX * scale_factor,
+        }
+    }
+
+    /// Snap a width to the nearest snap width in range, then clamp it to the limits.
+    fn apply(&self, width: f32) -> f32 {
+        let snapped = self
+            .snap_widths
+            .iter()
+            .map(|&snap_width| (snap_width, (snap_width - width).abs()))
+            .filter(|&(_, distance)| distance <= self.snap_distance)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(width, |(snap_width, _)| snap_width);
+
+        let width = self.max.map_or(snapped, |max| snapped.min(max));
+        width.max(self.min)
+    }
+}
+
 #[derive(Default)]
 pub struct TabPanel {
     enabled: bool,
@@ -98,8 +169,37 @@ pub struct TabPanel {
     hover: HoverState,
     drag: Option<DragState>,
     resize: Option<ResizeState>,
+    width_limits: PanelWidthLimits,
     drop_target: Option<DropTarget>,
     last_mouse_pos: Option<PhysicalPosition<f64>>,
+    hide_activity: bool,
+    close_button: TabCloseButton,
+    hover_since: Option<Instant>,
+    group_close: Option<usize>,
+    /// Button pressed with the left mouse button, activated once released on it.
+    pressed_button: Option<PanelButton>,
+    /// Whether key events are routed to the panel instead of the active tab.
+    focused: bool,
+    /// Tab under the keyboard cursor while the panel is focused.
+    focus_cursor: Option<TabId>,
+    /// Number of lines scrolled past the top of the panel.
+    scroll_offset: usize,
+    /// Pixel scroll delta which didn't add up to a full line yet.
+    scroll_pixels: f64,
+    /// Distance in physical pixels a pressed item is moved before it is dragged.
+    drag_threshold: f64,
+    /// Pixels added to the height of every row.
+    row_padding: f32,
+    /// Favicon size in cell widths, which is also the smallest row height.
+    icon_scale: f32,
+    #[cfg(target_os = "macos")]
+    favicons: FaviconRasterizer,
+    /// Decoded images of the tab favicons.
+    #[cfg(target_os = "macos")]
+    favicon_cache: FaviconCache,
+    /// Tabs whose favicon was evicted from the cache before its glyph could be rasterized.
+    #[cfg(target_os = "macos")]
+    evicted_favicons: Vec<TabId>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -107,15 +207,52 @@ struct EditState {
     target: TabPanelEditTarget,
     text: String,
     cursor: usize,
+    /// Text before the latest changes, most recent last.
+    undo: Vec<EditSnapshot>,
+    /// Text of undone changes, most recently undone last.
+    redo: Vec<EditSnapshot>,
+    /// Whether the last change typed a single character, which further typing extends.
+    typing: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct EditSnapshot {
+    text: String,
+    cursor: usize,
 }
 
 impl TabPanel {
     pub fn new() -> Self {
-        Self::default()
+        Self {
+            drag_threshold: DRAG_THRESHOLD_PX,
+            row_padding: PANEL_ROW_PADDING_PX,
+            icon_scale: PANEL_ICON_SCALE,
+            ..Self::default()
+        }
     }
 
     pub fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
+        if !enabled {
+            self.unfocus();
+        }
+    }
+
+    pub fn set_activity_enabled(&mut self, enabled: bool) {
+        self.hide_activity = !enabled;
+    }
+
+    pub fn set_close_button(&mut self, close_button: TabCloseButton) {
+        self.close_button = close_button;
+    }
+
+    pub fn set_drag_threshold(&mut self, config: &TabPanelConfig, scale_factor: f32) {
+        self.drag_threshold = config.drag_threshold as f64 * scale_factor as f64;
+    }
+
+    pub fn set_row_metrics(&mut self, config: &TabPanelConfig) {
+        self.row_padding = config.row_padding.max(0.);
+        self.icon_scale = config.icon_scale.max(0.);
     }
 
     pub fn set_dimensions(&mut self, dimensions: PanelDimensions) {
@@ -123,10 +260,37 @@ impl TabPanel {
         self.width_px = dimensions.width;
     }
 
+    pub fn set_width_limits(&mut self, width_limits: PanelWidthLimits) {
+        self.width_limits = width_limits;
+    }
+
     pub fn width(&self) -> f32 {
         self.width_px
     }
 
+    /// Whether favicons are still being rasterized and the panel must be drawn again.
+    #[cfg(target_os = "macos")]
+    pub fn favicons_pending(&self) -> bool {
+        self.favicons.has_pending()
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn set_favicon_budget(&mut self, budget: usize) {
+        self.favicon_cache.set_budget(budget);
+    }
+
+    /// Keep the decoded image of a favicon for rasterizing its glyph.
+    #[cfg(target_os = "macos")]
+    pub fn cache_favicon(&mut self, id: u64, image: Arc<FaviconImage>) {
+        self.favicon_cache.insert(id, image);
+    }
+
+    /// Take the tabs whose favicon must be fetched again to be drawn.
+    #[cfg(target_os = "macos")]
+    pub fn take_evicted_favicons(&mut self) -> Vec<TabId> {
+        mem::take(&mut self.evicted_favicons)
+    }
+
     pub fn is_enabled(&self) -> bool {
         self.enabled && self.width_cols > 0
     }
@@ -137,6 +301,7 @@ impl TabPanel {
         if self.groups != groups {
             self.groups = groups;
             self.validate_edit_target();
+            self.validate_focus_cursor();
             changed = true;
         }
 
@@ -152,6 +317,97 @@ impl TabPanel {
         self.edit.is_some()
     }
 
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Move keyboard focus between the panel and the active tab.
+    pub fn toggle_focus(&mut self) -> bool {
+        if self.focused {
+            return self.unfocus();
+        }
+
+        if !self.is_enabled() {
+            return false;
+        }
+
+        self.focused = true;
+        self.focus_cursor = None;
+        self.validate_focus_cursor();
+        true
+    }
+
+    /// Return keyboard focus to the active tab.
+    pub fn unfocus(&mut self) -> bool {
+        self.focus_cursor = None;
+        std::mem::take(&mut self.focused)
+    }
+
+    /// Handle a key event while the panel is focused.
+    pub fn handle_panel_key(&mut self, key: &KeyEvent) -> TabPanelKeyUpdate {
+        self.panel_key(key.logical_key.as_ref(), key.state)
+    }
+
+    fn panel_key(&mut self, key: Key<&str>, state: ElementState) -> TabPanelKeyUpdate {
+        if !self.focused {
+            return TabPanelKeyUpdate::default();
+        }
+
+        // Keys never reach the tab while the panel is focused.
+        let mut update = TabPanelKeyUpdate { capture: true, ..Default::default() };
+        if state == ElementState::Released {
+            return update;
+        }
+
+        match key {
+            Key::Named(NamedKey::Escape) => update.needs_redraw = self.unfocus(),
+            Key::Named(NamedKey::ArrowUp) | Key::Character("k") => {
+                update.needs_redraw = self.move_focus_cursor(false);
+            },
+            Key::Named(NamedKey::ArrowDown) | Key::Character("j") => {
+                update.needs_redraw = self.move_focus_cursor(true);
+            },
+            Key::Named(NamedKey::Enter) => {
+                update.command = self.focus_cursor.map(TabPanelCommand::Focus);
+                update.needs_redraw = self.unfocus();
+            },
+            _ => (),
+        }
+
+        update
+    }
+
+    fn move_focus_cursor(&mut self, forward: bool) -> bool {
+        let tab_ids: Vec<_> = self.tab_ids().collect();
+        let Some(index) = tab_ids.iter().position(|&id| Some(id) == self.focus_cursor) else {
+            return false;
+        };
+
+        let index = if forward { index + 1 } else { index.wrapping_sub(1) };
+        match tab_ids.get(index) {
+            Some(&tab_id) => {
+                self.focus_cursor = Some(tab_id);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Keep the keyboard cursor on an existing tab, falling back to the active one.
+    fn validate_focus_cursor(&mut self) {
+        if !self.focused || self.tab_ids().any(|id| Some(id) == self.focus_cursor) {
+            return;
+        }
+
+        let active = self.groups.iter().flat_map(|group| &group.tabs).find(|tab| tab.is_active);
+        let active = active.map(|tab| tab.tab_id);
+        self.focus_cursor = active.or_else(|| self.tab_ids().next());
+    }
+
+    fn tab_ids(&self) -> impl Iterator<Item = TabId> + '_ {
+        self.groups.iter().flat_map(|group| group.tabs.iter().map(|tab| tab.tab_id))
+    }
+
     pub fn begin_edit_tab(&mut self, tab_id: TabId, title: String) -> bool {
         self.begin_edit(TabPanelEditTarget::Tab(tab_id), title)
     }
@@ -164,16 +420,53 @@ impl TabPanel {
         self.edit.take().is_some()
     }
 
-    pub fn handle_key_event(&mut self, key: &KeyEvent) -> TabPanelEditOutcome {
+    pub fn handle_key_event(
+        &mut self,
+        key: &KeyEvent,
+        mods: ModifiersState,
+    ) -> TabPanelEditOutcome {
+        let text = key.text_with_all_modifiers().unwrap_or_default();
+        self.edit_key(key.logical_key.as_ref(), text, mods, key.state)
+    }
+
+    fn edit_key(
+        &mut self,
+        key: Key<&str>,
+        text: &str,
+        mods: ModifiersState,
+        state: ElementState,
+    ) -> TabPanelEditOutcome {
         let Some(edit) = self.edit.as_mut() else {
             return TabPanelEditOutcome::None;
         };
 
-        if key.state == ElementState::Released {
+        if state == ElementState::Released {
             return TabPanelEditOutcome::None;
         }
 
-        match key.logical_key.as_ref() {
+        match key {
+            Key::Character("w") if mods.control_key() => {
+                if edit.delete_word_back() {
+                    return TabPanelEditOutcome::Changed;
+                }
+                return TabPanelEditOutcome::None;
+            },
+            Key::Character("v") if mods.control_key() || mods.super_key() => {
+                return TabPanelEditOutcome::Paste;
+            },
+            Key::Character("z" | "Z") if mods.control_key() || mods.super_key() => {
+                let changed = if mods.shift_key() { edit.redo() } else { edit.undo() };
+                if changed {
+                    return TabPanelEditOutcome::Changed;
+                }
+                return TabPanelEditOutcome::None;
+            },
+            Key::Character("y") if mods.control_key() => {
+                if edit.redo() {
+                    return TabPanelEditOutcome::Changed;
+                }
+                return TabPanelEditOutcome::None;
+            },
             Key::Named(NamedKey::Escape) => {
                 self.edit = None;
                 return TabPanelEditOutcome::Cancelled;
@@ -218,14 +511,18 @@ impl TabPanel {
             _ => (),
         }
 
-        let text = key.text_with_all_modifiers().unwrap_or_default();
-        if edit.insert_text(&text) {
+        if edit.insert_text(text) {
             TabPanelEditOutcome::Changed
         } else {
             TabPanelEditOutcome::None
         }
     }
 
+    /// Insert clipboard text at the cursor of the title being edited.
+    pub fn paste(&mut self, text: &str) -> TabPanelEditOutcome {
+        self.handle_ime_commit(text)
+    }
+
     pub fn handle_ime_commit(&mut self, text: &str) -> TabPanelEditOutcome {
         let Some(edit) = self.edit.as_mut() else {
             return TabPanelEditOutcome::None;
@@ -239,8 +536,7 @@ impl TabPanel {
     }
 
     fn begin_edit(&mut self, target: TabPanelEditTarget, text: String) -> bool {
-        let cursor = text.chars().count();
-        let next = EditState { target, text, cursor };
+        let next = EditState::new(target, text);
         let changed = self.edit.as_ref() != Some(&next);
         self.edit = Some(next);
         self.drag = None;
@@ -299,6 +595,7 @@ impl TabPanel {
         if !capture {
             let needs_redraw = self.hover != HoverState::default() || self.drop_target.is_some();
             self.hover = HoverState::default();
+            self.hover_since = None;
             self.drop_target = None;
             return TabPanelCursorUpdate {
                 capture: false,
@@ -311,10 +608,12 @@ impl TabPanel {
         let hit = if resize_hit { None } else { self.hit_test(position, &panel_size_info) };
         let next_hover = HoverState::from_hit(&hit);
         let drag_started = self.update_drag(position);
-        let needs_redraw = drag_started
-            || next_hover != self.hover
-            || self.update_drop_target(position, &panel_size_info);
-        self.hover = next_hover;
+        let drop_target_changed = self.update_drop_target(position, &panel_size_info);
+        let needs_redraw = drag_started || drop_target_changed || next_hover != self.hover;
+        if next_hover != self.hover {
+            self.hover = next_hover;
+            self.arm_tooltip(Instant::now());
+        }
 
         let cursor = if resize_hit {
             Some(CursorIcon::EwResize)
@@ -372,7 +671,8 @@ impl TabPanel {
         }
 
         if matches!(state, ElementState::Pressed) && self.is_on_resize_handle(position) {
-            self.resize = Some(ResizeState::new(self.width_px, position));
+            let limits = self.width_limits.clone();
+            self.resize = Some(ResizeState::new(self.width_px, position, limits));
             return TabPanelMouseUpdate { capture: true, needs_redraw: true, command: None };
         }
 
@@ -385,7 +685,14 @@ impl TabPanel {
         let mut command = None;
 
         match state {
+            // Keep the item of a drag in progress until it's released.
+            ElementState::Pressed if self.drag.is_some() => (),
             ElementState::Pressed => {
+                if let Some(button) = self.button_hit(position, &panel_size_info) {
+                    self.pressed_button = Some(button);
+                    return TabPanelMouseUpdate { capture, needs_redraw: true, command: None };
+                }
+
                 match hit {
                     Some(PanelHit::Tab { tab_id }) => {
                         if !self.is_close_hit(position, &panel_size_info, tab_id) {
@@ -395,8 +702,12 @@ impl TabPanel {
                     },
                     Some(PanelHit::Group { group_index }) => {
                         if let Some(group) = self.groups.get(group_index) {
-                            self.drag =
-                                Some(DragState::new(DragItem::Group { group_id: group.id }, position));
+                            if self.is_group_close_hit(position, &panel_size_info, group_index) {
+                                self.group_close = Some(group.id);
+                            } else {
+                                let item = DragItem::Group { group_id: group.id };
+                                self.drag = Some(DragState::new(item, position));
+                            }
                             needs_redraw = true;
                         }
                     },
@@ -404,6 +715,8 @@ impl TabPanel {
                 }
             },
             ElementState::Released => {
+                let group_close = self.group_close.take();
+                let pressed_button = self.pressed_button.take();
                 if let Some(drag) = self.drag.take() {
                     if drag.dragging {
                         match drag.item {
@@ -455,10 +768,39 @@ impl TabPanel {
                                 command = Some(TabPanelCommand::Focus(tab_id));
                             }
                         }
+                    } else if let (
+                        DragItem::Group { group_id },
+                        Some(PanelHit::Group { group_index }),
+                    ) = (drag.item, hit)
+                    {
+                        if self.groups.get(group_index).is_some_and(|group| group.id == group_id) {
+                            command = Some(TabPanelCommand::FocusGroup(group_id));
+                        }
                     }
 
                     self.drop_target = None;
                     needs_redraw = true;
+                } else if let Some(group_id) = group_close {
+                    // Groups are only closed when the pointer stayed on the close glyph.
+                    if let Some(PanelHit::Group { group_index }) = hit {
+                        if self.groups.get(group_index).is_some_and(|group| group.id == group_id)
+                            && self.is_group_close_hit(position, &panel_size_info, group_index)
+                        {
+                            command = Some(TabPanelCommand::CloseGroup(group_id));
+                        }
+                    }
+                    needs_redraw = true;
+                } else if let Some(button) = pressed_button {
+                    // Buttons are only activated when the pointer stayed on them.
+                    if self.button_hit(position, &panel_size_info) == Some(button) {
+                        command = Some(match button {
+                            PanelButton::NewTab { group_id } => {
+                                TabPanelCommand::NewTab { group_id }
+                            },
+                            PanelButton::NewGroup => TabPanelCommand::NewGroup,
+                        });
+                    }
+                    needs_redraw = true;
                 } else if let Some(PanelHit::Tab { tab_id }) = hit {
                     if self.is_close_hit(position, &panel_size_info, tab_id)
                         && self.hover.tab == Some(tab_id)
@@ -491,6 +833,8 @@ impl TabPanel {
         let ghost_header_bg = mix(base, fg, 0.16);
         let ghost_drag_bg = mix(base, fg, 0.2);
         let divider = mix(base, fg, 0.2);
+        let focus_bg = mix(base, fg, 0.26);
+        let accent = config.colors.normal.blue;
 
         rects.push(RenderRect::new(0., 0., self.width_px, size_info.height(), panel_bg, 1.));
 
@@ -511,11 +855,15 @@ impl TabPanel {
         for item in &layout.items {
             let y = start_y + item.line as f32 * line_height;
             let bg = match &item.kind {
-                PanelItemKind::GroupHeader { .. } => header_bg,
+                PanelItemKind::GroupHeader { group_index } => {
+                    self.group_header_bg(*group_index, header_bg)
+                },
                 PanelItemKind::GhostGroupHeader { .. } => ghost_header_bg,
                 PanelItemKind::Tab { tab } => {
                     if item.style == RenderStyle::Ghost {
                         ghost_bg
+                    } else if self.focused && self.focus_cursor == Some(tab.tab_id) {
+                        focus_bg
                     } else if tab.is_active {
                         active_bg
                     } else {
@@ -525,6 +873,34 @@ impl TabPanel {
             };
 
             rects.push(RenderRect::new(0., y, self.width_px, line_height, bg, 1.));
+
+            if let PanelItemKind::Tab { tab } = &item.kind {
+                if let Some(color) = self.tab_group_color(tab.tab_id) {
+                    let edge = mix(base, color, 0.8);
+                    rects.push(RenderRect::new(0., y, GROUP_EDGE_WIDTH_PX, line_height, edge, 1.));
+                }
+
+                // Show the progress of page loads along the bottom of their row.
+                if let Some(progress) = load_progress(tab) {
+                    let y = y + line_height - LOAD_PROGRESS_HEIGHT_PX;
+                    let width = self.width_px * progress;
+                    rects.push(RenderRect::new(0., y, width, LOAD_PROGRESS_HEIGHT_PX, accent, 1.));
+                }
+            }
+        }
+
+        // Mark the header row where a dragged group will be inserted.
+        if let Some(DropTarget::Group(_)) = self.drop_target {
+            let header_line = layout.items.iter().find_map(|item| match item.kind {
+                PanelItemKind::GhostGroupHeader { .. } => Some(item.line),
+                _ => None,
+            });
+            if let Some(line) = header_line {
+                let y = start_y + line as f32 * line_height;
+                let width = (self.width_px - 1.).max(0.);
+                let indicator = mix(fg, base, 0.3);
+                rects.push(RenderRect::new(0., y, width, GROUP_DROP_INDICATOR_PX, indicator, 1.));
+            }
         }
 
         if let Some(DragState { item: DragItem::Tab(tab_id), dragging: true, .. }) =
@@ -546,10 +922,33 @@ impl TabPanel {
                 }
             }
         }
+
+        if self.focused {
+            let ring = mix(base, fg, 0.6);
+            let (width, height) = (self.width_px, size_info.height());
+            let ring_x = (width - FOCUS_RING_WIDTH_PX).max(0.);
+            let ring_y = (height - FOCUS_RING_WIDTH_PX).max(0.);
+            rects.push(RenderRect::new(0., 0., width, FOCUS_RING_WIDTH_PX, ring, 1.));
+            rects.push(RenderRect::new(0., ring_y, width, FOCUS_RING_WIDTH_PX, ring, 1.));
+            rects.push(RenderRect::new(0., 0., FOCUS_RING_WIDTH_PX, height, ring, 1.));
+            rects.push(RenderRect::new(ring_x, 0., FOCUS_RING_WIDTH_PX, height, ring, 1.));
+        }
+
+        if let Some(tooltip) = self.tooltip(size_info, &layout) {
+            let cell_width = panel_size_info.cell_width();
+            rects.push(RenderRect::new(
+                tooltip.column as f32 * cell_width,
+                start_y + tooltip.line as f32 * line_height,
+                tooltip.columns as f32 * cell_width,
+                line_height,
+                active_bg,
+                1.,
+            ));
+        }
     }
 
     pub fn draw_text(
-        &self,
+        &mut self,
         size_info: &SizeInfo,
         config: &UiConfig,
         renderer: &mut Renderer,
@@ -567,16 +966,13 @@ impl TabPanel {
             let font_key = glyph_cache.font_key;
             let font_size = glyph_cache.font_size;
             let metrics = glyph_cache.font_metrics();
+            let mut drawn = Vec::new();
             let mut missing = Vec::new();
 
             for item in &layout.items {
                 if let PanelItemKind::Tab { tab } = &item.kind {
                     if let Some(favicon) = &tab.favicon {
-                        let key =
-                            GlyphKey { font_key, size: font_size, character: favicon.character };
-                        if !glyph_cache.has_glyph(&key) {
-                            missing.push((key, favicon.clone()));
-                        }
+                        drawn.push((tab.tab_id, favicon.clone()));
                     }
                 }
             }
@@ -586,24 +982,41 @@ impl TabPanel {
             {
                 if let Some((tab, _, _)) = self.find_tab(*tab_id) {
                     if let Some(favicon) = &tab.favicon {
-                        let key =
-                            GlyphKey { font_key, size: font_size, character: favicon.character };
-                        if !glyph_cache.has_glyph(&key) {
-                            missing.push((key, favicon.clone()));
-                        }
+                        drawn.push((tab.tab_id, favicon.clone()));
                     }
                 }
             }
 
-            if !missing.is_empty() {
+            for (tab_id, favicon) in drawn {
+                // Touch every drawn favicon, to evict the least recently drawn ones first.
+                let image = self.favicon_cache.get(favicon.id());
+                let key = GlyphKey { font_key, size: font_size, character: favicon.character };
+                if glyph_cache.has_glyph(&key) || self.favicons.is_pending(&key) {
+                    continue;
+                }
+
+                match image {
+                    Some(image) => missing.push((key, image)),
+                    None => self.evicted_favicons.push(tab_id),
+                }
+            }
+
+            // Upload favicons rasterized since the last frame.
+            let rasterized = self.favicons.drain();
+            if !rasterized.is_empty() {
                 renderer.with_loader(|mut api| {
-                    for (key, favicon) in missing {
-                        let rasterized =
-                            favicon.image.rasterized_glyph(favicon.character, &panel_size_info, metrics);
-                        glyph_cache.insert_custom_glyph(key, rasterized, &mut api);
+                    for (key, glyph) in rasterized {
+                        glyph_cache.insert_custom_glyph(key, glyph, &mut api);
                     }
                 });
             }
+
+            for (key, image) in missing {
+                if !glyph_cache.has_glyph(&key) {
+                    let icon_scale = self.icon_scale;
+                    self.favicons.request(key, image, panel_size_info, metrics, icon_scale);
+                }
+            }
         }
 
         renderer.set_viewport(&panel_size_info);
@@ -619,6 +1032,7 @@ impl TabPanel {
         let ghost_header_bg = mix(base, fg, 0.16);
         let ghost_drag_bg = mix(base, fg, 0.2);
         let header_fg = mix(fg, base, 0.2);
+        let web_fg = mix(fg, config.colors.normal.blue, 0.25);
         let now = Instant::now();
         let dragging = self.drag.as_ref().is_some_and(|drag| drag.dragging);
 
@@ -635,10 +1049,9 @@ impl TabPanel {
                             },
                             _ => group.label.clone(),
                         };
-                        let title = format!("{}:", label);
-                        let max_cols = self.width_cols.saturating_sub(indent + 1);
-                        let text = truncate_to_columns(&title, max_cols);
-                        let bg = header_bg;
+                        let max_cols = self.width_cols.saturating_sub(indent + 2);
+                        let text = group_header_title(&label, group.muted, max_cols);
+                        let bg = self.group_header_bg(*group_index, header_bg);
                         let point = Point::new(item.line, Column(indent));
                         renderer.draw_string(
                             point,
@@ -648,6 +1061,31 @@ impl TabPanel {
                             &panel_size_info,
                             glyph_cache,
                         );
+
+                        if let Some(new_tab_col) = self.new_tab_col().filter(|_| !dragging) {
+                            let point = Point::new(item.line, Column(new_tab_col));
+                            renderer.draw_string(
+                                point,
+                                header_fg,
+                                bg,
+                                NEW_TAB_BUTTON.chars(),
+                                &panel_size_info,
+                                glyph_cache,
+                            );
+                        }
+
+                        let close_col = self.width_cols.saturating_sub(1);
+                        if !dragging && self.hover.group == Some(*group_index) && close_col > 1 {
+                            let point = Point::new(item.line, Column(close_col));
+                            renderer.draw_string(
+                                point,
+                                fg,
+                                bg,
+                                "x".chars(),
+                                &panel_size_info,
+                                glyph_cache,
+                            );
+                        }
                     }
                 },
                 PanelItemKind::GhostGroupHeader { label } => {
@@ -668,33 +1106,48 @@ impl TabPanel {
                 PanelItemKind::Tab { tab } => {
                     let is_ghost = item.style == RenderStyle::Ghost;
                     let indent = TAB_INDENT_COLS;
-                    let indicator_cols = if tab.activity.is_some() { ACTIVITY_INDICATOR_COLS } else { 0 };
+                    let indicator_cols = self.activity_indicator_cols(tab);
                     let text_col = indent + indicator_cols;
                     let close_col = self.width_cols.saturating_sub(1);
-                    let max_cols = self.width_cols.saturating_sub(text_col + 1);
+                    let max_cols = self.title_max_cols(text_col);
                     let title = match &self.edit {
                         Some(edit) if edit.target == TabPanelEditTarget::Tab(tab.tab_id) => {
                             render_edit_text(&edit.text, edit.cursor)
                         },
                         _ => tab.title.clone(),
                     };
-                    let show_close = !dragging && !is_ghost && self.hover.tab == Some(tab.tab_id);
+                    let hovered = self.hover.tab == Some(tab.tab_id);
+                    let show_close = !dragging
+                        && !is_ghost
+                        && match self.close_button {
+                            TabCloseButton::Hidden => false,
+                            TabCloseButton::AlwaysRight => true,
+                            TabCloseButton::HoverRight | TabCloseButton::HoverLeft => hovered,
+                        };
+                    let hover_right = self.close_button == TabCloseButton::HoverRight;
                     #[cfg(target_os = "macos")]
-                    let show_inline_close_favicon = show_close && tab.favicon.is_some();
+                    let show_inline_close_favicon =
+                        show_close && hover_right && tab.favicon.is_some();
                     #[cfg(not(target_os = "macos"))]
                     let show_inline_close_favicon = false;
-                    let show_inline_close_indicator = show_close && tab.activity.is_some();
+                    let show_inline_close_indicator =
+                        show_close && hover_right && self.has_activity_indicator(tab);
                     let show_inline_close = show_inline_close_favicon || show_inline_close_indicator;
-                    let show_trailing_close = show_close && !show_inline_close;
+                    let show_leading_close =
+                        show_close && self.close_button == TabCloseButton::HoverLeft;
+                    let show_trailing_close =
+                        show_close && !show_inline_close && !show_leading_close;
                     #[cfg(target_os = "macos")]
-                    let label = if let Some(favicon) = &tab.favicon {
-                        let icon = if show_inline_close_favicon { 'x' } else { favicon.character };
-                        format!("{}  {}", icon, title)
-                    } else {
-                        title
-                    };
+                    let favicon = tab.favicon.as_ref().and_then(|favicon| {
+                        if show_inline_close_favicon {
+                            Some('x')
+                        } else {
+                            self.favicon_icon(favicon, glyph_cache)
+                        }
+                    });
                     #[cfg(not(target_os = "macos"))]
-                    let label = title;
+                    let favicon = None;
+                    let label = self.tab_label(tab, favicon, &title);
                     let text = truncate_to_columns(&label, max_cols);
                     let bg = if is_ghost {
                         ghost_bg
@@ -703,7 +1156,13 @@ impl TabPanel {
                     } else {
                         panel_bg
                     };
-                    let text_fg = if is_ghost { ghost_fg } else { fg };
+                    let text_fg = if is_ghost {
+                        ghost_fg
+                    } else if matches!(tab.kind, TabKind::Web { .. }) {
+                        web_fg
+                    } else {
+                        fg
+                    };
 
                     if let Some(indicator) = tab_activity_indicator(tab, now, base, fg, config) {
                         let indicator_color = if is_ghost {
@@ -734,6 +1193,18 @@ impl TabPanel {
                         glyph_cache,
                     );
 
+                    if show_leading_close {
+                        let point = Point::new(item.line, Column(0));
+                        renderer.draw_string(
+                            point,
+                            fg,
+                            bg,
+                            "x".chars(),
+                            &panel_size_info,
+                            glyph_cache,
+                        );
+                    }
+
                     if show_trailing_close && close_col > text_col
                     {
                         let point = Point::new(item.line, Column(close_col));
@@ -757,19 +1228,18 @@ impl TabPanel {
                 if let Some(position) = self.last_mouse_pos {
                     if let Some(line) = self.drag_ghost_line(position, &panel_size_info, &layout) {
                         let indent = TAB_INDENT_COLS;
-                        let indicator_cols =
-                            if tab.activity.is_some() { ACTIVITY_INDICATOR_COLS } else { 0 };
+                        let indicator_cols = self.activity_indicator_cols(&tab);
                         let text_col = indent + indicator_cols;
-                        let max_cols = self.width_cols.saturating_sub(text_col + 1);
+                        let max_cols = self.title_max_cols(text_col);
                         let title = tab.title.clone();
                         #[cfg(target_os = "macos")]
-                        let label = if let Some(favicon) = &tab.favicon {
-                            format!("{}  {}", favicon.character, title)
-                        } else {
-                            title
-                        };
+                        let favicon = tab
+                            .favicon
+                            .as_ref()
+                            .and_then(|favicon| self.favicon_icon(favicon, glyph_cache));
                         #[cfg(not(target_os = "macos"))]
-                        let label = title;
+                        let favicon = None;
+                        let label = self.tab_label(&tab, favicon, &title);
                         let text = truncate_to_columns(&label, max_cols);
                         if let Some(indicator) = tab_activity_indicator(&tab, now, base, fg, config)
                         {
@@ -798,10 +1268,117 @@ impl TabPanel {
             }
         }
 
+        let footer_line = self.list_lines(&panel_size_info);
+        if footer_line < panel_size_info.screen_lines() {
+            let point = Point::new(footer_line, Column(GROUP_HEADER_INDENT_COLS));
+            renderer.draw_string(
+                point,
+                header_fg,
+                panel_bg,
+                NEW_GROUP_BUTTON.chars(),
+                &panel_size_info,
+                glyph_cache,
+            );
+        }
+
+        if let Some(tooltip) = self.tooltip(size_info, &layout) {
+            // Tooltips may extend past the panel into the terminal.
+            let tooltip_size_info = SizeInfo::new(
+                size_info.width(),
+                size_info.height(),
+                panel_size_info.cell_width(),
+                panel_size_info.cell_height(),
+                0.,
+                0.,
+                panel_size_info.padding_y(),
+                false,
+            );
+            renderer.set_viewport(&tooltip_size_info);
+            renderer.set_text_projection(&tooltip_size_info);
+
+            let point = Point::new(tooltip.line, Column(tooltip.column + 1));
+            renderer.draw_string(
+                point,
+                fg,
+                active_bg,
+                tooltip.text.chars(),
+                &tooltip_size_info,
+                glyph_cache,
+            );
+        }
+
         renderer.set_viewport(size_info);
         renderer.set_text_projection(size_info);
     }
 
+    /// Width of the area drawn by the panel, including tooltips extending past it.
+    pub fn damage_width(&self, size_info: &SizeInfo) -> f32 {
+        let panel_size_info = self.panel_size_info(size_info);
+        let layout = self.render_layout(&panel_size_info);
+        let tooltip_width = self.tooltip(size_info, &layout).map_or(0., |tooltip| {
+            (tooltip.column + tooltip.columns) as f32 * panel_size_info.cell_width()
+        });
+        self.width_px.max(tooltip_width)
+    }
+
+    /// Time at which the tooltip of the hovered tab is due.
+    pub fn tooltip_deadline(&self) -> Option<Instant> {
+        if self.drag.is_some() || self.edit.is_some() {
+            return None;
+        }
+
+        self.hover_since.map(|hover_since| hover_since + TAB_TOOLTIP_DELAY)
+    }
+
+    /// Start the tooltip delay when the hovered tab's title doesn't fit the panel.
+    fn arm_tooltip(&mut self, now: Instant) {
+        let truncated = self
+            .hover
+            .tab
+            .and_then(|tab_id| self.find_tab(tab_id))
+            .is_some_and(|(tab, ..)| self.title_truncated(&tab));
+        self.hover_since = truncated.then_some(now);
+    }
+
+    fn title_truncated(&self, tab: &TabPanelTab) -> bool {
+        let text_col = TAB_INDENT_COLS + self.activity_indicator_cols(tab);
+        let max_cols = self.title_max_cols(text_col);
+        self.favicon_cols() + text_columns(&tab.title) > max_cols
+    }
+
+    fn tooltip(&self, size_info: &SizeInfo, layout: &RenderLayout) -> Option<Tooltip> {
+        if self.tooltip_deadline()? > Instant::now() {
+            return None;
+        }
+
+        let tab_id = self.hover.tab?;
+        let (tab, ..) = self.find_tab(tab_id)?;
+        if !self.title_truncated(&tab) {
+            return None;
+        }
+
+        let panel_size_info = self.panel_size_info(size_info);
+        let tab_line = layout.items.iter().find_map(|item| match &item.kind {
+            PanelItemKind::Tab { tab } if tab.tab_id == tab_id => Some(item.line),
+            _ => None,
+        })?;
+        let line = if tab_line + 1 < panel_size_info.screen_lines() {
+            tab_line + 1
+        } else {
+            tab_line.checked_sub(1)?
+        };
+
+        // Keep the tooltip within the window, starting just right of the cursor.
+        let cell_width = panel_size_info.cell_width() as f64;
+        let window_cols = (size_info.width() / panel_size_info.cell_width()) as usize;
+        let columns = (text_columns(&tab.title) + 2).min(window_cols);
+        let cursor_col = (self.last_mouse_pos?.x / cell_width) as usize + 1;
+        let column = cursor_col.min(window_cols - columns);
+        let text = truncate_to_columns(&tab.title, columns.saturating_sub(2));
+
+        Some(Tooltip { text, line, column, columns })
+    }
+
     pub fn should_capture(&self, position: Option<PhysicalPosition<f64>>) -> bool {
         if !self.is_enabled() {
             return false;
@@ -823,13 +1400,14 @@ impl TabPanel {
             return false;
         };
 
-        if drag.dragging {
+        // Ignore movement of quick clicks, which shouldn't move their item.
+        if drag.dragging || drag.pressed_at.elapsed() < DRAG_MIN_HOLD {
             return false;
         }
 
         let dx = (position.x - drag.start_pos.x).abs();
         let dy = (position.y - drag.start_pos.y).abs();
-        if dx.max(dy) > DRAG_THRESHOLD_PX {
+        if dx.max(dy) > self.drag_threshold {
             drag.dragging = true;
             return true;
         }
@@ -837,9 +1415,133 @@ impl TabPanel {
         false
     }
 
+    /// Scroll the panel with the mouse wheel.
+    pub fn scroll(&mut self, delta: MouseScrollDelta, size_info: &SizeInfo) -> bool {
+        let lines = match delta {
+            MouseScrollDelta::LineDelta(_, lines) => -lines.round() as isize,
+            MouseScrollDelta::PixelDelta(delta) => {
+                let line_height = self.panel_cell_height(size_info) as f64;
+                self.scroll_pixels -= delta.y;
+                let lines = (self.scroll_pixels / line_height).trunc();
+                self.scroll_pixels -= lines * line_height;
+                lines as isize
+            },
+        };
+
+        self.scroll_lines(lines, size_info)
+    }
+
+    /// Check if a dragged item is held near an edge the panel can still scroll past.
+    pub fn drag_scroll_pending(&self, size_info: &SizeInfo) -> bool {
+        self.drag_scroll_direction(size_info).is_some()
+    }
+
+    /// Scroll the panel by one line under a dragged item held near one of its edges.
+    pub fn drag_scroll(&mut self, size_info: &SizeInfo) -> bool {
+        match self.drag_scroll_direction(size_info) {
+            Some(direction) => self.scroll_lines(direction, size_info),
+            None => false,
+        }
+    }
+
+    fn drag_scroll_direction(&self, size_info: &SizeInfo) -> Option<isize> {
+        self.drag.as_ref().filter(|drag| drag.dragging)?;
+        let position = self.last_mouse_pos.filter(|&position| self.is_inside_panel(position))?;
+
+        let panel_size_info = self.panel_size_info(size_info);
+        let line_height = panel_size_info.cell_height() as f64;
+        let margin = line_height * DRAG_SCROLL_MARGIN_LINES;
+        let top = panel_size_info.padding_y() as f64;
+        let bottom = top + self.list_lines(&panel_size_info) as f64 * line_height;
+
+        let scroll_offset = self.scroll_offset(&panel_size_info);
+        if position.y < top + margin && scroll_offset > 0 {
+            Some(-1)
+        } else if position.y >= bottom - margin
+            && scroll_offset < self.max_scroll_offset(&panel_size_info)
+        {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    /// Scroll the panel by `lines`, stopping at the start and end of the list.
+    fn scroll_lines(&mut self, lines: isize, size_info: &SizeInfo) -> bool {
+        let panel_size_info = self.panel_size_info(size_info);
+        let scroll_offset = self.scroll_offset(&panel_size_info);
+        let max_scroll_offset = self.max_scroll_offset(&panel_size_info);
+        self.scroll_offset = scroll_offset.saturating_add_signed(lines).min(max_scroll_offset);
+        if self.scroll_offset == scroll_offset {
+            return false;
+        }
+
+        // Items moved underneath the cursor.
+        if let Some(position) = self.last_mouse_pos {
+            if self.is_inside_panel(position) {
+                self.hover = HoverState::from_hit(&self.hit_test(position, &panel_size_info));
+            }
+            self.update_drop_target(position, &panel_size_info);
+        }
+
+        true
+    }
+
+    /// Lines scrolled past the top of the panel, limited to the current list length.
+    fn scroll_offset(&self, size_info: &SizeInfo) -> usize {
+        self.scroll_offset.min(self.max_scroll_offset(size_info))
+    }
+
+    fn max_scroll_offset(&self, size_info: &SizeInfo) -> usize {
+        let tab_lines: usize = self.groups.iter().map(|group| group.tabs.len() + 1).sum();
+        let content_lines = tab_lines + self.groups.len().saturating_sub(1);
+        content_lines.saturating_sub(self.list_lines(size_info))
+    }
+
+    /// Number of lines laid out, including the ones scrolled past the top of the panel.
+    fn layout_lines(&self, size_info: &SizeInfo) -> usize {
+        self.list_lines(size_info) + self.scroll_offset(size_info)
+    }
+
+    /// Visible lines of the tab list, leaving the last panel line to the new group button.
+    fn list_lines(&self, size_info: &SizeInfo) -> usize {
+        size_info.screen_lines().saturating_sub(1)
+    }
+
     fn panel_cell_height(&self, size_info: &SizeInfo) -> f32 {
-        let min_height = (size_info.cell_width() * PANEL_ICON_SCALE).ceil();
-        size_info.cell_height().max(min_height) + PANEL_ROW_PADDING_PX
+        let min_height = (size_info.cell_width() * self.icon_scale).ceil();
+        size_info.cell_height().max(min_height) + self.row_padding
+    }
+
+    /// Columns taken by a favicon and the gap separating it from the title.
+    fn favicon_cols(&self) -> usize {
+        self.icon_scale.ceil().max(1.) as usize + 1
+    }
+
+    /// Tab title behind its favicon, or behind the glyph of its kind without one.
+    fn tab_label(&self, tab: &TabPanelTab, favicon: Option<char>, title: &str) -> String {
+        let icon = favicon.unwrap_or_else(|| tab.kind.glyph());
+        format!("{icon}{:width$}{title}", "", width = self.favicon_cols() - 1)
+    }
+
+    /// Character drawn for a favicon, blank while its glyph is being rasterized.
+    ///
+    /// Favicons without a glyph which aren't being rasterized had their image evicted, so
+    /// nothing is returned until it is fetched again.
+    #[cfg(target_os = "macos")]
+    fn favicon_icon(&self, favicon: &TabFavicon, glyph_cache: &GlyphCache) -> Option<char> {
+        let key = GlyphKey {
+            font_key: glyph_cache.font_key,
+            size: glyph_cache.font_size,
+            character: favicon.character,
+        };
+        if self.favicons.is_pending(&key) {
+            Some(' ')
+        } else if glyph_cache.has_glyph(&key) {
+            Some(favicon.character)
+        } else {
+            None
+        }
     }
 
     fn panel_size_info(&self, size_info: &SizeInfo) -> SizeInfo {
@@ -871,8 +1573,15 @@ impl TabPanel {
         }
 
         let col = (position.x / cell_width).floor() as usize;
-        if let Some(inline_col) = self.inline_close_col(tab_id) {
-            return col == inline_col;
+        match self.close_button {
+            TabCloseButton::Hidden => return false,
+            TabCloseButton::HoverLeft => return col == 0,
+            TabCloseButton::HoverRight => {
+                if let Some(inline_col) = self.inline_close_col(tab_id) {
+                    return col == inline_col;
+                }
+            },
+            TabCloseButton::AlwaysRight => (),
         }
 
         let close_col = self.width_cols.saturating_sub(1);
@@ -883,6 +1592,85 @@ impl TabPanel {
         col == close_col
     }
 
+    /// Columns available to a tab title starting at `text_col`.
+    fn title_max_cols(&self, text_col: usize) -> usize {
+        let close_cols = match self.close_button {
+            TabCloseButton::HoverRight | TabCloseButton::AlwaysRight => 1,
+            TabCloseButton::Hidden | TabCloseButton::HoverLeft => 0,
+        };
+        self.width_cols.saturating_sub(text_col + close_cols)
+    }
+
+    /// Header background of a group, tinted with the group's color.
+    fn group_header_bg(&self, group_index: usize, header_bg: Rgb) -> Rgb {
+        match self.groups.get(group_index).and_then(|group| group.color) {
+            Some(color) => mix(header_bg, color, 0.35),
+            None => header_bg,
+        }
+    }
+
+    fn tab_group_color(&self, tab_id: TabId) -> Option<Rgb> {
+        let (_, group_index, _) = self.find_tab(tab_id)?;
+        self.groups[group_index].color
+    }
+
+    fn is_group_close_hit(
+        &self,
+        position: PhysicalPosition<f64>,
+        size_info: &SizeInfo,
+        group_index: usize,
+    ) -> bool {
+        if self.hover.group != Some(group_index) {
+            return false;
+        }
+
+        let cell_width = size_info.cell_width() as f64;
+        if cell_width <= 0.0 {
+            return false;
+        }
+
+        let close_col = self.width_cols.saturating_sub(1);
+        if close_col <= 1 {
+            return false;
+        }
+
+        (position.x / cell_width).floor() as usize == close_col
+    }
+
+    /// Column of the new tab button in group headers, left of the close glyph.
+    fn new_tab_col(&self) -> Option<usize> {
+        self.width_cols.checked_sub(2).filter(|&col| col > GROUP_HEADER_INDENT_COLS)
+    }
+
+    /// Button of the panel under `position`.
+    fn button_hit(
+        &self,
+        position: PhysicalPosition<f64>,
+        size_info: &SizeInfo,
+    ) -> Option<PanelButton> {
+        let cell_width = size_info.cell_width() as f64;
+        let top = size_info.padding_y() as f64;
+        if !self.is_inside_panel(position) || cell_width <= 0.0 || position.y < top {
+            return None;
+        }
+
+        let col = (position.x / cell_width).floor() as usize;
+        let line = ((position.y - top) / size_info.cell_height() as f64).floor() as usize;
+        let footer_line = self.list_lines(size_info);
+        if line == footer_line && line < size_info.screen_lines() {
+            let start = GROUP_HEADER_INDENT_COLS;
+            let button_cols = start..start + NEW_GROUP_BUTTON.len();
+            return button_cols.contains(&col).then_some(PanelButton::NewGroup);
+        }
+
+        match self.hit_test(position, size_info)? {
+            PanelHit::Group { group_index } if Some(col) == self.new_tab_col() => {
+                Some(PanelButton::NewTab { group_id: self.groups.get(group_index)?.id })
+            },
+            _ => None,
+        }
+    }
+
     fn inline_close_col(&self, tab_id: TabId) -> Option<usize> {
         if self.hover.tab != Some(tab_id) {
             return None;
@@ -892,17 +1680,25 @@ impl TabPanel {
 
         #[cfg(target_os = "macos")]
         if tab.favicon.is_some() {
-            let indicator_cols = if tab.activity.is_some() { ACTIVITY_INDICATOR_COLS } else { 0 };
+            let indicator_cols = self.activity_indicator_cols(&tab);
             return Some(TAB_INDENT_COLS + indicator_cols);
         }
 
-        if tab.activity.is_some() {
+        if self.has_activity_indicator(&tab) {
             return Some(TAB_INDENT_COLS);
         }
 
         None
     }
 
+    fn has_activity_indicator(&self, tab: &TabPanelTab) -> bool {
+        !self.hide_activity && tab.activity.is_some()
+    }
+
+    fn activity_indicator_cols(&self, tab: &TabPanelTab) -> usize {
+        if self.has_activity_indicator(tab) { ACTIVITY_INDICATOR_COLS } else { 0 }
+    }
+
     fn is_inside_panel(&self, position: PhysicalPosition<f64>) -> bool {
         position.x >= 0.0 && position.x < self.width_px as f64
     }
@@ -966,7 +1762,7 @@ impl TabPanel {
 
         let line_height = size_info.cell_height() as f64;
         let mut line = ((position.y - top) / line_height).floor() as usize;
-        let max_lines = size_info.screen_lines();
+        let max_lines = self.list_lines(size_info);
         if max_lines == 0 {
             return None;
         }
@@ -974,6 +1770,10 @@ impl TabPanel {
             line = max_lines - 1;
         }
 
+        let scroll_offset = self.scroll_offset(size_info);
+        let line = line + scroll_offset;
+        let max_lines = max_lines + scroll_offset;
+
         let mut current_line = 0;
 
         for (group_index, group) in self.groups.iter().enumerate() {
@@ -1028,7 +1828,7 @@ impl TabPanel {
             return None;
         }
 
-        let max_lines = size_info.screen_lines();
+        let max_lines = self.list_lines(size_info);
         if max_lines == 0 {
             return None;
         }
@@ -1036,9 +1836,9 @@ impl TabPanel {
         let line_height = size_info.cell_height() as f64;
         let mut line = ((position.y - top) / line_height).floor() as isize;
         line = line.clamp(0, (max_lines - 1) as isize);
-        let line = line as usize;
+        let line = line as usize + self.scroll_offset(size_info);
 
-        let layout = self.layout(size_info);
+        let layout = self.unscrolled_layout(size_info);
         let mut headers = Vec::new();
         for item in layout.items.iter() {
             if let PanelItemKind::GroupHeader { group_index } = item.kind {
@@ -1090,9 +1890,20 @@ impl TabPanel {
         })
     }
 
+    /// Items on the visible lines of the panel.
     fn layout(&self, size_info: &SizeInfo) -> PanelLayout {
+        let scroll_offset = self.scroll_offset(size_info);
+        let mut layout = self.unscrolled_layout(size_info);
+        layout.items.retain(|item| item.line >= scroll_offset);
+        for item in &mut layout.items {
+            item.line -= scroll_offset;
+        }
+        layout
+    }
+
+    fn unscrolled_layout(&self, size_info: &SizeInfo) -> PanelLayout {
         let mut items = Vec::new();
-        let max_lines = size_info.screen_lines();
+        let max_lines = self.layout_lines(size_info);
         let mut line = 0;
 
         for (group_index, group) in self.groups.iter().enumerate() {
@@ -1127,6 +1938,16 @@ impl TabPanel {
     }
 
     fn render_layout(&self, size_info: &SizeInfo) -> RenderLayout {
+        let scroll_offset = self.scroll_offset(size_info);
+        let mut layout = self.unscrolled_render_layout(size_info);
+        layout.items.retain(|item| item.line >= scroll_offset);
+        for item in &mut layout.items {
+            item.line -= scroll_offset;
+        }
+        layout
+    }
+
+    fn unscrolled_render_layout(&self, size_info: &SizeInfo) -> RenderLayout {
         if let Some(DragState { item, dragging: true, .. }) = self.drag.as_ref() {
             match item {
                 DragItem::Tab(tab_id) => {
@@ -1166,7 +1987,7 @@ impl TabPanel {
             }
         }
 
-        let layout = self.layout(size_info);
+        let layout = self.unscrolled_layout(size_info);
         let items = layout
             .items
             .into_iter()
@@ -1189,7 +2010,7 @@ impl TabPanel {
         target: TabDropTarget,
     ) -> RenderLayout {
         let mut items = Vec::new();
-        let max_lines = size_info.screen_lines();
+        let max_lines = self.layout_lines(size_info);
         let mut line = 0;
 
         let mut target_index = target.index;
@@ -1331,7 +2152,7 @@ impl TabPanel {
         target: GroupDropTarget,
     ) -> RenderLayout {
         let mut items = Vec::new();
-        let max_lines = size_info.screen_lines();
+        let max_lines = self.layout_lines(size_info);
         let mut line = 0;
 
         let effective_len = self.groups.len().saturating_sub(1);
@@ -1411,7 +2232,7 @@ impl TabPanel {
         drag_tab: TabPanelTab,
     ) -> RenderLayout {
         let mut items = Vec::new();
-        let max_lines = size_info.screen_lines();
+        let max_lines = self.layout_lines(size_info);
         let mut line = 0;
         let new_group_id = self.preview_group_id();
 
@@ -1502,7 +2323,7 @@ impl TabPanel {
         size_info: &SizeInfo,
         layout: &RenderLayout,
     ) -> Option<usize> {
-        let max_lines = size_info.screen_lines();
+        let max_lines = self.list_lines(size_info);
         if max_lines == 0 {
             return None;
         }
@@ -1526,12 +2347,18 @@ impl TabPanel {
 }
 
 impl EditState {
+    fn new(target: TabPanelEditTarget, text: String) -> Self {
+        let cursor = text.chars().count();
+        Self { target, text, cursor, undo: Vec::new(), redo: Vec::new(), typing: false }
+    }
+
     fn move_left(&mut self) -> bool {
         if self.cursor == 0 {
             return false;
         }
 
         self.cursor -= 1;
+        self.typing = false;
         true
     }
 
@@ -1542,6 +2369,7 @@ impl EditState {
         }
 
         self.cursor += 1;
+        self.typing = false;
         true
     }
 
@@ -1551,6 +2379,7 @@ impl EditState {
         }
 
         self.cursor = 0;
+        self.typing = false;
         true
     }
 
@@ -1561,14 +2390,58 @@ impl EditState {
         }
 
         self.cursor = len;
+        self.typing = false;
         true
     }
 
+    /// Record the text before a change, merging consecutively typed characters into one step.
+    fn checkpoint(&mut self, typing: bool) {
+        self.redo.clear();
+        if !(typing && self.typing) {
+            if self.undo.len() == EDIT_UNDO_DEPTH {
+                self.undo.remove(0);
+            }
+            self.undo.push(self.snapshot());
+        }
+        self.typing = typing;
+    }
+
+    fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo.pop() else {
+            return false;
+        };
+
+        self.redo.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo.pop() else {
+            return false;
+        };
+
+        self.undo.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    fn snapshot(&self) -> EditSnapshot {
+        EditSnapshot { text: self.text.clone(), cursor: self.cursor }
+    }
+
+    fn restore(&mut self, snapshot: EditSnapshot) {
+        self.text = snapshot.text;
+        self.cursor = snapshot.cursor;
+        self.typing = false;
+    }
+
     fn backspace(&mut self) -> bool {
         if self.cursor == 0 {
             return false;
         }
 
+        self.checkpoint(false);
         let start = char_to_byte_idx(&self.text, self.cursor - 1);
         let end = char_to_byte_idx(&self.text, self.cursor);
         self.text.replace_range(start..end, "");
@@ -1576,12 +2449,36 @@ impl EditState {
         true
     }
 
+    /// Delete the word before the cursor, along with the whitespace following it.
+    fn delete_word_back(&mut self) -> bool {
+        let before: Vec<char> = self.text.chars().take(self.cursor).collect();
+        let trailing_spaces = before.iter().rev().take_while(|ch| ch.is_whitespace()).count();
+        let word = before[..before.len() - trailing_spaces]
+            .iter()
+            .rev()
+            .take_while(|ch| !ch.is_whitespace())
+            .count();
+
+        let deleted = trailing_spaces + word;
+        if deleted == 0 {
+            return false;
+        }
+
+        self.checkpoint(false);
+        let start = char_to_byte_idx(&self.text, self.cursor - deleted);
+        let end = char_to_byte_idx(&self.text, self.cursor);
+        self.text.replace_range(start..end, "");
+        self.cursor -= deleted;
+        true
+    }
+
     fn delete(&mut self) -> bool {
         let len = self.text.chars().count();
         if self.cursor >= len {
             return false;
         }
 
+        self.checkpoint(false);
         let start = char_to_byte_idx(&self.text, self.cursor);
         let end = char_to_byte_idx(&self.text, self.cursor + 1);
         self.text.replace_range(start..end, "");
@@ -1600,6 +2497,7 @@ impl EditState {
             return false;
         }
 
+        self.checkpoint(filtered.chars().count() == 1);
         let idx = char_to_byte_idx(&self.text, self.cursor);
         self.text.insert_str(idx, &filtered);
         self.cursor += filtered.chars().count();
@@ -1610,13 +2508,16 @@ impl EditState {
 #[derive(Clone, Default, PartialEq, Eq)]
 struct HoverState {
     tab: Option<TabId>,
+    group: Option<usize>,
 }
 
 impl HoverState {
     fn from_hit(hit: &Option<PanelHit>) -> Self {
         match hit {
-            Some(PanelHit::Tab { tab_id }) => HoverState { tab: Some(*tab_id) },
-            Some(PanelHit::Group { .. }) => HoverState::default(),
+            Some(PanelHit::Tab { tab_id }) => HoverState { tab: Some(*tab_id), group: None },
+            Some(PanelHit::Group { group_index }) => {
+                HoverState { tab: None, group: Some(*group_index) }
+            },
             None => HoverState::default(),
         }
     }
@@ -1625,12 +2526,13 @@ impl HoverState {
 struct DragState {
     item: DragItem,
     start_pos: PhysicalPosition<f64>,
+    pressed_at: Instant,
     dragging: bool,
 }
 
 impl DragState {
     fn new(item: DragItem, start_pos: PhysicalPosition<f64>) -> Self {
-        Self { item, start_pos, dragging: false }
+        Self { item, start_pos, pressed_at: Instant::now(), dragging: false }
     }
 }
 
@@ -1660,15 +2562,16 @@ struct GroupDropTarget {
 
 struct ResizeState {
     offset: f64,
+    limits: PanelWidthLimits,
 }
 
 impl ResizeState {
-    fn new(width_px: f32, position: PhysicalPosition<f64>) -> Self {
-        Self { offset: width_px as f64 - position.x }
+    fn new(width_px: f32, position: PhysicalPosition<f64>, limits: PanelWidthLimits) -> Self {
+        Self { offset: width_px as f64 - position.x, limits }
     }
 
     fn width(&self, position: PhysicalPosition<f64>) -> f32 {
-        (position.x + self.offset).max(0.0) as f32
+        self.limits.apply((position.x + self.offset).max(0.0) as f32)
     }
 }
 
@@ -1706,12 +2609,18 @@ struct RenderLayout {
     items: Vec<RenderItem>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 enum PanelHit {
     Group { group_index: usize },
     Tab { tab_id: TabId },
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PanelButton {
+    NewTab { group_id: usize },
+    NewGroup,
+}
+
 #[derive(Default)]
 pub struct TabPanelCursorUpdate {
     pub capture: bool,
@@ -1727,6 +2636,13 @@ pub struct TabPanelMouseUpdate {
     pub command: Option<TabPanelCommand>,
 }
 
+#[derive(Default)]
+pub struct TabPanelKeyUpdate {
+    pub capture: bool,
+    pub needs_redraw: bool,
+    pub command: Option<TabPanelCommand>,
+}
+
 fn render_edit_text(text: &str, cursor: usize) -> String {
     let cursor = cursor.min(text.chars().count());
     let mut output = String::new();
@@ -1778,6 +2694,29 @@ fn truncate_to_columns(text: &str, max_cols: usize) -> String {
     output
 }
 
+/// Header text of a group, truncating the label to keep the muted marker visible.
+fn group_header_title(label: &str, muted: bool, max_cols: usize) -> String {
+    if !muted {
+        return truncate_to_columns(&format!("{label}:"), max_cols);
+    }
+
+    let label_cols = max_cols.saturating_sub(text_columns(GROUP_MUTED_MARKER) + 1);
+    let title = format!("{}:{GROUP_MUTED_MARKER}", truncate_to_columns(label, label_cols));
+    truncate_to_columns(&title, max_cols)
+}
+
+fn text_columns(text: &str) -> usize {
+    text.chars().map(|ch| ch.width().unwrap_or(0)).sum()
+}
+
+/// Full title of a truncated tab.
+struct Tooltip {
+    text: String,
+    line: usize,
+    column: usize,
+    columns: usize,
+}
+
 struct ActivityIndicator {
     glyph: char,
     color: Rgb,
@@ -1790,31 +2729,45 @@ fn tab_activity_indicator(
     fg: Rgb,
     config: &UiConfig,
 ) -> Option<ActivityIndicator> {
+    let activity_config = &config.window.tab_panel.activity;
+    if !activity_config.enabled {
+        return None;
+    }
+
     let activity = tab.activity.as_ref()?;
+    let filled = activity_config.filled.unwrap_or(ACTIVITY_INDICATOR_FILLED);
+
+    if tab.busy {
+        let color = if activity.is_active(now) {
+            activity_config.active.unwrap_or(config.colors.normal.green)
+        } else {
+            config.colors.normal.yellow
+        };
+        return Some(ActivityIndicator { glyph: ACTIVITY_INDICATOR_BUSY, color });
+    }
 
     if activity.is_active(now) {
         return Some(ActivityIndicator {
-            glyph: ACTIVITY_INDICATOR_FILLED,
-            color: config.colors.normal.green,
+            glyph: filled,
+            color: activity_config.active.unwrap_or(config.colors.normal.green),
         });
     }
 
     if activity.has_unseen_output {
-        let base_blue = config.colors.normal.blue;
-        let blue = Rgb::new(
-            base_blue.r,
-            base_blue.g.saturating_sub(0x28),
-            base_blue.b.saturating_add(0x28),
-        );
-        return Some(ActivityIndicator {
-            glyph: ACTIVITY_INDICATOR_FILLED,
-            color: blue,
+        let color = activity_config.unseen.unwrap_or_else(|| {
+            let base_blue = config.colors.normal.blue;
+            Rgb::new(
+                base_blue.r,
+                base_blue.g.saturating_sub(0x28),
+                base_blue.b.saturating_add(0x28),
+            )
         });
+        return Some(ActivityIndicator { glyph: filled, color });
     }
 
     Some(ActivityIndicator {
-        glyph: ACTIVITY_INDICATOR_OUTLINE,
-        color: mix(fg, base, 0.5),
+        glyph: activity_config.outline.unwrap_or(ACTIVITY_INDICATOR_OUTLINE),
+        color: activity_config.idle.unwrap_or_else(|| mix(fg, base, 0.5)),
     })
 }
 
@@ -1828,4 +2781,743 @@ fn mix(a: Rgb, b: Rgb, t: f32) -> Rgb {
     Rgb::new(mix_channel(a.r, b.r), mix_channel(a.g, b.g), mix_channel(a.b, b.b))
 }
 
+/// Drag threshold of panels without a configured threshold.
 const DRAG_THRESHOLD_PX: f64 = 4.0;
+
+/// Time an item has to be held before moving the mouse drags it.
+const DRAG_MIN_HOLD: Duration = Duration::from_millis(80);
+
+/// Progress of a page load in flight, `None` for terminals and loaded pages.
+fn load_progress(tab: &TabPanelTab) -> Option<f32> {
+    if !matches!(tab.kind, TabKind::Web { .. }) {
+        return None;
+    }
+    tab.load_progress.filter(|progress| *progress > 0. && *progress < 1.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tab_panel::TabActivity;
+
+    fn tab(activity: TabActivity) -> TabPanelTab {
+        TabPanelTab {
+            tab_id: TabId::new(0, 0),
+            title: String::from("zsh"),
+            is_active: false,
+            kind: TabKind::Terminal,
+            activity: Some(activity),
+            busy: false,
+            favicon: None,
+            load_progress: None,
+        }
+    }
+
+    #[test]
+    fn activity_indicator_disabled() {
+        let mut config = UiConfig::default();
+        config.window.tab_panel.activity.enabled = false;
+
+        let now = Instant::now();
+        let tab = tab(TabActivity { last_output: Some(now), has_unseen_output: true });
+        let (base, fg) = (Rgb::new(0, 0, 0), Rgb::new(0xff, 0xff, 0xff));
+        assert!(tab_activity_indicator(&tab, now, base, fg, &config).is_none());
+
+        let mut panel = TabPanel::new();
+        assert_eq!(panel.activity_indicator_cols(&tab), ACTIVITY_INDICATOR_COLS);
+        panel.set_activity_enabled(false);
+        assert_eq!(panel.activity_indicator_cols(&tab), 0);
+    }
+
+    #[test]
+    fn activity_indicator_busy_spinner() {
+        let config = UiConfig::default();
+        let now = Instant::now();
+        let (base, fg) = (Rgb::new(0, 0, 0), Rgb::new(0xff, 0xff, 0xff));
+
+        let mut busy = tab(TabActivity { last_output: None, has_unseen_output: true });
+        busy.busy = true;
+        let indicator = tab_activity_indicator(&busy, now, base, fg, &config).unwrap();
+        assert_eq!(indicator.glyph, ACTIVITY_INDICATOR_BUSY);
+        assert_eq!(indicator.color, config.colors.normal.yellow);
+
+        // Output of the running job keeps the active color.
+        busy.activity = Some(TabActivity { last_output: Some(now), has_unseen_output: false });
+        let indicator = tab_activity_indicator(&busy, now, base, fg, &config).unwrap();
+        assert_eq!(indicator.glyph, ACTIVITY_INDICATOR_BUSY);
+        assert_eq!(indicator.color, config.colors.normal.green);
+    }
+
+    #[test]
+    fn activity_indicator_custom_colors() {
+        let mut config = UiConfig::default();
+        let activity_config = &mut config.window.tab_panel.activity;
+        activity_config.active = Some(Rgb::new(0xff, 0, 0));
+        activity_config.unseen = Some(Rgb::new(0, 0xff, 0));
+        activity_config.idle = Some(Rgb::new(0, 0, 0xff));
+        activity_config.filled = Some('*');
+        activity_config.outline = Some('-');
+
+        let now = Instant::now();
+        let (base, fg) = (Rgb::new(0, 0, 0), Rgb::new(0xff, 0xff, 0xff));
+        let indicator = |activity| tab_activity_indicator(&tab(activity), now, base, fg, &config);
+
+        let active = indicator(TabActivity { last_output: Some(now), has_unseen_output: false });
+        let active = active.unwrap();
+        assert_eq!((active.glyph, active.color), ('*', Rgb::new(0xff, 0, 0)));
+
+        let unseen = indicator(TabActivity { last_output: None, has_unseen_output: true });
+        let unseen = unseen.unwrap();
+        assert_eq!((unseen.glyph, unseen.color), ('*', Rgb::new(0, 0xff, 0)));
+
+        let idle = indicator(TabActivity { last_output: None, has_unseen_output: false }).unwrap();
+        assert_eq!((idle.glyph, idle.color), ('-', Rgb::new(0, 0, 0xff)));
+    }
+
+    #[test]
+    fn tooltip_only_for_truncated_titles() {
+        let mut panel = TabPanel::new();
+        panel.width_cols = 12;
+
+        let mut short_tab = tab(TabActivity::default());
+        short_tab.title = String::from("zsh");
+        let mut long_tab = tab(TabActivity::default());
+        long_tab.tab_id = TabId::new(1, 0);
+        long_tab.title = String::from("vim ~/src/tabor/README.md");
+        let tabs = vec![short_tab.clone(), long_tab.clone()];
+        let label = String::from("1");
+        panel.groups = vec![TabPanelGroup { id: 0, label, color: None, muted: false, tabs }];
+
+        let now = Instant::now();
+        panel.hover.tab = Some(short_tab.tab_id);
+        panel.arm_tooltip(now);
+        assert_eq!(panel.tooltip_deadline(), None);
+
+        panel.hover.tab = Some(long_tab.tab_id);
+        panel.arm_tooltip(now);
+        assert_eq!(panel.tooltip_deadline(), Some(now + TAB_TOOLTIP_DELAY));
+
+        // Titles fitting exactly into the columns after the kind glyph are not truncated.
+        let mut panel_tab = tab(TabActivity::default());
+        panel_tab.title = String::from("12345");
+        assert!(!panel.title_truncated(&panel_tab));
+        panel_tab.title.push('9');
+        assert!(panel.title_truncated(&panel_tab));
+    }
+
+    #[test]
+    fn panel_width_clamped_to_limits() {
+        let mut config = UiConfig::default();
+        config.window.tab_panel.min_width = 120;
+        config.window.tab_panel.max_width = Some(300);
+
+        config.window.tab_panel.width = 10;
+        let dimensions = compute_panel_dimensions(&config, 10., 800., 0., 1.);
+        assert_eq!((dimensions.columns, dimensions.width), (12, 120.));
+
+        config.window.tab_panel.width = 500;
+        let dimensions = compute_panel_dimensions(&config, 10., 800., 0., 1.);
+        assert_eq!((dimensions.columns, dimensions.width), (30, 300.));
+
+        // Limits are in logical pixels.
+        let dimensions = compute_panel_dimensions(&config, 10., 800., 0., 2.);
+        assert_eq!((dimensions.columns, dimensions.width), (60, 600.));
+    }
+
+    #[test]
+    fn resize_width_limits() {
+        let mut config = TabPanelConfig::default();
+        config.min_width = 120;
+        config.max_width = Some(400);
+
+        let limits = PanelWidthLimits::new(&config, 1.);
+        let resize = ResizeState::new(200., PhysicalPosition::new(200., 10.), limits);
+        let width = |x: f64| resize.width(PhysicalPosition::new(x, 10.));
+        assert_eq!(width(250.), 250.);
+        assert_eq!(width(20.), 120.);
+        assert_eq!(width(600.), 400.);
+    }
+
+    #[test]
+    fn resize_snaps_to_nearest_width() {
+        let mut config = TabPanelConfig::default();
+        config.snap_widths = vec![200, 210, 300];
+
+        let limits = PanelWidthLimits::new(&config, 1.);
+        let resize = ResizeState::new(200., PhysicalPosition::new(200., 10.), limits);
+        let width = |x: f64| resize.width(PhysicalPosition::new(x, 10.));
+        assert_eq!(width(294.), 300.);
+        assert_eq!(width(197.), 200.);
+        assert_eq!(width(206.), 210.);
+        assert_eq!(width(280.), 280.);
+
+        // Snapping never escapes the width limits.
+        config.max_width = Some(250);
+        let limits = PanelWidthLimits::new(&config, 1.);
+        let resize = ResizeState::new(200., PhysicalPosition::new(200., 10.), limits);
+        assert_eq!(resize.width(PhysicalPosition::new(304., 10.)), 250.);
+    }
+
+    fn group_panel() -> (TabPanel, SizeInfo) {
+        let mut panel = TabPanel::new();
+        panel.set_enabled(true);
+        panel.set_dimensions(PanelDimensions { columns: 20, width: 200. });
+
+        // Panel rows are 24px high, putting group headers at lines 0, 4 and 7.
+        let group = |id: usize, tabs: &[u32]| {
+            let tabs = tabs
+                .iter()
+                .map(|&index| {
+                    let mut tab = tab(TabActivity::default());
+                    tab.tab_id = TabId::new(index, 0);
+                    tab
+                })
+                .collect();
+            TabPanelGroup { id, label: id.to_string(), color: None, muted: false, tabs }
+        };
+        panel.groups = vec![group(1, &[0, 1]), group(2, &[2]), group(3, &[3])];
+
+        let size_info = SizeInfo::new(800., 480., 10., 20., 0., 0., 0., false);
+        (panel, size_info)
+    }
+
+    fn drag(panel: &mut TabPanel, size_info: &SizeInfo, from_line: usize, to_line: usize) {
+        let position = |line: usize| PhysicalPosition::new(50., line as f64 * 24. + 12.);
+        panel.cursor_moved(position(from_line), size_info);
+        panel.mouse_input(ElementState::Pressed, MouseButton::Left, size_info);
+        hold_drag(panel);
+        panel.cursor_moved(position(to_line), size_info);
+    }
+
+    /// Pretend the pressed item has been held long enough to be dragged.
+    fn hold_drag(panel: &mut TabPanel) {
+        if let Some(drag) = panel.drag.as_mut() {
+            drag.pressed_at = Instant::now() - DRAG_MIN_HOLD;
+        }
+    }
+
+    #[test]
+    fn close_group_from_header() {
+        let (mut panel, size_info) = group_panel();
+        let panel_size_info = panel.panel_size_info(&size_info);
+        let close = PhysicalPosition::new(191., 4. * 24. + 12.);
+        let title = PhysicalPosition::new(50., 4. * 24. + 12.);
+
+        // The close glyph is only hit on the last column of a hovered header.
+        assert!(!panel.is_group_close_hit(close, &panel_size_info, 1));
+        panel.cursor_moved(close, &size_info);
+        assert!(panel.is_group_close_hit(close, &panel_size_info, 1));
+        assert!(!panel.is_group_close_hit(title, &panel_size_info, 1));
+        assert!(!panel.is_group_close_hit(close, &panel_size_info, 0));
+
+        panel.mouse_input(ElementState::Pressed, MouseButton::Left, &size_info);
+        assert!(panel.drag.is_none());
+        let update = panel.mouse_input(ElementState::Released, MouseButton::Left, &size_info);
+        assert!(matches!(update.command, Some(TabPanelCommand::CloseGroup(2))));
+
+        // Moving off the glyph before releasing cancels the close.
+        panel.mouse_input(ElementState::Pressed, MouseButton::Left, &size_info);
+        panel.cursor_moved(title, &size_info);
+        let update = panel.mouse_input(ElementState::Released, MouseButton::Left, &size_info);
+        assert!(update.command.is_none());
+    }
+
+    #[test]
+    fn new_tab_button_in_group_header() {
+        let (mut panel, size_info) = group_panel();
+        let panel_size_info = panel.panel_size_info(&size_info);
+        let button = |line: usize| PhysicalPosition::new(185., line as f64 * 24. + 12.);
+
+        // The button is on the column left of the close glyph of every group header.
+        let hit = |position| panel.button_hit(position, &panel_size_info);
+        assert_eq!(hit(button(0)), Some(PanelButton::NewTab { group_id: 1 }));
+        assert_eq!(hit(button(7)), Some(PanelButton::NewTab { group_id: 3 }));
+        assert_eq!(hit(button(1)), None);
+        assert_eq!(hit(PhysicalPosition::new(175., 12.)), None);
+        assert_eq!(hit(PhysicalPosition::new(195., 12.)), None);
+
+        panel.cursor_moved(button(4), &size_info);
+        panel.mouse_input(ElementState::Pressed, MouseButton::Left, &size_info);
+        assert!(panel.drag.is_none());
+        let update = panel.mouse_input(ElementState::Released, MouseButton::Left, &size_info);
+        assert!(matches!(update.command, Some(TabPanelCommand::NewTab { group_id: 2 })));
+
+        // Moving off the button before releasing cancels it.
+        panel.mouse_input(ElementState::Pressed, MouseButton::Left, &size_info);
+        panel.cursor_moved(button(5), &size_info);
+        let update = panel.mouse_input(ElementState::Released, MouseButton::Left, &size_info);
+        assert!(update.command.is_none());
+    }
+
+    #[test]
+    fn new_group_button_at_panel_bottom() {
+        let (mut panel, size_info) = group_panel();
+        let panel_size_info = panel.panel_size_info(&size_info);
+        let footer = |x: f64| PhysicalPosition::new(x, 19. * 24. + 12.);
+
+        assert_eq!(panel.button_hit(footer(15.), &panel_size_info), Some(PanelButton::NewGroup));
+        assert_eq!(panel.button_hit(footer(25.), &panel_size_info), Some(PanelButton::NewGroup));
+        assert_eq!(panel.button_hit(footer(5.), &panel_size_info), None);
+        assert_eq!(panel.button_hit(footer(35.), &panel_size_info), None);
+
+        panel.cursor_moved(footer(15.), &size_info);
+        panel.mouse_input(ElementState::Pressed, MouseButton::Left, &size_info);
+        let update = panel.mouse_input(ElementState::Released, MouseButton::Left, &size_info);
+        assert!(matches!(update.command, Some(TabPanelCommand::NewGroup)));
+
+        // The button line is never part of the scrolled tab list.
+        let size_info = SizeInfo::new(800., 120., 10., 20., 0., 0., 0., false);
+        panel.scroll(MouseScrollDelta::LineDelta(0., -10.), &size_info);
+        let panel_size_info = panel.panel_size_info(&size_info);
+        let position = PhysicalPosition::new(15., 4. * 24. + 12.);
+        assert!(panel.hit_test(position, &panel_size_info).is_none());
+        assert_eq!(panel.button_hit(position, &panel_size_info), Some(PanelButton::NewGroup));
+    }
+
+    #[test]
+    fn focus_group_from_header() {
+        let (mut panel, size_info) = group_panel();
+
+        drag(&mut panel, &size_info, 4, 4);
+        let update = panel.mouse_input(ElementState::Released, MouseButton::Left, &size_info);
+        assert!(matches!(update.command, Some(TabPanelCommand::FocusGroup(2))));
+        assert!(panel.drag.is_none());
+    }
+
+    #[test]
+    fn panel_focus_toggle() {
+        let (mut panel, _) = group_panel();
+        panel.groups[1].tabs[0].is_active = true;
+
+        assert!(panel.toggle_focus());
+        assert!(panel.is_focused());
+        assert_eq!(panel.focus_cursor, Some(TabId::new(2, 0)));
+
+        // The cursor stays on a tab when the active one is closed.
+        panel.set_groups(vec![panel.groups[0].clone()], None);
+        assert_eq!(panel.focus_cursor, Some(TabId::new(0, 0)));
+
+        assert!(panel.toggle_focus());
+        assert!(!panel.is_focused());
+        assert!(!panel.unfocus());
+
+        // Disabled panels can't take focus and give it back when hidden.
+        panel.toggle_focus();
+        panel.set_enabled(false);
+        assert!(!panel.is_focused());
+        assert!(!panel.toggle_focus());
+    }
+
+    #[test]
+    fn edit_delete_word_back() {
+        let target = TabPanelEditTarget::Group(1);
+        let text = String::from("build  logs   tail");
+        let mut edit = EditState::new(target, text);
+        edit.cursor = 14;
+
+        // Whitespace before the cursor is deleted along with the word before it.
+        assert!(edit.delete_word_back());
+        assert_eq!((edit.text.as_str(), edit.cursor), ("build  tail", 7));
+        assert!(edit.delete_word_back());
+        assert_eq!((edit.text.as_str(), edit.cursor), ("tail", 0));
+        assert!(!edit.delete_word_back());
+
+        let (mut panel, _) = group_panel();
+        panel.begin_edit_group(1, String::from("web  docs"));
+        let ctrl = ModifiersState::CONTROL;
+        let outcome = panel.edit_key(Key::Character("w"), "\u{17}", ctrl, ElementState::Pressed);
+        assert!(matches!(outcome, TabPanelEditOutcome::Changed));
+        assert_eq!(panel.edit.as_ref().unwrap().text, "web  ");
+    }
+
+    #[test]
+    fn edit_undo_redo() {
+        let mut edit = EditState::new(TabPanelEditTarget::Tab(TabId::new(0, 0)), String::new());
+        for ch in ["l", "o", "g", "s"] {
+            edit.insert_text(ch);
+        }
+        edit.insert_text(" tail");
+        edit.backspace();
+
+        assert!(edit.undo());
+        assert_eq!((edit.text.as_str(), edit.cursor), ("logs tail", 9));
+        assert!(edit.undo());
+        assert_eq!(edit.text, "logs");
+
+        // Consecutively typed characters are undone at once.
+        assert!(edit.undo());
+        assert_eq!((edit.text.as_str(), edit.cursor), ("", 0));
+        assert!(!edit.undo());
+
+        assert!(edit.redo());
+        assert_eq!((edit.text.as_str(), edit.cursor), ("logs", 4));
+        assert!(edit.redo());
+        assert_eq!(edit.text, "logs tail");
+
+        // New changes drop the undone ones.
+        edit.insert_text("!");
+        assert!(!edit.redo());
+        assert_eq!(edit.text, "logs tail!");
+
+        let mut edit = EditState::new(TabPanelEditTarget::Group(1), String::from("a"));
+        for _ in 0..EDIT_UNDO_DEPTH + 10 {
+            edit.backspace();
+            edit.insert_text("b");
+            edit.move_left();
+        }
+        assert_eq!(edit.undo.len(), EDIT_UNDO_DEPTH);
+    }
+
+    #[test]
+    fn edit_undo_keys() {
+        let (mut panel, _) = group_panel();
+        panel.begin_edit_tab(TabId::new(0, 0), String::from("zsh"));
+        panel.paste("!");
+
+        let ctrl = ModifiersState::CONTROL;
+        let pressed = ElementState::Pressed;
+        let outcome = panel.edit_key(Key::Character("z"), "\u{1a}", ctrl, pressed);
+        assert!(matches!(outcome, TabPanelEditOutcome::Changed));
+        assert_eq!(panel.edit.as_ref().unwrap().text, "zsh");
+        let outcome = panel.edit_key(Key::Character("y"), "\u{19}", ctrl, pressed);
+        assert!(matches!(outcome, TabPanelEditOutcome::Changed));
+        assert_eq!(panel.edit.as_ref().unwrap().text, "zsh!");
+
+        // Editing again starts without history.
+        panel.cancel_edit();
+        panel.begin_edit_tab(TabId::new(0, 0), String::from("zsh"));
+        let outcome = panel.edit_key(Key::Character("z"), "\u{1a}", ctrl, pressed);
+        assert!(matches!(outcome, TabPanelEditOutcome::None));
+    }
+
+    #[test]
+    fn edit_paste_at_cursor() {
+        let (mut panel, _) = group_panel();
+        panel.begin_edit_tab(TabId::new(0, 0), String::from("zsh"));
+        panel.edit.as_mut().unwrap().cursor = 1;
+
+        let ctrl = ModifiersState::CONTROL;
+        let outcome = panel.edit_key(Key::Character("v"), "\u{16}", ctrl, ElementState::Pressed);
+        assert!(matches!(outcome, TabPanelEditOutcome::Paste));
+
+        // Control characters of the clipboard text are dropped.
+        assert!(matches!(panel.paste("ab\ncd"), TabPanelEditOutcome::Changed));
+        let edit = panel.edit.as_ref().unwrap();
+        assert_eq!((edit.text.as_str(), edit.cursor), ("zabcdsh", 5));
+        assert!(matches!(panel.paste("\n"), TabPanelEditOutcome::None));
+    }
+
+    #[test]
+    fn panel_keys_only_routed_when_focused() {
+        let (mut panel, _) = group_panel();
+        let pressed = ElementState::Pressed;
+
+        let update = panel.panel_key(Key::Character("j"), pressed);
+        assert!(!update.capture);
+        assert!(!panel.is_focused());
+
+        panel.toggle_focus();
+        let update = panel.panel_key(Key::Character("j"), pressed);
+        assert!(update.capture && update.needs_redraw);
+        assert_eq!(panel.focus_cursor, Some(TabId::new(1, 0)));
+        panel.panel_key(Key::Named(NamedKey::ArrowDown), pressed);
+        panel.panel_key(Key::Named(NamedKey::ArrowDown), pressed);
+        panel.panel_key(Key::Named(NamedKey::ArrowDown), pressed);
+        assert_eq!(panel.focus_cursor, Some(TabId::new(3, 0)));
+        panel.panel_key(Key::Character("k"), pressed);
+        assert_eq!(panel.focus_cursor, Some(TabId::new(2, 0)));
+
+        // Other keys are swallowed instead of reaching the tab.
+        let update = panel.panel_key(Key::Character("x"), pressed);
+        assert!(update.capture && update.command.is_none());
+        assert!(panel.panel_key(Key::Character("x"), ElementState::Released).capture);
+
+        let update = panel.panel_key(Key::Named(NamedKey::Enter), pressed);
+        let selected = TabId::new(2, 0);
+        assert!(matches!(update.command, Some(TabPanelCommand::Focus(id)) if id == selected));
+        assert!(!panel.is_focused());
+
+        // Escape returns focus to the tab without selecting another one.
+        panel.toggle_focus();
+        let update = panel.panel_key(Key::Named(NamedKey::Escape), pressed);
+        assert!(update.capture && update.command.is_none());
+        assert!(!panel.is_focused());
+        assert!(!panel.panel_key(Key::Named(NamedKey::Escape), pressed).capture);
+    }
+
+    #[test]
+    fn panel_focus_ring() {
+        let (mut panel, size_info) = group_panel();
+        let config = UiConfig::default();
+        let ring_rects = |panel: &TabPanel| {
+            let mut rects = Vec::new();
+            panel.push_rects(&size_info, &config, &mut rects);
+            let ring = |rect: &&RenderRect| {
+                rect.width == FOCUS_RING_WIDTH_PX || rect.height == FOCUS_RING_WIDTH_PX
+            };
+            rects.iter().filter(ring).count()
+        };
+
+        assert_eq!(ring_rects(&panel), 0);
+        panel.toggle_focus();
+        assert_eq!(ring_rects(&panel), 4);
+    }
+
+    #[test]
+    fn group_color_tints_header() {
+        let (mut panel, size_info) = group_panel();
+        panel.groups[1].color = Some(Rgb::new(0xcc, 0x66, 0x66));
+
+        let config = UiConfig::default();
+        let mut rects = Vec::new();
+        panel.push_rects(&size_info, &config, &mut rects);
+
+        // Rows are 24px high, with group headers at lines 0 and 4.
+        let row_color = |line: usize| {
+            let y = line as f32 * 24.;
+            let mut rows = rects.iter().filter(|rect| rect.width == 200. && rect.height == 24.);
+            rows.find(|rect| rect.y == y).unwrap().color
+        };
+        assert_ne!(row_color(4), row_color(0));
+        assert_eq!(row_color(7), row_color(0));
+
+        // Only tabs of the colored group have a tinted edge.
+        let edges: Vec<_> = rects.iter().filter(|rect| rect.width == GROUP_EDGE_WIDTH_PX).collect();
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].y, 5. * 24.);
+    }
+
+    #[test]
+    fn load_progress_bar() {
+        let (mut panel, size_info) = group_panel();
+        let url = String::from("https://example.com");
+        panel.groups[1].tabs[0].kind = TabKind::Web { url, private: false };
+
+        let config = UiConfig::default();
+        let accent = config.colors.normal.blue;
+        let progress_rects = |panel: &TabPanel| {
+            let mut rects = Vec::new();
+            panel.push_rects(&size_info, &config, &mut rects);
+            rects.retain(|rect| rect.height == LOAD_PROGRESS_HEIGHT_PX && rect.color == accent);
+            rects
+        };
+
+        panel.groups[1].tabs[0].load_progress = Some(0.5);
+        let rects = progress_rects(&panel);
+        assert_eq!(rects.len(), 1);
+        assert_eq!(rects[0].width, 100.);
+        assert_eq!(rects[0].y, 6. * 24. - LOAD_PROGRESS_HEIGHT_PX);
+
+        // Finished loads and terminals don't show any progress.
+        panel.groups[1].tabs[0].load_progress = Some(1.);
+        assert!(progress_rects(&panel).is_empty());
+        panel.groups[1].tabs[0].load_progress = None;
+        assert!(progress_rects(&panel).is_empty());
+        panel.groups[0].tabs[0].load_progress = Some(0.5);
+        assert!(progress_rects(&panel).is_empty());
+    }
+
+    #[test]
+    fn close_button_position() {
+        let (mut panel, size_info) = group_panel();
+        let panel_size_info = panel.panel_size_info(&size_info);
+        panel.set_activity_enabled(false);
+        let tab_id = TabId::new(0, 0);
+        panel.hover.tab = Some(tab_id);
+
+        let first = PhysicalPosition::new(5., 36.);
+        let last = PhysicalPosition::new(195., 36.);
+        let close_hits = |panel: &TabPanel| {
+            let hit = |position| panel.is_close_hit(position, &panel_size_info, tab_id);
+            (hit(first), hit(last))
+        };
+
+        assert_eq!(close_hits(&panel), (false, true));
+        panel.set_close_button(TabCloseButton::AlwaysRight);
+        assert_eq!(close_hits(&panel), (false, true));
+        panel.set_close_button(TabCloseButton::HoverLeft);
+        assert_eq!(close_hits(&panel), (true, false));
+        panel.set_close_button(TabCloseButton::Hidden);
+        assert_eq!(close_hits(&panel), (false, false));
+
+        // Hovered tabs with an activity indicator replace it with the close button.
+        panel.set_activity_enabled(true);
+        panel.set_close_button(TabCloseButton::HoverRight);
+        let indicator = PhysicalPosition::new(15., 36.);
+        assert!(panel.is_close_hit(indicator, &panel_size_info, tab_id));
+        panel.set_close_button(TabCloseButton::AlwaysRight);
+        assert!(!panel.is_close_hit(indicator, &panel_size_info, tab_id));
+    }
+
+    #[test]
+    fn close_button_title_width() {
+        let (mut panel, _) = group_panel();
+
+        // Titles start after the indent and the two indicator columns.
+        assert_eq!(panel.title_max_cols(3), 16);
+        panel.set_close_button(TabCloseButton::AlwaysRight);
+        assert_eq!(panel.title_max_cols(3), 16);
+        panel.set_close_button(TabCloseButton::HoverLeft);
+        assert_eq!(panel.title_max_cols(3), 17);
+        panel.set_close_button(TabCloseButton::Hidden);
+        assert_eq!(panel.title_max_cols(3), 17);
+
+        let mut tab = tab(TabActivity::default());
+        tab.title = "a".repeat(17 - panel.favicon_cols());
+        assert!(!panel.title_truncated(&tab));
+        panel.set_close_button(TabCloseButton::HoverRight);
+        assert!(panel.title_truncated(&tab));
+    }
+
+    #[test]
+    fn drag_near_top_edge_scrolls_up() {
+        let (mut panel, _) = group_panel();
+        let size_info = SizeInfo::new(800., 120., 10., 20., 0., 0., 0., false);
+        panel.scroll_offset = 2;
+
+        // Only four of the nine panel lines fit, the second group's tab is on line 3.
+        drag(&mut panel, &size_info, 3, 0);
+        assert!(panel.drag_scroll_pending(&size_info));
+        assert_eq!(panel.drag_scroll_direction(&size_info), Some(-1));
+
+        assert!(panel.drag_scroll(&size_info));
+        assert!(panel.drag_scroll(&size_info));
+        assert_eq!(panel.scroll_offset, 0);
+        assert!(!panel.drag_scroll(&size_info));
+        assert!(!panel.drag_scroll_pending(&size_info));
+        assert_eq!(panel.scroll_offset, 0);
+
+        // The drop target follows the list scrolling under the cursor.
+        let target = TabDropTarget { group_index: 0, group_id: 1, index: 0 };
+        assert!(panel.drop_target == Some(DropTarget::Tab(target)));
+    }
+
+    #[test]
+    fn row_metrics_configured() {
+        let (mut panel, size_info) = group_panel();
+        assert_eq!(panel.panel_cell_height(&size_info), 24.);
+        assert_eq!(panel.favicon_cols(), 3);
+
+        let config = TabPanelConfig { row_padding: 10., ..TabPanelConfig::default() };
+        panel.set_row_metrics(&config);
+        assert_eq!(panel.panel_cell_height(&size_info), 30.);
+        assert_eq!(panel.panel_size_info(&size_info).cell_height(), 30.);
+
+        // Larger icons raise the smallest row height and push titles further right.
+        let config = TabPanelConfig { icon_scale: 3.5, ..TabPanelConfig::default() };
+        panel.set_row_metrics(&config);
+        assert_eq!(panel.panel_cell_height(&size_info), 39.);
+        assert_eq!(panel.favicon_cols(), 5);
+    }
+
+    #[test]
+    fn tab_label_kind_glyph() {
+        let panel = TabPanel::new();
+
+        let terminal = tab(TabActivity::default());
+        assert_eq!(panel.tab_label(&terminal, None, "zsh"), ">  zsh");
+
+        // Web tabs show their favicon once it is loaded.
+        let mut web = tab(TabActivity::default());
+        web.kind = TabKind::Web { url: String::from("https://example.com"), private: false };
+        assert_eq!(panel.tab_label(&web, None, "Example"), "@  Example");
+        assert_eq!(panel.tab_label(&web, Some('\u{E000}'), "Example"), "\u{E000}  Example");
+    }
+
+    #[test]
+    fn drag_threshold_scaled() {
+        let (mut panel, size_info) = group_panel();
+        panel.set_drag_threshold(&TabPanelConfig::default(), 2.);
+        let start = PhysicalPosition::new(50., 36.);
+        panel.cursor_moved(start, &size_info);
+        panel.mouse_input(ElementState::Pressed, MouseButton::Left, &size_info);
+        hold_drag(&mut panel);
+
+        // The default threshold of 4 logical pixels is 8 pixels at twice the scale.
+        panel.cursor_moved(PhysicalPosition::new(start.x, start.y + 6.), &size_info);
+        assert!(panel.drag.as_ref().is_some_and(|drag| !drag.dragging));
+        panel.cursor_moved(PhysicalPosition::new(start.x, start.y + 9.), &size_info);
+        assert!(panel.drag.as_ref().is_some_and(|drag| drag.dragging));
+    }
+
+    #[test]
+    fn quick_click_does_not_drag() {
+        let (mut panel, size_info) = group_panel();
+        let start = PhysicalPosition::new(50., 36.);
+        panel.cursor_moved(start, &size_info);
+        panel.mouse_input(ElementState::Pressed, MouseButton::Left, &size_info);
+
+        // Moving past the threshold right after the press keeps it a click.
+        panel.cursor_moved(PhysicalPosition::new(start.x + 20., start.y + 6.), &size_info);
+        assert!(panel.drag.as_ref().is_some_and(|drag| !drag.dragging));
+        let update = panel.mouse_input(ElementState::Released, MouseButton::Left, &size_info);
+        assert!(matches!(update.command, Some(TabPanelCommand::Focus(_))));
+    }
+
+    #[test]
+    fn wheel_scroll_clamped_to_list() {
+        let (mut panel, _) = group_panel();
+        let size_info = SizeInfo::new(800., 120., 10., 20., 0., 0., 0., false);
+
+        assert!(panel.scroll(MouseScrollDelta::LineDelta(0., -10.), &size_info));
+        assert_eq!(panel.scroll_offset, 5);
+        assert!(!panel.scroll(MouseScrollDelta::LineDelta(0., -1.), &size_info));
+
+        // Pixel deltas only scroll once they add up to a full line.
+        let delta = |y| MouseScrollDelta::PixelDelta(PhysicalPosition::new(0., y));
+        assert!(!panel.scroll(delta(12.), &size_info));
+        assert!(panel.scroll(delta(12.), &size_info));
+        assert_eq!(panel.scroll_offset, 4);
+    }
+
+    #[test]
+    fn drag_group_past_another() {
+        let (mut panel, size_info) = group_panel();
+
+        drag(&mut panel, &size_info, 0, 7);
+        assert!(matches!(panel.drop_target, Some(DropTarget::Group(GroupDropTarget { index: 2 }))));
+
+        let update = panel.mouse_input(ElementState::Released, MouseButton::Left, &size_info);
+        assert!(matches!(
+            update.command,
+            Some(TabPanelCommand::MoveGroup { group_id: 1, target_index: 2 })
+        ));
+        assert!(panel.drag.is_none());
+        assert!(panel.drop_target.is_none());
+    }
+
+    #[test]
+    fn drag_group_over_tabs() {
+        let (mut panel, size_info) = group_panel();
+
+        // Dropping a group onto a tab row moves the group rather than the tab.
+        drag(&mut panel, &size_info, 7, 1);
+        let update = panel.mouse_input(ElementState::Released, MouseButton::Left, &size_info);
+        assert!(matches!(
+            update.command,
+            Some(TabPanelCommand::MoveGroup { group_id: 3, target_index: 1 })
+        ));
+
+        // Presses on a tab during a group drag don't turn it into a tab drag.
+        drag(&mut panel, &size_info, 4, 1);
+        panel.mouse_input(ElementState::Pressed, MouseButton::Left, &size_info);
+        let item = panel.drag.as_ref().map(|drag| drag.item);
+        assert!(matches!(item, Some(DragItem::Group { group_id: 2 })));
+        let update = panel.mouse_input(ElementState::Released, MouseButton::Left, &size_info);
+        assert!(matches!(
+            update.command,
+            Some(TabPanelCommand::MoveGroup { group_id: 2, target_index: 1 })
+        ));
+    }
+
+    #[test]
+    fn group_header_keeps_muted_marker() {
+        assert_eq!(group_header_title("work", false, 20), "work:");
+        assert_eq!(group_header_title("work", true, 20), "work: (muted)");
+
+        // Long labels are truncated before the marker.
+        assert_eq!(group_header_title("notifications", true, 14), "notif: (muted)");
+        assert_eq!(group_header_title("notifications", false, 8), "notifica");
+    }
+}
@@ -235,7 +235,6 @@ impl Window {
         self.window.set_visible(visibility);
     }
 
-    #[cfg(target_os = "macos")]
     #[inline]
     pub fn focus_window(&self) {
         self.window.focus_window();
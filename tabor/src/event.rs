@@ -9,10 +9,9 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::error::Error;
 use std::ffi::OsStr;
 use std::fmt::Debug;
+use std::io::ErrorKind;
 #[cfg(not(windows))]
 use std::os::unix::io::RawFd;
-#[cfg(unix)]
-use std::os::unix::net::UnixStream;
 use std::path::PathBuf;
 use std::rc::Rc;
 #[cfg(unix)]
@@ -52,10 +51,11 @@ use tabor_terminal::vte::ansi::NamedColor;
 
 #[cfg(unix)]
 use crate::cli::ParsedOptions;
-use crate::cli::{Options as CliOptions, WindowOptions};
+use crate::cli::{Options as CliOptions, TerminalOptions, WindowOptions};
 use crate::clipboard::Clipboard;
 use crate::config::Action;
-use crate::config::ui_config::{HintAction, HintInternalAction};
+use crate::config::web::Bookmarklet;
+use crate::config::ui_config::{HintAction, HintInternalAction, config_diff};
 use crate::config::{self, UiConfig};
 #[cfg(not(windows))]
 use crate::daemon::foreground_process_path;
@@ -64,27 +64,36 @@ use crate::display::color::Rgb;
 use crate::display::hint::HintMatch;
 use crate::display::window::{ImeInhibitor, Window};
 use crate::display::{Display, Preedit, SizeInfo};
+use crate::history::UrlHistory;
 use crate::input::{self, ActionContext as _, FONT_SIZE_STEP};
 #[cfg(unix)]
-use crate::ipc::{self, IpcRequest, SocketReply};
+use crate::ipc::{self, IpcRequest, IpcStream, SocketReply};
 use crate::logging::{LOG_TARGET_CONFIG, LOG_TARGET_WINIT};
 use crate::message_bar::{Message, MessageBuffer};
 use crate::scheduler::{Scheduler, TimerId, Topic};
+use crate::session::{self, SessionGroup};
 use crate::tab_panel::TAB_ACTIVITY_TICK_INTERVAL;
 use crate::tabs::{TabCommand, TabId};
-use crate::web_url::normalize_web_url;
+use crate::web_url::parse_command_bar;
+#[cfg(target_os = "macos")]
+use crate::web_url::{EXTERNAL_OPENER, external_url, normalize_omnibar_input, normalize_web_url};
 use crate::window_kind::WindowKind;
 use crate::window_context::WindowContext;
 #[cfg(target_os = "macos")]
 use objc2_app_kit::NSEventModifierFlags;
 #[cfg(target_os = "macos")]
-use crate::macos::web_commands::{self, WebActions, WebCommandState, WebHintAction, WebKey};
+use crate::macos::web_commands::{
+    self, WebActions, WebCommandState, WebFieldKind, WebHintAction, WebHintMatch, WebKey,
+    WebNavigationError, WebScrollAnchor,
+};
 #[cfg(target_os = "macos")]
 use crate::macos::web_cursor::{web_cursor_from_css, web_cursor_script, WEB_CURSOR_BOOTSTRAP};
 #[cfg(target_os = "macos")]
 use crate::macos::favicon::FaviconImage;
 #[cfg(target_os = "macos")]
-use crate::macos::webview::WebView;
+use crate::macos::web_hibernation::HibernatedPage;
+#[cfg(target_os = "macos")]
+use crate::macos::webview::{clear_website_data, WebView, WebsiteDataTypes};
 #[cfg(target_os = "macos")]
 use url::Url;
 
@@ -94,6 +103,9 @@ pub const TYPING_SEARCH_DELAY: Duration = Duration::from_millis(500);
 /// Minimum delay between foreground process name refreshes.
 const FOREGROUND_PROCESS_REFRESH: Duration = Duration::from_millis(500);
 
+/// Delay before changed tabs are written to the session file, to batch bursts of changes.
+const SESSION_SAVE_DELAY: Duration = Duration::from_secs(1);
+
 #[cfg(target_os = "macos")]
 const WEB_HINTS_BOOTSTRAP: &str = r##"
 (function() {
@@ -162,13 +174,15 @@ const WEB_HINTS_BOOTSTRAP: &str = r##"
     window.__taborHintsState = { container: container, hints: hints };
     return hints.length;
   }
-  function update(keys) {
+  function update(keys, keep) {
     const state = window.__taborHintsState;
     if (!state) return "";
     let matched = null;
+    const candidates = [];
     for (const hint of state.hints) {
       if (hint.label.indexOf(keys) === 0) {
         hint.marker.style.display = "block";
+        candidates.push(hint);
         if (hint.label === keys) {
           matched = hint;
         }
@@ -176,16 +190,33 @@ const WEB_HINTS_BOOTSTRAP: &str = r##"
         hint.marker.style.display = "none";
       }
     }
+    if (matched && keep) {
+      matched.marker.style.background = "#9ad27a";
+      return JSON.stringify({ url: matched.href || "", complete: true });
+    }
     if (matched) {
       clearState();
-      return matched.href || "";
+      return JSON.stringify({ url: matched.href || "", complete: true });
+    }
+    if (keys && candidates.length === 1) {
+      return JSON.stringify({ url: candidates[0].href || "", complete: false });
     }
     return "";
   }
+  function resolve(labels) {
+    const state = window.__taborHintsState;
+    const urls = [];
+    for (const label of labels) {
+      const hint = state && state.hints.find((hint) => hint.label === label);
+      if (hint && hint.href) urls.push(hint.href);
+    }
+    clearState();
+    return JSON.stringify(urls);
+  }
   function cancel() {
     clearState();
   }
-  window.__taborHints = { start: start, update: update, cancel: cancel };
+  window.__taborHints = { start: start, update: update, resolve: resolve, cancel: cancel };
 })();
 "##;
 
@@ -195,32 +226,47 @@ Navigation:
   j/k/h/l    scroll
   d/u        half page
   gg/G       top/bottom
+  NN%        jump to percentage
   zH/zL      far left/right
+  zz/zt/zb   center/top/bottom element under mouse
 Links & inputs:
   f/F        open link / open in new tab
+  gf         open link in background tab
+  ;f         queue links, open in background on Esc
+  ;b         run bookmarklet
   yf         copy link URL
-  gi         focus input (insert mode)
+  gi/Ngi     focus first/Nth input (insert mode)
+  gn/gp      focus next/previous input
 Find & visual:
-  /          find
+  /          find (\C match case, \r regex)
   n/N        next/previous match
   v/V        visual/visual line
+  gc         caret browsing (h/j/k/l/w/b, v to select)
+  a/i        select page/element (visual)
   y          copy selection (visual)
 History & URL:
   H/L        back/forward
   yy         copy URL
+  yt/yd      copy title/domain
+  yM         copy markdown link
+  yl         copy visible link URLs
   p/P        open clipboard URL / new tab
   gu/gU      up one level / root
+  gx         open in external browser
+  gh         open from history
 Tabs & omnibar:
   t          new tab
   x/X        close/restore tab
+  gD         duplicate tab
   J/K        prev/next tab
   g0/g$      first/last tab
   o/O        omnibar / new tab
   b/B        bookmarks / new tab
   T          tab search
 Misc:
-  r          reload
+  r/R        reload / reload bypassing cache
   gs         view source
+  gm         toggle mute
   [[/]]      previous/next link
   m/`        set/jump mark
   ?          help
@@ -229,6 +275,14 @@ Misc:
 #[cfg(target_os = "macos")]
 const WEB_CURSOR_THROTTLE: Duration = Duration::from_millis(100);
 
+/// Form fields of a web page which can be focused by `gi` and `gn`/`gp`.
+#[cfg(target_os = "macos")]
+const WEB_FIELD_SELECTOR: &str =
+    "input:not([type='hidden']), textarea, select, [contenteditable='true']";
+
+/// Delay before window resizes are applied to the terminal and PTY, to coalesce bursts of resizes.
+const RESIZE_DEBOUNCE: Duration = Duration::from_millis(16);
+
 /// Maximum number of lines for the blocking search while still typing the search regex.
 const MAX_SEARCH_WHILE_TYPING: Option<usize> = Some(1000);
 
@@ -259,6 +313,8 @@ pub struct Processor {
     gl_config: Option<GlutinConfig>,
     #[cfg(unix)]
     global_ipc_options: ParsedOptions,
+    #[cfg(unix)]
+    ipc_subscribers: ipc::IpcSubscribers,
     cli_options: CliOptions,
     config: Rc<UiConfig>,
 }
@@ -295,14 +351,19 @@ impl ipc::IpcContext for IpcWindowContext<'_> {
         options: WindowOptions,
         group_id: Option<usize>,
         group_name: Option<String>,
+        focus: bool,
     ) -> Result<TabId, ipc::IpcError> {
-        self.window.ipc_create_tab(options, group_id, group_name, self.event_proxy)
+        self.window.ipc_create_tab(options, group_id, group_name, focus, self.event_proxy)
     }
 
     fn create_group(&mut self, name: Option<String>) -> Result<usize, ipc::IpcError> {
         self.window.ipc_create_group(name)
     }
 
+    fn duplicate_tab(&mut self, tab_id: TabId) -> Result<TabId, ipc::IpcError> {
+        self.window.ipc_duplicate_tab(tab_id, self.event_proxy)
+    }
+
     fn close_tab(&mut self, tab_id: TabId) -> Result<bool, ipc::IpcError> {
         self.window.ipc_close_tab(tab_id)
     }
@@ -325,12 +386,41 @@ impl ipc::IpcContext for IpcWindowContext<'_> {
         self.window.ipc_set_tab_title(tab_id, title)
     }
 
+    fn set_tab_title_template(
+        &mut self,
+        tab_id: TabId,
+        template: Option<String>,
+    ) -> Result<(), ipc::IpcError> {
+        self.window.ipc_set_tab_title_template(tab_id, template)
+    }
+
     fn set_group_name(&mut self, group_id: usize, name: Option<String>) -> Result<(), ipc::IpcError> {
         self.window.ipc_set_group_name(group_id, name)
     }
 
-    fn restore_closed_tab(&mut self) -> Result<(), ipc::IpcError> {
-        self.window.ipc_restore_closed_tab(self.event_proxy)
+    fn set_group_color(
+        &mut self,
+        group_id: usize,
+        color: Option<Rgb>,
+    ) -> Result<(), ipc::IpcError> {
+        self.window.ipc_set_group_color(group_id, color)
+    }
+
+    fn set_group_defaults(
+        &mut self,
+        group_id: usize,
+        cwd: Option<PathBuf>,
+        homepage: Option<String>,
+    ) -> Result<(), ipc::IpcError> {
+        self.window.ipc_set_group_defaults(group_id, cwd, homepage)
+    }
+
+    fn restore_closed_tab(&mut self, count: usize) -> Result<(), ipc::IpcError> {
+        self.window.ipc_restore_closed_tab(count, self.event_proxy)
+    }
+
+    fn closed_tabs(&self) -> Vec<ipc::IpcClosedTab> {
+        self.window.ipc_closed_tabs()
     }
 
     fn open_url_in_tab(&mut self, tab_id: TabId, url: String) -> Result<(), ipc::IpcError> {
@@ -341,9 +431,56 @@ impl ipc::IpcContext for IpcWindowContext<'_> {
         self.window.ipc_open_url_new_tab(url, self.event_proxy)
     }
 
-    fn reload_web(&mut self, tab_id: TabId) -> Result<(), ipc::IpcError> {
-        self.window
-            .ipc_reload_web(tab_id, self.event_loop, self.event_proxy, self.clipboard, self.scheduler)
+    fn reload_web(&mut self, tab_id: TabId, ignore_cache: bool) -> Result<(), ipc::IpcError> {
+        self.window.ipc_reload_web(
+            tab_id,
+            ignore_cache,
+            self.event_loop,
+            self.event_proxy,
+            self.clipboard,
+            self.scheduler,
+        )
+    }
+
+    fn open_external(&mut self, tab_id: TabId) -> Result<(), ipc::IpcError> {
+        self.window.ipc_open_external(tab_id)
+    }
+
+    fn run_bookmarklet(&mut self, tab_id: TabId, name: &str) -> Result<(), ipc::IpcError> {
+        self.window.ipc_run_bookmarklet(tab_id, name)
+    }
+
+    fn set_web_zoom(&mut self, tab_id: TabId, zoom: f64) -> Result<(), ipc::IpcError> {
+        self.window.ipc_set_web_zoom(tab_id, zoom)
+    }
+
+    fn set_web_muted(&mut self, tab_id: TabId, muted: bool) -> Result<(), ipc::IpcError> {
+        self.window.ipc_set_web_muted(tab_id, muted)
+    }
+
+    fn hibernate_web_tab(&mut self, tab_id: TabId) -> Result<(), ipc::IpcError> {
+        self.window.ipc_hibernate_web_tab(tab_id, self.event_proxy)
+    }
+
+    fn set_web_headers(
+        &mut self,
+        tab_id: TabId,
+        user_agent: Option<String>,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<(), ipc::IpcError> {
+        self.window.ipc_set_web_headers(tab_id, user_agent, headers)
+    }
+
+    fn web_history(&mut self, tab_id: TabId) -> Result<ipc::IpcWebHistory, ipc::IpcError> {
+        self.window.ipc_web_history(tab_id)
+    }
+
+    fn set_web_scroll(&mut self, tab_id: TabId, x: f64, y: f64) -> Result<(), ipc::IpcError> {
+        self.window.ipc_set_web_scroll(tab_id, x, y)
+    }
+
+    fn go_to_history_index(&mut self, tab_id: TabId, index: usize) -> Result<(), ipc::IpcError> {
+        self.window.ipc_go_to_history_index(tab_id, index)
     }
 
     fn open_inspector(&mut self, tab_id: TabId) -> Result<(), ipc::IpcError> {
@@ -356,6 +493,14 @@ impl ipc::IpcContext for IpcWindowContext<'_> {
         )
     }
 
+    fn set_inspector_dock(
+        &mut self,
+        tab_id: TabId,
+        side: ipc::InspectorDock,
+    ) -> Result<(), ipc::IpcError> {
+        self.window.ipc_set_inspector_dock(tab_id, side)
+    }
+
     fn tab_panel_state(&self) -> ipc::IpcTabPanelState {
         self.window.ipc_tab_panel_state()
     }
@@ -379,6 +524,10 @@ impl ipc::IpcContext for IpcWindowContext<'_> {
         self.window.ipc_send_input(tab_id, text)
     }
 
+    fn tab_contents(&mut self, tab_id: TabId, scrollback: bool) -> Result<Vec<String>, ipc::IpcError> {
+        self.window.ipc_tab_contents(tab_id, scrollback)
+    }
+
     fn run_command_bar(&mut self, tab_id: TabId, input: String) -> Result<(), ipc::IpcError> {
         self.window.ipc_run_command_bar(
             tab_id,
@@ -465,6 +614,8 @@ impl Processor {
             windows: Default::default(),
             #[cfg(unix)]
             global_ipc_options: Default::default(),
+            #[cfg(unix)]
+            ipc_subscribers: Default::default(),
             config_monitor,
         }
     }
@@ -477,7 +628,7 @@ impl Processor {
         &mut self,
         event_loop: &ActiveEventLoop,
         window_options: WindowOptions,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<WindowId, Box<dyn Error>> {
         let window_context = WindowContext::initial(
             event_loop,
             self.proxy.clone(),
@@ -489,7 +640,7 @@ impl Processor {
         let window_id = window_context.id();
         self.windows.insert(window_id, window_context);
 
-        Ok(())
+        Ok(window_id)
     }
 
     /// Create a new terminal window.
@@ -497,7 +648,7 @@ impl Processor {
         &mut self,
         event_loop: &ActiveEventLoop,
         options: WindowOptions,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<WindowId, Box<dyn Error>> {
         let gl_config = self.gl_config.as_ref().unwrap();
 
         // Override config with CLI/IPC options.
@@ -518,7 +669,30 @@ impl Processor {
 
         let window_id = window_context.id();
         self.windows.insert(window_id, window_context);
-        Ok(())
+        Ok(window_id)
+    }
+
+    /// Create a new window, loading the GL platform if no window was created yet.
+    ///
+    /// Windows are created without an initial window in daemon mode.
+    fn open_window(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        options: WindowOptions,
+    ) -> Result<WindowId, Box<dyn Error>> {
+        // XXX Ensure that no context is current when creating a new window,
+        // otherwise it may lock the backing buffer of the
+        // surface of current context when asking
+        // e.g. EGL on Wayland to create a new context.
+        for window_context in self.windows.values_mut() {
+            window_context.display.make_not_current();
+        }
+
+        if self.gl_config.is_none() {
+            self.create_initial_window(event_loop, options)
+        } else {
+            self.create_window(event_loop, options)
+        }
     }
 
     #[cfg(target_os = "macos")]
@@ -657,6 +831,71 @@ impl Processor {
                     ),
                 }
             },
+            IpcRequest::ListWindows => {
+                let now = Instant::now();
+                let mut windows = self
+                    .windows
+                    .iter()
+                    .map(|(window_id, window_context)| ipc::IpcWindow {
+                        window_id: u64::from(*window_id),
+                        title: window_context.display.window.title().to_owned(),
+                        focused: window_context.is_focused(),
+                        groups: window_context.ipc_tab_groups(now),
+                    })
+                    .collect::<Vec<_>>();
+                windows.sort_by_key(|window| window.window_id);
+                SocketReply::WindowList { windows }
+            },
+            IpcRequest::FocusWindow { window_id } => {
+                let window_ids = self.windows.keys().map(|id| u64::from(*id));
+                match ipc::resolve_window_id(window_id, window_ids) {
+                    Ok(window_id) => {
+                        let window = &self.windows[&WindowId::from(window_id)].display.window;
+                        window.set_minimized(false);
+                        window.focus_window();
+                        ipc::reply_ok()
+                    },
+                    Err(error) => SocketReply::Error { error },
+                }
+            },
+            IpcRequest::GetRenderStats { window_id } => {
+                let window_ids = self.windows.keys().map(|id| u64::from(*id));
+                let window_id = match ipc::resolve_window_id(window_id, window_ids) {
+                    Ok(window_id) => window_id,
+                    Err(error) => return SocketReply::Error { error },
+                };
+
+                let window_context = &self.windows[&WindowId::from(window_id)];
+                if !window_context.config().debug.frame_stats {
+                    return ipc::reply_error(
+                        ipc::IpcErrorCode::Unsupported,
+                        "Frame statistics are disabled, enable debug.frame_stats",
+                    );
+                }
+
+                let stats = window_context.display.frame_stats();
+                SocketReply::RenderStats {
+                    stats: ipc::IpcRenderStats {
+                        last_frame_us: stats.last_frame.as_micros() as u64,
+                        p99_us: stats.p99.as_micros() as u64,
+                        frames: stats.frames,
+                    },
+                }
+            },
+            IpcRequest::CreateWindow { options } => match self.open_window(event_loop, *options) {
+                Ok(window_id) => SocketReply::WindowCreated { window_id: u64::from(window_id) },
+                Err(err) => ipc::reply_error(
+                    ipc::IpcErrorCode::Internal,
+                    format!("Could not create window: {err}"),
+                ),
+            },
+            #[cfg(not(target_os = "macos"))]
+            IpcRequest::SnapshotTab { .. }
+            | IpcRequest::ClearWebData { .. }
+            | IpcRequest::GetWebScroll { .. } => ipc::reply_error(
+                ipc::IpcErrorCode::Unsupported,
+                "Web tabs are only supported on macOS",
+            ),
             request => {
                 let window_id = match self.window_for_ipc_request(&request) {
                     Ok(window_id) => window_id,
@@ -691,6 +930,82 @@ impl Processor {
         }
     }
 
+    /// Handle a request which is answered once its tab reported the result.
+    #[cfg(unix)]
+    fn handle_deferred_ipc_request(&mut self, request: IpcRequest, stream: Arc<IpcStream>) {
+        let reply = move |reply| {
+            if let Ok(mut stream) = stream.try_clone() {
+                ipc::send_reply(&mut stream, reply);
+            }
+        };
+
+        let window_context = match self.window_for_ipc_request(&request) {
+            Ok(window_id) => self.windows.get_mut(&window_id),
+            Err(err) => return reply(err),
+        };
+        let Some(window_context) = window_context else {
+            return reply(ipc::reply_error(ipc::IpcErrorCode::NotFound, "Target window not found"));
+        };
+
+        match request {
+            #[cfg(target_os = "macos")]
+            IpcRequest::SnapshotTab { tab_id, path } => {
+                window_context.ipc_snapshot_tab(tab_id.map(TabId::from), path, reply)
+            },
+            #[cfg(target_os = "macos")]
+            IpcRequest::GetWebScroll { tab_id } => {
+                window_context.ipc_web_scroll(tab_id.map(TabId::from), reply)
+            },
+            IpcRequest::GetSelection { tab_id } => {
+                window_context.ipc_selection(tab_id.map(TabId::from), reply)
+            },
+            IpcRequest::WaitTab { tab_id, timeout_ms } => {
+                let timeout = timeout_ms.map(Duration::from_millis);
+                window_context.ipc_wait_tab(tab_id.map(TabId::from), timeout, reply);
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    /// Remove website data, replying once WebKit finished the removal.
+    #[cfg(target_os = "macos")]
+    fn handle_ipc_clear_web_data(&mut self, request: IpcRequest, stream: Arc<IpcStream>) {
+        let reply = move |reply| {
+            if let Ok(mut stream) = stream.try_clone() {
+                ipc::send_reply(&mut stream, reply);
+            }
+        };
+
+        let window_id = request.target_tab_id().map(|_| self.window_for_ipc_request(&request));
+        let IpcRequest::ClearWebData { tab_id, cookies, cache, storage, since_seconds } = request
+        else {
+            return;
+        };
+        let types = WebsiteDataTypes { cookies, cache, storage };
+
+        let (Some(tab_id), Some(window_id)) = (tab_id, window_id) else {
+            let since = since_seconds.map(Duration::from_secs);
+            return clear_website_data(types, since, move || reply(ipc::reply_ok()));
+        };
+
+        if since_seconds.is_some() {
+            return reply(ipc::reply_error(
+                ipc::IpcErrorCode::InvalidRequest,
+                "since_seconds can only be used when clearing all sites",
+            ));
+        }
+
+        let window_context = match window_id {
+            Ok(window_id) => self.windows.get_mut(&window_id),
+            Err(err) => return reply(err),
+        };
+        let Some(window_context) = window_context else {
+            return reply(ipc::reply_error(ipc::IpcErrorCode::NotFound, "Target window not found"));
+        };
+
+        window_context.ipc_clear_web_data(tab_id.into(), types, reply);
+    }
+
     #[cfg(unix)]
     fn window_for_ipc_request(&self, request: &IpcRequest) -> Result<WindowId, SocketReply> {
         if let Some(tab_id) = request.target_tab_id() {
@@ -756,11 +1071,28 @@ impl Processor {
     }
 
     fn close_window(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        #[cfg(unix)]
+        if let Some(window_context) = self.windows.get_mut(&window_id) {
+            for event in window_context.take_closing_ipc_events() {
+                self.ipc_subscribers.broadcast(&event);
+            }
+        }
+
+        // Keep the tabs of the last window, so they are reopened on the next launch.
+        let last_window = self.windows.len() == 1 && self.windows.contains_key(&window_id);
+        if last_window {
+            self.save_session();
+        }
+
         let window_context = match self.windows.entry(window_id) {
             Entry::Occupied(window_context) => window_context.remove(),
             _ => return,
         };
 
+        if !last_window {
+            self.save_session();
+        }
+
         self.scheduler.unschedule_window(window_context.id());
 
         if self.windows.is_empty() && !self.cli_options.daemon {
@@ -772,6 +1104,49 @@ impl Processor {
         }
     }
 
+    /// Load the saved session.
+    ///
+    /// Nothing is restored when the launch options ask for a specific command, directory or URL.
+    fn load_session(&self, window_options: &WindowOptions) -> Vec<SessionGroup> {
+        let default_launch = window_options.terminal_options == TerminalOptions::default()
+            && window_options.window_kind == WindowKind::Terminal;
+        if !self.config.general.restore_session || !default_launch {
+            return Vec::new();
+        }
+
+        let Some(path) = session::session_path() else {
+            return Vec::new();
+        };
+
+        match session::load_session(&path) {
+            Ok(groups) => groups,
+            Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(err) => {
+                warn!("Unable to load session from {path:?}: {err}");
+                Vec::new()
+            },
+        }
+    }
+
+    /// Write the tabs of all windows to the session file.
+    ///
+    /// Sessions are neither saved nor restored in daemon mode, since windows come and go there.
+    fn save_session(&self) {
+        if !self.config.general.restore_session || self.cli_options.daemon {
+            return;
+        }
+
+        let Some(path) = session::session_path() else {
+            return;
+        };
+
+        let groups: Vec<_> =
+            self.windows.values().flat_map(WindowContext::session_groups).collect();
+        if let Err(err) = session::save_session(&path, &groups) {
+            warn!("Unable to save session to {path:?}: {err}");
+        }
+    }
+
     /// Check if an event is irrelevant and can be skipped.
     fn skip_window_event(event: &WindowEvent) -> bool {
         matches!(
@@ -807,12 +1182,24 @@ impl ApplicationHandler<Event> for Processor {
             return;
         }
 
-        if let Some(window_options) = self.initial_window_options.take() {
+        if let Some(mut window_options) = self.initial_window_options.take() {
+            // The first session tab replaces the default initial tab.
+            let session = self.load_session(&window_options);
+            if let Some(tab) = session.first().and_then(|group| group.tabs.first()) {
+                tab.apply(&mut window_options);
+            }
+
             if let Err(err) = self.create_initial_window(event_loop, window_options) {
                 self.initial_window_error = Some(err);
                 event_loop.exit();
                 return;
             }
+
+            if let Some(window_context) =
+                self.windows.values_mut().next().filter(|_| !session.is_empty())
+            {
+                window_context.restore_session(session, &self.proxy);
+            }
             #[cfg(target_os = "macos")]
             self.open_pending_urls();
         }
@@ -866,6 +1253,30 @@ impl ApplicationHandler<Event> for Processor {
 
         // Handle events which don't mandate the WindowId.
         match (payload, window_id) {
+            #[cfg(target_os = "macos")]
+            (EventType::IpcRequest(request @ IpcRequest::ClearWebData { .. }, stream), _) => {
+                self.handle_ipc_clear_web_data(request, stream);
+            },
+            #[cfg(target_os = "macos")]
+            (
+                EventType::IpcRequest(
+                    request @ (IpcRequest::SnapshotTab { .. } | IpcRequest::GetWebScroll { .. }),
+                    stream,
+                ),
+                _,
+            ) => self.handle_deferred_ipc_request(request, stream),
+            #[cfg(unix)]
+            (
+                EventType::IpcRequest(
+                    request @ (IpcRequest::GetSelection { .. } | IpcRequest::WaitTab { .. }),
+                    stream,
+                ),
+                _,
+            ) => self.handle_deferred_ipc_request(request, stream),
+            #[cfg(unix)]
+            (EventType::IpcRequest(IpcRequest::Subscribe { events }, stream), _) => {
+                self.ipc_subscribers.subscribe(&stream, events);
+            },
             #[cfg(unix)]
             (EventType::IpcRequest(request, stream), _) => {
                 let reply = self.handle_ipc_request(event_loop, request);
@@ -875,7 +1286,7 @@ impl ApplicationHandler<Event> for Processor {
             },
             (EventType::CreateTab(options), Some(window_id)) => {
                 if let Some(window_context) = self.windows.get_mut(&window_id) {
-                    if let Err(err) = window_context.create_tab(options, &self.proxy) {
+                    if let Err(err) = window_context.create_new_tab(options, tab_id, &self.proxy) {
                         error!("Could not create tab: {err:?}");
                     }
                 }
@@ -914,6 +1325,14 @@ impl ApplicationHandler<Event> for Processor {
                 }
             },
             #[cfg(target_os = "macos")]
+            (EventType::DuplicateTab(tab_id), Some(window_id)) => {
+                if let Some(window_context) = self.windows.get_mut(&window_id) {
+                    if let Err(err) = window_context.duplicate_tab(tab_id, &self.proxy) {
+                        error!("Could not duplicate tab: {err:?}");
+                    }
+                }
+            },
+            #[cfg(target_os = "macos")]
             (EventType::TabSearch(query), Some(window_id)) => {
                 if let Some(window_context) = self.windows.get_mut(&window_id) {
                     window_context.select_tab_by_query(&query);
@@ -934,6 +1353,7 @@ impl ApplicationHandler<Event> for Processor {
 
                 // Load config and update each terminal.
                 if let Ok(config) = config::reload(&path, &mut self.cli_options) {
+                    let changed = config_diff(&self.config, &config);
                     self.config = Rc::new(config);
 
                     // Restart config monitor if imports changed.
@@ -947,29 +1367,25 @@ impl ApplicationHandler<Event> for Processor {
                         };
                     }
 
-                    for window_context in self.windows.values_mut() {
-                        window_context.update_config(self.config.clone());
+                    // Keep runtime state untouched when no option has changed.
+                    if !changed.is_empty() {
+                        for window_context in self.windows.values_mut() {
+                            window_context.update_config(self.config.clone());
+                        }
                     }
                 }
             },
             // Create a new terminal window.
             (EventType::CreateWindow(options), _) => {
-                // XXX Ensure that no context is current when creating a new window,
-                // otherwise it may lock the backing buffer of the
-                // surface of current context when asking
-                // e.g. EGL on Wayland to create a new context.
-                for window_context in self.windows.values_mut() {
-                    window_context.display.make_not_current();
-                }
-
-                if self.gl_config.is_none() {
+                let initial = self.gl_config.is_none();
+                match self.open_window(event_loop, options) {
                     // Handle initial window creation in daemon mode.
-                    if let Err(err) = self.create_initial_window(event_loop, options) {
+                    Err(err) if initial => {
                         self.initial_window_error = Some(err);
                         event_loop.exit();
-                    }
-                } else if let Err(err) = self.create_window(event_loop, options) {
-                    error!("Could not open window: {err:?}");
+                    },
+                    Err(err) => error!("Could not open window: {err:?}"),
+                    Ok(_) => (),
                 }
             },
             // Process events affecting all windows.
@@ -1034,7 +1450,10 @@ impl ApplicationHandler<Event> for Processor {
                     self.ensure_tab_activity_tick(window_id);
                 }
             },
-            (EventType::Terminal(TerminalEvent::Exit | TerminalEvent::ChildExit(_)), Some(window_id)) => {
+            (
+                EventType::Terminal(event @ (TerminalEvent::Exit | TerminalEvent::ChildExit(_))),
+                Some(window_id),
+            ) => {
                 let Some(tab_id) = tab_id else {
                     return;
                 };
@@ -1050,6 +1469,17 @@ impl ApplicationHandler<Event> for Processor {
                     return;
                 }
 
+                #[cfg(unix)]
+                {
+                    let status = match event {
+                        TerminalEvent::ChildExit(code) => Some(code),
+                        _ => None,
+                    };
+                    window_context.note_child_exit(tab_id, status);
+                }
+                #[cfg(not(unix))]
+                let _ = event;
+
                 if window_context.display.window.hold {
                     return;
                 }
@@ -1057,21 +1487,7 @@ impl ApplicationHandler<Event> for Processor {
                 let should_close_window = window_context.close_tab(tab_id);
 
                 if should_close_window {
-                    let window_context = match self.windows.entry(window_id) {
-                        Entry::Occupied(window_context) => window_context.remove(),
-                        _ => return,
-                    };
-
-                    // Unschedule pending events.
-                    self.scheduler.unschedule_window(window_context.id());
-
-                    if self.windows.is_empty() && !self.cli_options.daemon {
-                        if self.config.debug.ref_test {
-                            window_context.write_ref_test_results();
-                        }
-
-                        event_loop.exit();
-                    }
+                    self.close_window(event_loop, window_id);
                 }
             },
             (EventType::TabActivityTick, Some(window_id)) => {
@@ -1089,11 +1505,13 @@ impl ApplicationHandler<Event> for Processor {
                     self.scheduler.unschedule(timer_id);
                 }
 
+                window_context.update_busy_tabs();
                 window_context.dirty = true;
                 if window_context.display.window.has_frame {
                     window_context.display.window.request_redraw();
                 }
             },
+            (EventType::SaveSession, _) => self.save_session(),
             // NOTE: This event bypasses batching to minimize input latency.
             (EventType::Frame, Some(window_id)) => {
                 if let Some(window_context) = self.windows.get_mut(&window_id) {
@@ -1133,6 +1551,19 @@ impl ApplicationHandler<Event> for Processor {
                 &mut self.scheduler,
                 WinitEvent::AboutToWait,
             );
+
+            #[cfg(unix)]
+            for event in window_context.take_ipc_events() {
+                self.ipc_subscribers.broadcast(&event);
+            }
+
+            let window_id = window_context.id();
+            let timer_id = TimerId::new(Topic::SaveSession, window_id);
+            if window_context.take_session_dirty() && !self.scheduler.scheduled(timer_id) {
+                let event = Event::new(EventType::SaveSession, window_id);
+                self.scheduler.schedule(event, SESSION_SAVE_DELAY, false, timer_id);
+            }
+            window_context.save_url_history();
         }
 
         // Update the scheduler after event processing to ensure
@@ -1149,6 +1580,11 @@ impl ApplicationHandler<Event> for Processor {
             info!("Exiting the event loop");
         }
 
+        // Windows are still open when quitting without closing them first.
+        if !self.windows.is_empty() {
+            self.save_session();
+        }
+
         match self.gl_config.take().map(|config| config.display()) {
             #[cfg(not(target_os = "macos"))]
             Some(glutin::display::Display::Egl(display)) => {
@@ -1217,6 +1653,7 @@ impl From<Event> for WinitEvent<Event> {
 #[derive(Debug, Clone)]
 pub enum WebCommand {
     OpenUrl { url: String, new_tab: bool },
+    OpenUrlInBackground { url: String },
     CopyToClipboard { text: String },
     SetMark {
         name: char,
@@ -1246,20 +1683,64 @@ pub enum EventType {
     #[cfg(target_os = "macos")]
     WebCursorRequest,
     #[cfg(target_os = "macos")]
+    WebFindResult { matched: bool },
+    /// Only link whose hint label starts with the typed keys, if there's exactly one.
+    #[cfg(target_os = "macos")]
+    WebHintPreview { keys: String, url: Option<String> },
+    /// Full hint label typed while queuing links.
+    #[cfg(target_os = "macos")]
+    WebHintQueued { keys: String },
+    /// Kind of the field focused for insert mode.
+    #[cfg(target_os = "macos")]
+    WebFieldFocused { kind: WebFieldKind },
+    /// Estimated fraction of the page which has been loaded.
+    #[cfg(target_os = "macos")]
+    WebLoadProgress { progress: f64 },
+    #[cfg(target_os = "macos")]
+    WebLoadFinished,
+    /// Page state of a background web tab requested for hibernation.
+    #[cfg(target_os = "macos")]
+    WebHibernate(Option<HibernatedPage>),
+    #[cfg(target_os = "macos")]
+    WebHibernationTick,
+    /// Scroll offset of a web page, polled for the IPC tab state.
+    #[cfg(target_os = "macos")]
+    WebScrollPolled { x: f64, y: f64 },
+    #[cfg(target_os = "macos")]
+    WebStatePollTick,
+    /// Page load which failed, including TLS certificate validation errors.
+    #[cfg(target_os = "macos")]
+    WebNavigationError(WebNavigationError),
+    /// Hover delay of a truncated tab title in the tab panel elapsed.
+    #[cfg(target_os = "macos")]
+    TabPanelTooltip,
+    /// Dragged item is held near the edge of a scrolled tab panel.
+    #[cfg(target_os = "macos")]
+    TabPanelDragScroll,
+    #[cfg(target_os = "macos")]
     CloseTab(TabId),
     #[cfg(target_os = "macos")]
     RestoreTab,
     #[cfg(target_os = "macos")]
+    DuplicateTab(TabId),
+    #[cfg(target_os = "macos")]
     TabSearch(String),
     #[cfg(target_os = "macos")]
     OpenUrls(Vec<String>),
     #[cfg(unix)]
-    IpcRequest(IpcRequest, Arc<UnixStream>),
+    IpcRequest(IpcRequest, Arc<IpcStream>),
+    /// Deadline of an IPC client waiting for a tab's command to exit elapsed.
+    #[cfg(unix)]
+    TabWaitTimeout,
     BlinkCursor,
     BlinkCursorTimeout,
     TabActivityTick,
     SearchNext,
+    /// Debounce interval of a burst of window resizes elapsed.
+    Resize,
     UpdateTabProgramName,
+    /// Delay batching changes to the saved session elapsed.
+    SaveSession,
     Frame,
 }
 
@@ -1414,63 +1895,6 @@ impl Default for CommandState {
     }
 }
 
-/// URL history for command bar completions.
-pub struct CommandHistory {
-    urls: Vec<String>,
-}
-
-impl CommandHistory {
-    pub(crate) fn record_url(&mut self, url: String) {
-        if url.is_empty() {
-            return;
-        }
-
-        if let Some(existing) = self.urls.iter().position(|entry| entry == &url) {
-            self.urls.remove(existing);
-        }
-
-        self.urls.insert(0, url);
-
-        const MAX_HISTORY: usize = 50;
-        if self.urls.len() > MAX_HISTORY {
-            self.urls.truncate(MAX_HISTORY);
-        }
-    }
-
-    fn complete(&self, prefix: &str, last_index: Option<usize>) -> Option<(String, usize)> {
-        if self.urls.is_empty() {
-            return None;
-        }
-
-        let mut start = last_index.map(|index| index + 1).unwrap_or(0);
-        if start >= self.urls.len() {
-            start = 0;
-        }
-
-        for (index, entry) in self.urls.iter().enumerate().skip(start) {
-            if entry.starts_with(prefix) {
-                return Some((entry.clone(), index));
-            }
-        }
-
-        if start > 0 {
-            for (index, entry) in self.urls.iter().enumerate().take(start) {
-                if entry.starts_with(prefix) {
-                    return Some((entry.clone(), index));
-                }
-            }
-        }
-
-        None
-    }
-}
-
-impl Default for CommandHistory {
-    fn default() -> Self {
-        Self { urls: Vec::new() }
-    }
-}
-
 /// Vi inline search state.
 pub struct InlineSearchState {
     /// Whether inline search is currently waiting for search character input.
@@ -1505,7 +1929,7 @@ pub struct ActionContext<'a, N, T> {
     pub cursor_blink_timed_out: &'a mut bool,
     pub prev_bell_cmd: &'a mut Option<Instant>,
     pub command_state: &'a mut CommandState,
-    pub command_history: &'a mut CommandHistory,
+    pub command_history: &'a mut UrlHistory,
     pub tab_id: TabId,
     pub tab_kind: &'a mut WindowKind,
     #[cfg(target_os = "macos")]
@@ -1527,19 +1951,9 @@ pub struct ActionContext<'a, N, T> {
     pub shell_pid: u32,
 }
 
+/// Position relative to the web view in CSS pixels, if it lies within the page.
 #[cfg(target_os = "macos")]
-pub(crate) fn request_web_cursor_update(
-    web_view: &mut WebView,
-    web_command_state: &mut WebCommandState,
-    display: &Display,
-    position: PhysicalPosition<f64>,
-    event_proxy: &EventLoopProxy<Event>,
-    scheduler: &mut Scheduler,
-    window_id: WindowId,
-    tab_id: TabId,
-) {
-    web_command_state.set_last_cursor_pos(position);
-
+fn web_view_position(display: &Display, position: PhysicalPosition<f64>) -> Option<(f64, f64)> {
     let scale_factor = display.window.scale_factor as f64;
     let size_info = display.size_info;
     let origin_x = f64::from(size_info.padding_x()) / scale_factor;
@@ -1553,9 +1967,29 @@ pub(crate) fn request_web_cursor_update(
     let local_y = position.y / scale_factor - origin_y;
 
     if local_x < 0.0 || local_y < 0.0 || local_x >= width || local_y >= height {
-        return;
+        return None;
     }
 
+    Some((local_x, local_y))
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn request_web_cursor_update(
+    web_view: &mut WebView,
+    web_command_state: &mut WebCommandState,
+    display: &Display,
+    position: PhysicalPosition<f64>,
+    event_proxy: &EventLoopProxy<Event>,
+    scheduler: &mut Scheduler,
+    window_id: WindowId,
+    tab_id: TabId,
+) {
+    web_command_state.set_last_cursor_pos(position);
+
+    let Some((local_x, local_y)) = web_view_position(display, position) else {
+        return;
+    };
+
     if web_command_state.cursor_pending() {
         return;
     }
@@ -1829,15 +2263,11 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
             .send_event(Event::new(EventType::CreateWindow(WindowOptions::default()), None));
     }
 
-    fn create_new_tab(&mut self) {
-        let mut options = WindowOptions::default();
-        #[cfg(not(windows))]
-        {
-            options.terminal_options.working_directory =
-                foreground_process_path(self.master_fd, self.shell_pid).ok();
-        }
-
-        let event = Event::new(EventType::CreateTab(options), self.display.window.id());
+    fn create_new_tab(&mut self) {
+        // The working directory is picked once the group of the new tab is known.
+        let options = WindowOptions::default();
+        let event =
+            Event::for_tab(EventType::CreateTab(options), self.display.window.id(), self.tab_id);
         let _ = self.event_proxy.send_event(event);
     }
 
@@ -1871,6 +2301,13 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         let _ = self.event_proxy.send_event(event);
     }
 
+    #[cfg(target_os = "macos")]
+    fn toggle_tab_panel_focus(&mut self) {
+        if self.display.tab_panel.toggle_focus() {
+            *self.dirty = true;
+        }
+    }
+
     fn spawn_daemon<I, S>(&self, program: &str, args: I)
     where
         I: IntoIterator<Item = S> + Debug + Copy,
@@ -2175,7 +2612,7 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
             #[cfg(target_os = "macos")]
             if self.modifiers.state().super_key() {
                 let mut options = WindowOptions::default();
-                options.window_kind = WindowKind::Web { url: String::new() };
+                options.window_kind = WindowKind::Web { url: String::new(), private: false };
                 options.command_input = Some(String::from("o "));
                 #[cfg(not(windows))]
                 {
@@ -2220,7 +2657,10 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         }
 
         let input_snapshot = self.command_state.input.clone();
-        let Some((start, prefix)) = command_url_prefix(&input_snapshot) else {
+        let bookmarklet_prefix = command_bookmarklet_prefix(&input_snapshot);
+        let Some((start, prefix)) =
+            bookmarklet_prefix.or_else(|| command_url_prefix(&input_snapshot))
+        else {
             return;
         };
 
@@ -2233,7 +2673,12 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
             }
         });
 
-        let Some((completion, index)) = self.command_history.complete(&prefix, last_index) else {
+        let completion = if bookmarklet_prefix.is_some() {
+            complete_bookmarklet(&self.config.web.bookmarklets, &prefix, last_index)
+        } else {
+            self.command_history.complete(&prefix, last_index)
+        };
+        let Some((completion, index)) = completion else {
             return;
         };
 
@@ -2673,6 +3118,13 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         );
     }
 
+    #[cfg(target_os = "macos")]
+    fn web_scroll_wheel(&mut self, dx: f64, dy: f64) {
+        if self.web_view.is_some() {
+            web_commands::scroll_wheel(self, dx, dy);
+        }
+    }
+
     #[cfg(target_os = "macos")]
     fn web_copy_selection(&mut self) {
         ActionContext::web_copy_selection(self);
@@ -2686,6 +3138,19 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
     }
 }
 
+/// Command running a hint action for the link `url`.
+#[cfg(target_os = "macos")]
+fn hint_command(action: WebHintAction, url: String) -> WebCommand {
+    match action {
+        WebHintAction::Open => WebCommand::OpenUrl { url, new_tab: false },
+        WebHintAction::OpenNewTab => WebCommand::OpenUrl { url, new_tab: true },
+        WebHintAction::OpenBackgroundTab | WebHintAction::Queue => {
+            WebCommand::OpenUrlInBackground { url }
+        },
+        WebHintAction::CopyLink => WebCommand::CopyToClipboard { text: url },
+    }
+}
+
 #[cfg(target_os = "macos")]
 fn web_key_from_event(key: &KeyEvent) -> WebKey {
     match key.logical_key.as_ref() {
@@ -2974,13 +3439,29 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
                     return;
                 }
 
-                let url = normalize_web_url(&url);
+                // Visited pages take precedence over searching for the input.
+                let web_config = &self.config.web;
+                let target = parse_command_bar(&url, &web_config.search_engines);
+                let target = self.command_history.resolve(target);
+                let url = target.into_url(&web_config.search_url);
                 if matches!(command, "O" | "B") {
                     self.open_web_url_new_tab(url);
                 } else {
                     self.open_web_url(url);
                 }
             },
+            "h" => {
+                let query = parts.collect::<Vec<_>>().join(" ");
+                if query.is_empty() {
+                    self.push_command_error(String::from("Missing history query for :h"));
+                    return;
+                }
+
+                match self.command_history.query(&query).first() {
+                    Some(url) => self.open_web_url(url.to_string()),
+                    None => self.push_command_error(format!("No history entry for {query}")),
+                }
+            },
             "T" => {
                 let query = parts.collect::<Vec<_>>().join(" ");
                 if query.is_empty() {
@@ -3002,7 +3483,15 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
                 }
             },
             "r" => {
-                self.reload_web();
+                self.reload_web(false);
+            },
+            "bm" => {
+                let name = parts.collect::<Vec<_>>().join(" ");
+                if name.is_empty() {
+                    self.push_command_error(String::from("Missing bookmarklet name for :bm"));
+                    return;
+                }
+                self.run_bookmarklet(&name);
             },
             "inspect" | "inspector" | "devtools" => {
                 self.open_web_inspector();
@@ -3015,12 +3504,14 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
 
     fn open_web_url(&mut self, url: String) {
         match &mut *self.tab_kind {
-            WindowKind::Web { url: current_url } => {
+            WindowKind::Web { url: current_url, .. } => {
                 *current_url = url.clone();
                 #[cfg(target_os = "macos")]
                 if let Some(web_view) = self.web_view.as_mut() {
                     if web_view.load_url(&url) {
-                        self.command_history.record_url(url);
+                        if !self.tab_kind.is_private() {
+                            self.command_history.add(url);
+                        }
                         return;
                     }
                 }
@@ -3029,19 +3520,19 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
             },
             WindowKind::Terminal => {
                 let mut options = WindowOptions::default();
-                options.window_kind = WindowKind::Web { url };
+                options.window_kind = WindowKind::Web { url, private: false };
                 #[cfg(not(windows))]
                 {
                     options.terminal_options.working_directory =
                         foreground_process_path(self.master_fd, self.shell_pid).ok();
                 }
                 let record_url = match &options.window_kind {
-                    WindowKind::Web { url } => Some(url.clone()),
+                    WindowKind::Web { url, .. } => Some(url.clone()),
                     WindowKind::Terminal => None,
                 };
                 let event = Event::new(EventType::CreateTab(options), self.display.window.id());
                 if let Some(url) = record_url {
-                    self.command_history.record_url(url);
+                    self.command_history.add(url);
                 }
                 let _ = self.event_proxy.send_event(event);
             },
@@ -3050,7 +3541,7 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
 
     fn open_web_url_new_tab(&mut self, url: String) {
         let mut options = WindowOptions::default();
-        options.window_kind = WindowKind::Web { url: url.clone() };
+        options.window_kind = WindowKind::Web { url: url.clone(), private: false };
         #[cfg(not(windows))]
         {
             options.terminal_options.working_directory =
@@ -3058,16 +3549,21 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         }
 
         let event = Event::new(EventType::CreateTab(options), self.display.window.id());
-        self.command_history.record_url(url);
+        self.command_history.add(url);
         let _ = self.event_proxy.send_event(event);
     }
 
-    pub(crate) fn reload_web(&mut self) {
+    /// Reload the active web tab, revalidating cached resources if `ignore_cache` is set.
+    pub(crate) fn reload_web(&mut self, ignore_cache: bool) {
         match &*self.tab_kind {
             WindowKind::Web { .. } => {
                 #[cfg(target_os = "macos")]
                 if let Some(web_view) = self.web_view.as_mut() {
-                    web_view.reload();
+                    if ignore_cache {
+                        web_view.reload_ignoring_cache();
+                    } else {
+                        web_view.reload();
+                    }
                     self.web_command_state.set_cursor_bootstrapped(false);
                     self.web_command_state.clear_last_cursor_request();
                     self.display.pending_update.dirty = true;
@@ -3075,6 +3571,8 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
                     *self.dirty = true;
                     return;
                 }
+                #[cfg(not(target_os = "macos"))]
+                let _ = ignore_cache;
 
                 self.push_command_error(String::from("Web view is unavailable"));
             },
@@ -3102,6 +3600,40 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         }
     }
 
+    /// Run the configured bookmarklet called `name` on the page of the active web tab.
+    fn run_bookmarklet(&mut self, name: &str) {
+        match &*self.tab_kind {
+            WindowKind::Web { .. } => {
+                #[cfg(target_os = "macos")]
+                if self.web_view.is_some() {
+                    let config = self.config;
+                    let Some(bookmarklet) = config.web.bookmarklet(name) else {
+                        self.push_command_error(format!("No bookmarklet named {name}"));
+                        return;
+                    };
+
+                    // Keep bookmarklets away from pages outside their allowlist.
+                    let url = self.current_web_url().unwrap_or_default();
+                    if !bookmarklet.allowed_on(&url) {
+                        let message = format!("Bookmarklet {name} is not allowed on this page");
+                        self.push_command_error(message);
+                        return;
+                    }
+
+                    self.web_exec_js(bookmarklet.source());
+                    return;
+                }
+                #[cfg(not(target_os = "macos"))]
+                let _ = name;
+
+                self.push_command_error(String::from("Web view is unavailable"));
+            },
+            WindowKind::Terminal => {
+                self.push_command_error(String::from("No active web tab to run bookmarklets in"));
+            },
+        }
+    }
+
     fn push_command_error(&mut self, message: String) {
         self.message_buffer
             .push(Message::new(message, crate::message_bar::MessageType::Error));
@@ -3152,7 +3684,7 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         }
 
         match &*self.tab_kind {
-            WindowKind::Web { url } if !url.is_empty() => Some(url.clone()),
+            WindowKind::Web { url, .. } if !url.is_empty() => Some(url.clone()),
             _ => None,
         }
     }
@@ -3170,27 +3702,55 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
     }
 
     fn web_hints_update(&mut self, keys: &str, action: WebHintAction) {
+        // Queued links keep the hints around for the next label.
+        let keep = action == WebHintAction::Queue;
         let script = format!(
-            "{WEB_HINTS_BOOTSTRAP}\nwindow.__taborHints.update({});",
+            "{WEB_HINTS_BOOTSTRAP}\nwindow.__taborHints.update({}, {keep});",
             Self::js_string(keys)
         );
         let proxy = self.event_proxy.clone();
         let window_id = self.display.window.id();
         let tab_id = self.tab_id;
 
+        let keys = keys.to_owned();
+
         self.web_eval_js_string(&script, move |result| {
-            let Some(url) = result.filter(|url| !url.is_empty()) else {
-                return;
-            };
+            let hint_match = result
+                .and_then(|result| serde_json::from_str::<WebHintMatch>(&result).ok())
+                .filter(|hint_match| !hint_match.url.is_empty());
 
-            let command = match action {
-                WebHintAction::Open => WebCommand::OpenUrl { url, new_tab: false },
-                WebHintAction::OpenNewTab => WebCommand::OpenUrl { url, new_tab: true },
-                WebHintAction::CopyLink => WebCommand::CopyToClipboard { text: url },
+            let event = match hint_match {
+                Some(WebHintMatch { complete: true, .. }) if keep => {
+                    EventType::WebHintQueued { keys }
+                },
+                Some(WebHintMatch { url, complete: true }) => {
+                    EventType::WebCommand(hint_command(action, url))
+                },
+                Some(WebHintMatch { url, complete: false }) => {
+                    EventType::WebHintPreview { keys, url: Some(url) }
+                },
+                None => EventType::WebHintPreview { keys, url: None },
             };
 
-            let event = Event::for_tab(EventType::WebCommand(command), window_id, tab_id);
-            let _ = proxy.send_event(event);
+            let _ = proxy.send_event(Event::for_tab(event, window_id, tab_id));
+        });
+    }
+
+    fn web_hints_open_multiple(&mut self, keys: &[String], action: WebHintAction) {
+        let labels = serde_json::to_string(keys).unwrap_or_default();
+        let script = format!("{WEB_HINTS_BOOTSTRAP}\nwindow.__taborHints.resolve({labels});");
+        let proxy = self.event_proxy.clone();
+        let window_id = self.display.window.id();
+        let tab_id = self.tab_id;
+
+        self.web_eval_js_string(&script, move |result| {
+            let urls = result
+                .and_then(|result| serde_json::from_str::<Vec<String>>(&result).ok())
+                .unwrap_or_default();
+            for url in urls {
+                let event = EventType::WebCommand(hint_command(action, url));
+                let _ = proxy.send_event(Event::for_tab(event, window_id, tab_id));
+            }
         });
     }
 
@@ -3257,6 +3817,27 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         self.web_exec_js(&script);
     }
 
+    fn web_select_all(&mut self) {
+        self.web_exec_js("window.getSelection().selectAllChildren(document.body);");
+    }
+
+    /// Select the contents of the block element containing the selection's focus.
+    fn web_select_element(&mut self) {
+        let script = r#"(function() {
+  const sel = window.getSelection();
+  if (!sel || !sel.focusNode) return;
+  let node = sel.focusNode;
+  if (node.nodeType !== Node.ELEMENT_NODE) node = node.parentElement;
+  while (node && node !== document.body) {
+    const display = window.getComputedStyle(node).display;
+    if (!display.startsWith("inline") && display !== "contents") break;
+    node = node.parentElement;
+  }
+  sel.selectAllChildren(node || document.body);
+})();"#;
+        self.web_exec_js(script);
+    }
+
     fn web_caret_move(&mut self, direction: &str, granularity: &str) {
         let script = format!(
             "(function() {{ const sel = window.getSelection(); if (!sel) return; sel.modify('move', '{}', '{}'); }})();",
@@ -3265,6 +3846,34 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         self.web_exec_js(&script);
     }
 
+    /// Make the page editable so the caret is shown, placing it in the middle of the viewport.
+    fn web_start_caret(&mut self) {
+        let script = r#"(function() {
+  document.designMode = "on";
+  const sel = window.getSelection();
+  if (!sel) return;
+  if (sel.rangeCount > 0) {
+    sel.collapseToStart();
+    return;
+  }
+  const x = window.innerWidth / 2;
+  const y = window.innerHeight / 2;
+  let range = document.caretRangeFromPoint ? document.caretRangeFromPoint(x, y) : null;
+  if (!range) {
+    range = document.createRange();
+    range.setStart(document.body, 0);
+  }
+  range.collapse(true);
+  sel.removeAllRanges();
+  sel.addRange(range);
+})();"#;
+        self.web_exec_js(script);
+    }
+
+    fn web_stop_caret(&mut self) {
+        self.web_exec_js("document.designMode = \"off\";");
+    }
+
     fn web_copy_selection(&mut self) {
         let proxy = self.event_proxy.clone();
         let window_id = self.display.window.id();
@@ -3295,6 +3904,40 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         });
     }
 
+    fn web_copy_visible_links(&mut self) {
+        let proxy = self.event_proxy.clone();
+        let window_id = self.display.window.id();
+        let tab_id = self.tab_id;
+        let max = web_commands::MAX_YANKED_LINKS;
+        let script = format!(
+            r#"(function() {{
+  const urls = new Set();
+  for (const link of document.querySelectorAll("a[href]")) {{
+    const rect = link.getBoundingClientRect();
+    if (rect.width === 0 || rect.height === 0) continue;
+    if (rect.bottom < 0 || rect.top > window.innerHeight) continue;
+    if (rect.right < 0 || rect.left > window.innerWidth) continue;
+    if (link.href.startsWith("javascript:")) continue;
+    urls.add(link.href);
+    if (urls.size >= {max}) break;
+  }}
+  return JSON.stringify(Array.from(urls));
+}})();"#
+        );
+        self.web_eval_js_string(&script, move |result| {
+            let links: Vec<String> =
+                result.and_then(|json| serde_json::from_str(&json).ok()).unwrap_or_default();
+            let links = web_commands::dedup_links(links, max);
+            if links.is_empty() {
+                return;
+            }
+
+            let command = WebCommand::CopyToClipboard { text: links.join("\n") };
+            let event = Event::for_tab(EventType::WebCommand(command), window_id, tab_id);
+            let _ = proxy.send_event(event);
+        });
+    }
+
     fn web_scroll_by(&mut self, dx: f64, dy: f64) {
         let script = format!("window.scrollBy({dx}, {dy});");
         self.web_exec_js(&script);
@@ -3305,9 +3948,15 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         self.web_exec_js(&script);
     }
 
-    fn web_scroll_half_page(&mut self, down: bool) {
-        let direction = if down { 1.0 } else { -1.0 };
-        let script = format!("window.scrollBy(0, window.innerHeight / 2 * {direction});");
+    fn web_scroll_half_page(&mut self, down: bool, fraction: f64, smooth: bool) {
+        let fraction = if down { fraction } else { -fraction };
+        let script = match self.web_view.as_ref().filter(|_| smooth) {
+            Some(web_view) => {
+                let distance = web_view.viewport_height() * fraction;
+                format!("window.scrollBy({{ top: {distance}, behavior: 'smooth' }});")
+            },
+            None => format!("window.scrollBy(0, window.innerHeight * {fraction});"),
+        };
         self.web_exec_js(&script);
     }
 
@@ -3321,10 +3970,36 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         );
     }
 
+    fn web_scroll_to_percent(&mut self, pct: f64) {
+        let fraction = pct.clamp(0., 100.) / 100.;
+        self.web_exec_js(&format!(
+            "window.scrollTo(window.scrollX, (Math.max(document.body.scrollHeight, \
+             document.documentElement.scrollHeight) - window.innerHeight) * {fraction});"
+        ));
+    }
+
     fn web_scroll_far_left(&mut self) {
         self.web_exec_js("window.scrollTo(0, window.scrollY);");
     }
 
+    fn web_scroll_relative(&mut self, anchor: WebScrollAnchor) {
+        // Anchor to the element under the mouse, falling back to the focused element.
+        let position = self.web_command_state.last_cursor_pos();
+        let local = position.and_then(|position| web_view_position(self.display, position));
+        let (x, y) = match local {
+            Some((x, y)) => (x.to_string(), y.to_string()),
+            None => (String::from("null"), String::from("null")),
+        };
+        let block = anchor.block();
+        self.web_exec_js(&format!(
+            "(() => {{ let target = {x} === null ? null : document.elementFromPoint({x}, {y}); \
+             const isPage = (element) => !element || element === document.body \
+             || element === document.documentElement; \
+             if (isPage(target)) {{ target = document.activeElement; }} \
+             if (!isPage(target)) {{ target.scrollIntoView({{ block: \"{block}\" }}); }} }})();"
+        ));
+    }
+
     fn web_scroll_far_right(&mut self) {
         self.web_exec_js(
             "window.scrollTo(Math.max(document.body.scrollWidth, document.documentElement.scrollWidth), window.scrollY);",
@@ -3347,28 +4022,99 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         self.start_command_prompt(':', input);
     }
 
+    fn web_open_history_search(&mut self) {
+        self.start_command_prompt(':', "h ");
+    }
+
     fn web_start_find(&mut self) {
         self.start_command_prompt('/', "");
     }
 
-    fn web_find(&mut self, query: &str, backwards: bool) {
-        let script = format!(
-            "window.find({}, false, {}, true, false, true, false);",
-            Self::js_string(query),
-            if backwards { "true" } else { "false" }
-        );
+    fn web_find(&mut self, query: &str, backwards: bool, case_sensitive: bool, regex: bool) {
+        if !regex {
+            if let Some(web_view) = self.web_view.as_mut() {
+                if web_view.find(query, backwards, true, case_sensitive) {
+                    return;
+                }
+            }
+        }
+
+        // Fall back to the DOM search on WebKit versions without native find. Regex queries
+        // search for the text of their first match in the page.
+        let query = Self::js_string(query);
+        let text = if regex {
+            let flags = if case_sensitive { "" } else { "i" };
+            format!(
+                r#"(function() {{
+  try {{
+    const match = document.body.innerText.match(new RegExp({query}, "{flags}"));
+    return match ? match[0] : "";
+  }} catch (e) {{
+    return "";
+  }}
+}})()"#
+            )
+        } else {
+            query
+        };
+        let options = format!("{case_sensitive}, {backwards}, true, false, true, false");
+        let script = format!("window.find({text}, {options});");
         self.web_exec_js(&script);
     }
 
+    /// Focus the input focused by `gn`/`gp`, or the first visible input.
     fn web_focus_input(&mut self) {
-        let script = r#"(function() {
-  const el = document.querySelector("input, textarea, select, [contenteditable='true']");
-  if (el) {
-    el.focus();
-    if (el.select) { el.select(); }
-  }
-})();"#;
-        self.web_exec_js(script);
+        self.web_focus_input_index(0);
+    }
+
+    /// Focus the `index`th visible input, or the first one when there are fewer inputs.
+    ///
+    /// An `index` of 0 keeps the focused input.
+    fn web_focus_input_index(&mut self, index: u32) {
+        let script = format!(
+            r#"(function() {{
+  const inputs = Array.from(document.querySelectorAll("{WEB_FIELD_SELECTOR}"))
+    .filter((el) => !el.disabled && el.getClientRects().length > 0);
+  const active = inputs.includes(document.activeElement) ? document.activeElement : null;
+  const el = {index} > 0 ? inputs[{index} - 1] || inputs[0] : active || inputs[0];
+  if (!el) {{ return ""; }}
+  el.focus();
+  if (el.select) {{ el.select(); }}
+  return el.tagName === "INPUT" ? "single_line" : "multi_line";
+}})()"#
+        );
+        let proxy = self.event_proxy.clone();
+        let window_id = self.display.window.id();
+        let tab_id = self.tab_id;
+
+        self.web_eval_js_string(&script, move |result| {
+            let kind = result.and_then(|kind| serde_json::from_value(kind.into()).ok());
+            if let Some(kind) = kind {
+                let event = EventType::WebFieldFocused { kind };
+                let _ = proxy.send_event(Event::for_tab(event, window_id, tab_id));
+            }
+        });
+    }
+
+    /// Move focus to the next or previous visible input in tab order, wrapping at the ends.
+    fn web_focus_field(&mut self, forward: bool) {
+        let step = if forward { 1 } else { -1 };
+        let script = format!(
+            r#"(function() {{
+  const fields = Array.from(document.querySelectorAll("{WEB_FIELD_SELECTOR}"))
+    .filter((el) => !el.disabled && el.tabIndex >= 0 && el.getClientRects().length > 0);
+  if (fields.length === 0) {{ return; }}
+  // Positive tabindex values come first, followed by the remaining fields in document order.
+  const order = (el) => el.tabIndex > 0 ? el.tabIndex : Number.MAX_SAFE_INTEGER;
+  fields.sort((a, b) => order(a) - order(b));
+  const current = fields.indexOf(document.activeElement);
+  const start = current < 0 ? ({step} > 0 ? -1 : 0) : current;
+  const el = fields[(start + {step} + fields.length) % fields.length];
+  el.focus();
+  el.scrollIntoView({{ block: "nearest" }});
+}})();"#
+        );
+        self.web_exec_js(&script);
     }
 
     fn web_view_source(&mut self) {
@@ -3384,6 +4130,13 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         self.open_web_url(url);
     }
 
+    fn web_toggle_mute(&mut self) {
+        if let Some(web_view) = self.web_view.as_mut() {
+            let muted = !web_view.muted();
+            web_view.set_muted(muted);
+        }
+    }
+
     fn web_follow_rel(&mut self, rel: &str) {
         let rel = Self::js_string(rel);
         let script = format!(
@@ -3415,15 +4168,61 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         self.clipboard.store(ClipboardType::Clipboard, url);
     }
 
+    fn web_copy_title(&mut self) {
+        let title = self.web_view.as_ref().and_then(|view| view.title());
+        let Some(title) = title.filter(|title| !title.is_empty()) else {
+            self.push_command_error(String::from("No page title"));
+            return;
+        };
+        self.clipboard.store(ClipboardType::Clipboard, title);
+    }
+
+    fn web_copy_markdown_link(&mut self) {
+        let Some(url) = self.current_web_url() else {
+            self.push_command_error(String::from("No active URL"));
+            return;
+        };
+        let title = self.web_view.as_ref().and_then(|view| view.title()).unwrap_or_default();
+        self.clipboard.store(ClipboardType::Clipboard, web_commands::markdown_link(&title, &url));
+    }
+
+    fn web_copy_domain(&mut self) {
+        let Some(current) = self.current_web_url() else {
+            self.push_command_error(String::from("No active URL"));
+            return;
+        };
+
+        let current = current.strip_prefix("view-source:").unwrap_or(&current);
+        let parsed = Url::parse(current).ok();
+        let Some(host) = parsed.as_ref().and_then(Url::host_str) else {
+            self.push_command_error(String::from("URL has no domain"));
+            return;
+        };
+        self.clipboard.store(ClipboardType::Clipboard, host.to_owned());
+    }
+
+    fn web_open_external(&mut self) {
+        let Some(current) = self.current_web_url() else {
+            self.push_command_error(String::from("No active URL"));
+            return;
+        };
+
+        let Some(url) = external_url(&current) else {
+            self.push_command_error(String::from("Only http(s) URLs can be opened externally"));
+            return;
+        };
+        self.spawn_daemon(EXTERNAL_OPENER, &[url]);
+    }
+
     fn web_open_clipboard(&mut self, new_tab: bool) {
         let raw = self.clipboard.load(ClipboardType::Clipboard);
-        let trimmed = raw.trim();
-        if trimmed.is_empty() {
+        if raw.trim().is_empty() {
             self.push_command_error(String::from("Clipboard is empty"));
             return;
         }
 
-        let url = normalize_web_url(trimmed);
+        // Text which isn't a URL is searched for instead.
+        let url = normalize_omnibar_input(&raw).into_url(&self.config.web.search_url);
         if new_tab {
             self.open_web_url_new_tab(url);
         } else {
@@ -3445,6 +4244,11 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         let _ = self.event_proxy.send_event(event);
     }
 
+    fn web_duplicate_tab(&mut self) {
+        let event = Event::new(EventType::DuplicateTab(self.tab_id), self.display.window.id());
+        let _ = self.event_proxy.send_event(event);
+    }
+
     fn web_show_help(&mut self) {
         let html = Self::js_string(WEB_HELP_HTML);
         let script = format!(
@@ -3523,8 +4327,8 @@ impl<'a, N: Notify + 'a, T: EventListener> WebActions for ActionContext<'a, N, T
         self.web_scroll_by(dx, dy);
     }
 
-    fn scroll_half_page(&mut self, down: bool) {
-        self.web_scroll_half_page(down);
+    fn scroll_half_page(&mut self, down: bool, fraction: f64, smooth: bool) {
+        self.web_scroll_half_page(down, fraction, smooth);
     }
 
     fn scroll_top(&mut self) {
@@ -3539,6 +4343,10 @@ impl<'a, N: Notify + 'a, T: EventListener> WebActions for ActionContext<'a, N, T
         self.web_scroll_far_left();
     }
 
+    fn scroll_relative(&mut self, anchor: WebScrollAnchor) {
+        self.web_scroll_relative(anchor);
+    }
+
     fn scroll_far_right(&mut self) {
         self.web_scroll_far_right();
     }
@@ -3547,6 +4355,10 @@ impl<'a, N: Notify + 'a, T: EventListener> WebActions for ActionContext<'a, N, T
         self.web_scroll_to(x, y);
     }
 
+    fn scroll_to_percent(&mut self, pct: f64) {
+        self.web_scroll_to_percent(pct);
+    }
+
     fn go_back(&mut self) {
         self.web_go_back();
     }
@@ -3559,12 +4371,22 @@ impl<'a, N: Notify + 'a, T: EventListener> WebActions for ActionContext<'a, N, T
         self.web_open_command_bar(input);
     }
 
+    fn open_history_search(&mut self) {
+        self.web_open_history_search();
+    }
+
     fn start_find_prompt(&mut self) {
         self.web_start_find();
     }
 
-    fn find(&mut self, query: &str, backwards: bool) {
-        self.web_find(query, backwards);
+    fn find(&mut self, query: &str, backwards: bool, case_sensitive: bool, regex: bool) {
+        self.web_find(query, backwards, case_sensitive, regex);
+    }
+
+    fn clear_find(&mut self) {
+        if let Some(web_view) = self.web_view.as_mut() {
+            web_view.clear_find();
+        }
     }
 
     fn hints_start(&mut self, action: WebHintAction) {
@@ -3575,10 +4397,18 @@ impl<'a, N: Notify + 'a, T: EventListener> WebActions for ActionContext<'a, N, T
         self.web_hints_update(keys, action);
     }
 
+    fn hint_preview_changed(&mut self) {
+        self.mark_dirty();
+    }
+
     fn hints_cancel(&mut self) {
         self.web_hints_cancel();
     }
 
+    fn hints_open_multiple(&mut self, keys: &[String], action: WebHintAction) {
+        self.web_hints_open_multiple(keys, action);
+    }
+
     fn copy_selection(&mut self) {
         self.web_copy_selection();
     }
@@ -3595,10 +4425,30 @@ impl<'a, N: Notify + 'a, T: EventListener> WebActions for ActionContext<'a, N, T
         self.web_visual_move(direction, granularity);
     }
 
+    fn select_all(&mut self) {
+        self.web_select_all();
+    }
+
+    fn select_element(&mut self) {
+        self.web_select_element();
+    }
+
     fn focus_input(&mut self) {
         self.web_focus_input();
     }
 
+    fn focus_input_index(&mut self, index: u32) {
+        self.web_focus_input_index(index);
+    }
+
+    fn focus_next_field(&mut self) {
+        self.web_focus_field(true);
+    }
+
+    fn focus_prev_field(&mut self) {
+        self.web_focus_field(false);
+    }
+
     fn blur_active_element(&mut self) {
         self.web_blur_active_element();
     }
@@ -3621,6 +4471,14 @@ impl<'a, N: Notify + 'a, T: EventListener> WebActions for ActionContext<'a, N, T
         self.web_exec_js("document.execCommand('insertParagraph');");
     }
 
+    fn submit_form(&mut self) {
+        let script = r#"(function() {
+  const form = document.activeElement && document.activeElement.form;
+  if (form) { form.requestSubmit(); }
+})();"#;
+        self.web_exec_js(script);
+    }
+
     fn insert_tab(&mut self) {
         let script =
             format!("document.execCommand('insertText', false, {});", Self::js_string("\t"));
@@ -3631,10 +4489,22 @@ impl<'a, N: Notify + 'a, T: EventListener> WebActions for ActionContext<'a, N, T
         self.web_caret_move(direction, granularity);
     }
 
+    fn start_caret(&mut self) {
+        self.web_start_caret();
+    }
+
+    fn stop_caret(&mut self) {
+        self.web_stop_caret();
+    }
+
     fn view_source(&mut self) {
         self.web_view_source();
     }
 
+    fn toggle_mute(&mut self) {
+        self.web_toggle_mute();
+    }
+
     fn follow_rel(&mut self, rel: &str) {
         self.web_follow_rel(rel);
     }
@@ -3643,6 +4513,26 @@ impl<'a, N: Notify + 'a, T: EventListener> WebActions for ActionContext<'a, N, T
         self.web_copy_url();
     }
 
+    fn copy_title(&mut self) {
+        self.web_copy_title();
+    }
+
+    fn copy_markdown_link(&mut self) {
+        self.web_copy_markdown_link();
+    }
+
+    fn copy_domain(&mut self) {
+        self.web_copy_domain();
+    }
+
+    fn copy_visible_links(&mut self) {
+        self.web_copy_visible_links();
+    }
+
+    fn open_external(&mut self) {
+        self.web_open_external();
+    }
+
     fn open_clipboard(&mut self, new_tab: bool) {
         self.web_open_clipboard(new_tab);
     }
@@ -3663,6 +4553,10 @@ impl<'a, N: Notify + 'a, T: EventListener> WebActions for ActionContext<'a, N, T
         self.web_restore_tab();
     }
 
+    fn duplicate_tab(&mut self) {
+        self.web_duplicate_tab();
+    }
+
     fn select_previous_tab(&mut self) {
         input::ActionContext::select_previous_tab(self);
     }
@@ -3680,7 +4574,11 @@ impl<'a, N: Notify + 'a, T: EventListener> WebActions for ActionContext<'a, N, T
     }
 
     fn reload(&mut self) {
-        self.reload_web();
+        self.reload_web(false);
+    }
+
+    fn hard_reload(&mut self) {
+        self.reload_web(true);
     }
 
     fn show_help(&mut self) {
@@ -3715,7 +4613,7 @@ fn command_url_prefix(input: &str) -> Option<(usize, &str)> {
     }
 
     let cmd = bytes[1] as char;
-    if !matches!(cmd, 'o' | 'O' | 'b' | 'B') {
+    if !matches!(cmd, 'o' | 'O' | 'b' | 'B' | 'h') {
         return None;
     }
 
@@ -3729,9 +4627,30 @@ fn command_url_prefix(input: &str) -> Option<(usize, &str)> {
     Some((start, trimmed))
 }
 
+/// Start and text of the bookmarklet name typed after `:bm`.
+fn command_bookmarklet_prefix(input: &str) -> Option<(usize, &str)> {
+    let rest = input.strip_prefix(":bm ")?;
+    let trimmed = rest.trim_start();
+    Some((input.len() - trimmed.len(), trimmed))
+}
+
+/// Next bookmarklet after `last_index` whose name starts with `prefix`, wrapping around.
+fn complete_bookmarklet(
+    bookmarklets: &[Bookmarklet],
+    prefix: &str,
+    last_index: Option<usize>,
+) -> Option<(String, usize)> {
+    let start = last_index.map_or(0, |index| index + 1);
+    (0..bookmarklets.len()).map(|offset| (start + offset) % bookmarklets.len()).find_map(|index| {
+        let name = &bookmarklets[index].name;
+        let head = name.get(..prefix.len())?;
+        head.eq_ignore_ascii_case(prefix).then(|| (name.clone(), index))
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{CommandHistory, command_url_prefix};
+    use super::{Bookmarklet, command_bookmarklet_prefix, command_url_prefix, complete_bookmarklet};
 
     #[test]
     fn command_url_prefix_parses_basic() {
@@ -3741,6 +4660,7 @@ mod tests {
         assert_eq!(command_url_prefix(":O test"), Some((3, "test")));
         assert_eq!(command_url_prefix(":b test"), Some((3, "test")));
         assert_eq!(command_url_prefix(":B test"), Some((3, "test")));
+        assert_eq!(command_url_prefix(":h test"), Some((3, "test")));
     }
 
     #[test]
@@ -3751,27 +4671,26 @@ mod tests {
     }
 
     #[test]
-    fn command_history_records_most_recent() {
-        let mut history = CommandHistory::default();
-        history.record_url(String::from("https://example.com"));
-        history.record_url(String::from("https://rust-lang.org"));
-        history.record_url(String::from("https://example.com"));
-
-        assert_eq!(history.urls[0], "https://example.com");
-        assert_eq!(history.urls[1], "https://rust-lang.org");
-    }
-
-    #[test]
-    fn command_history_cycles_completion() {
-        let mut history = CommandHistory::default();
-        history.record_url(String::from("https://example.com"));
-        history.record_url(String::from("https://rust-lang.org"));
-
-        let (first, first_index) = history.complete("https://", None).unwrap();
-        assert_eq!(first, "https://rust-lang.org");
+    fn bookmarklet_completion() {
+        assert_eq!(command_bookmarklet_prefix(":bm "), Some((4, "")));
+        assert_eq!(command_bookmarklet_prefix(":bm  rea"), Some((5, "rea")));
+        assert_eq!(command_bookmarklet_prefix(":b rea"), None);
+
+        let bookmarklet = |name: &str| Bookmarklet {
+            name: String::from(name),
+            url: String::new(),
+            matches: Vec::new(),
+        };
+        let bookmarklets = [bookmarklet("Readable"), bookmarklet("print"), bookmarklet("reader")];
 
-        let (second, _) = history.complete("https://", Some(first_index)).unwrap();
-        assert_eq!(second, "https://example.com");
+        let completion = complete_bookmarklet(&bookmarklets, "rea", None);
+        assert_eq!(completion, Some((String::from("Readable"), 0)));
+        let completion = complete_bookmarklet(&bookmarklets, "rea", Some(0));
+        assert_eq!(completion, Some((String::from("reader"), 2)));
+        let completion = complete_bookmarklet(&bookmarklets, "rea", Some(2));
+        assert_eq!(completion, Some((String::from("Readable"), 0)));
+        assert_eq!(complete_bookmarklet(&bookmarklets, "x", None), None);
+        assert_eq!(complete_bookmarklet(&[], "", None), None);
     }
 
 }
@@ -3912,6 +4831,11 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
         match event {
             WinitEvent::UserEvent(Event { payload, .. }) => match payload {
                 EventType::SearchNext => self.ctx.goto_match(None),
+                EventType::Resize => {
+                    if self.ctx.display.pending_resize.finish() {
+                        self.ctx.display.pending_update.dirty = true;
+                    }
+                },
                 EventType::Scroll(scroll) => self.ctx.scroll(scroll),
                 EventType::BlinkCursor => {
                     // Only change state when timeout isn't reached, since we could get
@@ -3933,6 +4857,26 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                 EventType::WebCommand(command) => {
                     self.ctx.handle_web_command(command);
                 },
+                #[cfg(target_os = "macos")]
+                EventType::WebNavigationError(error) => {
+                    web_commands::navigation_error(&mut self.ctx, &error);
+                },
+                #[cfg(target_os = "macos")]
+                EventType::WebHintPreview { keys, url } => {
+                    self.ctx.with_web_command_state(|state, ctx| {
+                        web_commands::preview_hint(state, ctx, &keys, url);
+                    });
+                },
+                #[cfg(target_os = "macos")]
+                EventType::WebHintQueued { keys } => {
+                    self.ctx.with_web_command_state(|state, ctx| {
+                        web_commands::queue_hint(state, ctx, &keys);
+                    });
+                },
+                #[cfg(target_os = "macos")]
+                EventType::WebFieldFocused { kind } => {
+                    web_commands::field_focused(self.ctx.web_command_state, kind);
+                },
                 // Add message only if it's not already queued.
                 EventType::Message(message) if !self.ctx.message_buffer.is_queued(&message) => {
                     self.ctx.message_buffer.push(message);
@@ -4000,10 +4944,13 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                     TerminalEvent::PtyWrite(text) => self.ctx.write_to_pty(text.into_bytes()),
                     TerminalEvent::MouseCursorDirty => self.reset_mouse_cursor(),
                     TerminalEvent::CursorBlinkingChange => self.ctx.update_cursor_blinking(),
-                    TerminalEvent::Exit | TerminalEvent::ChildExit(_) | TerminalEvent::Wakeup => (),
+                    TerminalEvent::Exit
+                    | TerminalEvent::ChildExit(_)
+                    | TerminalEvent::WorkingDirectory(_)
+                    | TerminalEvent::Wakeup => (),
                 },
                 #[cfg(unix)]
-                EventType::IpcRequest(..) => (),
+                EventType::IpcRequest(..) | EventType::TabWaitTimeout => (),
                 #[cfg(target_os = "macos")]
                 EventType::Message(_)
                 | EventType::ConfigReload(_)
@@ -4012,14 +4959,25 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                 | EventType::TabCommand(_)
                 | EventType::UpdateTabProgramName
                 | EventType::TabActivityTick
+                | EventType::TabPanelTooltip
+                | EventType::TabPanelDragScroll
                 | EventType::CloseTab(_)
                 | EventType::WebPopup { .. }
                 | EventType::RestoreTab
+                | EventType::DuplicateTab(_)
                 | EventType::WebFavicon { .. }
                 | EventType::WebCursor { .. }
                 | EventType::WebCursorRequest
+                | EventType::WebFindResult { .. }
+                | EventType::WebLoadProgress { .. }
+                | EventType::WebLoadFinished
+                | EventType::WebHibernate(_)
+                | EventType::WebHibernationTick
+                | EventType::WebScrollPolled { .. }
+                | EventType::WebStatePollTick
                 | EventType::TabSearch(_)
                 | EventType::OpenUrls(_)
+                | EventType::SaveSession
                 | EventType::Frame => (),
                 #[cfg(not(target_os = "macos"))]
                 EventType::Message(_)
@@ -4029,6 +4987,7 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                 | EventType::TabCommand(_)
                 | EventType::UpdateTabProgramName
                 | EventType::TabActivityTick
+                | EventType::SaveSession
                 | EventType::Frame => (),
             },
             WinitEvent::WindowEvent { event, .. } => {
@@ -4071,6 +5030,15 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                         }
 
                         self.ctx.display.pending_update.set_dimensions(size);
+
+                        // Resize the terminal and PTY only once a burst of resizes is over,
+                        // instead of for every step of an interactive resize.
+                        if self.ctx.display.pending_resize.push() {
+                            let window_id = self.ctx.display.window.id();
+                            let event = Event::new(EventType::Resize, window_id);
+                            let timer_id = TimerId::new(Topic::Resize, window_id);
+                            self.ctx.scheduler.schedule(event, RESIZE_DEBOUNCE, false, timer_id);
+                        }
                     },
                     WindowEvent::KeyboardInput { event, is_synthetic: false, .. } => {
                         self.key_input(event);
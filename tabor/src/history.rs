@@ -0,0 +1,279 @@
+//! Visited URLs persisted across restarts.
+
+use std::collections::VecDeque;
+#[cfg(windows)]
+use std::env;
+use std::io::{Error as IoError, ErrorKind, Result as IoResult};
+use std::path::{Path, PathBuf};
+use std::{fs, mem};
+
+use serde::{Deserialize, Serialize};
+
+use crate::web_url::OmniTarget;
+
+/// History file name inside the state directory.
+const HISTORY_FILE: &str = "history.json";
+
+/// Maximum number of URLs kept in the history.
+const MAX_HISTORY: usize = 1000;
+
+/// Visited URLs, most recent first.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct UrlHistory {
+    urls: VecDeque<String>,
+
+    /// URLs added since the history was last written, oldest first.
+    #[serde(skip)]
+    added: Vec<String>,
+}
+
+impl UrlHistory {
+    /// Read the history file.
+    pub fn load(path: &Path) -> IoResult<Self> {
+        let json = fs::read_to_string(path)?;
+        let mut history: Self = serde_json::from_str(&json).map_err(IoError::other)?;
+        history.urls.truncate(MAX_HISTORY);
+        Ok(history)
+    }
+
+    /// Write the history file, replacing any previous history.
+    pub fn save(&self, path: &Path) -> IoResult<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string(self).map_err(IoError::other)?;
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, json)?;
+        fs::rename(&tmp_path, path)
+    }
+
+    /// Add a visited URL, moving it to the front if it was visited before.
+    pub fn add(&mut self, url: String) {
+        if url.is_empty() {
+            return;
+        }
+
+        if let Some(existing) = self.urls.iter().position(|entry| entry == &url) {
+            self.urls.remove(existing);
+        }
+
+        self.urls.push_front(url.clone());
+        self.urls.truncate(MAX_HISTORY);
+
+        self.added.retain(|added| added != &url);
+        if self.added.len() == MAX_HISTORY {
+            self.added.remove(0);
+        }
+        self.added.push(url);
+    }
+
+    /// Add the URLs visited since the last call to the history file.
+    ///
+    /// The file is read first, so URLs saved by other windows are kept and picked up by this
+    /// history too. Returns `false` without touching the file if no URLs were added.
+    pub fn save_merged(&mut self, path: &Path) -> IoResult<bool> {
+        let added = mem::take(&mut self.added);
+        if added.is_empty() {
+            return Ok(false);
+        }
+
+        let mut history = match Self::load(path) {
+            Ok(history) => history,
+            Err(err) if err.kind() == ErrorKind::NotFound => Self::default(),
+            Err(err) => return Err(err),
+        };
+        for url in added {
+            history.add(url);
+        }
+
+        history.save(path)?;
+        self.urls = history.urls;
+        Ok(true)
+    }
+
+    /// URLs containing `text`, most recently visited first.
+    ///
+    /// URLs starting with `text`, ignoring their scheme and `www.`, are listed before URLs which
+    /// only contain it somewhere else. Matching is case insensitive.
+    pub fn query(&self, text: &str) -> Vec<&str> {
+        let text = text.trim().to_lowercase();
+        let (mut prefix_matches, mut other_matches) = (Vec::new(), Vec::new());
+        for url in &self.urls {
+            let lowercase = url.to_lowercase();
+            if is_prefix_match(&lowercase, &text) {
+                prefix_matches.push(url.as_str());
+            } else if lowercase.contains(&text) {
+                other_matches.push(url.as_str());
+            }
+        }
+
+        prefix_matches.append(&mut other_matches);
+        prefix_matches
+    }
+
+    /// Open a visited page for omnibar input which would otherwise be searched for.
+    pub fn resolve(&self, target: OmniTarget) -> OmniTarget {
+        let query = match target {
+            OmniTarget::Search(query) if !query.is_empty() => query,
+            target => return target,
+        };
+
+        let lowercase = query.to_lowercase();
+        match self.urls.iter().find(|url| is_prefix_match(&url.to_lowercase(), &lowercase)) {
+            Some(url) => OmniTarget::Url(url.clone()),
+            None => OmniTarget::Search(query),
+        }
+    }
+
+    /// Cycle through the URLs starting with `prefix`, after the one at `last_index`.
+    pub fn complete(&self, prefix: &str, last_index: Option<usize>) -> Option<(String, usize)> {
+        if self.urls.is_empty() {
+            return None;
+        }
+
+        let mut start = last_index.map(|index| index + 1).unwrap_or(0);
+        if start >= self.urls.len() {
+            start = 0;
+        }
+
+        for (index, entry) in self.urls.iter().enumerate().skip(start) {
+            if entry.starts_with(prefix) {
+                return Some((entry.clone(), index));
+            }
+        }
+
+        for (index, entry) in self.urls.iter().enumerate().take(start) {
+            if entry.starts_with(prefix) {
+                return Some((entry.clone(), index));
+            }
+        }
+
+        None
+    }
+}
+
+/// Check if `url` starts with `text`, with or without its scheme and `www.`.
+fn is_prefix_match(url: &str, text: &str) -> bool {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let without_www = without_scheme.strip_prefix("www.").unwrap_or(without_scheme);
+    [url, without_scheme, without_www].iter().any(|url| url.starts_with(text))
+}
+
+/// Default location of the history file.
+#[cfg(not(windows))]
+pub fn history_path() -> Option<PathBuf> {
+    xdg::BaseDirectories::with_prefix("tabor").get_state_file(HISTORY_FILE)
+}
+
+/// Default location of the history file.
+#[cfg(windows)]
+pub fn history_path() -> Option<PathBuf> {
+    let app_data = env::var_os("APPDATA")?;
+    Some(PathBuf::from(app_data).join("tabor").join(HISTORY_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(urls: &[&str]) -> UrlHistory {
+        let mut history = UrlHistory::default();
+        for url in urls {
+            history.add(String::from(*url));
+        }
+        history
+    }
+
+    #[test]
+    fn history_moves_revisited_url_to_front() {
+        let mut history = history(&["https://example.com", "https://rust-lang.org"]);
+        history.add(String::from("https://example.com"));
+        assert_eq!(history.urls, ["https://example.com", "https://rust-lang.org"]);
+        assert_eq!(history.added, ["https://rust-lang.org", "https://example.com"]);
+
+        for index in 0..MAX_HISTORY {
+            history.add(format!("https://example.com/{index}"));
+        }
+        assert_eq!(history.urls.len(), MAX_HISTORY);
+        assert_eq!(history.urls[0], format!("https://example.com/{}", MAX_HISTORY - 1));
+    }
+
+    #[test]
+    fn history_query_orders_prefix_matches_first() {
+        let history = history(&[
+            "https://docs.rs/tokio",
+            "https://www.rust-lang.org/learn",
+            "https://crates.io/search?q=rust",
+            "https://rust-lang.org",
+        ]);
+
+        assert_eq!(history.query("rust"), [
+            "https://rust-lang.org",
+            "https://www.rust-lang.org/learn",
+            "https://crates.io/search?q=rust",
+        ]);
+        assert_eq!(history.query("https://docs"), ["https://docs.rs/tokio"]);
+        assert_eq!(history.query("DOCS.rs"), ["https://docs.rs/tokio"]);
+        assert!(history.query("github").is_empty());
+    }
+
+    #[test]
+    fn history_resolves_searches() {
+        let history = history(&["https://crates.io/search?q=rust", "https://rust-lang.org"]);
+
+        let target = history.resolve(OmniTarget::Search(String::from("rust")));
+        assert_eq!(target, OmniTarget::Url(String::from("https://rust-lang.org")));
+
+        let target = OmniTarget::Search(String::from("rust book"));
+        assert_eq!(history.resolve(target.clone()), target);
+
+        let target = OmniTarget::Url(String::from("https://rust.example/"));
+        assert_eq!(history.resolve(target.clone()), target);
+    }
+
+    #[test]
+    fn history_cycles_completion() {
+        let history = history(&["https://example.com", "https://rust-lang.org"]);
+
+        let (first, first_index) = history.complete("https://", None).unwrap();
+        assert_eq!(first, "https://rust-lang.org");
+
+        let (second, second_index) = history.complete("https://", Some(first_index)).unwrap();
+        assert_eq!(second, "https://example.com");
+
+        let (third, _) = history.complete("https://", Some(second_index)).unwrap();
+        assert_eq!(third, "https://rust-lang.org");
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join(HISTORY_FILE);
+
+        let history = history(&["https://example.com", "https://rust-lang.org"]);
+        history.save(&path).unwrap();
+        assert_eq!(UrlHistory::load(&path).unwrap().urls, history.urls);
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn history_save_keeps_other_windows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(HISTORY_FILE);
+        history(&["https://example.com"]).save(&path).unwrap();
+
+        let mut first = UrlHistory::load(&path).unwrap();
+        let mut second = UrlHistory::load(&path).unwrap();
+        first.add(String::from("https://rust-lang.org"));
+        second.add(String::from("https://docs.rs"));
+
+        assert!(first.save_merged(&path).unwrap());
+        assert!(!first.save_merged(&path).unwrap());
+        assert!(second.save_merged(&path).unwrap());
+
+        let expected = ["https://docs.rs", "https://rust-lang.org", "https://example.com"];
+        assert_eq!(UrlHistory::load(&path).unwrap().urls, expected);
+        assert_eq!(second.urls, expected);
+    }
+}
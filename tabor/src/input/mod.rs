@@ -119,6 +119,8 @@ pub trait ActionContext<T: EventListener> {
     #[cfg(target_os = "macos")]
     fn web_mouse_input(&mut self, _state: ElementState, _button: MouseButton) {}
     #[cfg(target_os = "macos")]
+    fn web_scroll_wheel(&mut self, _dx: f64, _dy: f64) {}
+    #[cfg(target_os = "macos")]
     fn web_copy_selection(&mut self) {}
     #[cfg(target_os = "macos")]
     fn web_paste_text(&mut self, _text: &str) {}
@@ -130,6 +132,8 @@ pub trait ActionContext<T: EventListener> {
     fn select_tab_at_index(&mut self, _index: usize) {}
     #[cfg(target_os = "macos")]
     fn select_last_tab(&mut self) {}
+    #[cfg(target_os = "macos")]
+    fn toggle_tab_panel_focus(&mut self) {}
     fn start_search(&mut self, _direction: Direction) {}
     fn start_seeded_search(&mut self, _direction: Direction, _text: String) {}
     fn confirm_search(&mut self) {}
@@ -429,6 +433,7 @@ impl<T: EventListener> Execute<T> for Action {
             },
             Action::ScrollLineUp => ctx.scroll(Scroll::Delta(1)),
             Action::ScrollLineDown => ctx.scroll(Scroll::Delta(-1)),
+            Action::ScrollLines(lines) => ctx.scroll(Scroll::Delta(*lines)),
             Action::ScrollToTop => {
                 ctx.scroll(Scroll::Top);
 
@@ -479,6 +484,8 @@ impl<T: EventListener> Execute<T> for Action {
             Action::SelectTab9 => ctx.select_tab_at_index(8),
             #[cfg(target_os = "macos")]
             Action::SelectLastTab => ctx.select_last_tab(),
+            #[cfg(target_os = "macos")]
+            Action::ToggleTabPanelFocus => ctx.toggle_tab_panel_focus(),
             _ => (),
         }
     }
@@ -775,6 +782,8 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
 
     pub fn mouse_wheel_input(&mut self, delta: MouseScrollDelta, phase: TouchPhase) {
         if self.ctx.window_kind().is_web() {
+            #[cfg(target_os = "macos")]
+            self.web_wheel_input(delta);
             return;
         }
 
@@ -812,6 +821,23 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
         }
     }
 
+    /// Scroll web pages along both axes, so diagonal gestures aren't locked to one direction.
+    #[cfg(target_os = "macos")]
+    fn web_wheel_input(&mut self, delta: MouseScrollDelta) {
+        let (dx, dy) = match delta {
+            MouseScrollDelta::LineDelta(columns, lines) => {
+                let size_info = self.ctx.size_info();
+                let dx = columns * size_info.cell_width();
+                let dy = lines * size_info.cell_height();
+                (f64::from(dx), f64::from(dy))
+            },
+            MouseScrollDelta::PixelDelta(position) => (position.x, position.y),
+        };
+
+        let scale_factor = self.ctx.window().scale_factor;
+        self.ctx.web_scroll_wheel(dx / scale_factor, dy / scale_factor);
+    }
+
     fn scroll_terminal(&mut self, new_scroll_x_px: f64, new_scroll_y_px: f64, multiplier: f64) {
         const MOUSE_WHEEL_UP: u8 = 64;
         const MOUSE_WHEEL_DOWN: u8 = 65;
@@ -1465,7 +1491,7 @@ mod tests {
             message_buffer: &mut message_buffer,
             inline_search_state: &mut inline_search_state,
             config: &cfg,
-            window_kind: WindowKind::Web { url: String::from("about:blank") },
+            window_kind: WindowKind::Web { url: String::from("about:blank"), private: false },
         };
 
         let mut processor = Processor::new(context);
@@ -1493,7 +1519,7 @@ mod tests {
             message_buffer: &mut message_buffer,
             inline_search_state: &mut inline_search_state,
             config: &cfg,
-            window_kind: WindowKind::Web { url: String::from("about:blank") },
+            window_kind: WindowKind::Web { url: String::from("about:blank"), private: false },
         };
 
         let mut processor = Processor::new(context);
@@ -1521,7 +1547,7 @@ mod tests {
             message_buffer: &mut message_buffer,
             inline_search_state: &mut inline_search_state,
             config: &cfg,
-            window_kind: WindowKind::Web { url: String::from("about:blank") },
+            window_kind: WindowKind::Web { url: String::from("about:blank"), private: false },
         };
 
         let mut processor = Processor::new(context);
@@ -0,0 +1,156 @@
+//! Web Inspector sessions controlled over IPC.
+
+use crate::ipc::{IpcError, IpcErrorCode, IpcInspectorMessage, IpcInspectorSession};
+#[cfg(target_os = "macos")]
+use crate::macos::remote_inspector::{InspectorError, RemoteInspectorClient};
+use crate::tabs::TabId;
+
+/// Web Inspector connection of the current platform.
+#[cfg(target_os = "macos")]
+pub type PlatformInspector = RemoteInspectorClient;
+#[cfg(not(target_os = "macos"))]
+pub type PlatformInspector = UnsupportedInspector;
+
+/// Raw protocol access to the Web Inspector of web tabs.
+pub trait InspectorSession: Sized {
+    /// Connect to the inspector of this process.
+    fn connect() -> Result<Self, IpcError>;
+
+    /// Open a session with the inspector target of a tab.
+    fn attach(&self, tab_id: TabId, target_id: u64) -> Result<IpcInspectorSession, IpcError>;
+
+    /// Close a session.
+    fn detach(&self, session_id: &str) -> Result<(), IpcError>;
+
+    /// Send a raw protocol message to the target of a session.
+    fn send(&self, session_id: &str, message: &str) -> Result<(), IpcError>;
+
+    /// Take up to `max` queued messages of a session, or all of them.
+    fn poll(
+        &self,
+        session_id: &str,
+        max: Option<usize>,
+    ) -> Result<Vec<IpcInspectorMessage>, IpcError>;
+
+    /// Check if the session is open on this connection.
+    fn has_session(&self, session_id: &str) -> bool;
+}
+
+/// Error replied to inspector requests on platforms without Web Inspector support.
+pub fn unsupported() -> IpcError {
+    IpcError::new(IpcErrorCode::Unsupported, "Web Inspector is not supported on this platform")
+}
+
+/// Inspector of platforms without web tabs, failing every request.
+#[cfg(not(target_os = "macos"))]
+pub struct UnsupportedInspector;
+
+#[cfg(not(target_os = "macos"))]
+impl InspectorSession for UnsupportedInspector {
+    fn connect() -> Result<Self, IpcError> {
+        Ok(Self)
+    }
+
+    fn attach(&self, _tab_id: TabId, _target_id: u64) -> Result<IpcInspectorSession, IpcError> {
+        Err(unsupported())
+    }
+
+    fn detach(&self, _session_id: &str) -> Result<(), IpcError> {
+        Err(unsupported())
+    }
+
+    fn send(&self, _session_id: &str, _message: &str) -> Result<(), IpcError> {
+        Err(unsupported())
+    }
+
+    fn poll(
+        &self,
+        _session_id: &str,
+        _max: Option<usize>,
+    ) -> Result<Vec<IpcInspectorMessage>, IpcError> {
+        Err(unsupported())
+    }
+
+    fn has_session(&self, _session_id: &str) -> bool {
+        false
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl InspectorSession for RemoteInspectorClient {
+    fn connect() -> Result<Self, IpcError> {
+        RemoteInspectorClient::connect().map_err(map_inspector_error)
+    }
+
+    fn attach(&self, tab_id: TabId, target_id: u64) -> Result<IpcInspectorSession, IpcError> {
+        let session =
+            RemoteInspectorClient::attach(self, tab_id, target_id).map_err(map_inspector_error)?;
+        Ok(IpcInspectorSession {
+            session_id: session.session_id,
+            target_id: session.target_id,
+            tab_id: session.tab_id.into(),
+        })
+    }
+
+    fn detach(&self, session_id: &str) -> Result<(), IpcError> {
+        RemoteInspectorClient::detach(self, session_id).map_err(map_inspector_error)
+    }
+
+    fn send(&self, session_id: &str, message: &str) -> Result<(), IpcError> {
+        self.send_message(session_id, message).map_err(map_inspector_error)
+    }
+
+    fn poll(
+        &self,
+        session_id: &str,
+        max: Option<usize>,
+    ) -> Result<Vec<IpcInspectorMessage>, IpcError> {
+        let messages = self.poll_messages(session_id, max).map_err(map_inspector_error)?;
+        Ok(messages
+            .into_iter()
+            .map(|message| IpcInspectorMessage {
+                session_id: message.session_id,
+                payload: message.payload,
+            })
+            .collect())
+    }
+
+    fn has_session(&self, session_id: &str) -> bool {
+        RemoteInspectorClient::has_session(self, session_id)
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn map_inspector_error(error: InspectorError) -> IpcError {
+    match error {
+        InspectorError::PermissionDenied => {
+            IpcError::new(IpcErrorCode::PermissionDenied, "Inspector permission denied")
+        },
+        InspectorError::ConnectionFailed(message) => IpcError::new(IpcErrorCode::Internal, message),
+        InspectorError::Timeout => {
+            IpcError::new(IpcErrorCode::Timeout, "Inspector request timed out")
+        },
+        InspectorError::NotFound(message) => IpcError::new(IpcErrorCode::NotFound, message),
+        InspectorError::Ambiguous(message) => IpcError::new(IpcErrorCode::Ambiguous, message),
+        InspectorError::InvalidMessage(message) => {
+            IpcError::new(IpcErrorCode::InvalidRequest, message)
+        },
+    }
+}
+
+#[cfg(all(test, not(target_os = "macos")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsupported_inspector_requests() {
+        let inspector = PlatformInspector::connect().unwrap();
+        let is_unsupported = |error: IpcError| error.code == IpcErrorCode::Unsupported;
+
+        assert!(inspector.attach(TabId::new(0, 0), 1).is_err_and(is_unsupported));
+        assert!(inspector.detach("session").is_err_and(is_unsupported));
+        assert!(inspector.send("session", "{}").is_err_and(is_unsupported));
+        assert!(inspector.poll("session", None).is_err_and(is_unsupported));
+        assert!(!inspector.has_session("session"));
+    }
+}
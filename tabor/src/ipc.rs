@@ -2,16 +2,20 @@
 
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::ffi::OsStr;
-use std::io::{BufRead, BufReader, Error as IoError, ErrorKind, Result as IoResult, Write};
-use std::net::Shutdown;
+use std::io::{
+    self, BufRead, BufReader, Error as IoError, ErrorKind, IsTerminal, Read, Result as IoResult,
+    Write,
+};
+use std::net::{Ipv4Addr, Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
 use std::os::unix::net::{UnixListener, UnixStream};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{env, fs, process};
 
-use log::{error, warn};
+use log::{debug, error, warn};
 use std::result::Result;
 use winit::event_loop::EventLoopProxy;
 
@@ -21,6 +25,7 @@ use tabor_terminal::vi_mode::ViMotion;
 use crate::cli::{IpcConfig, IpcGetConfig, Options, WindowOptions};
 use crate::config::{Action, MouseAction, SearchAction, ViAction};
 use crate::config::ui_config::Program;
+use crate::display::color::Rgb;
 use crate::event::{Event, EventType};
 use crate::tabs::TabId;
 use crate::window_kind::WindowKind;
@@ -28,7 +33,17 @@ use crate::window_kind::WindowKind;
 /// Environment variable name for the IPC socket path.
 const TABOR_SOCKET_ENV: &str = "TABOR_SOCKET";
 
-const IPC_PROTOCOL_VERSION: u32 = 1;
+/// Environment variable name for the IPC authentication token.
+const TABOR_IPC_TOKEN_ENV: &str = "TABOR_IPC_TOKEN";
+
+/// Request field carrying the IPC authentication token.
+const IPC_TOKEN_FIELD: &str = "token";
+
+/// Prefix of socket paths connecting to a TCP listener instead of a unix socket.
+const TCP_SOCKET_PREFIX: &str = "tcp://";
+
+/// Version of the IPC protocol, bumped whenever requests or replies change incompatibly.
+pub const IPC_PROTOCOL_VERSION: u32 = 2;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct IpcTabId {
@@ -59,7 +74,7 @@ impl From<&WindowKind> for IpcTabKind {
     fn from(kind: &WindowKind) -> Self {
         match kind {
             WindowKind::Terminal => Self::Terminal,
-            WindowKind::Web { url } => Self::Web { url: url.clone() },
+            WindowKind::Web { url, .. } => Self::Web { url: url.clone() },
         }
     }
 }
@@ -70,7 +85,7 @@ pub struct IpcTabActivity {
     pub last_output_ms_ago: Option<u64>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct IpcTabState {
     pub tab_id: IpcTabId,
     pub group_id: usize,
@@ -81,21 +96,100 @@ pub struct IpcTabState {
     pub program_name: String,
     pub kind: IpcTabKind,
     pub activity: Option<IpcTabActivity>,
+    /// Progress of the page load in flight, from 0 to 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_progress: Option<f64>,
+    /// Whether a job other than the shell runs in the foreground of a terminal tab.
+    #[serde(default)]
+    pub busy: bool,
+    /// Name of the foreground job of a busy terminal tab.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub foreground_program: Option<String>,
+    /// Page zoom of a loaded web tab.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zoom: Option<f64>,
+    /// Scroll offset of a web tab, as of the last poll of its page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scroll: Option<IpcScrollPosition>,
+    /// Working directory of a terminal tab, as reported through OSC 7 or of its shell process.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    /// Whether the Web Inspector of a loaded web tab is open.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inspector_visible: Option<bool>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+/// Scroll offset of a web page in CSS pixels.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct IpcScrollPosition {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct IpcTabGroup {
     pub id: usize,
     pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Rgb>,
+    /// Working directory of new terminal tabs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_cwd: Option<PathBuf>,
+    /// Page loaded by new blank web tabs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
     pub tabs: Vec<IpcTabState>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IpcWindow {
+    /// Window id, as exported through `TABOR_WINDOW_ID`.
+    pub window_id: u64,
+    pub title: String,
+    pub focused: bool,
+    pub groups: Vec<IpcTabGroup>,
+}
+
+/// Closed tab which can be restored.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IpcClosedTab {
+    pub title: String,
+    pub custom_title: Option<String>,
+    pub kind: IpcTabKind,
+    /// Group the tab is restored into, next to its former neighbors.
+    pub group_id: Option<usize>,
+}
+
+/// Draw times of a window's recent frames, in microseconds.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IpcRenderStats {
+    pub last_frame_us: u64,
+    pub p99_us: u64,
+    /// Number of frames the percentile is based on.
+    pub frames: usize,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct IpcTabPanelState {
     pub enabled: bool,
     pub width: usize,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IpcWebHistory {
+    /// Back-forward list, oldest entry first.
+    pub entries: Vec<IpcWebHistoryEntry>,
+    /// Position of the current page in `entries`.
+    pub current: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IpcWebHistoryEntry {
+    pub url: String,
+    /// Page title, empty while the page is still loading.
+    pub title: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct IpcInspectorTarget {
     pub target_id: u64,
@@ -125,7 +219,16 @@ pub struct IpcCapabilities {
     pub protocol_version: u32,
     pub platform: String,
     pub version: String,
+    pub requests: Vec<String>,
+    pub features: IpcFeatures,
+}
+
+/// Optional features available in this build.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpcFeatures {
     pub web_tabs: bool,
+    pub tab_panel: bool,
+    pub inspector: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
@@ -150,13 +253,29 @@ pub struct IpcError {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum TabSelection {
     Active,
-    Next,
-    Previous,
+    Next {
+        /// Cycle to the first tab after the last one.
+        #[serde(default = "default_wrap")]
+        wrap: bool,
+    },
+    Previous {
+        /// Cycle to the last tab before the first one.
+        #[serde(default = "default_wrap")]
+        wrap: bool,
+    },
     Last,
     ByIndex { index: usize },
     ById { tab_id: IpcTabId },
 }
 
+fn default_wrap() -> bool {
+    true
+}
+
+fn default_focus() -> bool {
+    true
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum UrlTarget {
@@ -165,6 +284,17 @@ pub enum UrlTarget {
     TabId { tab_id: IpcTabId },
 }
 
+/// Where the Web Inspector of a web tab is shown.
+#[derive(Serialize, Deserialize, clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InspectorDock {
+    Left,
+    Right,
+    Bottom,
+    /// In a window of its own.
+    Detached,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum IpcAction {
@@ -175,35 +305,154 @@ pub enum IpcAction {
     MouseAction { action: String },
     Esc { sequence: String },
     Command { program: Program },
+    /// Scroll the scrollback by `lines`, positive values scroll up.
+    Scroll { lines: i32 },
+    ScrollToTop,
+    ScrollToBottom,
 }
 
+/// Event names accepted by [`IpcRequest::Subscribe`].
+pub const IPC_EVENT_NAMES: &[&str] =
+    &["tab_created", "tab_closed", "title_changed", "url_changed", "child_exited"];
+
+/// Write timeout after which a subscriber that stopped reading is dropped.
+const SUBSCRIBER_WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Timeout of every read and write on a client connection.
+///
+/// Requests of all connections are read by a single thread, which a stalled client would block.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Size limit of a single request, including its trailing newline.
+const MAX_REQUEST_BYTES: u64 = 16 * 1024 * 1024;
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "type", rename_all = "snake_case")]
+pub enum IpcEvent {
+    TabCreated { tab_id: IpcTabId },
+    TabClosed { tab_id: IpcTabId },
+    TitleChanged { tab_id: IpcTabId, title: String },
+    UrlChanged { tab_id: IpcTabId, url: String },
+    /// Command of a terminal tab exited, `status` is `None` if it was killed by a signal.
+    ChildExited { tab_id: IpcTabId, status: Option<i32> },
+}
+
+impl IpcEvent {
+    /// Name used to subscribe to this event.
+    pub fn name(&self) -> &'static str {
+        match self {
+            IpcEvent::TabCreated { .. } => "tab_created",
+            IpcEvent::TabClosed { .. } => "tab_closed",
+            IpcEvent::TitleChanged { .. } => "title_changed",
+            IpcEvent::UrlChanged { .. } => "url_changed",
+            IpcEvent::ChildExited { .. } => "child_exited",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum IpcRequest {
     Ping,
     GetCapabilities,
     ListTabs,
+    ListWindows,
     GetTabState { tab_id: IpcTabId },
-    CreateTab { options: WindowOptions, group_id: Option<usize>, group_name: Option<String> },
+    CreateTab {
+        options: Box<WindowOptions>,
+        group_id: Option<usize>,
+        group_name: Option<String>,
+        /// Focus the new tab, otherwise it opens in the background.
+        #[serde(default = "default_focus")]
+        focus: bool,
+    },
     CreateGroup { name: Option<String> },
+    DuplicateTab { tab_id: Option<IpcTabId> },
+    CreateWindow { options: Box<WindowOptions> },
+    FocusWindow { window_id: u64 },
+    GetRenderStats { window_id: u64 },
     CloseTab { tab_id: Option<IpcTabId> },
     SelectTab { selection: TabSelection },
     MoveTab {
         tab_id: IpcTabId,
         target_group_id: Option<usize>,
         target_index: Option<usize>,
+        /// Place the tab right before this tab, in its group.
+        #[serde(default)]
+        before: Option<IpcTabId>,
+        /// Place the tab right after this tab, in its group.
+        #[serde(default)]
+        after: Option<IpcTabId>,
     },
     SetTabTitle { tab_id: Option<IpcTabId>, title: Option<String> },
+    SetTabTitleTemplate { tab_id: Option<IpcTabId>, template: Option<String> },
     SetGroupName { group_id: usize, name: Option<String> },
-    RestoreClosedTab,
+    SetGroupColor { group_id: usize, color: Option<Rgb> },
+    SetGroupDefaults { group_id: usize, cwd: Option<PathBuf>, homepage: Option<String> },
+    RestoreClosedTab {
+        /// Number of tabs to restore, most recently closed first.
+        #[serde(default)]
+        count: Option<usize>,
+    },
+    ListClosedTabs,
     OpenUrl { url: String, target: UrlTarget },
     SetWebUrl { tab_id: Option<IpcTabId>, url: String },
-    ReloadWeb { tab_id: Option<IpcTabId> },
+    ReloadWeb {
+        tab_id: Option<IpcTabId>,
+        /// Revalidate the page and its resources instead of loading them from the cache.
+        #[serde(default)]
+        ignore_cache: bool,
+    },
+    OpenExternal { tab_id: Option<IpcTabId> },
+    RunBookmarklet { tab_id: Option<IpcTabId>, name: String },
+    SetWebZoom { tab_id: Option<IpcTabId>, zoom: f64 },
+    SetWebMuted { tab_id: Option<IpcTabId>, muted: bool },
+    SetGroupMuted { group_id: usize, muted: bool },
+    HibernateGroup { group_id: usize },
+    SetWebHeaders {
+        tab_id: Option<IpcTabId>,
+        user_agent: Option<String>,
+        headers: Option<HashMap<String, String>>,
+    },
+    GetWebHistory { tab_id: Option<IpcTabId> },
+    GetWebScroll { tab_id: Option<IpcTabId> },
+    SetWebScroll { tab_id: Option<IpcTabId>, x: f64, y: f64 },
+    ClearWebData {
+        tab_id: Option<IpcTabId>,
+        #[serde(default)]
+        cookies: bool,
+        #[serde(default)]
+        cache: bool,
+        #[serde(default)]
+        storage: bool,
+        since_seconds: Option<u64>,
+    },
+    GoToHistoryIndex { tab_id: Option<IpcTabId>, index: usize },
+    SnapshotTab { tab_id: Option<IpcTabId>, path: PathBuf },
     OpenInspector { tab_id: Option<IpcTabId> },
+    SetInspectorDock { tab_id: Option<IpcTabId>, side: InspectorDock },
     GetTabPanel,
     SetTabPanel { enabled: Option<bool>, width: Option<usize> },
     DispatchAction { tab_id: Option<IpcTabId>, action: IpcAction },
-    SendInput { tab_id: Option<IpcTabId>, text: String },
+    SendInput {
+        tab_id: Option<IpcTabId>,
+        text: String,
+        #[serde(default)]
+        interpret_escapes: bool,
+    },
+    GetTabContents {
+        tab_id: Option<IpcTabId>,
+        #[serde(default)]
+        scrollback: bool,
+    },
+    GetSelection { tab_id: Option<IpcTabId> },
+    /// Wait for the command of a terminal tab to exit.
+    WaitTab {
+        tab_id: Option<IpcTabId>,
+        /// Time in milliseconds after which the request fails, waits indefinitely if omitted.
+        #[serde(default)]
+        timeout_ms: Option<u64>,
+    },
     RunCommandBar { tab_id: Option<IpcTabId>, input: String },
     ListInspectorTargets,
     AttachInspector { tab_id: Option<IpcTabId>, target_id: Option<u64> },
@@ -212,13 +461,31 @@ pub enum IpcRequest {
     PollInspectorMessages { session_id: String, max: Option<usize> },
     SetConfig(IpcConfig),
     GetConfig(IpcGetConfig),
+    Subscribe {
+        #[serde(default)]
+        events: Vec<String>,
+    },
 }
 
+#[derive(Serialize, Debug)]
 pub struct IpcRequestHelp {
     pub name: &'static str,
     pub summary: &'static str,
 }
 
+/// JSON output of `tabor msg list-requests --json`.
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename = "request_list")]
+pub struct IpcRequestList {
+    pub requests: &'static [IpcRequestHelp],
+}
+
+impl Default for IpcRequestList {
+    fn default() -> Self {
+        Self { requests: ipc_request_help() }
+    }
+}
+
 pub fn ipc_request_help() -> &'static [IpcRequestHelp] {
     &[
         IpcRequestHelp {
@@ -233,6 +500,10 @@ pub fn ipc_request_help() -> &'static [IpcRequestHelp] {
             name: "list_tabs",
             summary: "List tabs grouped by tab group.",
         },
+        IpcRequestHelp {
+            name: "list_windows",
+            summary: "List windows with their tab groups.",
+        },
         IpcRequestHelp {
             name: "get_tab_state",
             summary: "Get state for a specific tab.",
@@ -245,6 +516,22 @@ pub fn ipc_request_help() -> &'static [IpcRequestHelp] {
             name: "create_group",
             summary: "Create a new tab group.",
         },
+        IpcRequestHelp {
+            name: "duplicate_tab",
+            summary: "Open a copy of a tab right after it.",
+        },
+        IpcRequestHelp {
+            name: "create_window",
+            summary: "Open a new terminal or web window.",
+        },
+        IpcRequestHelp {
+            name: "focus_window",
+            summary: "Raise and focus a window.",
+        },
+        IpcRequestHelp {
+            name: "get_render_stats",
+            summary: "Get frame draw times of a window.",
+        },
         IpcRequestHelp {
             name: "close_tab",
             summary: "Close a tab (defaults to active).",
@@ -261,13 +548,29 @@ pub fn ipc_request_help() -> &'static [IpcRequestHelp] {
             name: "set_tab_title",
             summary: "Set or clear a tab custom title.",
         },
+        IpcRequestHelp {
+            name: "set_tab_title_template",
+            summary: "Set or clear a terminal tab title template.",
+        },
         IpcRequestHelp {
             name: "set_group_name",
             summary: "Set a tab group name.",
         },
+        IpcRequestHelp {
+            name: "set_group_color",
+            summary: "Set or clear a tab group color.",
+        },
+        IpcRequestHelp {
+            name: "set_group_defaults",
+            summary: "Set or clear the working directory and homepage of new tabs in a group.",
+        },
         IpcRequestHelp {
             name: "restore_closed_tab",
-            summary: "Restore the most recently closed tab.",
+            summary: "Restore the most recently closed tabs.",
+        },
+        IpcRequestHelp {
+            name: "list_closed_tabs",
+            summary: "List closed tabs which can be restored.",
         },
         IpcRequestHelp {
             name: "open_url",
@@ -281,10 +584,66 @@ pub fn ipc_request_help() -> &'static [IpcRequestHelp] {
             name: "reload_web",
             summary: "Reload a web tab.",
         },
+        IpcRequestHelp {
+            name: "open_external",
+            summary: "Open a web tab's URL in the default browser.",
+        },
+        IpcRequestHelp {
+            name: "run_bookmarklet",
+            summary: "Run a configured bookmarklet on a web tab's page.",
+        },
+        IpcRequestHelp {
+            name: "set_web_zoom",
+            summary: "Set the page zoom of a web tab.",
+        },
+        IpcRequestHelp {
+            name: "set_web_muted",
+            summary: "Mute or unmute audio in a web tab.",
+        },
+        IpcRequestHelp {
+            name: "set_group_muted",
+            summary: "Mute or unmute audio in every web tab of a group.",
+        },
+        IpcRequestHelp {
+            name: "hibernate_group",
+            summary: "Unload every background web tab of a group.",
+        },
+        IpcRequestHelp {
+            name: "set_web_headers",
+            summary: "Override the user agent and add request headers for a web tab.",
+        },
+        IpcRequestHelp {
+            name: "clear_web_data",
+            summary: "Clear cookies, caches and storage of a web tab's site or all sites.",
+        },
+        IpcRequestHelp {
+            name: "get_web_history",
+            summary: "List the back-forward history of a web tab.",
+        },
+        IpcRequestHelp {
+            name: "get_web_scroll",
+            summary: "Read the scroll position of a web tab.",
+        },
+        IpcRequestHelp {
+            name: "set_web_scroll",
+            summary: "Scroll a web tab to a position.",
+        },
+        IpcRequestHelp {
+            name: "go_to_history_index",
+            summary: "Navigate a web tab to a back-forward history entry.",
+        },
+        IpcRequestHelp {
+            name: "snapshot_tab",
+            summary: "Write a PNG snapshot of a web tab.",
+        },
         IpcRequestHelp {
             name: "open_inspector",
             summary: "Open Web Inspector for a web tab.",
         },
+        IpcRequestHelp {
+            name: "set_inspector_dock",
+            summary: "Dock the Web Inspector of a web tab to a side, or detach it.",
+        },
         IpcRequestHelp {
             name: "get_tab_panel",
             summary: "Get tab panel state.",
@@ -299,7 +658,19 @@ pub fn ipc_request_help() -> &'static [IpcRequestHelp] {
         },
         IpcRequestHelp {
             name: "send_input",
-            summary: "Send literal input text to a tab.",
+            summary: "Send input text to a tab, optionally interpreting escapes.",
+        },
+        IpcRequestHelp {
+            name: "get_tab_contents",
+            summary: "Read the visible text or scrollback of a terminal tab.",
+        },
+        IpcRequestHelp {
+            name: "get_selection",
+            summary: "Read the selected text of a terminal or web tab.",
+        },
+        IpcRequestHelp {
+            name: "wait_tab",
+            summary: "Wait for the command of a terminal tab to exit.",
         },
         IpcRequestHelp {
             name: "run_command_bar",
@@ -333,6 +704,10 @@ pub fn ipc_request_help() -> &'static [IpcRequestHelp] {
             name: "get_config",
             summary: "Read runtime config.",
         },
+        IpcRequestHelp {
+            name: "subscribe",
+            summary: "Stream tab events until the connection is closed.",
+        },
     ]
 }
 
@@ -341,14 +716,31 @@ impl IpcRequest {
         match self {
             IpcRequest::GetTabState { tab_id } => Some(*tab_id),
             IpcRequest::CloseTab { tab_id } => *tab_id,
+            IpcRequest::DuplicateTab { tab_id } => *tab_id,
             IpcRequest::MoveTab { tab_id, .. } => Some(*tab_id),
             IpcRequest::SetTabTitle { tab_id, .. } => *tab_id,
+            IpcRequest::SetTabTitleTemplate { tab_id, .. } => *tab_id,
             IpcRequest::DispatchAction { tab_id, .. } => *tab_id,
             IpcRequest::SendInput { tab_id, .. } => *tab_id,
+            IpcRequest::GetTabContents { tab_id, .. } => *tab_id,
+            IpcRequest::GetSelection { tab_id } => *tab_id,
+            IpcRequest::WaitTab { tab_id, .. } => *tab_id,
             IpcRequest::RunCommandBar { tab_id, .. } => *tab_id,
             IpcRequest::AttachInspector { tab_id, .. } => *tab_id,
             IpcRequest::OpenInspector { tab_id }
-            | IpcRequest::ReloadWeb { tab_id }
+            | IpcRequest::SetInspectorDock { tab_id, .. }
+            | IpcRequest::ReloadWeb { tab_id, .. }
+            | IpcRequest::OpenExternal { tab_id }
+            | IpcRequest::RunBookmarklet { tab_id, .. }
+            | IpcRequest::SetWebZoom { tab_id, .. }
+            | IpcRequest::SetWebMuted { tab_id, .. }
+            | IpcRequest::SetWebHeaders { tab_id, .. }
+            | IpcRequest::ClearWebData { tab_id, .. }
+            | IpcRequest::GetWebHistory { tab_id }
+            | IpcRequest::GetWebScroll { tab_id }
+            | IpcRequest::SetWebScroll { tab_id, .. }
+            | IpcRequest::GoToHistoryIndex { tab_id, .. }
+            | IpcRequest::SnapshotTab { tab_id, .. }
             | IpcRequest::SetWebUrl { tab_id, .. } => *tab_id,
             IpcRequest::OpenUrl { target, .. } => match target {
                 UrlTarget::TabId { tab_id } => Some(*tab_id),
@@ -370,6 +762,74 @@ impl IpcRequest {
             _ => None,
         }
     }
+
+    /// Check if a reply could answer this request.
+    ///
+    /// Error replies answer every request.
+    pub fn accepts_reply(&self, reply: &SocketReply) -> bool {
+        matches!(
+            (self, reply),
+            (_, SocketReply::Error { .. })
+                | (IpcRequest::Ping, SocketReply::Pong)
+                | (IpcRequest::GetCapabilities, SocketReply::Capabilities { .. })
+                | (IpcRequest::ListTabs, SocketReply::TabList { .. })
+                | (IpcRequest::ListWindows, SocketReply::WindowList { .. })
+                | (IpcRequest::GetTabState { .. }, SocketReply::TabState { .. })
+                | (IpcRequest::CreateTab { .. }, SocketReply::TabCreated { .. })
+                | (IpcRequest::DuplicateTab { .. }, SocketReply::TabCreated { .. })
+                | (IpcRequest::CreateGroup { .. }, SocketReply::GroupCreated { .. })
+                | (IpcRequest::CreateWindow { .. }, SocketReply::WindowCreated { .. })
+                | (IpcRequest::GetRenderStats { .. }, SocketReply::RenderStats { .. })
+                | (IpcRequest::ListClosedTabs, SocketReply::ClosedTabs { .. })
+                | (IpcRequest::OpenUrl { .. }, SocketReply::TabCreated { .. } | SocketReply::Ok)
+                | (IpcRequest::GetWebHistory { .. }, SocketReply::WebHistory { .. })
+                | (IpcRequest::GetWebScroll { .. }, SocketReply::WebScroll { .. })
+                | (IpcRequest::SnapshotTab { .. }, SocketReply::Snapshot { .. })
+                | (IpcRequest::GetTabPanel, SocketReply::TabPanel { .. })
+                | (IpcRequest::GetTabContents { .. }, SocketReply::TabContents { .. })
+                | (IpcRequest::GetSelection { .. }, SocketReply::Selection { .. })
+                | (IpcRequest::WaitTab { .. }, SocketReply::TabExited { .. })
+                | (IpcRequest::ListInspectorTargets, SocketReply::InspectorTargets { .. })
+                | (IpcRequest::AttachInspector { .. }, SocketReply::InspectorAttached { .. })
+                | (IpcRequest::PollInspectorMessages { .. }, SocketReply::InspectorMessages { .. })
+                | (IpcRequest::GetConfig(_), SocketReply::Config { .. })
+                | (IpcRequest::Subscribe { .. }, SocketReply::Ok | SocketReply::Event { .. })
+                | (
+                    IpcRequest::FocusWindow { .. }
+                    | IpcRequest::CloseTab { .. }
+                    | IpcRequest::SelectTab { .. }
+                    | IpcRequest::MoveTab { .. }
+                    | IpcRequest::SetTabTitle { .. }
+                    | IpcRequest::SetTabTitleTemplate { .. }
+                    | IpcRequest::SetGroupName { .. }
+                    | IpcRequest::SetGroupColor { .. }
+                    | IpcRequest::SetGroupDefaults { .. }
+                    | IpcRequest::RestoreClosedTab { .. }
+                    | IpcRequest::SetWebUrl { .. }
+                    | IpcRequest::ReloadWeb { .. }
+                    | IpcRequest::OpenExternal { .. }
+                    | IpcRequest::RunBookmarklet { .. }
+                    | IpcRequest::SetWebZoom { .. }
+                    | IpcRequest::SetWebMuted { .. }
+                    | IpcRequest::SetGroupMuted { .. }
+                    | IpcRequest::HibernateGroup { .. }
+                    | IpcRequest::SetWebHeaders { .. }
+                    | IpcRequest::SetWebScroll { .. }
+                    | IpcRequest::ClearWebData { .. }
+                    | IpcRequest::GoToHistoryIndex { .. }
+                    | IpcRequest::OpenInspector { .. }
+                    | IpcRequest::SetInspectorDock { .. }
+                    | IpcRequest::SetTabPanel { .. }
+                    | IpcRequest::DispatchAction { .. }
+                    | IpcRequest::SendInput { .. }
+                    | IpcRequest::RunCommandBar { .. }
+                    | IpcRequest::DetachInspector { .. }
+                    | IpcRequest::SendInspectorMessage { .. }
+                    | IpcRequest::SetConfig(_),
+                    SocketReply::Ok,
+                )
+        )
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -379,13 +839,26 @@ pub enum SocketReply {
     Pong,
     Capabilities { capabilities: IpcCapabilities },
     TabList { groups: Vec<IpcTabGroup> },
+    ClosedTabs { tabs: Vec<IpcClosedTab> },
+    WindowList { windows: Vec<IpcWindow> },
     TabState { tab: IpcTabState },
     TabCreated { tab_id: IpcTabId },
     GroupCreated { group_id: usize },
+    WindowCreated { window_id: u64 },
+    RenderStats { stats: IpcRenderStats },
     TabPanel { panel: IpcTabPanelState },
     InspectorTargets { targets: Vec<IpcInspectorTarget> },
     InspectorAttached { session: IpcInspectorSession },
     InspectorMessages { messages: Vec<IpcInspectorMessage> },
+    Snapshot { path: PathBuf },
+    WebHistory { history: IpcWebHistory },
+    WebScroll { x: f64, y: f64 },
+    Event { event: IpcEvent },
+    TabContents { lines: Vec<String> },
+    /// Selected text, `None` when nothing is selected.
+    Selection { text: Option<String> },
+    /// Command of a tab exited, `status` is `None` if it was killed by a signal.
+    TabExited { tab_id: IpcTabId, status: Option<i32> },
     Config { config: serde_json::Value },
     Error { error: IpcError },
 }
@@ -396,7 +869,12 @@ impl IpcCapabilities {
             protocol_version: IPC_PROTOCOL_VERSION,
             platform: std::env::consts::OS.to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
-            web_tabs: cfg!(target_os = "macos"),
+            requests: ipc_request_help().iter().map(|help| help.name.to_string()).collect(),
+            features: IpcFeatures {
+                web_tabs: cfg!(target_os = "macos"),
+                tab_panel: cfg!(target_os = "macos"),
+                inspector: cfg!(target_os = "macos"),
+            },
         }
     }
 }
@@ -430,6 +908,9 @@ pub fn ipc_action_to_action(action: IpcAction) -> Result<Action, IpcError> {
         },
         IpcAction::Esc { sequence } => Ok(Action::Esc(sequence)),
         IpcAction::Command { program } => Ok(Action::Command(program)),
+        IpcAction::Scroll { lines } => Ok(Action::ScrollLines(lines)),
+        IpcAction::ScrollToTop => Ok(Action::ScrollToTop),
+        IpcAction::ScrollToBottom => Ok(Action::ScrollToBottom),
     }
 }
 
@@ -448,6 +929,76 @@ fn parse_action_name<T: DeserializeOwned>(name: &str, label: &str) -> Result<T,
     })
 }
 
+/// Replace the escape sequences of `send_input` text with the characters they stand for.
+///
+/// Supported are `\n`, `\r`, `\t`, `\e`, `\\`, `\xNN` for ASCII and `\u{NNNN}` for any
+/// unicode scalar value.
+fn interpret_input_escapes(text: &str) -> Result<String, IpcError> {
+    let invalid = |message: String| IpcError::new(IpcErrorCode::InvalidRequest, message);
+
+    let mut input = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            input.push(ch);
+            continue;
+        }
+
+        let escaped = match chars.next() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('e') => '\x1b',
+            Some('\\') => '\\',
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                match u8::from_str_radix(&hex, 16) {
+                    Ok(byte) if is_hex(&hex) && hex.len() == 2 && byte.is_ascii() => {
+                        char::from(byte)
+                    },
+                    _ => return Err(invalid(format!("Invalid escape \\x{hex}"))),
+                }
+            },
+            Some('u') => {
+                let rest = chars.as_str();
+                let code = rest
+                    .strip_prefix('{')
+                    .and_then(|rest| rest.split_once('}'))
+                    .map(|(code, _)| code)
+                    .ok_or_else(|| invalid(String::from("Unterminated escape \\u{")))?;
+                let escaped = u32::from_str_radix(code, 16)
+                    .ok()
+                    .filter(|_| is_hex(code))
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| invalid(format!("Invalid escape \\u{{{code}}}")))?;
+                chars = rest[code.len() + 2..].chars();
+                escaped
+            },
+            Some(other) => return Err(invalid(format!("Invalid escape \\{other}"))),
+            None => return Err(invalid(String::from("Trailing backslash"))),
+        };
+        input.push(escaped);
+    }
+
+    Ok(input)
+}
+
+fn is_hex(digits: &str) -> bool {
+    digits.chars().all(|ch| ch.is_ascii_hexdigit())
+}
+
+/// Find the window targeted by `window_id`, as passed through `TABOR_WINDOW_ID`.
+pub fn resolve_window_id<I>(window_id: u64, windows: I) -> Result<u64, IpcError>
+where
+    I: IntoIterator<Item = u64>,
+{
+    windows
+        .into_iter()
+        .any(|id| id == window_id)
+        .then_some(window_id)
+        .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Window not found"))
+}
+
 pub struct IpcResponse {
     pub reply: SocketReply,
     pub close_window: bool,
@@ -463,8 +1014,10 @@ pub trait IpcContext {
         options: WindowOptions,
         group_id: Option<usize>,
         group_name: Option<String>,
+        focus: bool,
     ) -> Result<TabId, IpcError>;
     fn create_group(&mut self, name: Option<String>) -> Result<usize, IpcError>;
+    fn duplicate_tab(&mut self, tab_id: TabId) -> Result<TabId, IpcError>;
     fn close_tab(&mut self, tab_id: TabId) -> Result<bool, IpcError>;
     fn select_tab(&mut self, selection: TabSelection) -> Result<(), IpcError>;
     fn move_tab(
@@ -474,16 +1027,46 @@ pub trait IpcContext {
         target_index: Option<usize>,
     ) -> Result<(), IpcError>;
     fn set_tab_title(&mut self, tab_id: TabId, title: Option<String>) -> Result<(), IpcError>;
+    fn set_tab_title_template(
+        &mut self,
+        tab_id: TabId,
+        template: Option<String>,
+    ) -> Result<(), IpcError>;
     fn set_group_name(&mut self, group_id: usize, name: Option<String>) -> Result<(), IpcError>;
-    fn restore_closed_tab(&mut self) -> Result<(), IpcError>;
+    fn set_group_color(&mut self, group_id: usize, color: Option<Rgb>) -> Result<(), IpcError>;
+    fn set_group_defaults(
+        &mut self,
+        group_id: usize,
+        cwd: Option<PathBuf>,
+        homepage: Option<String>,
+    ) -> Result<(), IpcError>;
+    fn restore_closed_tab(&mut self, count: usize) -> Result<(), IpcError>;
+    fn closed_tabs(&self) -> Vec<IpcClosedTab>;
     fn open_url_in_tab(&mut self, tab_id: TabId, url: String) -> Result<(), IpcError>;
     fn open_url_new_tab(&mut self, url: String) -> Result<TabId, IpcError>;
-    fn reload_web(&mut self, tab_id: TabId) -> Result<(), IpcError>;
+    fn reload_web(&mut self, tab_id: TabId, ignore_cache: bool) -> Result<(), IpcError>;
+    fn open_external(&mut self, tab_id: TabId) -> Result<(), IpcError>;
+    fn run_bookmarklet(&mut self, tab_id: TabId, name: &str) -> Result<(), IpcError>;
+    fn set_web_zoom(&mut self, tab_id: TabId, zoom: f64) -> Result<(), IpcError>;
+    fn set_web_muted(&mut self, tab_id: TabId, muted: bool) -> Result<(), IpcError>;
+    /// Unload a background web tab; tabs which must stay loaded are left untouched.
+    fn hibernate_web_tab(&mut self, tab_id: TabId) -> Result<(), IpcError>;
+    fn set_web_headers(
+        &mut self,
+        tab_id: TabId,
+        user_agent: Option<String>,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<(), IpcError>;
+    fn web_history(&mut self, tab_id: TabId) -> Result<IpcWebHistory, IpcError>;
+    fn go_to_history_index(&mut self, tab_id: TabId, index: usize) -> Result<(), IpcError>;
+    fn set_web_scroll(&mut self, tab_id: TabId, x: f64, y: f64) -> Result<(), IpcError>;
     fn open_inspector(&mut self, tab_id: TabId) -> Result<(), IpcError>;
+    fn set_inspector_dock(&mut self, tab_id: TabId, side: InspectorDock) -> Result<(), IpcError>;
     fn tab_panel_state(&self) -> IpcTabPanelState;
     fn set_tab_panel(&mut self, enabled: Option<bool>, width: Option<usize>) -> Result<(), IpcError>;
     fn dispatch_action(&mut self, tab_id: TabId, action: Action) -> Result<(), IpcError>;
     fn send_input(&mut self, tab_id: TabId, text: String) -> Result<(), IpcError>;
+    fn tab_contents(&mut self, tab_id: TabId, scrollback: bool) -> Result<Vec<String>, IpcError>;
     fn run_command_bar(&mut self, tab_id: TabId, input: String) -> Result<(), IpcError>;
     fn list_inspector_targets(&mut self) -> Result<Vec<IpcInspectorTarget>, IpcError>;
     fn attach_inspector(
@@ -531,6 +1114,7 @@ pub fn handle_request<C: IpcContext>(ctx: &mut C, request: IpcRequest) -> IpcRes
             options,
             group_id,
             group_name,
+            focus,
         } => {
             if group_id.is_some() && group_name.is_some() {
                 return IpcResponse {
@@ -541,7 +1125,7 @@ pub fn handle_request<C: IpcContext>(ctx: &mut C, request: IpcRequest) -> IpcRes
                     close_window: false,
                 };
             }
-            match ctx.create_tab(options, group_id, group_name) {
+            match ctx.create_tab(*options, group_id, group_name, focus) {
             Ok(tab_id) => IpcResponse {
                 reply: SocketReply::TabCreated { tab_id: tab_id.into() },
                 close_window: false,
@@ -556,6 +1140,24 @@ pub fn handle_request<C: IpcContext>(ctx: &mut C, request: IpcRequest) -> IpcRes
             },
             Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
         },
+        IpcRequest::DuplicateTab { tab_id } => {
+            let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
+                Some(tab_id) => tab_id.into(),
+                None => {
+                    return IpcResponse {
+                        reply: reply_error(IpcErrorCode::NotFound, "No active tab"),
+                        close_window: false,
+                    };
+                },
+            };
+            match ctx.duplicate_tab(tab_id) {
+                Ok(tab_id) => IpcResponse {
+                    reply: SocketReply::TabCreated { tab_id: tab_id.into() },
+                    close_window: false,
+                },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
         IpcRequest::CloseTab { tab_id } => {
             let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
                 Some(tab_id) => tab_id.into(),
@@ -580,9 +1182,50 @@ pub fn handle_request<C: IpcContext>(ctx: &mut C, request: IpcRequest) -> IpcRes
             tab_id,
             target_group_id,
             target_index,
-        } => match ctx.move_tab(tab_id.into(), target_group_id, target_index) {
-            Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
-            Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            before,
+            after,
+        } => {
+            // Anchors are resolved now, since the tab layout might have changed since the
+            // client looked at it.
+            let (target_group_id, target_index) = match (before, after) {
+                (Some(_), Some(_)) => {
+                    return IpcResponse {
+                        reply: reply_error(
+                            IpcErrorCode::InvalidRequest,
+                            "before and after are mutually exclusive",
+                        ),
+                        close_window: false,
+                    };
+                },
+                (Some(anchor), None) | (None, Some(anchor)) if anchor == tab_id => {
+                    return IpcResponse {
+                        reply: reply_error(
+                            IpcErrorCode::InvalidRequest,
+                            "Cannot move a tab relative to itself",
+                        ),
+                        close_window: false,
+                    };
+                },
+                (Some(anchor), None) | (None, Some(anchor)) => {
+                    match ctx.tab_state(anchor.into(), now) {
+                        Some(state) => {
+                            (Some(state.group_id), Some(state.index + after.is_some() as usize))
+                        },
+                        None => {
+                            return IpcResponse {
+                                reply: reply_error(IpcErrorCode::NotFound, "Anchor tab not found"),
+                                close_window: false,
+                            };
+                        },
+                    }
+                },
+                (None, None) => (target_group_id, target_index),
+            };
+
+            match ctx.move_tab(tab_id.into(), target_group_id, target_index) {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
         },
         IpcRequest::SetTabTitle { tab_id, title } => {
             let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
@@ -599,14 +1242,49 @@ pub fn handle_request<C: IpcContext>(ctx: &mut C, request: IpcRequest) -> IpcRes
                 Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
             }
         },
+        IpcRequest::SetTabTitleTemplate { tab_id, template } => {
+            let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
+                Some(tab_id) => tab_id.into(),
+                None => {
+                    return IpcResponse {
+                        reply: reply_error(IpcErrorCode::NotFound, "No active tab"),
+                        close_window: false,
+                    };
+                },
+            };
+            match ctx.set_tab_title_template(tab_id, template) {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
         IpcRequest::SetGroupName { group_id, name } => match ctx.set_group_name(group_id, name) {
             Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
             Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
         },
-        IpcRequest::RestoreClosedTab => match ctx.restore_closed_tab() {
+        IpcRequest::SetGroupColor { group_id, color } => {
+            match ctx.set_group_color(group_id, color) {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => {
+                    IpcResponse { reply: SocketReply::Error { error: err }, close_window: false }
+                },
+            }
+        },
+        IpcRequest::SetGroupDefaults { group_id, cwd, homepage } => {
+            match ctx.set_group_defaults(group_id, cwd, homepage) {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => {
+                    IpcResponse { reply: SocketReply::Error { error: err }, close_window: false }
+                },
+            }
+        },
+        IpcRequest::RestoreClosedTab { count } => match ctx.restore_closed_tab(count.unwrap_or(1)) {
             Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
             Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
         },
+        IpcRequest::ListClosedTabs => IpcResponse {
+            reply: SocketReply::ClosedTabs { tabs: ctx.closed_tabs() },
+            close_window: false,
+        },
         IpcRequest::OpenUrl { url, target } => {
             let result = match target {
                 UrlTarget::NewTab => ctx.open_url_new_tab(url).map(|id| Some(id)),
@@ -645,7 +1323,7 @@ pub fn handle_request<C: IpcContext>(ctx: &mut C, request: IpcRequest) -> IpcRes
                 Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
             }
         },
-        IpcRequest::ReloadWeb { tab_id } => {
+        IpcRequest::ReloadWeb { tab_id, ignore_cache } => {
             let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
                 Some(tab_id) => tab_id.into(),
                 None => {
@@ -655,12 +1333,12 @@ pub fn handle_request<C: IpcContext>(ctx: &mut C, request: IpcRequest) -> IpcRes
                     };
                 },
             };
-            match ctx.reload_web(tab_id) {
+            match ctx.reload_web(tab_id, ignore_cache) {
                 Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
                 Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
             }
         },
-        IpcRequest::OpenInspector { tab_id } => {
+        IpcRequest::OpenExternal { tab_id } => {
             let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
                 Some(tab_id) => tab_id.into(),
                 None => {
@@ -670,20 +1348,12 @@ pub fn handle_request<C: IpcContext>(ctx: &mut C, request: IpcRequest) -> IpcRes
                     };
                 },
             };
-            match ctx.open_inspector(tab_id) {
+            match ctx.open_external(tab_id) {
                 Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
                 Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
             }
         },
-        IpcRequest::GetTabPanel => IpcResponse {
-            reply: SocketReply::TabPanel { panel: ctx.tab_panel_state() },
-            close_window: false,
-        },
-        IpcRequest::SetTabPanel { enabled, width } => match ctx.set_tab_panel(enabled, width) {
-            Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
-            Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
-        },
-        IpcRequest::DispatchAction { tab_id, action } => {
+        IpcRequest::RunBookmarklet { tab_id, name } => {
             let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
                 Some(tab_id) => tab_id.into(),
                 None => {
@@ -693,18 +1363,18 @@ pub fn handle_request<C: IpcContext>(ctx: &mut C, request: IpcRequest) -> IpcRes
                     };
                 },
             };
-            let action = match ipc_action_to_action(action) {
-                Ok(action) => action,
-                Err(err) => {
-                    return IpcResponse { reply: SocketReply::Error { error: err }, close_window: false };
-                },
-            };
-            match ctx.dispatch_action(tab_id, action) {
+            match ctx.run_bookmarklet(tab_id, &name) {
                 Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
                 Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
             }
         },
-        IpcRequest::SendInput { tab_id, text } => {
+        IpcRequest::SetWebZoom { tab_id, zoom } => {
+            if !zoom.is_finite() || zoom <= 0. {
+                return IpcResponse {
+                    reply: reply_error(IpcErrorCode::InvalidRequest, "Zoom must be a positive number"),
+                    close_window: false,
+                };
+            }
             let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
                 Some(tab_id) => tab_id.into(),
                 None => {
@@ -714,12 +1384,12 @@ pub fn handle_request<C: IpcContext>(ctx: &mut C, request: IpcRequest) -> IpcRes
                     };
                 },
             };
-            match ctx.send_input(tab_id, text) {
+            match ctx.set_web_zoom(tab_id, zoom) {
                 Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
                 Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
             }
         },
-        IpcRequest::RunCommandBar { tab_id, input } => {
+        IpcRequest::SetWebMuted { tab_id, muted } => {
             let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
                 Some(tab_id) => tab_id.into(),
                 None => {
@@ -729,24 +1399,232 @@ pub fn handle_request<C: IpcContext>(ctx: &mut C, request: IpcRequest) -> IpcRes
                     };
                 },
             };
-            match ctx.run_command_bar(tab_id, input) {
+            match ctx.set_web_muted(tab_id, muted) {
                 Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
                 Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
             }
         },
-        IpcRequest::ListInspectorTargets => match ctx.list_inspector_targets() {
-            Ok(targets) => IpcResponse {
-                reply: SocketReply::InspectorTargets { targets },
-                close_window: false,
-            },
-            Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+        IpcRequest::SetGroupMuted { group_id, muted } => {
+            let result = group_web_tabs(ctx, group_id).and_then(|tabs| {
+                tabs.into_iter().try_for_each(|tab_id| ctx.set_web_muted(tab_id, muted))
+            });
+            match result {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
         },
-        IpcRequest::AttachInspector { tab_id, target_id } => {
-            let tab_id = tab_id.map(Into::into);
-            match ctx.attach_inspector(tab_id, target_id) {
-                Ok(session) => IpcResponse {
-                    reply: SocketReply::InspectorAttached { session },
-                    close_window: false,
+        IpcRequest::HibernateGroup { group_id } => {
+            let result = group_web_tabs(ctx, group_id).and_then(|tabs| {
+                tabs.into_iter().try_for_each(|tab_id| ctx.hibernate_web_tab(tab_id))
+            });
+            match result {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
+        IpcRequest::SetWebHeaders { tab_id, user_agent, headers } => {
+            if let Err(err) = headers.as_ref().map(crate::web_url::request_headers).transpose() {
+                return IpcResponse {
+                    reply: reply_error(IpcErrorCode::InvalidRequest, err),
+                    close_window: false,
+                };
+            }
+            let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
+                Some(tab_id) => tab_id.into(),
+                None => {
+                    return IpcResponse {
+                        reply: reply_error(IpcErrorCode::NotFound, "No active tab"),
+                        close_window: false,
+                    };
+                },
+            };
+            match ctx.set_web_headers(tab_id, user_agent, headers) {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
+        IpcRequest::GetWebHistory { tab_id } => {
+            let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
+                Some(tab_id) => tab_id.into(),
+                None => {
+                    return IpcResponse {
+                        reply: reply_error(IpcErrorCode::NotFound, "No active tab"),
+                        close_window: false,
+                    };
+                },
+            };
+            match ctx.web_history(tab_id) {
+                Ok(history) => {
+                    IpcResponse { reply: SocketReply::WebHistory { history }, close_window: false }
+                },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
+        IpcRequest::GoToHistoryIndex { tab_id, index } => {
+            let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
+                Some(tab_id) => tab_id.into(),
+                None => {
+                    return IpcResponse {
+                        reply: reply_error(IpcErrorCode::NotFound, "No active tab"),
+                        close_window: false,
+                    };
+                },
+            };
+            match ctx.go_to_history_index(tab_id, index) {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
+        IpcRequest::SetWebScroll { tab_id, x, y } => {
+            if !x.is_finite() || !y.is_finite() {
+                return IpcResponse {
+                    reply: reply_error(IpcErrorCode::InvalidRequest, "Scroll position must be finite"),
+                    close_window: false,
+                };
+            }
+            let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
+                Some(tab_id) => tab_id.into(),
+                None => {
+                    return IpcResponse {
+                        reply: reply_error(IpcErrorCode::NotFound, "No active tab"),
+                        close_window: false,
+                    };
+                },
+            };
+            match ctx.set_web_scroll(tab_id, x, y) {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
+        IpcRequest::OpenInspector { tab_id } => {
+            let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
+                Some(tab_id) => tab_id.into(),
+                None => {
+                    return IpcResponse {
+                        reply: reply_error(IpcErrorCode::NotFound, "No active tab"),
+                        close_window: false,
+                    };
+                },
+            };
+            match ctx.open_inspector(tab_id) {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
+        IpcRequest::SetInspectorDock { tab_id, side } => {
+            let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
+                Some(tab_id) => tab_id.into(),
+                None => {
+                    return IpcResponse {
+                        reply: reply_error(IpcErrorCode::NotFound, "No active tab"),
+                        close_window: false,
+                    };
+                },
+            };
+            match ctx.set_inspector_dock(tab_id, side) {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
+        IpcRequest::GetTabPanel => IpcResponse {
+            reply: SocketReply::TabPanel { panel: ctx.tab_panel_state() },
+            close_window: false,
+        },
+        IpcRequest::SetTabPanel { enabled, width } => match ctx.set_tab_panel(enabled, width) {
+            Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+            Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+        },
+        IpcRequest::DispatchAction { tab_id, action } => {
+            let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
+                Some(tab_id) => tab_id.into(),
+                None => {
+                    return IpcResponse {
+                        reply: reply_error(IpcErrorCode::NotFound, "No active tab"),
+                        close_window: false,
+                    };
+                },
+            };
+            let action = match ipc_action_to_action(action) {
+                Ok(action) => action,
+                Err(err) => {
+                    return IpcResponse { reply: SocketReply::Error { error: err }, close_window: false };
+                },
+            };
+            match ctx.dispatch_action(tab_id, action) {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
+        IpcRequest::SendInput { tab_id, text, interpret_escapes } => {
+            let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
+                Some(tab_id) => tab_id.into(),
+                None => {
+                    return IpcResponse {
+                        reply: reply_error(IpcErrorCode::NotFound, "No active tab"),
+                        close_window: false,
+                    };
+                },
+            };
+            let text = if interpret_escapes {
+                match interpret_input_escapes(&text) {
+                    Ok(text) => text,
+                    Err(err) => {
+                        return IpcResponse { reply: SocketReply::Error { error: err }, close_window: false };
+                    },
+                }
+            } else {
+                text
+            };
+            match ctx.send_input(tab_id, text) {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
+        IpcRequest::GetTabContents { tab_id, scrollback } => {
+            let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
+                Some(tab_id) => tab_id.into(),
+                None => {
+                    return IpcResponse {
+                        reply: reply_error(IpcErrorCode::NotFound, "No active tab"),
+                        close_window: false,
+                    };
+                },
+            };
+            match ctx.tab_contents(tab_id, scrollback) {
+                Ok(lines) => {
+                    IpcResponse { reply: SocketReply::TabContents { lines }, close_window: false }
+                },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
+        IpcRequest::RunCommandBar { tab_id, input } => {
+            let tab_id = match tab_id.or_else(|| ctx.active_tab_id().map(IpcTabId::from)) {
+                Some(tab_id) => tab_id.into(),
+                None => {
+                    return IpcResponse {
+                        reply: reply_error(IpcErrorCode::NotFound, "No active tab"),
+                        close_window: false,
+                    };
+                },
+            };
+            match ctx.run_command_bar(tab_id, input) {
+                Ok(()) => IpcResponse { reply: reply_ok(), close_window: false },
+                Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+            }
+        },
+        IpcRequest::ListInspectorTargets => match ctx.list_inspector_targets() {
+            Ok(targets) => IpcResponse {
+                reply: SocketReply::InspectorTargets { targets },
+                close_window: false,
+            },
+            Err(err) => IpcResponse { reply: SocketReply::Error { error: err }, close_window: false },
+        },
+        IpcRequest::AttachInspector { tab_id, target_id } => {
+            let tab_id = tab_id.map(Into::into);
+            match ctx.attach_inspector(tab_id, target_id) {
+                Ok(session) => IpcResponse {
+                    reply: SocketReply::InspectorAttached { session },
+                    close_window: false,
                 },
                 Err(err) => {
                     IpcResponse { reply: SocketReply::Error { error: err }, close_window: false }
@@ -780,105 +1658,522 @@ pub fn handle_request<C: IpcContext>(ctx: &mut C, request: IpcRequest) -> IpcRes
             reply: reply_error(IpcErrorCode::InvalidRequest, "Config requests must be handled at the IPC router"),
             close_window: false,
         },
+        IpcRequest::CreateWindow { .. }
+        | IpcRequest::FocusWindow { .. }
+        | IpcRequest::GetRenderStats { .. }
+        | IpcRequest::ListWindows => IpcResponse {
+            reply: reply_error(
+                IpcErrorCode::InvalidRequest,
+                "Window requests must be handled at the IPC router",
+            ),
+            close_window: false,
+        },
+        IpcRequest::SnapshotTab { .. } => IpcResponse {
+            reply: reply_error(IpcErrorCode::InvalidRequest, "Snapshot requests must be handled at the IPC router"),
+            close_window: false,
+        },
+        IpcRequest::GetWebScroll { .. } => IpcResponse {
+            reply: reply_error(
+                IpcErrorCode::InvalidRequest,
+                "Web scroll requests must be handled at the IPC router",
+            ),
+            close_window: false,
+        },
+        IpcRequest::GetSelection { .. } => IpcResponse {
+            reply: reply_error(
+                IpcErrorCode::InvalidRequest,
+                "Selection requests must be handled at the IPC router",
+            ),
+            close_window: false,
+        },
+        IpcRequest::WaitTab { .. } => IpcResponse {
+            reply: reply_error(
+                IpcErrorCode::InvalidRequest,
+                "Wait requests must be handled at the IPC router",
+            ),
+            close_window: false,
+        },
+        IpcRequest::ClearWebData { .. } => IpcResponse {
+            reply: reply_error(
+                IpcErrorCode::InvalidRequest,
+                "Clear web data requests must be handled at the IPC router",
+            ),
+            close_window: false,
+        },
+        IpcRequest::Subscribe { .. } => IpcResponse {
+            reply: reply_error(IpcErrorCode::InvalidRequest, "Subscribe requests must be handled at the IPC router"),
+            close_window: false,
+        },
     };
 
     response
 }
 
-/// Create an IPC socket.
-pub fn spawn_ipc_socket(
-    options: &Options,
-    event_proxy: EventLoopProxy<Event>,
-) -> IoResult<PathBuf> {
-    // Create the IPC socket and export its path as env.
+/// Web tabs of a group, terminal tabs are skipped.
+fn group_web_tabs<C: IpcContext>(ctx: &C, group_id: usize) -> Result<Vec<TabId>, IpcError> {
+    let group = ctx
+        .list_tabs(Instant::now())
+        .into_iter()
+        .find(|group| group.id == group_id)
+        .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Group not found"))?;
+    Ok(group
+        .tabs
+        .into_iter()
+        .filter(|tab| matches!(tab.kind, IpcTabKind::Web { .. }))
+        .map(|tab| tab.tab_id.into())
+        .collect())
+}
 
-    let socket_path = options.socket.clone().unwrap_or_else(|| {
-        let mut path = socket_dir();
-        path.push(format!("{}-{}.sock", socket_prefix(), process::id()));
-        path
-    });
+/// Connections streaming events after an [`IpcRequest::Subscribe`].
+#[derive(Default)]
+pub struct IpcSubscribers {
+    subscribers: Vec<IpcSubscriber>,
+}
 
-    let listener = UnixListener::bind(&socket_path)?;
+struct IpcSubscriber {
+    stream: IpcStream,
+    /// Subscribed event names, empty for all events.
+    events: Vec<String>,
+}
 
-    unsafe { env::set_var(TABOR_SOCKET_ENV, socket_path.as_os_str()) };
-    if options.daemon {
-        println!("TABOR_SOCKET={}; export TABOR_SOCKET", socket_path.display());
+impl IpcSubscribers {
+    /// Keep a connection open to stream events.
+    ///
+    /// The subscription is acknowledged with an `ok` reply, unknown event names are rejected
+    /// with an error reply instead.
+    pub fn subscribe(&mut self, stream: &IpcStream, events: Vec<String>) {
+        let Ok(mut stream) = stream.try_clone() else {
+            return;
+        };
+
+        if let Some(name) = events.iter().find(|name| !IPC_EVENT_NAMES.contains(&name.as_str())) {
+            let message = format!("Unknown event {name:?}, expected one of {IPC_EVENT_NAMES:?}");
+            send_reply(&mut stream, reply_error(IpcErrorCode::InvalidRequest, message));
+            return;
+        }
+
+        let _ = stream.set_write_timeout(Some(SUBSCRIBER_WRITE_TIMEOUT));
+        if send_reply_line(&mut stream, &reply_ok()).is_ok() {
+            self.subscribers.push(IpcSubscriber { stream, events });
+        }
     }
 
-    // Spawn a thread to listen on the IPC socket.
-    thread::spawn_named("socket listener", move || {
-        let mut data = String::new();
-        for stream in listener.incoming().filter_map(Result::ok) {
-            data.clear();
-            let mut reader = BufReader::new(&stream);
+    /// Send an event to all interested subscribers.
+    ///
+    /// Subscribers which can't be written to are assumed to be disconnected and removed.
+    pub fn broadcast(&mut self, event: &IpcEvent) {
+        if self.subscribers.is_empty() {
+            return;
+        }
 
-            match reader.read_line(&mut data) {
-                Ok(0) | Err(_) => continue,
-                Ok(_) => (),
-            };
+        let reply = SocketReply::Event { event: event.clone() };
+        self.subscribers.retain_mut(|subscriber| {
+            if !subscriber.events.is_empty()
+                && !subscriber.events.iter().any(|name| name == event.name())
+            {
+                return true;
+            }
 
-            // Read pending events on socket.
-            let message: IpcRequest = match serde_json::from_str(&data) {
-                Ok(message) => message,
+            match send_reply_line(&mut subscriber.stream, &reply) {
+                Ok(()) => true,
                 Err(err) => {
-                    warn!("Failed to convert data from socket: {err}");
-                    continue;
+                    debug!("Dropping IPC subscriber: {err}");
+                    false
                 },
-            };
+            }
+        });
+    }
+}
 
-            let event = Event::new(EventType::IpcRequest(message, Arc::new(stream)), None);
-            let _ = event_proxy.send_event(event);
-        }
-    });
+/// Connections waiting for the command of a tab to exit after an [`IpcRequest::WaitTab`].
+pub struct TabWaiters<R = Box<dyn FnOnce(SocketReply)>> {
+    waiters: Vec<TabWaiter<R>>,
+}
 
-    Ok(socket_path)
+struct TabWaiter<R> {
+    tab_id: IpcTabId,
+    deadline: Option<Instant>,
+    reply: R,
 }
 
-/// Send a message to the active Tabor socket.
-pub fn send_message(socket: Option<PathBuf>, message: IpcRequest) -> IoResult<Option<SocketReply>> {
-    let message_json = serde_json::to_string(&message)?;
-    send_raw_message(socket, &message_json)
+impl<R> Default for TabWaiters<R> {
+    fn default() -> Self {
+        Self { waiters: Vec::new() }
+    }
 }
 
-/// Send raw JSON to the active Tabor socket.
-pub fn send_raw_message(socket: Option<PathBuf>, message_json: &str) -> IoResult<Option<SocketReply>> {
-    let mut socket = find_socket(socket)?;
+impl<R: FnOnce(SocketReply)> TabWaiters<R> {
+    /// Reply once the command of a tab exited, or with a timeout error after `deadline`.
+    pub fn wait(&mut self, tab_id: IpcTabId, deadline: Option<Instant>, reply: R) {
+        self.waiters.push(TabWaiter { tab_id, deadline, reply });
+    }
 
-    socket.write_all(message_json.as_bytes())?;
-    let _ = socket.flush();
-    socket.shutdown(Shutdown::Write)?;
+    /// Reply to all connections waiting for a tab whose command exited.
+    pub fn exited(&mut self, tab_id: IpcTabId, status: Option<i32>) {
+        for waiter in self.take(|waiter| waiter.tab_id == tab_id) {
+            (waiter.reply)(SocketReply::TabExited { tab_id, status });
+        }
+    }
 
-    read_reply(&socket)
-}
+    /// Reply with a timeout error to all connections waiting past their deadline.
+    pub fn expire(&mut self, now: Instant) {
+        let expired = self.take(|waiter| waiter.deadline.is_some_and(|deadline| deadline <= now));
+        for waiter in expired {
+            let message = "Tab command did not exit before the timeout";
+            (waiter.reply)(reply_error(IpcErrorCode::Timeout, message));
+        }
+    }
 
-/// Read IPC responses.
-fn read_reply(stream: &UnixStream) -> IoResult<Option<SocketReply>> {
-    let mut buffer = String::new();
-    let mut reader = BufReader::new(stream);
-    if let Ok(0) | Err(_) = reader.read_line(&mut buffer) {
-        return Ok(None);
+    /// Earliest deadline of all waiting connections.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.waiters.iter().filter_map(|waiter| waiter.deadline).min()
     }
 
-    let reply: SocketReply = serde_json::from_str(&buffer)
-        .map_err(|err| IoError::other(format!("Invalid IPC format: {err}")))?;
+    fn take(&mut self, mut predicate: impl FnMut(&TabWaiter<R>) -> bool) -> Vec<TabWaiter<R>> {
+        let (taken, waiters) = self.waiters.drain(..).partition(|waiter| predicate(waiter));
+        self.waiters = waiters;
+        taken
+    }
+}
+
+/// Connection of an IPC client, through the unix socket or the TCP listener.
+#[derive(Debug)]
+pub enum IpcStream {
+    Unix(UnixStream),
+    Tcp(TcpStream),
+}
+
+impl IpcStream {
+    pub fn try_clone(&self) -> IoResult<Self> {
+        match self {
+            Self::Unix(stream) => stream.try_clone().map(Self::Unix),
+            Self::Tcp(stream) => stream.try_clone().map(Self::Tcp),
+        }
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> IoResult<()> {
+        match self {
+            Self::Unix(stream) => stream.set_read_timeout(timeout),
+            Self::Tcp(stream) => stream.set_read_timeout(timeout),
+        }
+    }
+
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> IoResult<()> {
+        match self {
+            Self::Unix(stream) => stream.set_write_timeout(timeout),
+            Self::Tcp(stream) => stream.set_write_timeout(timeout),
+        }
+    }
+
+    fn shutdown(&self, how: Shutdown) -> IoResult<()> {
+        match self {
+            Self::Unix(stream) => stream.shutdown(how),
+            Self::Tcp(stream) => stream.shutdown(how),
+        }
+    }
+}
+
+impl Read for &IpcStream {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        match self {
+            IpcStream::Unix(stream) => (&*stream).read(buf),
+            IpcStream::Tcp(stream) => (&*stream).read(buf),
+        }
+    }
+}
+
+impl Read for IpcStream {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        (&*self).read(buf)
+    }
+}
+
+impl Write for &IpcStream {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        match self {
+            IpcStream::Unix(stream) => (&*stream).write(buf),
+            IpcStream::Tcp(stream) => (&*stream).write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        match self {
+            IpcStream::Unix(stream) => (&*stream).flush(),
+            IpcStream::Tcp(stream) => (&*stream).flush(),
+        }
+    }
+}
+
+impl Write for IpcStream {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        (&*self).write(buf)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        (&*self).flush()
+    }
+}
+
+/// Create an IPC socket.
+///
+/// When `token` is set, only requests carrying the same token are accepted.
+pub fn spawn_ipc_socket(
+    options: &Options,
+    token: Option<String>,
+    event_proxy: EventLoopProxy<Event>,
+) -> IoResult<PathBuf> {
+    // Create the IPC socket and export its path as env.
+
+    let socket_path = options.socket.clone().unwrap_or_else(|| {
+        let mut path = socket_dir();
+        path.push(format!("{}-{}.sock", socket_prefix(), process::id()));
+        path
+    });
+
+    let listener = UnixListener::bind(&socket_path)?;
+
+    unsafe { env::set_var(TABOR_SOCKET_ENV, socket_path.as_os_str()) };
+    if let Some(token) = &token {
+        unsafe { env::set_var(TABOR_IPC_TOKEN_ENV, token) };
+    }
+    if options.daemon {
+        println!("TABOR_SOCKET={}; export TABOR_SOCKET", socket_path.display());
+    }
+
+    // Spawn a thread to listen on the IPC socket.
+    thread::spawn_named("socket listener", move || {
+        for stream in listener.incoming().filter_map(Result::ok) {
+            forward_request(IpcStream::Unix(stream), token.as_deref(), &event_proxy);
+        }
+    });
+
+    Ok(socket_path)
+}
+
+/// Create an IPC listener on a TCP address.
+///
+/// Since any local process can connect to it, the listener is only created when `token` is set.
+pub fn spawn_ipc_tcp_listener(
+    addr: SocketAddr,
+    token: Option<String>,
+    event_proxy: EventLoopProxy<Event>,
+) -> IoResult<SocketAddr> {
+    let Some(token) = token else {
+        let message = "IPC over TCP requires general.ipc_token";
+        return Err(IoError::new(ErrorKind::PermissionDenied, message));
+    };
+
+    let listener = TcpListener::bind(addr)?;
+    let addr = listener.local_addr()?;
+
+    thread::spawn_named("tcp socket listener", move || {
+        for stream in listener.incoming().filter_map(Result::ok) {
+            forward_request(IpcStream::Tcp(stream), Some(&token), &event_proxy);
+        }
+    });
+
+    Ok(addr)
+}
+
+/// Resolve the address of the IPC TCP listener.
+///
+/// A bare port listens on the IPv4 loopback address. Addresses other than loopback are refused,
+/// unless `allow_remote` is set.
+pub fn tcp_listen_addr(spec: &str, allow_remote: bool) -> IoResult<SocketAddr> {
+    let spec = spec.trim();
+    let addr = match spec.parse::<u16>() {
+        Ok(port) => SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+        Err(_) => spec.to_socket_addrs()?.next().ok_or_else(|| {
+            IoError::new(ErrorKind::InvalidInput, format!("invalid IPC address {spec:?}"))
+        })?,
+    };
+
+    if !allow_remote && !addr.ip().is_loopback() {
+        let message = format!("refusing to listen for IPC on non-loopback address {addr}");
+        return Err(IoError::new(ErrorKind::PermissionDenied, message));
+    }
+
+    Ok(addr)
+}
+
+/// Read the request of a new connection and forward it to the event loop.
+fn forward_request(stream: IpcStream, token: Option<&str>, event_proxy: &EventLoopProxy<Event>) {
+    let mut stream = stream;
+    if let Some(message) = read_request(&mut stream, token) {
+        let event = Event::new(EventType::IpcRequest(message, Arc::new(stream)), None);
+        let _ = event_proxy.send_event(event);
+    }
+}
+
+/// Read a request from a new connection.
+///
+/// Requests without the expected `token` are answered with an error reply.
+fn read_request(stream: &mut IpcStream, token: Option<&str>) -> Option<IpcRequest> {
+    if stream.set_read_timeout(Some(CONNECTION_TIMEOUT)).is_err()
+        || stream.set_write_timeout(Some(CONNECTION_TIMEOUT)).is_err()
+    {
+        return None;
+    }
+
+    let mut data = String::new();
+    let mut reader = BufReader::new((&*stream).take(MAX_REQUEST_BYTES));
+
+    match reader.read_line(&mut data) {
+        Ok(0) | Err(_) => return None,
+        Ok(len) if len as u64 == MAX_REQUEST_BYTES && !data.ends_with('\n') => {
+            warn!("Rejected IPC request exceeding {MAX_REQUEST_BYTES} bytes");
+            return None;
+        },
+        Ok(_) => (),
+    };
+
+    // Read pending events on socket.
+    let mut message: serde_json::Value = match serde_json::from_str(&data) {
+        Ok(message) => message,
+        Err(err) => {
+            warn!("Failed to convert data from socket: {err}");
+            return None;
+        },
+    };
+
+    if let Err(error) = authenticate_request(&mut message, token) {
+        warn!("Rejected IPC request: {}", error.message);
+        send_reply(stream, SocketReply::Error { error });
+        return None;
+    }
+
+    match serde_json::from_value(message) {
+        Ok(message) => Some(message),
+        Err(err) => {
+            warn!("Failed to convert data from socket: {err}");
+            None
+        },
+    }
+}
+
+/// Remove the token from a request, checking it against the expected `token`.
+///
+/// Requests are accepted without a token when no token is expected.
+fn authenticate_request(
+    request: &mut serde_json::Value,
+    token: Option<&str>,
+) -> Result<(), IpcError> {
+    let provided = request.as_object_mut().and_then(|request| request.remove(IPC_TOKEN_FIELD));
+
+    let Some(token) = token else {
+        return Ok(());
+    };
+
+    match provided {
+        Some(serde_json::Value::String(provided)) if tokens_match(&provided, token) => Ok(()),
+        _ => Err(IpcError::new(IpcErrorCode::PermissionDenied, "Missing or invalid IPC token")),
+    }
+}
+
+/// Compare tokens in constant time for equal lengths.
+fn tokens_match(lhs: &str, rhs: &str) -> bool {
+    lhs.len() == rhs.len()
+        && lhs.bytes().zip(rhs.bytes()).fold(0, |diff, (lhs, rhs)| diff | (lhs ^ rhs)) == 0
+}
+
+/// Add `token` to a raw JSON request object.
+///
+/// Anything but a JSON object is returned unchanged.
+fn attach_token(message_json: &str, token: Option<&str>) -> String {
+    let Some(token) = token.filter(|token| !token.is_empty()) else {
+        return message_json.to_owned();
+    };
+
+    match serde_json::from_str::<serde_json::Value>(message_json) {
+        Ok(serde_json::Value::Object(mut request)) => {
+            request.insert(IPC_TOKEN_FIELD.into(), serde_json::Value::String(token.into()));
+            serde_json::Value::Object(request).to_string()
+        },
+        _ => message_json.to_owned(),
+    }
+}
+
+/// Send a message to the active Tabor socket.
+pub fn send_message(socket: Option<PathBuf>, message: IpcRequest) -> IoResult<Option<SocketReply>> {
+    let message_json = serde_json::to_string(&message)?;
+    send_raw_message(socket, &message_json)
+}
+
+/// Send raw JSON to the active Tabor socket.
+pub fn send_raw_message(socket: Option<PathBuf>, message_json: &str) -> IoResult<Option<SocketReply>> {
+    let mut socket = find_socket(socket)?;
+
+    let message_json = attach_token(message_json, env::var(TABOR_IPC_TOKEN_ENV).ok().as_deref());
+    write_request(&mut socket, &message_json)?;
+
+    read_reply(&socket)
+}
+
+/// Write a request, closing the sending half of the connection.
+fn write_request(socket: &mut IpcStream, message_json: &str) -> IoResult<()> {
+    socket.write_all(message_json.as_bytes())?;
+    let _ = socket.flush();
+    socket.shutdown(Shutdown::Write)
+}
+
+/// Subscribe to events of the active Tabor socket.
+///
+/// The returned iterator yields the subscription acknowledgement followed by one reply per event,
+/// until the connection is closed.
+pub fn subscribe(
+    socket: Option<PathBuf>,
+    events: Vec<String>,
+) -> IoResult<impl Iterator<Item = IoResult<SocketReply>>> {
+    let message_json = serde_json::to_string(&IpcRequest::Subscribe { events })?;
+    let message_json = attach_token(&message_json, env::var(TABOR_IPC_TOKEN_ENV).ok().as_deref());
+    let mut socket = find_socket(socket)?;
+    write_request(&mut socket, &message_json)?;
+
+    Ok(BufReader::new(socket).lines().map(|line| {
+        serde_json::from_str(&line?).map_err(|err| {
+            IoError::new(ErrorKind::InvalidData, format!("Invalid IPC format: {err}"))
+        })
+    }))
+}
+
+/// Read IPC responses.
+fn read_reply(stream: &IpcStream) -> IoResult<Option<SocketReply>> {
+    let mut buffer = String::new();
+    let mut reader = BufReader::new(stream);
+    if let Ok(0) | Err(_) = reader.read_line(&mut buffer) {
+        return Ok(None);
+    }
+
+    let reply: SocketReply = serde_json::from_str(&buffer).map_err(|err| {
+        IoError::new(ErrorKind::InvalidData, format!("Invalid IPC format: {err}"))
+    })?;
     Ok(Some(reply))
 }
 
 /// Send IPC message reply.
-pub fn send_reply(stream: &mut UnixStream, message: SocketReply) {
+pub fn send_reply(stream: &mut IpcStream, message: SocketReply) {
     if let Err(err) = send_reply_fallible(stream, message) {
         error!("Failed to send IPC reply: {err}");
     }
 }
 
 /// Send IPC message reply, returning possible errors.
-fn send_reply_fallible(stream: &mut UnixStream, message: SocketReply) -> IoResult<()> {
+fn send_reply_fallible(stream: &mut IpcStream, message: SocketReply) -> IoResult<()> {
     let json = serde_json::to_string(&message).map_err(IoError::other)?;
     stream.write_all(json.as_bytes())?;
     stream.flush()?;
     Ok(())
 }
 
+/// Send a newline-terminated IPC reply, keeping the connection open for more.
+fn send_reply_line(stream: &mut IpcStream, message: &SocketReply) -> IoResult<()> {
+    let mut json = serde_json::to_string(message).map_err(IoError::other)?;
+    json.push('\n');
+    stream.write_all(json.as_bytes())?;
+    stream.flush()?;
+    Ok(())
+}
+
 /// Directory for the IPC socket file.
 #[cfg(not(target_os = "macos"))]
 fn socket_dir() -> PathBuf {
@@ -896,35 +2191,37 @@ fn socket_dir() -> PathBuf {
     env::temp_dir()
 }
 
-/// Find the IPC socket path.
-fn find_socket(socket_path: Option<PathBuf>) -> IoResult<UnixStream> {
-    // Handle --socket CLI override.
-    if let Some(socket_path) = socket_path {
-        // Ensure we inform the user about an invalid path.
-        return UnixStream::connect(&socket_path).map_err(|err| {
-            let message = format!("invalid socket path {socket_path:?}");
-            IoError::new(err.kind(), message)
-        });
+/// Remove the socket exported by a parent Tabor instance from the environment.
+///
+/// Shells of an instance without its own socket would otherwise message the parent instance.
+pub fn clear_socket_env() {
+    unsafe {
+        env::remove_var(TABOR_SOCKET_ENV);
+        env::remove_var(TABOR_IPC_TOKEN_ENV);
     }
+}
 
-    // Handle environment variable.
-    if let Ok(path) = env::var(TABOR_SOCKET_ENV) {
-        let socket_path = PathBuf::from(path);
-        if let Ok(socket) = UnixStream::connect(socket_path) {
-            return Ok(socket);
-        }
-    }
+/// Sockets of all running Tabor instances, most recently created first.
+///
+/// Sockets left behind by instances which didn't exit cleanly are deleted.
+pub fn discover_sockets() -> Vec<PathBuf> {
+    discover_sockets_in(&socket_dir(), &socket_prefix())
+}
 
-    // Search for sockets files.
-    for entry in fs::read_dir(socket_dir())?.filter_map(|entry| entry.ok()) {
+fn discover_sockets_in(dir: &Path, socket_prefix: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut sockets = Vec::new();
+    for entry in entries.filter_map(|entry| entry.ok()) {
         let path = entry.path();
 
         // Skip files that aren't Tabor sockets.
-        let socket_prefix = socket_prefix();
         if path
             .file_name()
             .and_then(OsStr::to_str)
-            .filter(|file| file.starts_with(&socket_prefix) && file.ends_with(".sock"))
+            .filter(|file| file.starts_with(socket_prefix) && file.ends_with(".sock"))
             .is_none()
         {
             continue;
@@ -932,7 +2229,10 @@ fn find_socket(socket_path: Option<PathBuf>) -> IoResult<UnixStream> {
 
         // Attempt to connect to the socket.
         match UnixStream::connect(&path) {
-            Ok(socket) => return Ok(socket),
+            Ok(_) => {
+                let created = entry.metadata().and_then(|metadata| metadata.modified()).ok();
+                sockets.push((created, path));
+            },
             // Delete orphan sockets.
             Err(error) if error.kind() == ErrorKind::ConnectionRefused => {
                 let _ = fs::remove_file(&path);
@@ -942,7 +2242,75 @@ fn find_socket(socket_path: Option<PathBuf>) -> IoResult<UnixStream> {
         }
     }
 
-    Err(IoError::new(ErrorKind::NotFound, "no socket found"))
+    sockets.sort_by(|(a, _), (b, _)| b.cmp(a));
+    sockets.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Ask which of multiple running instances should receive the message.
+///
+/// Without a terminal to prompt on, the most recently started instance is picked.
+fn choose_socket(sockets: &[PathBuf]) -> IoResult<&Path> {
+    let stdin = io::stdin();
+    if !stdin.is_terminal() || !io::stderr().is_terminal() {
+        return Ok(&sockets[0]);
+    }
+
+    eprintln!("Multiple Tabor instances are running:");
+    for (i, socket) in sockets.iter().enumerate() {
+        eprintln!("  {}) {}", i + 1, socket.display());
+    }
+    eprint!("Select an instance [1]: ");
+
+    let mut input = String::new();
+    stdin.read_line(&mut input)?;
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(&sockets[0]);
+    }
+
+    input
+        .parse::<usize>()
+        .ok()
+        .and_then(|index| sockets.get(index.checked_sub(1)?))
+        .map(PathBuf::as_path)
+        .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, format!("no instance {input:?}")))
+}
+
+/// Find the IPC socket path.
+fn find_socket(socket_path: Option<PathBuf>) -> IoResult<IpcStream> {
+    // Handle --socket CLI override.
+    if let Some(socket_path) = socket_path {
+        // Ensure we inform the user about an invalid path.
+        return connect_socket(&socket_path).map_err(|err| {
+            let message = format!("invalid socket path {socket_path:?}");
+            IoError::new(err.kind(), message)
+        });
+    }
+
+    // Handle environment variable.
+    if let Ok(path) = env::var(TABOR_SOCKET_ENV) {
+        let socket_path = PathBuf::from(path);
+        if let Ok(socket) = connect_socket(&socket_path) {
+            return Ok(socket);
+        }
+    }
+
+    // Search for sockets files.
+    let sockets = discover_sockets();
+    let socket_path = match sockets.as_slice() {
+        [] => return Err(IoError::new(ErrorKind::NotFound, "no socket found")),
+        [socket_path] => socket_path,
+        sockets => choose_socket(sockets)?,
+    };
+    UnixStream::connect(socket_path).map(IpcStream::Unix)
+}
+
+/// Connect to a unix socket path, or a TCP listener for paths like `tcp://127.0.0.1:7373`.
+fn connect_socket(socket_path: &Path) -> IoResult<IpcStream> {
+    match socket_path.to_str().and_then(|path| path.strip_prefix(TCP_SOCKET_PREFIX)) {
+        Some(addr) => TcpStream::connect(addr).map(IpcStream::Tcp),
+        None => UnixStream::connect(socket_path).map(IpcStream::Unix),
+    }
 }
 
 /// File prefix matching all available sockets.
@@ -963,7 +2331,9 @@ fn socket_prefix() -> String {
 
 #[cfg(test)]
 mod tests {
+    use std::cell::RefCell;
     use std::collections::{HashMap, VecDeque};
+    use std::rc::Rc;
 
     use super::*;
 
@@ -972,13 +2342,19 @@ mod tests {
         id: TabId,
         title: String,
         custom_title: Option<String>,
+        title_template: Option<String>,
         program_name: String,
         kind: IpcTabKind,
+        muted: bool,
+        hibernated: bool,
     }
 
     struct MockGroup {
         id: usize,
         name: Option<String>,
+        color: Option<Rgb>,
+        default_cwd: Option<PathBuf>,
+        homepage: Option<String>,
         tabs: Vec<TabId>,
     }
 
@@ -993,6 +2369,7 @@ mod tests {
         last_input: Option<String>,
         last_command: Option<String>,
         web_supported: bool,
+        closed_tabs: Vec<MockTab>,
         inspector_targets: Vec<IpcInspectorTarget>,
         inspector_sessions: HashMap<String, IpcInspectorSession>,
         inspector_messages: HashMap<String, VecDeque<String>>,
@@ -1011,6 +2388,7 @@ mod tests {
                 last_input: None,
                 last_command: None,
                 web_supported,
+                closed_tabs: Vec::new(),
                 inspector_targets: Vec::new(),
                 inspector_sessions: HashMap::new(),
                 inspector_messages: HashMap::new(),
@@ -1033,13 +2411,23 @@ mod tests {
                 id: tab_id,
                 title,
                 custom_title: None,
+                title_template: None,
                 program_name: String::new(),
                 kind,
+                muted: false,
+                hibernated: false,
             };
             self.tabs.insert(tab_id, tab);
 
             if self.groups.is_empty() {
-                let group = MockGroup { id: self.next_group_id, name: None, tabs: Vec::new() };
+                let group = MockGroup {
+                    id: self.next_group_id,
+                    name: None,
+                    color: None,
+                    default_cwd: None,
+                    homepage: None,
+                    tabs: Vec::new(),
+                };
                 self.next_group_id += 1;
                 self.groups.push(group);
             }
@@ -1057,6 +2445,9 @@ mod tests {
                     let group = MockGroup {
                         id: self.next_group_id,
                         name: Some(name),
+                        color: None,
+                        default_cwd: None,
+                        homepage: None,
                         tabs: Vec::new(),
                     };
                     self.next_group_id += 1;
@@ -1112,10 +2503,24 @@ mod tests {
                                 program_name: tab.program_name.clone(),
                                 kind: tab.kind.clone(),
                                 activity: None,
+                                load_progress: None,
+                                busy: false,
+                                foreground_program: None,
+                                zoom: None,
+                                scroll: None,
+                                cwd: None,
+                                inspector_visible: None,
                             })
                         })
                         .collect();
-                    IpcTabGroup { id: group.id, name: group.name.clone(), tabs }
+                    IpcTabGroup {
+                        id: group.id,
+                        name: group.name.clone(),
+                        color: group.color,
+                        default_cwd: group.default_cwd.clone(),
+                        homepage: group.homepage.clone(),
+                        tabs,
+                    }
                 })
                 .collect()
         }
@@ -1133,6 +2538,13 @@ mod tests {
                 program_name: tab.program_name.clone(),
                 kind: tab.kind.clone(),
                 activity: None,
+                load_progress: None,
+                busy: false,
+                foreground_program: None,
+                zoom: None,
+                scroll: None,
+                cwd: None,
+                inspector_visible: None,
             })
         }
 
@@ -1145,32 +2557,58 @@ mod tests {
             options: WindowOptions,
             group_id: Option<usize>,
             group_name: Option<String>,
+            focus: bool,
         ) -> Result<TabId, IpcError> {
-            match options.window_kind {
-                WindowKind::Terminal => self.add_tab(IpcTabKind::Terminal, group_id, group_name),
-                WindowKind::Web { url } => {
+            let previous = self.active;
+            let tab_id = match options.window_kind {
+                WindowKind::Terminal => self.add_tab(IpcTabKind::Terminal, group_id, group_name)?,
+                WindowKind::Web { url, .. } => {
                     if !self.web_supported {
                         return Err(IpcError::new(
                             IpcErrorCode::Unsupported,
                             "Web tabs are not supported",
                         ));
                     }
-                    self.add_tab(IpcTabKind::Web { url }, group_id, group_name)
+                    self.add_tab(IpcTabKind::Web { url }, group_id, group_name)?
                 },
+            };
+            if !focus && previous.is_some() {
+                self.active = previous;
             }
+            Ok(tab_id)
         }
 
         fn create_group(&mut self, name: Option<String>) -> Result<usize, IpcError> {
             let group_id = self.next_group_id;
             self.next_group_id += 1;
-            self.groups.push(MockGroup { id: group_id, name, tabs: Vec::new() });
+            self.groups.push(MockGroup {
+                id: group_id,
+                name,
+                color: None,
+                default_cwd: None,
+                homepage: None,
+                tabs: Vec::new(),
+            });
             Ok(group_id)
         }
 
+        fn duplicate_tab(&mut self, tab_id: TabId) -> Result<TabId, IpcError> {
+            let kind = self
+                .tabs
+                .get(&tab_id)
+                .map(|tab| tab.kind.clone())
+                .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Tab not found"))?;
+            let (group_id, index) = self.group_for_tab(tab_id).expect("tab group");
+            let duplicate_id = self.add_tab(kind, Some(group_id), None)?;
+            self.move_tab(duplicate_id, Some(group_id), Some(index + 1))?;
+            Ok(duplicate_id)
+        }
+
         fn close_tab(&mut self, tab_id: TabId) -> Result<bool, IpcError> {
-            if self.tabs.remove(&tab_id).is_none() {
+            let Some(tab) = self.tabs.remove(&tab_id) else {
                 return Err(IpcError::new(IpcErrorCode::NotFound, "Tab not found"));
-            }
+            };
+            self.closed_tabs.push(tab);
             for group in &mut self.groups {
                 group.tabs.retain(|id| *id != tab_id);
             }
@@ -1184,21 +2622,26 @@ mod tests {
         fn select_tab(&mut self, selection: TabSelection) -> Result<(), IpcError> {
             let target = match selection {
                 TabSelection::Active => self.active,
-                TabSelection::Next => {
+                TabSelection::Next { wrap } => {
                     let ordered = self.tabs_ordered();
                     let active = self.active.ok_or_else(|| {
                         IpcError::new(IpcErrorCode::NotFound, "No active tab")
                     })?;
                     let pos = ordered.iter().position(|id| *id == active).unwrap_or(0);
-                    ordered.get((pos + 1) % ordered.len()).copied()
+                    let next = if wrap { (pos + 1) % ordered.len() } else { pos + 1 };
+                    ordered.get(next.min(ordered.len() - 1)).copied()
                 },
-                TabSelection::Previous => {
+                TabSelection::Previous { wrap } => {
                     let ordered = self.tabs_ordered();
                     let active = self.active.ok_or_else(|| {
                         IpcError::new(IpcErrorCode::NotFound, "No active tab")
                     })?;
                     let pos = ordered.iter().position(|id| *id == active).unwrap_or(0);
-                    let prev = if pos == 0 { ordered.len() - 1 } else { pos - 1 };
+                    let prev = match pos.checked_sub(1) {
+                        Some(prev) => prev,
+                        None if wrap => ordered.len() - 1,
+                        None => 0,
+                    };
                     ordered.get(prev).copied()
                 },
                 TabSelection::Last => self.tabs_ordered().last().copied(),
@@ -1227,21 +2670,39 @@ mod tests {
                 return Err(IpcError::new(IpcErrorCode::NotFound, "Tab not found"));
             }
 
-            let mut origin_group_id = None;
+            let mut origin = None;
             for group in &mut self.groups {
                 if let Some(pos) = group.tabs.iter().position(|id| *id == tab_id) {
                     group.tabs.remove(pos);
-                    origin_group_id = Some(group.id);
+                    origin = Some((group.id, pos));
                     break;
                 }
             }
+            let origin_group_id = origin.map(|(group_id, _)| group_id);
+
+            // Like the window, indices refer to the group before the tab was taken out.
+            let target_index = match (origin, target_index) {
+                (Some((group_id, pos)), Some(index))
+                    if target_group_id == Some(group_id) && index > pos =>
+                {
+                    Some(index - 1)
+                },
+                _ => target_index,
+            };
 
             self.groups.retain(|group| !group.tabs.is_empty());
 
             let target_group_id = target_group_id.unwrap_or_else(|| {
                 let id = self.next_group_id;
                 self.next_group_id += 1;
-                self.groups.push(MockGroup { id, name: None, tabs: Vec::new() });
+                self.groups.push(MockGroup {
+                    id,
+                    name: None,
+                    color: None,
+                    default_cwd: None,
+                    homepage: None,
+                    tabs: Vec::new(),
+                });
                 id
             });
 
@@ -1270,6 +2731,25 @@ mod tests {
             Ok(())
         }
 
+        fn set_tab_title_template(
+            &mut self,
+            tab_id: TabId,
+            template: Option<String>,
+        ) -> Result<(), IpcError> {
+            let tab = self
+                .tabs
+                .get_mut(&tab_id)
+                .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Tab not found"))?;
+            if matches!(tab.kind, IpcTabKind::Web { .. }) {
+                return Err(IpcError::new(
+                    IpcErrorCode::Unsupported,
+                    "Title templates only apply to terminal tabs",
+                ));
+            }
+            tab.title_template = template;
+            Ok(())
+        }
+
         fn set_group_name(&mut self, group_id: usize, name: Option<String>) -> Result<(), IpcError> {
             let group = self
                 .groups
@@ -1280,12 +2760,58 @@ mod tests {
             Ok(())
         }
 
-        fn restore_closed_tab(&mut self) -> Result<(), IpcError> {
+        fn set_group_color(&mut self, group_id: usize, color: Option<Rgb>) -> Result<(), IpcError> {
+            let group = self
+                .groups
+                .iter_mut()
+                .find(|group| group.id == group_id)
+                .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Group not found"))?;
+            group.color = color;
             Ok(())
         }
 
-        fn open_url_in_tab(&mut self, tab_id: TabId, url: String) -> Result<(), IpcError> {
-            let tab = self
+        fn set_group_defaults(
+            &mut self,
+            group_id: usize,
+            cwd: Option<PathBuf>,
+            homepage: Option<String>,
+        ) -> Result<(), IpcError> {
+            let group = self
+                .groups
+                .iter_mut()
+                .find(|group| group.id == group_id)
+                .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Group not found"))?;
+            group.default_cwd = cwd;
+            group.homepage = homepage;
+            Ok(())
+        }
+
+        fn restore_closed_tab(&mut self, count: usize) -> Result<(), IpcError> {
+            for _ in 0..count {
+                let Some(closed) = self.closed_tabs.pop() else {
+                    break;
+                };
+                let tab_id = self.add_tab(closed.kind, None, None)?;
+                self.tabs.get_mut(&tab_id).unwrap().title = closed.title;
+            }
+            Ok(())
+        }
+
+        fn closed_tabs(&self) -> Vec<IpcClosedTab> {
+            self.closed_tabs
+                .iter()
+                .rev()
+                .map(|tab| IpcClosedTab {
+                    title: tab.title.clone(),
+                    custom_title: tab.custom_title.clone(),
+                    kind: tab.kind.clone(),
+                    group_id: None,
+                })
+                .collect()
+        }
+
+        fn open_url_in_tab(&mut self, tab_id: TabId, url: String) -> Result<(), IpcError> {
+            let tab = self
                 .tabs
                 .get_mut(&tab_id)
                 .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Tab not found"))?;
@@ -1311,7 +2837,7 @@ mod tests {
             self.add_tab(IpcTabKind::Web { url }, None, None)
         }
 
-        fn reload_web(&mut self, tab_id: TabId) -> Result<(), IpcError> {
+        fn reload_web(&mut self, tab_id: TabId, _ignore_cache: bool) -> Result<(), IpcError> {
             let tab = self
                 .tabs
                 .get(&tab_id)
@@ -1325,8 +2851,84 @@ mod tests {
             }
         }
 
+        fn open_external(&mut self, tab_id: TabId) -> Result<(), IpcError> {
+            self.reload_web(tab_id, false)
+        }
+
+        fn run_bookmarklet(&mut self, tab_id: TabId, name: &str) -> Result<(), IpcError> {
+            self.reload_web(tab_id, false)?;
+            match name {
+                "top" => Ok(()),
+                _ => Err(IpcError::new(IpcErrorCode::NotFound, "Bookmarklet not found")),
+            }
+        }
+
+        fn set_web_zoom(&mut self, tab_id: TabId, _zoom: f64) -> Result<(), IpcError> {
+            self.reload_web(tab_id, false)
+        }
+
+        fn set_web_muted(&mut self, tab_id: TabId, muted: bool) -> Result<(), IpcError> {
+            self.reload_web(tab_id, false)?;
+            self.tabs.get_mut(&tab_id).unwrap().muted = muted;
+            Ok(())
+        }
+
+        fn hibernate_web_tab(&mut self, tab_id: TabId) -> Result<(), IpcError> {
+            self.reload_web(tab_id, false)?;
+            if Some(tab_id) != self.active {
+                self.tabs.get_mut(&tab_id).unwrap().hibernated = true;
+            }
+            Ok(())
+        }
+
+        fn set_web_headers(
+            &mut self,
+            tab_id: TabId,
+            _user_agent: Option<String>,
+            _headers: Option<HashMap<String, String>>,
+        ) -> Result<(), IpcError> {
+            self.reload_web(tab_id, false)
+        }
+
+        fn web_history(&mut self, tab_id: TabId) -> Result<IpcWebHistory, IpcError> {
+            let tab = self
+                .tabs
+                .get(&tab_id)
+                .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Tab not found"))?;
+            match &tab.kind {
+                IpcTabKind::Web { url } => {
+                    let entry = IpcWebHistoryEntry { url: url.clone(), title: String::new() };
+                    Ok(IpcWebHistory { entries: vec![entry], current: Some(0) })
+                },
+                IpcTabKind::Terminal => Err(IpcError::new(
+                    IpcErrorCode::InvalidRequest,
+                    "Not a web tab",
+                )),
+            }
+        }
+
+        fn go_to_history_index(&mut self, tab_id: TabId, index: usize) -> Result<(), IpcError> {
+            let history = self.web_history(tab_id)?;
+            if index >= history.entries.len() {
+                return Err(IpcError::new(IpcErrorCode::InvalidRequest, "No history entry at index"));
+            }
+            Ok(())
+        }
+
+        fn set_web_scroll(&mut self, tab_id: TabId, _x: f64, _y: f64) -> Result<(), IpcError> {
+            self.reload_web(tab_id, false)
+        }
+
         fn open_inspector(&mut self, tab_id: TabId) -> Result<(), IpcError> {
-            self.reload_web(tab_id)
+            self.reload_web(tab_id, false)
+        }
+
+        fn set_inspector_dock(
+            &mut self,
+            tab_id: TabId,
+            _side: InspectorDock,
+        ) -> Result<(), IpcError> {
+            self.reload_web(tab_id, false)
         }
 
         fn tab_panel_state(&self) -> IpcTabPanelState {
@@ -1359,6 +2961,23 @@ mod tests {
             Ok(())
         }
 
+        fn tab_contents(&mut self, tab_id: TabId, scrollback: bool) -> Result<Vec<String>, IpcError> {
+            let tab = self
+                .tabs
+                .get(&tab_id)
+                .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Tab not found"))?;
+            match tab.kind {
+                IpcTabKind::Terminal if scrollback => {
+                    Ok(vec![String::from("$ ls"), String::from("$ pwd")])
+                },
+                IpcTabKind::Terminal => Ok(vec![String::from("$ pwd")]),
+                IpcTabKind::Web { .. } => Err(IpcError::new(
+                    IpcErrorCode::InvalidRequest,
+                    "Not a terminal tab",
+                )),
+            }
+        }
+
         fn run_command_bar(&mut self, _tab_id: TabId, input: String) -> Result<(), IpcError> {
             self.last_command = Some(input);
             Ok(())
@@ -1456,9 +3075,10 @@ mod tests {
         let response = handle_request(
             &mut ctx,
             IpcRequest::CreateTab {
-                options: WindowOptions::default(),
+                options: Box::default(),
                 group_id: None,
                 group_name: None,
+                focus: true,
             },
         );
         match response.reply {
@@ -1495,6 +3115,8 @@ mod tests {
                 tab_id: initial_tab.into(),
                 target_group_id: None,
                 target_index: Some(0),
+                before: None,
+                after: None,
             },
         );
         assert!(matches!(response.reply, SocketReply::Ok));
@@ -1514,6 +3136,74 @@ mod tests {
         assert!(response.close_window);
     }
 
+    #[test]
+    fn ipc_moves_tab_relative_to_anchor() {
+        let mut ctx = MockContext::new(false);
+        let group_id = ctx.groups[0].id;
+        let first = ctx.groups[0].tabs[0];
+        let second = ctx.add_tab(IpcTabKind::Terminal, Some(group_id), None).unwrap();
+        let third = ctx.add_tab(IpcTabKind::Terminal, Some(group_id), None).unwrap();
+        let move_tab = |tab_id: TabId, before: Option<TabId>, after: Option<TabId>| {
+            IpcRequest::MoveTab {
+                tab_id: tab_id.into(),
+                target_group_id: None,
+                target_index: None,
+                before: before.map(IpcTabId::from),
+                after: after.map(IpcTabId::from),
+            }
+        };
+
+        let response = handle_request(&mut ctx, move_tab(third, Some(first), None));
+        assert!(matches!(response.reply, SocketReply::Ok));
+        assert_eq!(ctx.groups[0].tabs, vec![third, first, second]);
+
+        let response = handle_request(&mut ctx, move_tab(third, None, Some(second)));
+        assert!(matches!(response.reply, SocketReply::Ok));
+        assert_eq!(ctx.groups[0].tabs, vec![first, second, third]);
+
+        let response = handle_request(&mut ctx, move_tab(first, None, Some(second)));
+        assert!(matches!(response.reply, SocketReply::Ok));
+        assert_eq!(ctx.groups[0].tabs, vec![second, first, third]);
+    }
+
+    #[test]
+    fn ipc_move_tab_rejects_invalid_anchors() {
+        let mut ctx = MockContext::new(false);
+        let group_id = ctx.groups[0].id;
+        let first = ctx.groups[0].tabs[0];
+        let second = ctx.add_tab(IpcTabKind::Terminal, Some(group_id), None).unwrap();
+        let closed = ctx.add_tab(IpcTabKind::Terminal, Some(group_id), None).unwrap();
+        assert!(ctx.close_tab(closed).is_ok());
+        let move_tab = |before: TabId, after: Option<TabId>| IpcRequest::MoveTab {
+            tab_id: first.into(),
+            target_group_id: None,
+            target_index: Some(0),
+            before: Some(before.into()),
+            after: after.map(IpcTabId::from),
+        };
+
+        // The anchor was closed before the request was handled.
+        let response = handle_request(&mut ctx, move_tab(closed, None));
+        let SocketReply::Error { error } = response.reply else {
+            panic!("expected error reply");
+        };
+        assert_eq!(error.code, IpcErrorCode::NotFound);
+        assert_eq!(ctx.groups[0].tabs, vec![first, second]);
+
+        let response = handle_request(&mut ctx, move_tab(second, Some(second)));
+        assert!(matches!(
+            response.reply,
+            SocketReply::Error { error: IpcError { code: IpcErrorCode::InvalidRequest, .. } }
+        ));
+
+        let response = handle_request(&mut ctx, move_tab(first, None));
+        assert!(matches!(
+            response.reply,
+            SocketReply::Error { error: IpcError { code: IpcErrorCode::InvalidRequest, .. } }
+        ));
+        assert_eq!(ctx.groups[0].tabs, vec![first, second]);
+    }
+
     #[test]
     fn ipc_creates_group() {
         let mut ctx = MockContext::new(false);
@@ -1531,6 +3221,63 @@ mod tests {
         assert!(ctx.groups.iter().any(|group| group.id == group_id));
     }
 
+    #[test]
+    fn ipc_sets_group_color() {
+        let mut ctx = MockContext::new(true);
+        let group_id = ctx.groups[0].id;
+        let color = Some(Rgb::new(0xcc, 0x66, 0x66));
+
+        let response = handle_request(&mut ctx, IpcRequest::SetGroupColor { group_id, color });
+        assert_eq!(response.reply, reply_ok());
+        let SocketReply::TabList { groups } = handle_request(&mut ctx, IpcRequest::ListTabs).reply
+        else {
+            panic!("expected tab_list reply");
+        };
+        assert_eq!(groups[0].color, color);
+
+        let request = IpcRequest::SetGroupColor { group_id: group_id + 1, color: None };
+        let SocketReply::Error { error } = handle_request(&mut ctx, request).reply else {
+            panic!("expected error reply");
+        };
+        assert_eq!(error.code, IpcErrorCode::NotFound);
+    }
+
+    #[test]
+    fn ipc_group_mute_and_hibernate_skip_terminals() {
+        let mut ctx = MockContext::new(true);
+        let group_id = ctx.groups[0].id;
+        let terminal_id = ctx.groups[0].tabs[0];
+        let url = String::from("https://example.com");
+        let active_id =
+            ctx.add_tab(IpcTabKind::Web { url: url.clone() }, Some(group_id), None).unwrap();
+        let background_id =
+            ctx.add_tab(IpcTabKind::Web { url: url.clone() }, Some(group_id), None).unwrap();
+        let other_group = ctx.add_tab(IpcTabKind::Web { url }, None, Some(String::from("other")));
+        let other_id = other_group.unwrap();
+        ctx.active = Some(active_id);
+
+        let request = IpcRequest::SetGroupMuted { group_id, muted: true };
+        let response = handle_request(&mut ctx, request);
+        assert_eq!(response.reply, reply_ok());
+        assert!(ctx.tabs[&active_id].muted);
+        assert!(ctx.tabs[&background_id].muted);
+        assert!(!ctx.tabs[&terminal_id].muted);
+        assert!(!ctx.tabs[&other_id].muted);
+
+        let response = handle_request(&mut ctx, IpcRequest::HibernateGroup { group_id });
+        assert_eq!(response.reply, reply_ok());
+        assert!(!ctx.tabs[&active_id].hibernated);
+        assert!(ctx.tabs[&background_id].hibernated);
+        assert!(!ctx.tabs[&terminal_id].hibernated);
+        assert!(!ctx.tabs[&other_id].hibernated);
+
+        let request = IpcRequest::HibernateGroup { group_id: group_id + 5 };
+        let SocketReply::Error { error } = handle_request(&mut ctx, request).reply else {
+            panic!("expected error reply");
+        };
+        assert_eq!(error.code, IpcErrorCode::NotFound);
+    }
+
     #[test]
     fn ipc_handles_list_and_state() {
         let mut ctx = MockContext::new(true);
@@ -1581,12 +3328,88 @@ mod tests {
         );
         assert!(matches!(response.reply, SocketReply::Ok));
 
-        let response = handle_request(&mut ctx, IpcRequest::ReloadWeb { tab_id: Some(tab_id) });
+        let request = IpcRequest::ReloadWeb { tab_id: Some(tab_id), ignore_cache: true };
+        let response = handle_request(&mut ctx, request);
+        assert!(matches!(response.reply, SocketReply::Ok));
+
+        let response = handle_request(&mut ctx, IpcRequest::OpenExternal { tab_id: Some(tab_id) });
+        assert!(matches!(response.reply, SocketReply::Ok));
+
+        let name = String::from("top");
+        let request = IpcRequest::RunBookmarklet { tab_id: Some(tab_id), name };
+        let response = handle_request(&mut ctx, request);
+        assert!(matches!(response.reply, SocketReply::Ok));
+
+        let name = String::from("missing");
+        let request = IpcRequest::RunBookmarklet { tab_id: Some(tab_id), name };
+        let response = handle_request(&mut ctx, request);
+        assert!(matches!(
+            response.reply,
+            SocketReply::Error { error: IpcError { code: IpcErrorCode::NotFound, .. } }
+        ));
+
+        let response =
+            handle_request(&mut ctx, IpcRequest::SetWebZoom { tab_id: Some(tab_id), zoom: 1.5 });
+        assert!(matches!(response.reply, SocketReply::Ok));
+
+        let response =
+            handle_request(&mut ctx, IpcRequest::SetWebZoom { tab_id: Some(tab_id), zoom: 0. });
+        assert!(matches!(
+            response.reply,
+            SocketReply::Error { error: IpcError { code: IpcErrorCode::InvalidRequest, .. } }
+        ));
+
+        let response =
+            handle_request(&mut ctx, IpcRequest::SetWebMuted { tab_id: Some(tab_id), muted: true });
         assert!(matches!(response.reply, SocketReply::Ok));
 
+        let headers = HashMap::from([(String::from("X-Team"), String::from("tools"))]);
+        let request = IpcRequest::SetWebHeaders {
+            tab_id: Some(tab_id),
+            user_agent: Some(String::new()),
+            headers: Some(headers),
+        };
+        assert!(matches!(handle_request(&mut ctx, request).reply, SocketReply::Ok));
+
+        let headers = HashMap::from([(String::from("Host"), String::from("example.com"))]);
+        let request =
+            IpcRequest::SetWebHeaders { tab_id: Some(tab_id), user_agent: None, headers: Some(headers) };
+        assert!(matches!(
+            handle_request(&mut ctx, request).reply,
+            SocketReply::Error { error: IpcError { code: IpcErrorCode::InvalidRequest, .. } }
+        ));
+
+        let response = handle_request(&mut ctx, IpcRequest::GetWebHistory { tab_id: Some(tab_id) });
+        let SocketReply::WebHistory { history } = response.reply else {
+            panic!("expected web_history reply");
+        };
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.current, Some(0));
+
+        let response = handle_request(
+            &mut ctx,
+            IpcRequest::GoToHistoryIndex { tab_id: Some(tab_id), index: 0 },
+        );
+        assert!(matches!(response.reply, SocketReply::Ok));
+
+        let response = handle_request(
+            &mut ctx,
+            IpcRequest::GoToHistoryIndex { tab_id: Some(tab_id), index: 3 },
+        );
+        assert!(matches!(
+            response.reply,
+            SocketReply::Error { error: IpcError { code: IpcErrorCode::InvalidRequest, .. } }
+        ));
+
         let response = handle_request(&mut ctx, IpcRequest::OpenInspector { tab_id: Some(tab_id) });
         assert!(matches!(response.reply, SocketReply::Ok));
 
+        let response = handle_request(
+            &mut ctx,
+            IpcRequest::SetInspectorDock { tab_id: Some(tab_id), side: InspectorDock::Left },
+        );
+        assert!(matches!(response.reply, SocketReply::Ok));
+
         let response = handle_request(
             &mut ctx,
             IpcRequest::SetTabPanel {
@@ -1631,16 +3454,47 @@ mod tests {
         assert!(matches!(response.reply, SocketReply::Ok));
         assert_eq!(ctx.last_action, Some(Action::Vi(ViAction::ToggleNormalSelection)));
 
+        let response = handle_request(
+            &mut ctx,
+            IpcRequest::DispatchAction {
+                tab_id: Some(tab_id.into()),
+                action: IpcAction::Scroll { lines: 10 },
+            },
+        );
+        assert!(matches!(response.reply, SocketReply::Ok));
+        assert_eq!(ctx.last_action, Some(Action::ScrollLines(10)));
+
         let response = handle_request(
             &mut ctx,
             IpcRequest::SendInput {
                 tab_id: Some(tab_id.into()),
                 text: String::from("ls\n"),
+                interpret_escapes: false,
             },
         );
         assert!(matches!(response.reply, SocketReply::Ok));
         assert_eq!(ctx.last_input.as_deref(), Some("ls\n"));
 
+        let response = handle_request(
+            &mut ctx,
+            IpcRequest::SendInput {
+                tab_id: Some(tab_id.into()),
+                text: String::from(r"ls\n\x03"),
+                interpret_escapes: true,
+            },
+        );
+        assert!(matches!(response.reply, SocketReply::Ok));
+        assert_eq!(ctx.last_input.as_deref(), Some("ls\n\x03"));
+
+        let response = handle_request(
+            &mut ctx,
+            IpcRequest::GetTabContents { tab_id: Some(tab_id.into()), scrollback: true },
+        );
+        let SocketReply::TabContents { lines } = response.reply else {
+            panic!("expected tab_contents reply");
+        };
+        assert_eq!(lines.len(), 2);
+
         let response = handle_request(
             &mut ctx,
             IpcRequest::RunCommandBar {
@@ -1708,4 +3562,1085 @@ mod tests {
         );
         assert!(matches!(response.reply, SocketReply::Ok));
     }
+
+    #[test]
+    fn ipc_snapshot_serialization() {
+        let request = IpcRequest::SnapshotTab {
+            tab_id: Some(IpcTabId { index: 1, generation: 1 }),
+            path: PathBuf::from("/tmp/tab.png"),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"snapshot_tab","tab_id":{"index":1,"generation":1},"path":"/tmp/tab.png"}"#
+        );
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let reply = SocketReply::Snapshot { path: PathBuf::from("/tmp/tab.png") };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert_eq!(json, r#"{"type":"snapshot","path":"/tmp/tab.png"}"#);
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+    }
+
+    #[test]
+    fn ipc_set_web_muted_serialization() {
+        let request =
+            IpcRequest::SetWebMuted { tab_id: Some(IpcTabId { index: 1, generation: 1 }), muted: true };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"set_web_muted","tab_id":{"index":1,"generation":1},"muted":true}"#
+        );
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let request: IpcRequest =
+            serde_json::from_str(r#"{"type":"set_web_muted","muted":false}"#).unwrap();
+        assert_eq!(request, IpcRequest::SetWebMuted { tab_id: None, muted: false });
+    }
+
+    #[test]
+    fn ipc_reload_web_serialization() {
+        let request = IpcRequest::ReloadWeb {
+            tab_id: Some(IpcTabId { index: 1, generation: 1 }),
+            ignore_cache: true,
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"reload_web","tab_id":{"index":1,"generation":1},"ignore_cache":true}"#
+        );
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let request: IpcRequest = serde_json::from_str(r#"{"type":"reload_web"}"#).unwrap();
+        assert_eq!(request, IpcRequest::ReloadWeb { tab_id: None, ignore_cache: false });
+    }
+
+    #[test]
+    fn ipc_scroll_action_serialization() {
+        let action = IpcAction::Scroll { lines: -5 };
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"type":"scroll","lines":-5}"#);
+        assert_eq!(serde_json::from_str::<IpcAction>(&json).unwrap(), action);
+
+        let action: IpcAction = serde_json::from_str(r#"{"type":"scroll_to_top"}"#).unwrap();
+        assert_eq!(action, IpcAction::ScrollToTop);
+        let action: IpcAction = serde_json::from_str(r#"{"type":"scroll_to_bottom"}"#).unwrap();
+        assert_eq!(action, IpcAction::ScrollToBottom);
+
+        assert_eq!(
+            ipc_action_to_action(IpcAction::Scroll { lines: 3 }).unwrap(),
+            Action::ScrollLines(3)
+        );
+        assert_eq!(ipc_action_to_action(IpcAction::ScrollToTop).unwrap(), Action::ScrollToTop);
+    }
+
+    #[test]
+    fn ipc_set_inspector_dock_serialization() {
+        let request = IpcRequest::SetInspectorDock {
+            tab_id: Some(IpcTabId { index: 1, generation: 1 }),
+            side: InspectorDock::Right,
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"set_inspector_dock","tab_id":{"index":1,"generation":1},"side":"right"}"#
+        );
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        for (name, side) in [
+            ("left", InspectorDock::Left),
+            ("right", InspectorDock::Right),
+            ("bottom", InspectorDock::Bottom),
+            ("detached", InspectorDock::Detached),
+        ] {
+            let json = format!(r#"{{"type":"set_inspector_dock","side":"{name}"}}"#);
+            let request: IpcRequest = serde_json::from_str(&json).unwrap();
+            assert_eq!(request, IpcRequest::SetInspectorDock { tab_id: None, side });
+        }
+
+        let json = r#"{"type":"set_inspector_dock","side":"top"}"#;
+        assert!(serde_json::from_str::<IpcRequest>(json).is_err());
+    }
+
+    #[test]
+    fn ipc_group_mute_and_hibernate_serialization() {
+        let request = IpcRequest::SetGroupMuted { group_id: 2, muted: true };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"type":"set_group_muted","group_id":2,"muted":true}"#);
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let request = IpcRequest::HibernateGroup { group_id: 3 };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"type":"hibernate_group","group_id":3}"#);
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+    }
+
+    #[test]
+    fn ipc_web_history_serialization() {
+        let request = IpcRequest::GoToHistoryIndex { tab_id: None, index: 2 };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"type":"go_to_history_index","tab_id":null,"index":2}"#);
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let request: IpcRequest = serde_json::from_str(r#"{"type":"get_web_history"}"#).unwrap();
+        assert_eq!(request, IpcRequest::GetWebHistory { tab_id: None });
+
+        let reply = SocketReply::WebHistory {
+            history: IpcWebHistory {
+                entries: vec![
+                    IpcWebHistoryEntry {
+                        url: String::from("https://example.com/"),
+                        title: String::from("Example"),
+                    },
+                    IpcWebHistoryEntry {
+                        url: String::from("https://example.org/"),
+                        title: String::new(),
+                    },
+                ],
+                current: Some(1),
+            },
+        };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"web_history","history":{"entries":[{"url":"https://example.com/","title":"Example"},{"url":"https://example.org/","title":""}],"current":1}}"#
+        );
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+    }
+
+    #[test]
+    fn ipc_web_scroll_serialization() {
+        let request: IpcRequest = serde_json::from_str(r#"{"type":"get_web_scroll"}"#).unwrap();
+        assert_eq!(request, IpcRequest::GetWebScroll { tab_id: None });
+
+        let request = IpcRequest::SetWebScroll {
+            tab_id: Some(IpcTabId { index: 1, generation: 2 }),
+            x: 0.,
+            y: 480.5,
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"set_web_scroll","tab_id":{"index":1,"generation":2},"x":0.0,"y":480.5}"#
+        );
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let reply = SocketReply::WebScroll { x: 12., y: 480.5 };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert_eq!(json, r#"{"type":"web_scroll","x":12.0,"y":480.5}"#);
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+    }
+
+    #[test]
+    fn ipc_set_web_scroll() {
+        let mut ctx = MockContext::new(true);
+        let terminal_id = ctx.active.unwrap();
+        let web_id =
+            ctx.add_tab(IpcTabKind::Web { url: String::from("https://example.com") }, None, None)
+                .expect("add tab");
+
+        let request = IpcRequest::SetWebScroll { tab_id: Some(web_id.into()), x: 0., y: 240. };
+        assert_eq!(handle_request(&mut ctx, request).reply, reply_ok());
+
+        let request = IpcRequest::SetWebScroll { tab_id: Some(web_id.into()), x: 0., y: f64::NAN };
+        let response = handle_request(&mut ctx, request);
+        assert!(matches!(
+            response.reply,
+            SocketReply::Error { error: IpcError { code: IpcErrorCode::InvalidRequest, .. } }
+        ));
+
+        let request = IpcRequest::SetWebScroll { tab_id: Some(terminal_id.into()), x: 0., y: 0. };
+        let response = handle_request(&mut ctx, request);
+        assert!(matches!(
+            response.reply,
+            SocketReply::Error { error: IpcError { code: IpcErrorCode::InvalidRequest, .. } }
+        ));
+
+        let response = handle_request(&mut ctx, IpcRequest::GetWebScroll { tab_id: None });
+        assert!(matches!(response.reply, SocketReply::Error { .. }));
+    }
+
+    #[test]
+    fn ipc_subscribe_serialization() {
+        let request: IpcRequest = serde_json::from_str(r#"{"type":"subscribe"}"#).unwrap();
+        assert_eq!(request, IpcRequest::Subscribe { events: Vec::new() });
+
+        let request = IpcRequest::Subscribe { events: vec![String::from("tab_created")] };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"type":"subscribe","events":["tab_created"]}"#);
+
+        let reply = SocketReply::Event {
+            event: IpcEvent::UrlChanged {
+                tab_id: IpcTabId { index: 1, generation: 1 },
+                url: String::from("https://example.com/"),
+            },
+        };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"event","event":{"type":"url_changed","tab_id":{"index":1,"generation":1},"url":"https://example.com/"}}"#
+        );
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+    }
+
+    #[test]
+    fn ipc_subscribers_stream_events() {
+        let read_reply_line = |reader: &mut BufReader<UnixStream>| {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            serde_json::from_str::<SocketReply>(&line).unwrap()
+        };
+
+        let mut subscribers = IpcSubscribers::default();
+        let (server, client) = UnixStream::pair().unwrap();
+        let server = IpcStream::Unix(server);
+        subscribers.subscribe(&server, vec![String::from("tab_closed")]);
+        drop(server);
+
+        let mut reader = BufReader::new(client);
+        assert_eq!(read_reply_line(&mut reader), SocketReply::Ok);
+
+        let tab_id = IpcTabId { index: 1, generation: 1 };
+        subscribers.broadcast(&IpcEvent::TabCreated { tab_id });
+        subscribers.broadcast(&IpcEvent::TabClosed { tab_id });
+        let reply = read_reply_line(&mut reader);
+        assert_eq!(reply, SocketReply::Event { event: IpcEvent::TabClosed { tab_id } });
+
+        // Disconnected subscribers are dropped on the next event.
+        drop(reader);
+        subscribers.broadcast(&IpcEvent::TabClosed { tab_id });
+        assert!(subscribers.subscribers.is_empty());
+
+        let (server, client) = UnixStream::pair().unwrap();
+        let server = IpcStream::Unix(server);
+        subscribers.subscribe(&server, vec![String::from("unknown")]);
+        drop(server);
+        let mut reader = BufReader::new(client);
+        assert!(matches!(
+            read_reply_line(&mut reader),
+            SocketReply::Error { error: IpcError { code: IpcErrorCode::InvalidRequest, .. } }
+        ));
+        assert!(subscribers.subscribers.is_empty());
+    }
+
+    #[test]
+    fn ipc_tab_contents_serialization() {
+        let request: IpcRequest = serde_json::from_str(r#"{"type":"get_tab_contents"}"#).unwrap();
+        assert_eq!(request, IpcRequest::GetTabContents { tab_id: None, scrollback: false });
+
+        let request = IpcRequest::GetTabContents {
+            tab_id: Some(IpcTabId { index: 1, generation: 1 }),
+            scrollback: true,
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"get_tab_contents","tab_id":{"index":1,"generation":1},"scrollback":true}"#
+        );
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let reply = SocketReply::TabContents { lines: vec![String::from("$ ls"), String::new()] };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert_eq!(json, r#"{"type":"tab_contents","lines":["$ ls",""]}"#);
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+    }
+
+    #[test]
+    fn ipc_selection_serialization() {
+        let request: IpcRequest = serde_json::from_str(r#"{"type":"get_selection"}"#).unwrap();
+        assert_eq!(request, IpcRequest::GetSelection { tab_id: None });
+
+        let tab_id = Some(IpcTabId { index: 1, generation: 1 });
+        let request = IpcRequest::GetSelection { tab_id };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"type":"get_selection","tab_id":{"index":1,"generation":1}}"#);
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        // Terminal selections keep their line breaks.
+        let reply = SocketReply::Selection { text: Some(String::from("$ ls\nCargo.toml")) };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert_eq!(json, r#"{"type":"selection","text":"$ ls\nCargo.toml"}"#);
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+        assert!(request.accepts_reply(&reply));
+
+        // Web selections are read from the page.
+        let reply = SocketReply::Selection { text: Some(String::from("Example Domain")) };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert_eq!(json, r#"{"type":"selection","text":"Example Domain"}"#);
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+
+        let reply = SocketReply::Selection { text: None };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert_eq!(json, r#"{"type":"selection","text":null}"#);
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+        let contents = IpcRequest::GetTabContents { tab_id: None, scrollback: false };
+        assert!(!contents.accepts_reply(&reply));
+    }
+
+    #[test]
+    fn ipc_wait_tab_serialization() {
+        let request: IpcRequest = serde_json::from_str(r#"{"type":"wait_tab"}"#).unwrap();
+        assert_eq!(request, IpcRequest::WaitTab { tab_id: None, timeout_ms: None });
+
+        let tab_id = IpcTabId { index: 1, generation: 1 };
+        let request = IpcRequest::WaitTab { tab_id: Some(tab_id), timeout_ms: Some(500) };
+        let json = serde_json::to_string(&request).unwrap();
+        let expected = r#"{"type":"wait_tab","tab_id":{"index":1,"generation":1},"timeout_ms":500}"#;
+        assert_eq!(json, expected);
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let reply = SocketReply::TabExited { tab_id, status: Some(2) };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert_eq!(json, r#"{"type":"tab_exited","tab_id":{"index":1,"generation":1},"status":2}"#);
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+        assert!(request.accepts_reply(&reply));
+        assert!(request.accepts_reply(&reply_error(IpcErrorCode::Timeout, "timeout")));
+
+        // Commands killed by a signal have no exit status.
+        let event = IpcEvent::ChildExited { tab_id, status: None };
+        let json = serde_json::to_string(&SocketReply::Event { event: event.clone() }).unwrap();
+        let expected = r#"{"type":"event","event":{"type":"child_exited","tab_id":{"index":1,"generation":1},"status":null}}"#;
+        assert_eq!(json, expected);
+        assert!(IPC_EVENT_NAMES.contains(&event.name()));
+    }
+
+    #[test]
+    fn tab_waiters_reply_on_exit_or_timeout() {
+        let replies = Rc::new(RefCell::new(Vec::new()));
+        let reply = || {
+            let replies = replies.clone();
+            move |reply| replies.borrow_mut().push(reply)
+        };
+
+        let now = Instant::now();
+        let first = IpcTabId { index: 1, generation: 0 };
+        let second = IpcTabId { index: 2, generation: 0 };
+        let mut waiters = TabWaiters::default();
+        waiters.wait(first, None, reply());
+        waiters.wait(second, Some(now + Duration::from_millis(100)), reply());
+        waiters.wait(second, Some(now + Duration::from_millis(50)), reply());
+        assert_eq!(waiters.next_deadline(), Some(now + Duration::from_millis(50)));
+
+        // Only waiters past their deadline time out.
+        waiters.expire(now + Duration::from_millis(50));
+        assert_eq!(replies.borrow().len(), 1);
+        let message = "Tab command did not exit before the timeout";
+        assert_eq!(replies.borrow()[0], reply_error(IpcErrorCode::Timeout, message));
+        assert_eq!(waiters.next_deadline(), Some(now + Duration::from_millis(100)));
+
+        // Exits reply to the waiters of that tab, with the status of the command.
+        waiters.exited(first, Some(0));
+        assert_eq!(replies.borrow()[1], SocketReply::TabExited { tab_id: first, status: Some(0) });
+        waiters.exited(first, Some(1));
+        assert_eq!(replies.borrow().len(), 2);
+
+        waiters.exited(second, None);
+        assert_eq!(replies.borrow()[2], SocketReply::TabExited { tab_id: second, status: None });
+        assert_eq!(waiters.next_deadline(), None);
+    }
+
+    #[test]
+    fn ipc_token_accepts_matching_requests() {
+        let json = attach_token(r#"{"type":"ping"}"#, Some("secret"));
+        let mut request: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(request["token"], "secret");
+
+        assert!(authenticate_request(&mut request, Some("secret")).is_ok());
+        assert_eq!(serde_json::from_value::<IpcRequest>(request).unwrap(), IpcRequest::Ping);
+
+        // Tokens are ignored when the socket doesn't require one.
+        let mut request: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(authenticate_request(&mut request, None).is_ok());
+        assert_eq!(serde_json::from_value::<IpcRequest>(request).unwrap(), IpcRequest::Ping);
+
+        let mut request = serde_json::json!({ "type": "ping" });
+        assert!(authenticate_request(&mut request, None).is_ok());
+
+        assert_eq!(attach_token(r#"{"type":"ping"}"#, None), r#"{"type":"ping"}"#);
+        assert_eq!(attach_token("not json", Some("secret")), "not json");
+    }
+
+    #[test]
+    fn ipc_token_rejects_invalid_requests() {
+        for mut request in [
+            serde_json::json!({ "type": "ping" }),
+            serde_json::json!({ "type": "ping", "token": "secreT" }),
+            serde_json::json!({ "type": "ping", "token": "secret2" }),
+            serde_json::json!({ "type": "ping", "token": 1 }),
+        ] {
+            let error = authenticate_request(&mut request, Some("secret")).unwrap_err();
+            assert_eq!(error.code, IpcErrorCode::PermissionDenied);
+
+            let json = serde_json::to_string(&SocketReply::Error { error }).unwrap();
+            assert!(!json.contains("secret"));
+            assert!(!json.contains("token\":"));
+        }
+
+        // The token is not exposed through `get_config` either.
+        let mut config = crate::config::UiConfig::default();
+        config.general.ipc_token = Some(String::from("secret"));
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("secret"));
+    }
+
+    #[test]
+    fn ipc_tcp_listen_addr() {
+        let addr = tcp_listen_addr("7373", false).unwrap();
+        assert_eq!(addr, SocketAddr::from((Ipv4Addr::LOCALHOST, 7373)));
+        assert_eq!(tcp_listen_addr(" 127.0.0.1:80 ", false).unwrap().port(), 80);
+        assert!(tcp_listen_addr("[::1]:7373", false).unwrap().ip().is_loopback());
+
+        // Other interfaces need to be allowed explicitly.
+        let error = tcp_listen_addr("0.0.0.0:7373", false).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::PermissionDenied);
+        let addr = tcp_listen_addr("0.0.0.0:7373", true).unwrap();
+        assert_eq!(addr, SocketAddr::from((Ipv4Addr::UNSPECIFIED, 7373)));
+
+        assert!(tcp_listen_addr("127.0.0.1", false).is_err());
+        assert!(tcp_listen_addr("127.0.0.1:port", false).is_err());
+    }
+
+    #[test]
+    fn ipc_stalled_client_times_out() {
+        let (stream, mut client) = UnixStream::pair().unwrap();
+        client.write_all(br#"{"type":"#).unwrap();
+
+        // The connection stays open, but the request is never finished.
+        let start = Instant::now();
+        assert_eq!(read_request(&mut IpcStream::Unix(stream), None), None);
+        assert!(start.elapsed() < CONNECTION_TIMEOUT * 2);
+    }
+
+    #[test]
+    fn ipc_tcp_request_round_trip() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let socket_path = PathBuf::from(format!("tcp://{}", listener.local_addr().unwrap()));
+
+        let client = std::thread::spawn(move || {
+            let mut results = Vec::new();
+            for token in [Some("secret"), Some("wrong")] {
+                let mut socket = connect_socket(&socket_path).unwrap();
+                let json = attach_token(r#"{"type":"ping"}"#, token);
+                write_request(&mut socket, &json).unwrap();
+                results.push(read_reply(&socket).unwrap());
+            }
+            results
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut stream = IpcStream::Tcp(stream);
+        assert_eq!(read_request(&mut stream, Some("secret")), Some(IpcRequest::Ping));
+        send_reply(&mut stream, SocketReply::Pong);
+        drop(stream);
+
+        let (stream, _) = listener.accept().unwrap();
+        assert_eq!(read_request(&mut IpcStream::Tcp(stream), Some("secret")), None);
+
+        let replies = client.join().unwrap();
+        assert_eq!(replies[0], Some(SocketReply::Pong));
+        let denied = IpcErrorCode::PermissionDenied;
+        assert!(matches!(&replies[1], Some(SocketReply::Error { error }) if error.code == denied));
+    }
+
+    #[test]
+    fn ipc_capabilities_reply() {
+        let mut ctx = MockContext::new(false);
+        let response = handle_request(&mut ctx, IpcRequest::GetCapabilities);
+        let capabilities = match response.reply {
+            SocketReply::Capabilities { capabilities } => capabilities,
+            reply => panic!("unexpected reply: {reply:?}"),
+        };
+
+        assert_eq!(capabilities.protocol_version, IPC_PROTOCOL_VERSION);
+        for name in ["ping", "get_capabilities", "list_tabs", "create_tab", "close_tab"] {
+            assert!(capabilities.requests.iter().any(|request| request == name), "{name}");
+        }
+
+        let json = serde_json::to_value(&capabilities).unwrap();
+        assert_eq!(json["features"]["web_tabs"], cfg!(target_os = "macos"));
+    }
+
+    #[test]
+    fn ipc_request_list_json() {
+        let json = serde_json::to_value(IpcRequestList::default()).unwrap();
+        assert_eq!(json["type"], "request_list");
+
+        let requests = json["requests"].as_array().unwrap();
+        assert_eq!(requests.len(), ipc_request_help().len());
+        let ping = serde_json::json!({ "name": "ping", "summary": "Health check (pong)." });
+        assert_eq!(requests[0], ping);
+        assert!(requests.iter().all(|request| request.as_object().unwrap().len() == 2));
+    }
+
+    #[test]
+    fn ipc_tab_state_busy_serialization() {
+        let tab = IpcTabState {
+            tab_id: IpcTabId { index: 1, generation: 0 },
+            group_id: 1,
+            index: 0,
+            is_active: false,
+            title: String::from("make"),
+            custom_title: None,
+            program_name: String::from("make"),
+            kind: IpcTabKind::Terminal,
+            activity: None,
+            load_progress: None,
+            busy: true,
+            foreground_program: Some(String::from("make")),
+            zoom: None,
+            scroll: None,
+            cwd: None,
+            inspector_visible: None,
+        };
+
+        let reply = SocketReply::TabState { tab: tab.clone() };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert!(json.contains(r#""busy":true,"foreground_program":"make""#));
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+
+        // Idle tabs have no foreground program, and older replies default to idle.
+        let idle = IpcTabState { busy: false, foreground_program: None, ..tab };
+        let json = serde_json::to_string(&idle).unwrap();
+        assert!(json.contains(r#""busy":false"#) && !json.contains("foreground_program"));
+        let json = json.replace(r#","busy":false"#, "");
+        assert_eq!(serde_json::from_str::<IpcTabState>(&json).unwrap(), idle);
+    }
+
+    #[test]
+    fn ipc_tab_state_load_progress_serialization() {
+        let mut tab = IpcTabState {
+            tab_id: IpcTabId { index: 1, generation: 0 },
+            group_id: 1,
+            index: 0,
+            is_active: true,
+            title: String::from("Example"),
+            custom_title: None,
+            program_name: String::new(),
+            kind: IpcTabKind::Web { url: String::from("https://example.com/") },
+            activity: None,
+            load_progress: Some(0.5),
+            busy: false,
+            foreground_program: None,
+            zoom: None,
+            scroll: None,
+            cwd: None,
+            inspector_visible: None,
+        };
+
+        let reply = SocketReply::TabState { tab: tab.clone() };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert!(json.contains(r#""load_progress":0.5"#));
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+
+        // Tabs without a load in flight omit the field.
+        tab.load_progress = None;
+        let reply = SocketReply::TabState { tab };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert!(!json.contains("load_progress"));
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+    }
+
+    #[test]
+    fn ipc_tab_state_web_page_serialization() {
+        let tab = IpcTabState {
+            tab_id: IpcTabId { index: 2, generation: 0 },
+            group_id: 1,
+            index: 1,
+            is_active: false,
+            title: String::from("Example Domain"),
+            custom_title: None,
+            program_name: String::new(),
+            kind: IpcTabKind::Web { url: String::from("https://example.com/") },
+            activity: None,
+            load_progress: None,
+            busy: false,
+            foreground_program: None,
+            zoom: Some(1.25),
+            scroll: Some(IpcScrollPosition { x: 0., y: 480. }),
+            cwd: None,
+            inspector_visible: Some(true),
+        };
+
+        let reply = SocketReply::TabState { tab };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert!(json.contains(r#""url":"https://example.com/""#));
+        assert!(json.contains(r#""zoom":1.25,"scroll":{"x":0.0,"y":480.0}"#));
+        assert!(json.contains(r#""inspector_visible":true"#));
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+    }
+
+    #[test]
+    fn ipc_tab_state_terminal_omits_web_fields() {
+        let tab = IpcTabState {
+            tab_id: IpcTabId { index: 1, generation: 0 },
+            group_id: 1,
+            index: 0,
+            is_active: true,
+            title: String::from("zsh"),
+            custom_title: None,
+            program_name: String::from("zsh"),
+            kind: IpcTabKind::Terminal,
+            activity: None,
+            load_progress: None,
+            busy: false,
+            foreground_program: None,
+            zoom: None,
+            scroll: None,
+            cwd: Some(PathBuf::from("/home/user/src")),
+            inspector_visible: None,
+        };
+
+        let reply = SocketReply::TabState { tab };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert!(json.contains(r#""cwd":"/home/user/src""#));
+        assert!(!json.contains("zoom") && !json.contains("scroll"));
+        assert!(!json.contains("inspector_visible"));
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+    }
+
+    #[test]
+    fn ipc_set_web_headers_serialization() {
+        let request = IpcRequest::SetWebHeaders {
+            tab_id: Some(IpcTabId { index: 1, generation: 1 }),
+            user_agent: Some(String::from("Tools/1.0")),
+            headers: Some(HashMap::from([(String::from("X-Team"), String::from("tools"))])),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"set_web_headers","tab_id":{"index":1,"generation":1},"user_agent":"Tools/1.0","headers":{"X-Team":"tools"}}"#
+        );
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let request: IpcRequest =
+            serde_json::from_str(r#"{"type":"set_web_headers","user_agent":""}"#).unwrap();
+        assert_eq!(request, IpcRequest::SetWebHeaders {
+            tab_id: None,
+            user_agent: Some(String::new()),
+            headers: None,
+        });
+    }
+
+    #[test]
+    fn ipc_clear_web_data_serialization() {
+        let request = IpcRequest::ClearWebData {
+            tab_id: Some(IpcTabId { index: 1, generation: 1 }),
+            cookies: true,
+            cache: false,
+            storage: true,
+            since_seconds: Some(3600),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"clear_web_data","tab_id":{"index":1,"generation":1},"cookies":true,"cache":false,"storage":true,"since_seconds":3600}"#
+        );
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        // Without any fields, all data of all sites is removed.
+        let request: IpcRequest = serde_json::from_str(r#"{"type":"clear_web_data"}"#).unwrap();
+        assert_eq!(request, IpcRequest::ClearWebData {
+            tab_id: None,
+            cookies: false,
+            cache: false,
+            storage: false,
+            since_seconds: None,
+        });
+        assert_eq!(request.target_tab_id(), None);
+    }
+
+    #[test]
+    fn ipc_set_group_color_serialization() {
+        let color = Some(Rgb::new(0, 0x80, 0xff));
+        let request = IpcRequest::SetGroupColor { group_id: 2, color };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r##"{"type":"set_group_color","group_id":2,"color":"#0080ff"}"##);
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let json = r#"{"type":"set_group_color","group_id":2,"color":null}"#;
+        let request: IpcRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request, IpcRequest::SetGroupColor { group_id: 2, color: None });
+    }
+
+    #[test]
+    fn ipc_set_group_defaults() {
+        let mut ctx = MockContext::new(true);
+        let group_id = ctx.groups[0].id;
+        let cwd = Some(PathBuf::from("/srv/project"));
+        let homepage = Some(String::from("https://example.com/"));
+
+        let request =
+            IpcRequest::SetGroupDefaults { group_id, cwd: cwd.clone(), homepage: homepage.clone() };
+        assert_eq!(handle_request(&mut ctx, request).reply, reply_ok());
+        let SocketReply::TabList { groups } = handle_request(&mut ctx, IpcRequest::ListTabs).reply
+        else {
+            panic!("expected tab_list reply");
+        };
+        assert_eq!(groups[0].default_cwd, cwd);
+        assert_eq!(groups[0].homepage, homepage);
+
+        let request = IpcRequest::SetGroupDefaults { group_id: group_id + 1, cwd, homepage };
+        let SocketReply::Error { error } = handle_request(&mut ctx, request).reply else {
+            panic!("expected error reply");
+        };
+        assert_eq!(error.code, IpcErrorCode::NotFound);
+    }
+
+    #[test]
+    fn ipc_set_group_defaults_serialization() {
+        let request = IpcRequest::SetGroupDefaults {
+            group_id: 2,
+            cwd: Some(PathBuf::from("/srv/project")),
+            homepage: None,
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        let expected =
+            r#"{"type":"set_group_defaults","group_id":2,"cwd":"/srv/project","homepage":null}"#;
+        assert_eq!(json, expected);
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        // Omitted defaults are cleared.
+        let json = r#"{"type":"set_group_defaults","group_id":2}"#;
+        let request: IpcRequest = serde_json::from_str(json).unwrap();
+        let cleared = IpcRequest::SetGroupDefaults { group_id: 2, cwd: None, homepage: None };
+        assert_eq!(request, cleared);
+
+        // Groups without defaults keep their previous serialization.
+        let group = IpcTabGroup {
+            id: 1,
+            name: None,
+            color: None,
+            default_cwd: None,
+            homepage: None,
+            tabs: Vec::new(),
+        };
+        let json = serde_json::to_string(&group).unwrap();
+        assert_eq!(json, r#"{"id":1,"name":null,"tabs":[]}"#);
+    }
+
+    #[test]
+    fn send_input_escapes() {
+        let escapes = interpret_input_escapes(r"a\nb\tc\r\e[A\\\x03\u{1F600}\u{e9}").unwrap();
+        assert_eq!(escapes, "a\nb\tc\r\x1b[A\\\x03\u{1F600}\u{e9}");
+
+        // Text without escapes is unchanged.
+        assert_eq!(interpret_input_escapes("ls -la").unwrap(), "ls -la");
+
+        let invalid =
+            [r"\q", r"\x", r"\x0", r"\xzz", r"\xff", r"\u{}", r"\u{d800}", r"\u{41", "\\"];
+        for invalid in invalid {
+            let err = interpret_input_escapes(invalid).unwrap_err();
+            assert_eq!(err.code, IpcErrorCode::InvalidRequest, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn send_input_escapes_default_literal() {
+        let json = r#"{"type":"send_input","tab_id":null,"text":"\\n"}"#;
+        let request: IpcRequest = serde_json::from_str(json).unwrap();
+        let IpcRequest::SendInput { text, interpret_escapes, .. } = request else {
+            panic!("expected send_input request");
+        };
+        assert_eq!(text, r"\n");
+        assert!(!interpret_escapes);
+    }
+
+    #[test]
+    fn ipc_create_window_serialization() {
+        let mut options = WindowOptions::default();
+        options.window_kind =
+            WindowKind::Web { url: String::from("https://example.com"), private: false };
+        let request = IpcRequest::CreateWindow { options: Box::new(options) };
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.starts_with(r#"{"type":"create_window","options":{"#));
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let json = r#"{"type":"create_window","options":{"terminal_options":{"hold":false,"command":[]},"window_identity":{},"option":[]}}"#;
+        let IpcRequest::CreateWindow { options } = serde_json::from_str(json).unwrap() else {
+            panic!("expected create_window request");
+        };
+        assert_eq!(options.window_kind, WindowKind::Terminal);
+
+        let reply = SocketReply::WindowCreated { window_id: 4294967297 };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert_eq!(json, r#"{"type":"window_created","window_id":4294967297}"#);
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+    }
+
+    #[test]
+    fn ipc_list_windows_serialization() {
+        let request: IpcRequest = serde_json::from_str(r#"{"type":"list_windows"}"#).unwrap();
+        assert_eq!(request, IpcRequest::ListWindows);
+
+        // Daemons without any window reply with an empty list.
+        let reply = SocketReply::WindowList { windows: Vec::new() };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert_eq!(json, r#"{"type":"window_list","windows":[]}"#);
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+
+        let ctx = MockContext::new(true);
+        let reply = SocketReply::WindowList {
+            windows: vec![IpcWindow {
+                window_id: 4294967297,
+                title: String::from("Tabor"),
+                focused: true,
+                groups: ctx.list_tabs(Instant::now()),
+            }],
+        };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert!(json.starts_with(
+            r#"{"type":"window_list","windows":[{"window_id":4294967297,"title":"Tabor","focused":true,"groups":[{"#
+        ));
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+    }
+
+    #[test]
+    fn ipc_focus_window() {
+        let request: IpcRequest =
+            serde_json::from_str(r#"{"type":"focus_window","window_id":4294967297}"#).unwrap();
+        assert_eq!(request, IpcRequest::FocusWindow { window_id: 4294967297 });
+
+        let windows = [1, 4294967297];
+        assert_eq!(resolve_window_id(4294967297, windows), Ok(4294967297));
+        assert_eq!(resolve_window_id(1, windows), Ok(1));
+
+        for window_id in [0, 2, u64::MAX] {
+            let err = resolve_window_id(window_id, windows).unwrap_err();
+            assert_eq!(err.code, IpcErrorCode::NotFound);
+        }
+
+        let mut ctx = MockContext::new(true);
+        let response = handle_request(&mut ctx, IpcRequest::FocusWindow { window_id: 1 });
+        assert!(matches!(response.reply, SocketReply::Error { .. }));
+    }
+
+    #[test]
+    fn ipc_render_stats_round_trip() {
+        let json = r#"{"type":"get_render_stats","window_id":4294967297}"#;
+        let request: IpcRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request, IpcRequest::GetRenderStats { window_id: 4294967297 });
+
+        let reply = SocketReply::RenderStats {
+            stats: IpcRenderStats { last_frame_us: 1200, p99_us: 8300, frames: 120 },
+        };
+        let json = serde_json::to_string(&reply).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"render_stats","stats":{"last_frame_us":1200,"p99_us":8300,"frames":120}}"#
+        );
+        assert_eq!(serde_json::from_str::<SocketReply>(&json).unwrap(), reply);
+    }
+
+    #[test]
+    fn ipc_duplicate_tab() {
+        let request: IpcRequest = serde_json::from_str(r#"{"type":"duplicate_tab"}"#).unwrap();
+        assert_eq!(request, IpcRequest::DuplicateTab { tab_id: None });
+
+        let mut ctx = MockContext::new(true);
+        let terminal_id = ctx.active_tab_id().unwrap();
+        let url = String::from("https://example.com");
+        let web_id = ctx.add_tab(IpcTabKind::Web { url: url.clone() }, None, None).unwrap();
+
+        // Copies are placed right after the source tab.
+        let request = IpcRequest::DuplicateTab { tab_id: Some(terminal_id.into()) };
+        let response = handle_request(&mut ctx, request);
+        let SocketReply::TabCreated { tab_id } = response.reply else {
+            panic!("expected tab_created reply");
+        };
+        let terminal_copy = TabId::from(tab_id);
+        assert_eq!(ctx.groups[0].tabs, vec![terminal_id, terminal_copy, web_id]);
+        assert_eq!(ctx.tabs[&terminal_copy].kind, IpcTabKind::Terminal);
+
+        // Without a tab id the active tab is duplicated.
+        ctx.active = Some(web_id);
+        let response = handle_request(&mut ctx, IpcRequest::DuplicateTab { tab_id: None });
+        let SocketReply::TabCreated { tab_id } = response.reply else {
+            panic!("expected tab_created reply");
+        };
+        let web_copy = TabId::from(tab_id);
+        assert_eq!(ctx.groups[0].tabs, vec![terminal_id, terminal_copy, web_id, web_copy]);
+        assert_eq!(ctx.tabs[&web_copy].kind, IpcTabKind::Web { url });
+
+        let request = IpcRequest::DuplicateTab { tab_id: Some(TabId::new(99, 0).into()) };
+        let response = handle_request(&mut ctx, request);
+        assert!(matches!(response.reply, SocketReply::Error { .. }));
+    }
+
+    #[test]
+    fn ipc_select_tab_wrap() {
+        let mut ctx = MockContext::new(false);
+        let first = ctx.active.unwrap();
+        let _ = ctx.add_tab(IpcTabKind::Terminal, None, None).unwrap();
+        let last = ctx.add_tab(IpcTabKind::Terminal, None, None).unwrap();
+
+        let select = |ctx: &mut MockContext, selection| {
+            let response = handle_request(ctx, IpcRequest::SelectTab { selection });
+            assert!(matches!(response.reply, SocketReply::Ok));
+            ctx.active
+        };
+
+        assert_eq!(select(&mut ctx, TabSelection::Next { wrap: false }), Some(last));
+        assert_eq!(select(&mut ctx, TabSelection::Next { wrap: true }), Some(first));
+        assert_eq!(select(&mut ctx, TabSelection::Previous { wrap: false }), Some(first));
+        assert_eq!(select(&mut ctx, TabSelection::Previous { wrap: true }), Some(last));
+
+        // Selections without `wrap` keep cycling.
+        let json = r#"{"type":"select_tab","selection":{"type":"next"}}"#;
+        let parsed: IpcRequest = serde_json::from_str(json).unwrap();
+        let selection = TabSelection::Next { wrap: true };
+        assert_eq!(parsed, IpcRequest::SelectTab { selection });
+    }
+
+    #[test]
+    fn ipc_set_tab_title_template() {
+        let mut ctx = MockContext::new(true);
+        let tab_id = ctx.active.unwrap();
+
+        let request = IpcRequest::SetTabTitleTemplate {
+            tab_id: None,
+            template: Some(String::from("{cwd_basename}")),
+        };
+        let response = handle_request(&mut ctx, request.clone());
+        assert!(matches!(response.reply, SocketReply::Ok));
+        let template = ctx.tabs.get(&tab_id).unwrap().title_template.as_deref();
+        assert_eq!(template, Some("{cwd_basename}"));
+
+        let request = IpcRequest::SetTabTitleTemplate { tab_id: None, template: None };
+        let response = handle_request(&mut ctx, request);
+        assert!(matches!(response.reply, SocketReply::Ok));
+        assert_eq!(ctx.tabs.get(&tab_id).unwrap().title_template, None);
+
+        let json = r#"{"type":"set_tab_title_template","template":"{program}"}"#;
+        let parsed: IpcRequest = serde_json::from_str(json).unwrap();
+        let template = Some(String::from("{program}"));
+        assert_eq!(parsed, IpcRequest::SetTabTitleTemplate { tab_id: None, template });
+
+        let kind = IpcTabKind::Web { url: String::from("https://example.com/") };
+        let web_tab = ctx.add_tab(kind, None, None).unwrap();
+        let request = IpcRequest::SetTabTitleTemplate {
+            tab_id: Some(web_tab.into()),
+            template: Some(String::from("{program}")),
+        };
+        let response = handle_request(&mut ctx, request);
+        assert!(matches!(
+            response.reply,
+            SocketReply::Error { error: IpcError { code: IpcErrorCode::Unsupported, .. } }
+        ));
+    }
+
+    #[test]
+    fn ipc_restore_closed_tabs() {
+        let mut ctx = MockContext::new(true);
+        let kind = IpcTabKind::Web { url: String::from("https://example.com/") };
+        let first = ctx.add_tab(kind.clone(), None, None).unwrap();
+        let second = ctx.add_tab(IpcTabKind::Terminal, None, None).unwrap();
+        for tab_id in [first, second] {
+            let request = IpcRequest::CloseTab { tab_id: Some(tab_id.into()) };
+            assert!(matches!(handle_request(&mut ctx, request).reply, SocketReply::Ok));
+        }
+
+        let SocketReply::ClosedTabs { tabs } =
+            handle_request(&mut ctx, IpcRequest::ListClosedTabs).reply
+        else {
+            panic!("expected closed tabs");
+        };
+        let titles: Vec<_> = tabs.iter().map(|tab| tab.title.as_str()).collect();
+        assert_eq!(titles, ["tab-3", "tab-2"]);
+        assert_eq!(tabs[1].kind, kind);
+
+        // Requests without a count restore the most recent tab.
+        let json = r#"{"type":"restore_closed_tab"}"#;
+        let request: IpcRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request, IpcRequest::RestoreClosedTab { count: None });
+        assert!(matches!(handle_request(&mut ctx, request).reply, SocketReply::Ok));
+        assert_eq!(ctx.closed_tabs().len(), 1);
+        assert_eq!(ctx.tabs.len(), 2);
+
+        // Restoring more tabs than were closed restores all of them.
+        let request = IpcRequest::RestoreClosedTab { count: Some(5) };
+        assert!(matches!(handle_request(&mut ctx, request).reply, SocketReply::Ok));
+        assert!(ctx.closed_tabs().is_empty());
+        assert_eq!(ctx.tabs.len(), 3);
+    }
+
+    #[test]
+    fn ipc_create_tab_private_round_trip() {
+        let mut options = WindowOptions::default();
+        options.window_kind =
+            WindowKind::Web { url: String::from("https://example.com"), private: true };
+        let request = IpcRequest::CreateTab {
+            options: Box::new(options),
+            group_id: None,
+            group_name: None,
+            focus: true,
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains(r#""window_kind":{"kind":"web","url":"https://example.com","private":true}"#));
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let kind: WindowKind =
+            serde_json::from_str(r#"{"kind":"web","url":"https://example.com"}"#).unwrap();
+        assert!(!kind.is_private());
+    }
+
+    #[test]
+    fn ipc_create_tab_focus() {
+        let request = IpcRequest::CreateTab {
+            options: Box::default(),
+            group_id: None,
+            group_name: None,
+            focus: false,
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains(r#""focus":false"#));
+        assert_eq!(serde_json::from_str::<IpcRequest>(&json).unwrap(), request);
+
+        let json = json.replace(r#","focus":false"#, "");
+        let focused: IpcRequest = serde_json::from_str(&json).unwrap();
+        assert!(matches!(focused, IpcRequest::CreateTab { focus: true, .. }));
+
+        // Background tabs leave the active tab focused.
+        let mut ctx = MockContext::new(false);
+        let active = ctx.active_tab_id();
+        let response = handle_request(&mut ctx, request);
+        let SocketReply::TabCreated { tab_id } = response.reply else {
+            panic!("expected tab_created reply");
+        };
+        assert!(ctx.tabs.contains_key(&tab_id.into()));
+        assert_eq!(ctx.active_tab_id(), active);
+    }
+
+    #[test]
+    fn discover_live_sockets() {
+        let dir = tempfile::tempdir().unwrap();
+        let _first = UnixListener::bind(dir.path().join("Tabor-:0-1.sock")).unwrap();
+        let _second = UnixListener::bind(dir.path().join("Tabor-:0-2.sock")).unwrap();
+
+        // Sockets of other displays and unrelated files are ignored.
+        let _other_display = UnixListener::bind(dir.path().join("Tabor-:1-3.sock")).unwrap();
+        let _other_name = UnixListener::bind(dir.path().join("other-4.sock")).unwrap();
+        fs::write(dir.path().join("Tabor-:0-5.log"), "").unwrap();
+
+        let mut sockets = discover_sockets_in(dir.path(), "Tabor-:0");
+        sockets.sort();
+        let expected = [dir.path().join("Tabor-:0-1.sock"), dir.path().join("Tabor-:0-2.sock")];
+        assert_eq!(sockets, expected);
+
+        assert!(discover_sockets_in(&dir.path().join("missing"), "Tabor-:0").is_empty());
+    }
+
+    #[test]
+    fn discover_skips_stale_sockets() {
+        let dir = tempfile::tempdir().unwrap();
+        let live_path = dir.path().join("Tabor-:0-1.sock");
+        let _live = UnixListener::bind(&live_path).unwrap();
+
+        // Dropping the listener leaves a socket file refusing connections behind.
+        let stale_path = dir.path().join("Tabor-:0-2.sock");
+        drop(UnixListener::bind(&stale_path).unwrap());
+        assert!(stale_path.exists());
+
+        assert_eq!(discover_sockets_in(dir.path(), "Tabor-:0"), [live_path]);
+        assert!(!stale_path.exists());
+    }
 }
@@ -10,7 +10,7 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Instant;
-use std::{env, process};
+use std::{env, mem, process};
 
 use log::{Level, LevelFilter};
 use winit::event_loop::EventLoopProxy;
@@ -159,6 +159,57 @@ impl log::Log for Logger {
     fn flush(&self) {}
 }
 
+/// Logger keeping config errors and warnings instead of printing them.
+///
+/// Used to validate the configuration without creating a window.
+#[derive(Default)]
+pub struct ConfigErrorLogger {
+    errors: Mutex<Vec<String>>,
+    warnings: Mutex<Vec<String>>,
+}
+
+impl ConfigErrorLogger {
+    /// Install a new logger as the global logger.
+    pub fn install() -> Result<&'static Self, log::SetLoggerError> {
+        let logger: &'static Self = Box::leak(Box::default());
+        log::set_logger(logger)?;
+        log::set_max_level(LevelFilter::Warn);
+        Ok(logger)
+    }
+
+    /// Take all errors logged so far.
+    pub fn take_errors(&self) -> Vec<String> {
+        self.errors.lock().map(|mut errors| mem::take(&mut *errors)).unwrap_or_default()
+    }
+
+    /// Take all warnings logged so far.
+    pub fn take_warnings(&self) -> Vec<String> {
+        self.warnings.lock().map(|mut warnings| mem::take(&mut *warnings)).unwrap_or_default()
+    }
+}
+
+impl log::Log for ConfigErrorLogger {
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        metadata.target() == LOG_TARGET_CONFIG && metadata.level() <= Level::Warn
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let messages = match record.level() {
+            Level::Error => &self.errors,
+            _ => &self.warnings,
+        };
+        if let Ok(mut messages) = messages.lock() {
+            messages.push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
 fn create_log_message(record: &log::Record<'_>, target: &str, start: Instant) -> String {
     let runtime = start.elapsed();
     let secs = runtime.as_secs();
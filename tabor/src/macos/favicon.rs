@@ -1,22 +1,34 @@
+use std::collections::{HashSet, VecDeque};
+use std::env;
 use std::io::Read;
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Arc;
-use std::time::Duration;
 
-use crossfont::{BitmapBuffer, Metrics, RasterizedGlyph};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as Base64;
+use crossfont::{BitmapBuffer, GlyphKey, Metrics, RasterizedGlyph};
 use image::imageops::{self, FilterType};
-use image::{Rgba, RgbaImage};
+use image::{ImageFormat, Rgba, RgbaImage};
+use log::debug;
 use url::Url;
 
+use tabor_terminal::thread;
+
+use crate::config::web::FaviconConfig;
 use crate::display::SizeInfo;
 
 const MAX_FAVICON_BYTES: usize = 512 * 1024;
-const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
-const READ_TIMEOUT: Duration = Duration::from_secs(5);
-const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
-const FAVICON_SCALE: f32 = 2.0;
+const ICO_HEADER_LEN: usize = 6;
+const ICO_ENTRY_LEN: usize = 16;
 
 #[derive(Clone, Debug)]
 pub struct FaviconImage {
+    /// Decoded frames, more than one for ICO files bundling multiple sizes.
+    frames: Vec<FaviconFrame>,
+}
+
+#[derive(Clone, Debug)]
+struct FaviconFrame {
     width: u32,
     height: u32,
     rgba: Arc<[u8]>,
@@ -24,24 +36,48 @@ pub struct FaviconImage {
 
 impl FaviconImage {
     pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        // The generic loader only decodes a single ICO frame, so split them up first.
+        let frames: Vec<_> = ico_frames(bytes)
+            .into_iter()
+            .filter_map(|ico| image::load_from_memory_with_format(&ico, ImageFormat::Ico).ok())
+            .map(|image| FaviconFrame::new(image.to_rgba8()))
+            .collect();
+        if !frames.is_empty() {
+            return Some(Self { frames });
+        }
+
         let image = image::load_from_memory(bytes).ok()?;
-        let rgba = image.to_rgba8();
-        let (width, height) = rgba.dimensions();
-        Some(Self { width, height, rgba: Arc::from(rgba.into_raw()) })
+        Some(Self { frames: vec![FaviconFrame::new(image.to_rgba8())] })
+    }
+
+    /// Bytes of RGBA data held by all frames.
+    pub fn byte_size(&self) -> usize {
+        self.frames.iter().map(|frame| frame.rgba.len()).sum()
+    }
+
+    /// Smallest frame covering `size`, or the largest frame if none does.
+    fn frame_for_size(&self, size: u32) -> &FaviconFrame {
+        let covering = self.frames.iter().filter(|frame| frame.size() >= size);
+        covering
+            .min_by_key(|frame| frame.size())
+            .or_else(|| self.frames.iter().max_by_key(|frame| frame.size()))
+            .expect("favicon without frames")
     }
 
+    /// Rasterize the favicon at `icon_scale` times the smaller side of a cell.
     pub fn rasterized_glyph(
         &self,
         character: char,
         size_info: &SizeInfo,
         metrics: Metrics,
+        icon_scale: f32,
     ) -> RasterizedGlyph {
         let cell_width = size_info.cell_width().round().max(1.0) as i32;
         let cell_height = size_info.cell_height().round().max(1.0) as i32;
-        let base_size = cell_width.min(cell_height).max(1) as f32;
-        let icon_size = (base_size * FAVICON_SCALE).round().max(1.0) as u32;
+        let slot = FaviconSlot::new(cell_width, cell_height, icon_scale);
+        let icon_size = slot.icon_size;
 
-        let mut image = self.to_image();
+        let mut image = self.frame_for_size(icon_size).to_image();
         if image.width() != icon_size || image.height() != icon_size {
             image = resize_to_square(&image, icon_size);
         }
@@ -49,28 +85,253 @@ impl FaviconImage {
         let mut buffer = image.into_raw();
         premultiply_rgba(&mut buffer);
 
-        let slot_width = (cell_width as f32 * FAVICON_SCALE).round().max(1.0) as i32;
-        let offset_x = (slot_width - icon_size as i32).max(0) / 2;
-        let offset_y = (cell_height - icon_size as i32).max(0) / 2;
-        let top = cell_height - offset_y + metrics.descent.round() as i32;
+        let top = cell_height - slot.offset_y + metrics.descent.round() as i32;
 
         RasterizedGlyph {
             character,
             width: icon_size as i32,
             height: icon_size as i32,
             top,
-            left: offset_x,
+            left: slot.offset_x,
             advance: (cell_width, 0),
             buffer: BitmapBuffer::Rgba(buffer),
         }
     }
 
+}
+
+/// Size and position of a favicon within its cell, in pixels.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct FaviconSlot {
+    icon_size: u32,
+    offset_x: i32,
+    offset_y: i32,
+}
+
+impl FaviconSlot {
+    fn new(cell_width: i32, cell_height: i32, icon_scale: f32) -> Self {
+        let base_size = cell_width.min(cell_height).max(1) as f32;
+        let icon_size = (base_size * icon_scale).round().max(1.0) as u32;
+
+        let slot_width = (cell_width as f32 * icon_scale).round().max(1.0) as i32;
+        let offset_x = (slot_width - icon_size as i32).max(0) / 2;
+        let offset_y = (cell_height - icon_size as i32).max(0) / 2;
+
+        Self { icon_size, offset_x, offset_y }
+    }
+}
+
+/// Decoded favicons, evicting the least recently drawn ones past a byte budget.
+///
+/// Evicted favicons stay visible while their glyph is cached, they're only fetched again once
+/// the glyph has to be rasterized anew.
+#[derive(Debug)]
+pub struct FaviconCache {
+    budget: usize,
+
+    /// Bytes of all cached images.
+    used: usize,
+
+    /// Cached images by favicon id, least recently used first.
+    entries: VecDeque<(u64, Arc<FaviconImage>)>,
+}
+
+impl Default for FaviconCache {
+    fn default() -> Self {
+        Self { budget: usize::MAX, used: 0, entries: VecDeque::new() }
+    }
+}
+
+impl FaviconCache {
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.evict();
+    }
+
+    /// Add the image of favicon `id` as the most recently used one.
+    pub fn insert(&mut self, id: u64, image: Arc<FaviconImage>) {
+        self.remove(id);
+        self.used += image.byte_size();
+        self.entries.push_back((id, image));
+        self.evict();
+    }
+
+    /// Image of favicon `id`, marking it as the most recently used one.
+    pub fn get(&mut self, id: u64) -> Option<Arc<FaviconImage>> {
+        let index = self.entries.iter().position(|(entry_id, _)| *entry_id == id)?;
+        let entry = self.entries.remove(index)?;
+        let image = entry.1.clone();
+        self.entries.push_back(entry);
+        Some(image)
+    }
+
+    fn remove(&mut self, id: u64) {
+        if let Some(index) = self.entries.iter().position(|(entry_id, _)| *entry_id == id) {
+            if let Some((_, image)) = self.entries.remove(index) {
+                self.used -= image.byte_size();
+            }
+        }
+    }
+
+    /// Drop the least recently used images until the budget is met.
+    ///
+    /// The most recently used image is kept even if it exceeds the budget on its own.
+    fn evict(&mut self) {
+        while self.used > self.budget && self.entries.len() > 1 {
+            if let Some((id, image)) = self.entries.pop_front() {
+                debug!("Evicting favicon {id} from the favicon cache");
+                self.used -= image.byte_size();
+            }
+        }
+    }
+}
+
+type RasterizeJob = Box<dyn FnOnce() -> RasterizedGlyph + Send>;
+
+/// Rasterizes favicon glyphs on a background thread.
+///
+/// Finished glyphs are collected with [`FaviconRasterizer::drain`] and uploaded by the renderer
+/// on the next frame.
+pub struct FaviconRasterizer {
+    /// Glyphs queued for rasterization which haven't been drained yet.
+    pending: HashSet<GlyphKey>,
+
+    /// Job queue of the worker thread, spawned with the first request.
+    jobs: Option<Sender<(GlyphKey, RasterizeJob)>>,
+
+    ready_tx: Sender<(GlyphKey, RasterizedGlyph)>,
+    ready_rx: Receiver<(GlyphKey, RasterizedGlyph)>,
+}
+
+impl Default for FaviconRasterizer {
+    fn default() -> Self {
+        let (ready_tx, ready_rx) = mpsc::channel();
+        Self { pending: HashSet::new(), jobs: None, ready_tx, ready_rx }
+    }
+}
+
+impl FaviconRasterizer {
+    /// Queue rasterization of a favicon glyph.
+    pub fn request(
+        &mut self,
+        key: GlyphKey,
+        image: Arc<FaviconImage>,
+        size_info: SizeInfo,
+        metrics: Metrics,
+        icon_scale: f32,
+    ) {
+        self.queue(key, move || {
+            image.rasterized_glyph(key.character, &size_info, metrics, icon_scale)
+        });
+    }
+
+    /// Queue a rasterization job, unless the glyph is already pending.
+    fn queue<F>(&mut self, key: GlyphKey, rasterize: F)
+    where
+        F: FnOnce() -> RasterizedGlyph + Send + 'static,
+    {
+        if !self.pending.insert(key) {
+            return;
+        }
+
+        let ready_tx = &self.ready_tx;
+        let jobs = self.jobs.get_or_insert_with(|| {
+            let (jobs_tx, jobs_rx) = mpsc::channel::<(GlyphKey, RasterizeJob)>();
+            let ready_tx = ready_tx.clone();
+            thread::spawn_named("favicon rasterizer", move || {
+                for (key, rasterize) in jobs_rx {
+                    if ready_tx.send((key, rasterize())).is_err() {
+                        break;
+                    }
+                }
+            });
+            jobs_tx
+        });
+
+        if jobs.send((key, Box::new(rasterize))).is_err() {
+            self.pending.remove(&key);
+        }
+    }
+
+    /// Take all glyphs which finished rasterization.
+    pub fn drain(&mut self) -> Vec<(GlyphKey, RasterizedGlyph)> {
+        let glyphs: Vec<_> = self.ready_rx.try_iter().collect();
+        for (key, _) in &glyphs {
+            self.pending.remove(key);
+        }
+        glyphs
+    }
+
+    /// Whether the glyph is still being rasterized.
+    pub fn is_pending(&self, key: &GlyphKey) -> bool {
+        self.pending.contains(key)
+    }
+
+    /// Whether any glyph is still being rasterized.
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+}
+
+impl FaviconFrame {
+    fn new(image: RgbaImage) -> Self {
+        let (width, height) = image.dimensions();
+        Self { width, height, rgba: Arc::from(image.into_raw()) }
+    }
+
+    fn size(&self) -> u32 {
+        self.width.max(self.height)
+    }
+
     fn to_image(&self) -> RgbaImage {
         RgbaImage::from_raw(self.width, self.height, self.rgba.to_vec())
             .unwrap_or_else(|| RgbaImage::from_pixel(1, 1, Rgba([0, 0, 0, 0])))
     }
 }
 
+/// Split an ICO file into single-frame ICO files.
+///
+/// Returns nothing if `bytes` is not an ICO file.
+fn ico_frames(bytes: &[u8]) -> Vec<Vec<u8>> {
+    if bytes.len() < ICO_HEADER_LEN || bytes[..4] != [0, 0, 1, 0] {
+        return Vec::new();
+    }
+
+    let count = u16::from_le_bytes([bytes[4], bytes[5]]) as usize;
+    (0..count)
+        .filter_map(|index| {
+            let start = ICO_HEADER_LEN + index * ICO_ENTRY_LEN;
+            let entry = bytes.get(start..start + ICO_ENTRY_LEN)?;
+            let len = u32::from_le_bytes(entry[8..12].try_into().ok()?) as usize;
+            let offset = u32::from_le_bytes(entry[12..16].try_into().ok()?) as usize;
+            let data = bytes.get(offset..offset.checked_add(len)?)?;
+            Some(build_ico(&[(entry, data)]))
+        })
+        .collect()
+}
+
+/// Assemble an ICO file from directory entries and their image data.
+///
+/// Size and offset of the entries are replaced to match the new layout.
+fn build_ico(frames: &[(&[u8], &[u8])]) -> Vec<u8> {
+    let mut ico = vec![0, 0, 1, 0];
+    ico.extend_from_slice(&(frames.len() as u16).to_le_bytes());
+
+    let mut offset = ICO_HEADER_LEN + frames.len() * ICO_ENTRY_LEN;
+    for (entry, data) in frames {
+        ico.extend_from_slice(&entry[..8]);
+        ico.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        ico.extend_from_slice(&(offset as u32).to_le_bytes());
+        offset += data.len();
+    }
+
+    for (_, data) in frames {
+        ico.extend_from_slice(data);
+    }
+
+    ico
+}
+
 pub fn resolve_favicon_url(page_url: &str, icon_hint: &str) -> Option<String> {
     let hint = icon_hint.trim();
     let hint = hint.trim_matches('"');
@@ -88,25 +349,29 @@ pub fn resolve_favicon_url(page_url: &str, icon_hint: &str) -> Option<String> {
     };
 
     if let Some(url) = icon_url {
-        if url.scheme() != "data" {
-            return Some(url.to_string());
-        }
+        return Some(url.to_string());
     }
 
     let base = Url::parse(page_url).ok()?;
     base.join("/favicon.ico").ok().map(|url| url.to_string())
 }
 
-pub fn fetch_favicon(url: &str) -> Option<FaviconImage> {
+pub fn fetch_favicon(url: &str, config: &FaviconConfig) -> Option<FaviconImage> {
     if url.starts_with("data:") {
-        return None;
+        return decode_data_url_favicon(url);
     }
 
-    let agent = ureq::AgentBuilder::new()
-        .timeout_connect(CONNECT_TIMEOUT)
-        .timeout_read(READ_TIMEOUT)
-        .timeout_write(WRITE_TIMEOUT)
-        .build();
+    let mut agent = ureq::AgentBuilder::new()
+        .timeout_connect(config.connect_timeout())
+        .timeout_read(config.read_timeout())
+        .timeout_write(config.write_timeout());
+    if let Some(proxy) = config.proxy_for(url, |name| env::var(name).ok()) {
+        match ureq::Proxy::new(&proxy) {
+            Ok(proxy) => agent = agent.proxy(proxy),
+            Err(err) => debug!("Ignoring invalid favicon proxy {proxy:?}: {err}"),
+        }
+    }
+    let agent = agent.build();
 
     let response = agent.get(url).call().ok()?;
     if response.status() >= 400 {
@@ -123,6 +388,51 @@ pub fn fetch_favicon(url: &str) -> Option<FaviconImage> {
     FaviconImage::from_bytes(&bytes)
 }
 
+/// Decode an inline `data:image/...` favicon.
+pub fn decode_data_url_favicon(url: &str) -> Option<FaviconImage> {
+    let (header, payload) = url.strip_prefix("data:")?.split_once(',')?;
+    let mut params = header.split(';');
+    let mime = params.next()?.trim();
+    if !mime.to_ascii_lowercase().starts_with("image/") {
+        return None;
+    }
+
+    let bytes = if params.any(|param| param.trim().eq_ignore_ascii_case("base64")) {
+        let payload: String = percent_decode(payload)?
+            .into_iter()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .map(char::from)
+            .collect();
+        Base64.decode(payload).ok()?
+    } else {
+        percent_decode(payload)?
+    };
+
+    if bytes.len() > MAX_FAVICON_BYTES {
+        return None;
+    }
+
+    FaviconImage::from_bytes(&bytes)
+}
+
+/// Decode `%XX` escapes, failing on truncated or invalid escapes.
+fn percent_decode(input: &str) -> Option<Vec<u8>> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(index + 1..index + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    Some(decoded)
+}
+
 fn resize_to_square(image: &RgbaImage, size: u32) -> RgbaImage {
     let (width, height) = image.dimensions();
     if width == 0 || height == 0 || size == 0 {
@@ -159,3 +469,193 @@ fn premultiply_rgba(buffer: &mut [u8]) {
         chunk[2] = b as u8;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::time::{Duration, Instant};
+
+    use crossfont::{FontKey, Size};
+
+    use super::*;
+
+    fn png_frame(size: u32) -> Vec<u8> {
+        let image = RgbaImage::from_pixel(size, size, Rgba([255, 0, 0, 255]));
+        let mut bytes = Vec::new();
+        image.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png).unwrap();
+        bytes
+    }
+
+    fn ico_entry(size: u32) -> [u8; ICO_ENTRY_LEN] {
+        let mut entry = [0; ICO_ENTRY_LEN];
+        entry[0] = size as u8;
+        entry[1] = size as u8;
+        entry[4] = 1;
+        entry[6] = 32;
+        entry
+    }
+
+    #[test]
+    fn ico_selects_best_frame() {
+        let (small, large) = (png_frame(16), png_frame(48));
+        let (small_entry, large_entry) = (ico_entry(16), ico_entry(48));
+        let ico = build_ico(&[(&small_entry[..], &small[..]), (&large_entry[..], &large[..])]);
+
+        let image = FaviconImage::from_bytes(&ico).unwrap();
+        assert_eq!(image.frames.len(), 2);
+        assert_eq!(image.frame_for_size(32).width, 48);
+        assert_eq!(image.frame_for_size(96).width, 48);
+        assert_eq!(image.frame_for_size(16).width, 16);
+        assert_eq!(image.frame_for_size(8).width, 16);
+    }
+
+    #[test]
+    fn data_url_favicon() {
+        let url = format!("data:image/png;base64,{}", Base64.encode(png_frame(16)));
+        let image = decode_data_url_favicon(&url).unwrap();
+        assert_eq!(image.frame_for_size(16).width, 16);
+
+        let icon_url = resolve_favicon_url("https://example.com/", &url);
+        assert_eq!(icon_url.as_deref(), Some(url.as_str()));
+
+        assert!(decode_data_url_favicon("data:image/png;base64,not*base64").is_none());
+        assert!(decode_data_url_favicon("data:image/png;base64").is_none());
+        assert!(decode_data_url_favicon("data:image/png,%zz").is_none());
+        assert!(decode_data_url_favicon("data:text/plain;base64,aGk=").is_none());
+    }
+
+    #[test]
+    fn slot_scales_with_icon_scale() {
+        let slot = FaviconSlot::new(8, 24, 2.);
+        assert_eq!(slot, FaviconSlot { icon_size: 16, offset_x: 0, offset_y: 4 });
+
+        let slot = FaviconSlot::new(8, 24, 3.);
+        assert_eq!(slot, FaviconSlot { icon_size: 24, offset_x: 0, offset_y: 0 });
+
+        // Icons are centered vertically in rows taller than the icon.
+        let slot = FaviconSlot::new(8, 24, 1.);
+        assert_eq!(slot, FaviconSlot { icon_size: 8, offset_x: 0, offset_y: 8 });
+
+        // Icons are centered horizontally in slots of cells wider than they are tall.
+        let slot = FaviconSlot::new(12, 10, 2.);
+        assert_eq!(slot, FaviconSlot { icon_size: 20, offset_x: 2, offset_y: 0 });
+
+        // Icons never shrink below a single pixel.
+        assert_eq!(FaviconSlot::new(8, 24, 0.).icon_size, 1);
+    }
+
+    #[test]
+    fn png_is_single_frame() {
+        let image = FaviconImage::from_bytes(&png_frame(32)).unwrap();
+        assert_eq!(image.frames.len(), 1);
+        assert_eq!(image.frame_for_size(64).width, 32);
+    }
+
+    fn square_image(size: u32) -> Arc<FaviconImage> {
+        let frame = FaviconFrame::new(RgbaImage::new(size, size));
+        Arc::new(FaviconImage { frames: vec![frame] })
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used() {
+        // Images of 16x16, 8x8 and 4x4 pixels, with 4 bytes per pixel.
+        let (large, medium, small) = (square_image(16), square_image(8), square_image(4));
+        assert_eq!(large.byte_size(), 1024);
+
+        let mut cache = FaviconCache::default();
+        cache.set_budget(1024 + 256);
+        cache.insert(0, large.clone());
+        cache.insert(1, medium.clone());
+        assert!(cache.get(0).is_some());
+        assert!(cache.get(1).is_some());
+
+        // The large image was drawn least recently, so it makes room for the small one.
+        cache.insert(2, small.clone());
+        assert!(cache.get(0).is_none());
+        assert_eq!(cache.used, 256 + 64);
+
+        // Drawing an image protects it from the next eviction.
+        cache.insert(3, medium.clone());
+        cache.get(1);
+        cache.insert(0, large.clone());
+        assert!(cache.get(2).is_none());
+        assert!(cache.get(3).is_none());
+        assert!(cache.get(1).is_some());
+        assert!(cache.get(0).is_some());
+        assert_eq!(cache.used, 1024 + 256);
+
+        // Shrinking the budget evicts right away, but keeps the latest image.
+        cache.insert(2, small);
+        cache.set_budget(512);
+        assert!(cache.get(0).is_none());
+        assert!(cache.get(2).is_some());
+        cache.set_budget(0);
+        assert!(cache.get(2).is_some());
+        assert_eq!(cache.used, 64);
+
+        // Replacing an image doesn't count it twice.
+        cache.set_budget(usize::MAX);
+        cache.insert(1, medium.clone());
+        cache.insert(1, medium);
+        assert_eq!(cache.used, 64 + 256);
+    }
+
+    fn glyph_key(character: char) -> GlyphKey {
+        GlyphKey { font_key: FontKey::next(), size: Size::new(12.), character }
+    }
+
+    fn glyph(character: char) -> RasterizedGlyph {
+        RasterizedGlyph { character, ..RasterizedGlyph::default() }
+    }
+
+    fn drain_until(rasterizer: &mut FaviconRasterizer, count: usize) -> Vec<GlyphKey> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut keys = Vec::new();
+        while keys.len() < count && Instant::now() < deadline {
+            keys.extend(rasterizer.drain().into_iter().map(|(key, _)| key));
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        keys
+    }
+
+    #[test]
+    fn rasterizer_drains_finished_glyphs() {
+        let mut rasterizer = FaviconRasterizer::default();
+        assert!(!rasterizer.has_pending());
+        assert!(rasterizer.drain().is_empty());
+
+        let (first, second) = (glyph_key('\u{e000}'), glyph_key('\u{e001}'));
+        rasterizer.queue(first, || glyph('\u{e000}'));
+        rasterizer.queue(second, || glyph('\u{e001}'));
+        assert!(rasterizer.is_pending(&first));
+        assert!(rasterizer.is_pending(&second));
+
+        assert_eq!(drain_until(&mut rasterizer, 2), vec![first, second]);
+        assert!(!rasterizer.is_pending(&first));
+        assert!(!rasterizer.has_pending());
+    }
+
+    #[test]
+    fn rasterizer_skips_pending_glyphs() {
+        let mut rasterizer = FaviconRasterizer::default();
+        let key = glyph_key('\u{e000}');
+
+        // Block the worker until both requests are queued.
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        rasterizer.queue(key, move || {
+            let _ = release_rx.recv();
+            glyph('\u{e000}')
+        });
+        rasterizer.queue(key, || panic!("pending glyph rasterized twice"));
+        assert!(rasterizer.drain().is_empty());
+        assert!(rasterizer.is_pending(&key));
+
+        release_tx.send(()).unwrap();
+        assert_eq!(drain_until(&mut rasterizer, 1), vec![key]);
+        assert!(!rasterizer.has_pending());
+
+        // Glyphs can be queued again once drained.
+        rasterizer.queue(key, || glyph('\u{e000}'));
+        assert_eq!(drain_until(&mut rasterizer, 1), vec![key]);
+    }
+}
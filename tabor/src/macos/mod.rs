@@ -19,6 +19,7 @@ pub mod proc;
 pub mod remote_inspector;
 pub mod web_commands;
 pub mod web_cursor;
+pub mod web_hibernation;
 pub mod webview;
 
 pub(crate) use open_documents::register_open_documents_handler;
@@ -1,11 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::mem;
 use std::time::Instant;
+
+use log::warn;
+use serde::{Deserialize, Serialize};
 use winit::dpi::PhysicalPosition;
 use winit::window::CursorIcon;
 
-pub const WEB_SCROLL_STEP: f64 = 48.0;
+use crate::config::web::{WebConfig, WebKeyAction};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Most link URLs copied at once by `yl`.
+pub const MAX_YANKED_LINKS: usize = 500;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum WebKey {
     Escape,
     Enter,
@@ -25,6 +32,7 @@ pub(crate) enum WebMode {
     Insert,
     Visual,
     VisualLine,
+    Caret,
     Hint,
     MarkSet,
     MarkJump,
@@ -34,13 +42,96 @@ pub(crate) enum WebMode {
 pub enum WebHintAction {
     Open,
     OpenNewTab,
+    OpenBackgroundTab,
     CopyLink,
+    /// Queue links until hint mode is left, then open them all in background tabs.
+    Queue,
+}
+
+/// Kind of the page field edited in insert mode.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebFieldKind {
+    /// An `<input>`, where Enter submits its form.
+    SingleLine,
+    /// A `<textarea>` or editable element, where Enter starts a new paragraph.
+    #[default]
+    MultiLine,
+}
+
+/// Viewport position of the element scrolled into view by `zz`, `zt` and `zb`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebScrollAnchor {
+    Center,
+    Top,
+    Bottom,
+}
+
+impl WebScrollAnchor {
+    /// Value of the `block` option of `Element.scrollIntoView`.
+    pub fn block(self) -> &'static str {
+        match self {
+            Self::Center => "center",
+            Self::Top => "start",
+            Self::Bottom => "end",
+        }
+    }
+}
+
+/// Page load which failed before or while the page was committed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WebNavigationError {
+    /// `NSError` code, like `NSURLErrorServerCertificateUntrusted`.
+    pub code: i64,
+    /// Localized description of the failure.
+    pub message: String,
 }
 
 #[derive(Clone, Debug)]
 struct WebHintState {
     action: WebHintAction,
     keys: String,
+    /// URL of the only link whose label starts with the typed keys.
+    preview: Option<String>,
+    /// Labels queued by [`WebHintAction::Queue`], opened together when hint mode is left.
+    selected: Vec<String>,
+}
+
+/// Link of the page matching the typed keys of hint mode.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebHintMatch {
+    pub url: String,
+    /// Whether the keys are the full label of the link, rather than a unique prefix.
+    pub complete: bool,
+}
+
+/// Key of a `web.keybindings` entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum WebBindingKey {
+    Char(char),
+    Named(WebKey),
+}
+
+impl WebBindingKey {
+    fn parse(key: &str) -> Option<Self> {
+        if let Some(ch) = single_char(key) {
+            return Some(Self::Char(ch));
+        }
+
+        let named = match key.to_ascii_lowercase().as_str() {
+            "space" => return Some(Self::Char(' ')),
+            "enter" => WebKey::Enter,
+            "backspace" => WebKey::Backspace,
+            "delete" => WebKey::Delete,
+            "tab" => WebKey::Tab,
+            "arrowleft" | "left" => WebKey::ArrowLeft,
+            "arrowright" | "right" => WebKey::ArrowRight,
+            "arrowup" | "up" => WebKey::ArrowUp,
+            "arrowdown" | "down" => WebKey::ArrowDown,
+            _ => return None,
+        };
+        Some(Self::Named(named))
+    }
 }
 
 #[derive(Default)]
@@ -48,7 +139,10 @@ struct WebPending {
     g: bool,
     z: bool,
     y: bool,
+    semicolon: bool,
     bracket: Option<char>,
+    /// Count typed before a command, like the `50` of `50%`.
+    count: Option<u32>,
 }
 
 #[derive(Clone, Debug)]
@@ -68,9 +162,12 @@ struct WebPendingScroll {
 pub struct WebCommandState {
     mode: WebMode,
     pending: WebPending,
+    field_kind: WebFieldKind,
     hint: Option<WebHintState>,
     last_find: Option<String>,
     last_find_backward: bool,
+    find_case_sensitive: bool,
+    find_regex: bool,
     marks: HashMap<char, WebMark>,
     pending_scroll: Option<WebPendingScroll>,
     help_visible: bool,
@@ -79,13 +176,66 @@ pub struct WebCommandState {
     last_cursor_pos: Option<PhysicalPosition<f64>>,
     cursor_bootstrapped: bool,
     last_cursor_request: Option<Instant>,
+    scroll_step: f64,
+    half_page_fraction: f64,
+    momentum: bool,
+    keybindings: HashMap<WebBindingKey, WebKeyAction>,
 }
 
 impl WebCommandState {
+    pub fn new(config: &WebConfig) -> Self {
+        let mut keybindings = HashMap::new();
+        for (key, action) in &config.keybindings {
+            match WebBindingKey::parse(key) {
+                Some(key) => {
+                    keybindings.insert(key, *action);
+                },
+                None => warn!("Ignoring web keybinding for unknown key {key:?}"),
+            }
+        }
+
+        Self {
+            mode: WebMode::Normal,
+            pending: WebPending::default(),
+            field_kind: WebFieldKind::default(),
+            hint: None,
+            last_find: None,
+            last_find_backward: false,
+            find_case_sensitive: false,
+            find_regex: false,
+            marks: HashMap::default(),
+            pending_scroll: None,
+            help_visible: false,
+            cursor_pending: false,
+            last_cursor: None,
+            last_cursor_pos: None,
+            cursor_bootstrapped: false,
+            last_cursor_request: None,
+            scroll_step: config.scroll_step,
+            half_page_fraction: config.half_page_fraction,
+            momentum: config.momentum,
+            keybindings,
+        }
+    }
+
     fn reset_pending(&mut self) {
         self.pending = WebPending::default();
     }
 
+    /// Action bound to a key, unless the key completes a pending command like `gs`.
+    fn binding(&self, key: WebKey, text: &str) -> Option<WebKeyAction> {
+        let pending = &self.pending;
+        if pending.g || pending.z || pending.y || pending.semicolon || pending.bracket.is_some() {
+            return None;
+        }
+
+        let key = match key {
+            WebKey::Other => WebBindingKey::Char(single_char(text)?),
+            key => WebBindingKey::Named(key),
+        };
+        self.keybindings.get(&key).copied()
+    }
+
     fn set_mode(&mut self, mode: WebMode) {
         self.mode = mode;
         if mode != WebMode::Hint {
@@ -106,16 +256,32 @@ impl WebCommandState {
             WebMode::Insert => "INSERT",
             WebMode::Visual => "VISUAL",
             WebMode::VisualLine => "VISUAL LINE",
+            WebMode::Caret => "CARET",
             WebMode::Hint => "HINT",
             WebMode::MarkSet => "MARK SET",
             WebMode::MarkJump => "MARK JUMP",
         }
     }
 
+    /// URL of the link the typed hint keys narrowed down to.
+    pub(crate) fn hint_preview(&self) -> Option<&str> {
+        self.hint.as_ref()?.preview.as_deref()
+    }
+
     pub(crate) fn set_mark(&mut self, name: char, url: String, scroll_x: f64, scroll_y: f64) {
         self.marks.insert(name, WebMark { url, scroll_x, scroll_y });
     }
 
+    /// Scroll to a position once `url` has been loaded.
+    pub(crate) fn set_pending_scroll(&mut self, url: String, scroll_x: f64, scroll_y: f64) {
+        self.pending_scroll = Some(WebPendingScroll { url, scroll_x, scroll_y });
+    }
+
+    /// Drop the scroll waiting for a page load, so it can't override a newer scroll.
+    pub(crate) fn clear_pending_scroll(&mut self) {
+        self.pending_scroll = None;
+    }
+
     pub(crate) fn take_pending_scroll(&mut self, url: &str) -> Option<(f64, f64)> {
         let pending = self.pending_scroll.take()?;
         if pending.url == url {
@@ -173,73 +339,89 @@ impl WebCommandState {
 
 impl Default for WebCommandState {
     fn default() -> Self {
-        Self {
-            mode: WebMode::Normal,
-            pending: WebPending::default(),
-            hint: None,
-            last_find: None,
-            last_find_backward: false,
-            marks: HashMap::default(),
-            pending_scroll: None,
-            help_visible: false,
-            cursor_pending: false,
-            last_cursor: None,
-            last_cursor_pos: None,
-            cursor_bootstrapped: false,
-            last_cursor_request: None,
-        }
+        Self::new(&WebConfig::default())
     }
 }
 
 pub trait WebActions {
     fn scroll_by(&mut self, dx: f64, dy: f64);
-    fn scroll_half_page(&mut self, down: bool);
+    /// Scroll by a fraction of the viewport height, animated if `smooth` is set.
+    fn scroll_half_page(&mut self, down: bool, fraction: f64, smooth: bool);
     fn scroll_top(&mut self);
     fn scroll_bottom(&mut self);
     fn scroll_far_left(&mut self);
     fn scroll_far_right(&mut self);
+    fn scroll_relative(&mut self, anchor: WebScrollAnchor);
     fn scroll_to(&mut self, x: f64, y: f64);
+    fn scroll_to_percent(&mut self, pct: f64);
 
     fn go_back(&mut self);
     fn go_forward(&mut self);
 
     fn open_command_bar(&mut self, input: &str);
+    fn open_history_search(&mut self);
     fn start_find_prompt(&mut self);
-    fn find(&mut self, query: &str, backwards: bool);
+    fn find(&mut self, query: &str, backwards: bool, case_sensitive: bool, regex: bool);
+    fn clear_find(&mut self);
 
     fn hints_start(&mut self, action: WebHintAction);
     fn hints_update(&mut self, keys: &str, action: WebHintAction);
     fn hints_cancel(&mut self);
+    /// Run `action` for the links labeled `keys` and remove the hints.
+    fn hints_open_multiple(&mut self, keys: &[String], action: WebHintAction);
+    /// Redraw the link preview of hint mode.
+    fn hint_preview_changed(&mut self);
 
     fn copy_selection(&mut self);
     fn clear_selection(&mut self);
     fn start_visual_selection(&mut self);
     fn visual_move(&mut self, direction: &str, granularity: &str);
+    fn select_all(&mut self);
+    fn select_element(&mut self);
 
     fn focus_input(&mut self);
+    /// Focus the `index`th input of the page, starting at 1.
+    fn focus_input_index(&mut self, index: u32);
+    /// Focus the next input of the page in tab order, without entering insert mode.
+    fn focus_next_field(&mut self);
+    /// Focus the previous input of the page in tab order, without entering insert mode.
+    fn focus_prev_field(&mut self);
     fn blur_active_element(&mut self);
 
     fn insert_text(&mut self, text: &str);
     fn delete_backward(&mut self);
     fn delete_forward(&mut self);
     fn insert_paragraph(&mut self);
+    /// Submit the form of the focused input.
+    fn submit_form(&mut self);
     fn insert_tab(&mut self);
     fn caret_move(&mut self, direction: &str, granularity: &str);
+    fn start_caret(&mut self);
+    fn stop_caret(&mut self);
 
     fn view_source(&mut self);
+    fn toggle_mute(&mut self);
     fn follow_rel(&mut self, rel: &str);
     fn copy_url(&mut self);
+    fn copy_title(&mut self);
+    fn copy_markdown_link(&mut self);
+    fn copy_domain(&mut self);
+    fn copy_visible_links(&mut self);
+    fn open_external(&mut self);
     fn open_clipboard(&mut self, new_tab: bool);
     fn up_url(&mut self, root: bool);
 
     fn new_tab(&mut self);
     fn close_tab(&mut self);
     fn restore_tab(&mut self);
+    fn duplicate_tab(&mut self);
     fn select_previous_tab(&mut self);
     fn select_next_tab(&mut self);
     fn select_tab_at_index(&mut self, index: usize);
     fn select_last_tab(&mut self);
     fn reload(&mut self);
+    /// Reload the page, bypassing the cache.
+    fn hard_reload(&mut self);
 
     fn show_help(&mut self);
     fn hide_help(&mut self);
@@ -267,16 +449,88 @@ pub fn handle_key(
         WebMode::MarkSet => return handle_mark_set(state, actions, text),
         WebMode::MarkJump => return handle_mark_jump(state, actions, text),
         WebMode::Visual | WebMode::VisualLine => return handle_visual(state, actions, text),
+        WebMode::Caret => return handle_caret(state, actions, text),
         WebMode::Normal => (),
     }
 
-    let mut chars = text.chars();
-    let Some(ch) = chars.next() else {
-        return false;
-    };
-    if chars.next().is_some() {
-        return false;
+    if let Some(action) = state.binding(key, text) {
+        for ch in default_keys(action).chars() {
+            handle_normal(state, actions, ch);
+        }
+        return true;
+    }
+
+    match single_char(text) {
+        Some(ch) => handle_normal(state, actions, ch),
+        None => false,
     }
+}
+
+/// Keys running a bindable action in normal mode.
+fn default_keys(action: WebKeyAction) -> &'static str {
+    match action {
+        WebKeyAction::ScrollDown => "j",
+        WebKeyAction::ScrollUp => "k",
+        WebKeyAction::ScrollLeft => "h",
+        WebKeyAction::ScrollRight => "l",
+        WebKeyAction::HalfPageDown => "d",
+        WebKeyAction::HalfPageUp => "u",
+        WebKeyAction::ScrollTop => "gg",
+        WebKeyAction::ScrollBottom => "G",
+        WebKeyAction::ScrollFarLeft => "zH",
+        WebKeyAction::ScrollFarRight => "zL",
+        WebKeyAction::Back => "H",
+        WebKeyAction::Forward => "L",
+        WebKeyAction::Reload => "r",
+        WebKeyAction::HardReload => "R",
+        WebKeyAction::PreviousPage => "[[",
+        WebKeyAction::NextPage => "]]",
+        WebKeyAction::ParentUrl => "gu",
+        WebKeyAction::RootUrl => "gU",
+        WebKeyAction::FollowHint => "f",
+        WebKeyAction::FollowHintNewTab => "F",
+        WebKeyAction::FollowHintBackground => "gf",
+        WebKeyAction::QueueHints => ";f",
+        WebKeyAction::CopyHint => "yf",
+        WebKeyAction::CopyUrl => "yy",
+        WebKeyAction::CopyTitle => "yt",
+        WebKeyAction::CopyMarkdownLink => "yM",
+        WebKeyAction::CopyDomain => "yd",
+        WebKeyAction::CopyVisibleLinks => "yl",
+        WebKeyAction::Find => "/",
+        WebKeyAction::FindNext => "n",
+        WebKeyAction::FindPrevious => "N",
+        WebKeyAction::Visual => "v",
+        WebKeyAction::VisualLine => "V",
+        WebKeyAction::Caret => "gc",
+        WebKeyAction::FocusInput => "gi",
+        WebKeyAction::NextInput => "gn",
+        WebKeyAction::PreviousInput => "gp",
+        WebKeyAction::NewTab => "t",
+        WebKeyAction::CloseTab => "x",
+        WebKeyAction::RestoreTab => "X",
+        WebKeyAction::DuplicateTab => "gD",
+        WebKeyAction::PreviousTab => "J",
+        WebKeyAction::NextTab => "K",
+        WebKeyAction::FirstTab => "g0",
+        WebKeyAction::LastTab => "g$",
+        WebKeyAction::Open => "o",
+        WebKeyAction::OpenNewTab => "O",
+        WebKeyAction::OpenClipboard => "p",
+        WebKeyAction::OpenClipboardNewTab => "P",
+        WebKeyAction::HistorySearch => "gh",
+        WebKeyAction::ToggleMute => "gm",
+        WebKeyAction::ViewSource => "gs",
+        WebKeyAction::OpenExternal => "gx",
+        WebKeyAction::RunBookmarklet => ";b",
+        WebKeyAction::SetMark => "m",
+        WebKeyAction::JumpToMark => "`",
+        WebKeyAction::Help => "?",
+    }
+}
+
+fn handle_normal(state: &mut WebCommandState, actions: &mut impl WebActions, ch: char) -> bool {
+    let count = state.pending.count.take();
 
     let mut retry = true;
     while retry {
@@ -329,11 +583,48 @@ pub fn handle_key(
                     actions.view_source();
                     return true;
                 },
+                'm' => {
+                    actions.toggle_mute();
+                    return true;
+                },
+                'x' => {
+                    actions.open_external();
+                    return true;
+                },
+                'h' => {
+                    actions.open_history_search();
+                    return true;
+                },
+                'D' => {
+                    actions.duplicate_tab();
+                    return true;
+                },
+                'f' => {
+                    start_hints(state, actions, WebHintAction::OpenBackgroundTab);
+                    return true;
+                },
                 'i' => {
-                    actions.focus_input();
+                    match count {
+                        Some(index) => actions.focus_input_index(index),
+                        None => actions.focus_input(),
+                    }
+                    state.field_kind = WebFieldKind::default();
                     state.set_mode(WebMode::Insert);
                     return true;
                 },
+                'c' => {
+                    actions.start_caret();
+                    state.set_mode(WebMode::Caret);
+                    return true;
+                },
+                'n' => {
+                    actions.focus_next_field();
+                    return true;
+                },
+                'p' => {
+                    actions.focus_prev_field();
+                    return true;
+                },
                 _ => {
                     retry = true;
                     continue;
@@ -352,6 +643,18 @@ pub fn handle_key(
                     actions.scroll_far_right();
                     return true;
                 },
+                'z' => {
+                    actions.scroll_relative(WebScrollAnchor::Center);
+                    return true;
+                },
+                't' => {
+                    actions.scroll_relative(WebScrollAnchor::Top);
+                    return true;
+                },
+                'b' => {
+                    actions.scroll_relative(WebScrollAnchor::Bottom);
+                    return true;
+                },
                 _ => {
                     retry = true;
                     continue;
@@ -359,6 +662,23 @@ pub fn handle_key(
             }
         }
 
+        if state.pending.semicolon {
+            state.pending.semicolon = false;
+            match ch {
+                'f' => {
+                    start_hints(state, actions, WebHintAction::Queue);
+                    return true;
+                },
+                'b' => {
+                    actions.open_command_bar("bm ");
+                    return true;
+                },
+                _ => (),
+            }
+            retry = true;
+            continue;
+        }
+
         if state.pending.y {
             state.pending.y = false;
             match ch {
@@ -366,6 +686,22 @@ pub fn handle_key(
                     actions.copy_url();
                     return true;
                 },
+                't' => {
+                    actions.copy_title();
+                    return true;
+                },
+                'M' => {
+                    actions.copy_markdown_link();
+                    return true;
+                },
+                'd' => {
+                    actions.copy_domain();
+                    return true;
+                },
+                'l' => {
+                    actions.copy_visible_links();
+                    return true;
+                },
                 'f' => {
                     start_hints(state, actions, WebHintAction::CopyLink);
                     return true;
@@ -378,16 +714,30 @@ pub fn handle_key(
         }
     }
 
+    if let Some(digit) = ch.to_digit(10).filter(|digit| *digit != 0 || count.is_some()) {
+        let count = count.unwrap_or(0).saturating_mul(10).saturating_add(digit);
+        state.pending.count = Some(count);
+        return true;
+    }
+
     match ch {
-        'j' => actions.scroll_by(0.0, WEB_SCROLL_STEP),
-        'k' => actions.scroll_by(0.0, -WEB_SCROLL_STEP),
-        'h' => actions.scroll_by(-WEB_SCROLL_STEP, 0.0),
-        'l' => actions.scroll_by(WEB_SCROLL_STEP, 0.0),
-        'd' => actions.scroll_half_page(true),
-        'u' => actions.scroll_half_page(false),
+        'j' => actions.scroll_by(0.0, state.scroll_step),
+        'k' => actions.scroll_by(0.0, -state.scroll_step),
+        'h' => actions.scroll_by(-state.scroll_step, 0.0),
+        'l' => actions.scroll_by(state.scroll_step, 0.0),
+        'd' => actions.scroll_half_page(true, state.half_page_fraction, state.momentum),
+        'u' => actions.scroll_half_page(false, state.half_page_fraction, state.momentum),
         'G' => actions.scroll_bottom(),
+        '%' => {
+            if let Some(count) = count {
+                actions.scroll_to_percent(count.min(100) as f64);
+            }
+            return true;
+        },
         'g' => {
+            // Keep the count for commands like `2gi`.
             state.pending.g = true;
+            state.pending.count = count;
             return true;
         },
         'z' => {
@@ -414,6 +764,10 @@ pub fn handle_key(
             state.pending.y = true;
             return true;
         },
+        ';' => {
+            state.pending.semicolon = true;
+            return true;
+        },
         'H' => {
             actions.go_back();
             return true;
@@ -427,11 +781,11 @@ pub fn handle_key(
             return true;
         },
         'n' => {
-            find_next(state, actions, false);
+            search(state, actions, false);
             return true;
         },
         'N' => {
-            find_next(state, actions, true);
+            search(state, actions, true);
             return true;
         },
         'v' => {
@@ -494,6 +848,10 @@ pub fn handle_key(
             actions.reload();
             return true;
         },
+        'R' => {
+            actions.hard_reload();
+            return true;
+        },
         'm' => {
             state.set_mode(WebMode::MarkSet);
             return true;
@@ -512,18 +870,63 @@ pub fn handle_key(
     true
 }
 
+/// Search the page for a query of the find prompt.
+///
+/// `\c` and `\C` in the query force case-insensitive or case-sensitive matching, otherwise only
+/// queries containing uppercase characters are case-sensitive. `\r` makes the query a regex.
 pub fn find(state: &mut WebCommandState, actions: &mut impl WebActions, query: &str, backwards: bool) {
-    actions.find(query, backwards);
-    state.last_find = Some(query.to_string());
-    state.last_find_backward = backwards;
+    let mut case_sensitive = None;
+    let mut regex = false;
+    let mut text = String::with_capacity(query.len());
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some('c')) => case_sensitive = Some(false),
+            ('\\', Some('C')) => case_sensitive = Some(true),
+            ('\\', Some('r')) => regex = true,
+            _ => {
+                text.push(c);
+                continue;
+            },
+        }
+        chars.next();
+    }
+
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+
+    state.find_case_sensitive =
+        case_sensitive.unwrap_or_else(|| text.chars().any(char::is_uppercase));
+    state.find_regex = regex;
+    state.last_find = Some(text.to_string());
+    search(state, actions, backwards);
 }
 
-fn find_next(state: &mut WebCommandState, actions: &mut impl WebActions, backwards: bool) {
-    let Some(query) = state.last_find.clone() else {
+/// Search for the last query with its options.
+fn search(state: &mut WebCommandState, actions: &mut impl WebActions, backwards: bool) {
+    let Some(query) = state.last_find.as_deref() else {
         actions.push_error(String::from("No active search"));
         return;
     };
-    find(state, actions, &query, backwards);
+
+    actions.find(query, backwards, state.find_case_sensitive, state.find_regex);
+    state.last_find_backward = backwards;
+}
+
+/// Scroll the page by a mouse wheel or trackpad delta in points.
+///
+/// Positive deltas reveal content above and to the left, like winit's wheel deltas.
+pub fn scroll_wheel(actions: &mut impl WebActions, dx: f64, dy: f64) {
+    if dx != 0.0 || dy != 0.0 {
+        actions.scroll_by(-dx, -dy);
+    }
+}
+
+/// Show a failed page load in the command bar.
+pub fn navigation_error(actions: &mut impl WebActions, error: &WebNavigationError) {
+    actions.push_error(format!("Failed to load page: {} ({})", error.message, error.code));
 }
 
 fn handle_escape(state: &mut WebCommandState, actions: &mut impl WebActions) {
@@ -534,9 +937,19 @@ fn handle_escape(state: &mut WebCommandState, actions: &mut impl WebActions) {
     }
 
     match state.mode {
-        WebMode::Hint => actions.hints_cancel(),
+        WebMode::Hint => {
+            // Links queued with `;f` are opened once hint mode is left.
+            let selected = state.hint.as_ref().map_or(&[][..], |hint| &hint.selected);
+            if selected.is_empty() {
+                actions.hints_cancel();
+            } else {
+                actions.hints_open_multiple(selected, WebHintAction::OpenBackgroundTab);
+            }
+        },
         WebMode::Visual | WebMode::VisualLine => actions.clear_selection(),
         WebMode::Insert => actions.blur_active_element(),
+        WebMode::Caret => actions.stop_caret(),
+        WebMode::Normal if state.last_find.is_some() => actions.clear_find(),
         WebMode::Normal | WebMode::MarkSet | WebMode::MarkJump => (),
     }
 
@@ -563,7 +976,10 @@ fn handle_insert(
             return true;
         },
         WebKey::Enter => {
-            actions.insert_paragraph();
+            match state.field_kind {
+                WebFieldKind::SingleLine => actions.submit_form(),
+                WebFieldKind::MultiLine => actions.insert_paragraph(),
+            }
             return true;
         },
         WebKey::Tab => {
@@ -614,10 +1030,9 @@ fn handle_hint(
             return true;
         },
         WebKey::Backspace => {
-            let (keys, action) = {
-                hint.keys.pop();
-                (hint.keys.clone(), hint.action)
-            };
+            hint.keys.pop();
+            clear_hint_preview(hint, actions);
+            let (keys, action) = (hint.keys.clone(), hint.action);
             actions.hints_update(&keys, action);
             return true;
         },
@@ -633,14 +1048,61 @@ fn handle_hint(
         return true;
     };
     hint.keys.push(ch.to_ascii_lowercase());
+    clear_hint_preview(hint, actions);
     let (keys, action) = (hint.keys.clone(), hint.action);
     actions.hints_update(&keys, action);
     true
 }
 
+fn clear_hint_preview(hint: &mut WebHintState, actions: &mut impl WebActions) {
+    if hint.preview.take().is_some() {
+        actions.hint_preview_changed();
+    }
+}
+
+/// Preview the link which the page reported as the only match of the typed hint keys.
+///
+/// Previews for keys which have changed since are ignored.
+pub fn preview_hint(
+    state: &mut WebCommandState,
+    actions: &mut impl WebActions,
+    keys: &str,
+    url: Option<String>,
+) {
+    let Some(hint) = state.hint.as_mut().filter(|hint| hint.keys == keys) else {
+        return;
+    };
+
+    if hint.preview != url {
+        hint.preview = url;
+        actions.hint_preview_changed();
+    }
+}
+
+/// Queue the link whose full label was typed with [`WebHintAction::Queue`].
+///
+/// The typed keys are cleared, so the next label can be typed right away.
+pub fn queue_hint(state: &mut WebCommandState, actions: &mut impl WebActions, keys: &str) {
+    let Some(hint) = state.hint.as_mut().filter(|hint| hint.keys == keys) else {
+        return;
+    };
+
+    hint.selected.push(mem::take(&mut hint.keys));
+    clear_hint_preview(hint, actions);
+    actions.hints_update("", hint.action);
+}
+
+/// Record the kind of the field focused for insert mode.
+pub fn field_focused(state: &mut WebCommandState, kind: WebFieldKind) {
+    if state.mode == WebMode::Insert {
+        state.field_kind = kind;
+    }
+}
+
 fn start_hints(state: &mut WebCommandState, actions: &mut impl WebActions, action: WebHintAction) {
     state.set_mode(WebMode::Hint);
-    state.hint = Some(WebHintState { action, keys: String::new() });
+    let selected = Vec::new();
+    state.hint = Some(WebHintState { action, keys: String::new(), preview: None, selected });
     actions.hints_start(action);
 }
 
@@ -716,6 +1178,14 @@ fn handle_visual(
             toggle_visual(state, actions, true);
             return true;
         },
+        'a' => {
+            actions.select_all();
+            return true;
+        },
+        'i' => {
+            actions.select_element();
+            return true;
+        },
         _ => (),
     }
 
@@ -732,6 +1202,29 @@ fn handle_visual(
     true
 }
 
+fn handle_caret(state: &mut WebCommandState, actions: &mut impl WebActions, text: &str) -> bool {
+    let Some(ch) = single_char(text) else {
+        return true;
+    };
+
+    match ch {
+        'h' => actions.caret_move("backward", "character"),
+        'l' => actions.caret_move("forward", "character"),
+        'k' => actions.caret_move("backward", "line"),
+        'j' => actions.caret_move("forward", "line"),
+        'w' => actions.caret_move("forward", "word"),
+        'b' => actions.caret_move("backward", "word"),
+        'v' => {
+            // Extend the selection from the caret's position.
+            actions.stop_caret();
+            toggle_visual(state, actions, false);
+        },
+        _ => (),
+    }
+
+    true
+}
+
 fn toggle_visual(
     state: &mut WebCommandState,
     actions: &mut impl WebActions,
@@ -758,6 +1251,28 @@ fn toggle_help(state: &mut WebCommandState, actions: &mut impl WebActions) {
     }
 }
 
+/// Drop repeated link URLs, keeping the first `max` distinct ones in their original order.
+pub fn dedup_links(links: Vec<String>, max: usize) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let links = links.into_iter().filter(|link| !link.is_empty() && seen.insert(link.clone()));
+    links.take(max).collect()
+}
+
+/// Format a page as a markdown link, using its URL when it has no title.
+pub fn markdown_link(title: &str, url: &str) -> String {
+    let title = if title.trim().is_empty() { url } else { title };
+    let mut text = String::with_capacity(title.len());
+    for ch in title.chars() {
+        if matches!(ch, '\\' | '[' | ']') {
+            text.push('\\');
+        }
+        text.push(ch);
+    }
+
+    let url = url.replace(' ', "%20").replace('(', "%28").replace(')', "%29");
+    format!("[{text}]({url})")
+}
+
 fn single_char(text: &str) -> Option<char> {
     let mut chars = text.chars();
     let ch = chars.next()?;
@@ -774,45 +1289,67 @@ mod tests {
     #[derive(Debug, Clone, PartialEq)]
     enum ActionCall {
         ScrollBy(f64, f64),
-        ScrollHalfPage(bool),
+        ScrollHalfPage(bool, f64, bool),
         ScrollTop,
         ScrollBottom,
         ScrollFarLeft,
         ScrollFarRight,
+        ScrollRelative(WebScrollAnchor),
         ScrollTo(f64, f64),
+        ScrollToPercent(f64),
         GoBack,
         GoForward,
         OpenCommandBar(String),
+        OpenHistorySearch,
         StartFindPrompt,
-        Find(String, bool),
+        Find(String, bool, bool, bool),
+        ClearFind,
         HintsStart(WebHintAction),
         HintsUpdate(String, WebHintAction),
+        HintPreviewChanged,
         HintsCancel,
+        HintsOpenMultiple(Vec<String>, WebHintAction),
         CopySelection,
         ClearSelection,
         StartVisualSelection,
         VisualMove(String, String),
+        SelectAll,
+        SelectElement,
         FocusInput,
+        FocusInputIndex(u32),
+        FocusNextField,
+        FocusPrevField,
         BlurActiveElement,
         InsertText(String),
         DeleteBackward,
         DeleteForward,
         InsertParagraph,
+        SubmitForm,
         InsertTab,
         CaretMove(String, String),
+        StartCaret,
+        StopCaret,
         ViewSource,
+        ToggleMute,
         FollowRel(String),
         CopyUrl,
+        CopyTitle,
+        CopyMarkdownLink,
+        CopyDomain,
+        CopyVisibleLinks,
+        OpenExternal,
         OpenClipboard(bool),
         UpUrl(bool),
         NewTab,
         CloseTab,
         RestoreTab,
+        DuplicateTab,
         SelectPreviousTab,
         SelectNextTab,
         SelectTabAtIndex(usize),
         SelectLastTab,
         Reload,
+        HardReload,
         ShowHelp,
         HideHelp,
         RequestMarkSet(char, String),
@@ -837,8 +1374,8 @@ mod tests {
             self.calls.push(ActionCall::ScrollBy(dx, dy));
         }
 
-        fn scroll_half_page(&mut self, down: bool) {
-            self.calls.push(ActionCall::ScrollHalfPage(down));
+        fn scroll_half_page(&mut self, down: bool, fraction: f64, smooth: bool) {
+            self.calls.push(ActionCall::ScrollHalfPage(down, fraction, smooth));
         }
 
         fn scroll_top(&mut self) {
@@ -853,6 +1390,10 @@ mod tests {
             self.calls.push(ActionCall::ScrollFarLeft);
         }
 
+        fn scroll_relative(&mut self, anchor: WebScrollAnchor) {
+            self.calls.push(ActionCall::ScrollRelative(anchor));
+        }
+
         fn scroll_far_right(&mut self) {
             self.calls.push(ActionCall::ScrollFarRight);
         }
@@ -861,6 +1402,10 @@ mod tests {
             self.calls.push(ActionCall::ScrollTo(x, y));
         }
 
+        fn scroll_to_percent(&mut self, pct: f64) {
+            self.calls.push(ActionCall::ScrollToPercent(pct));
+        }
+
         fn go_back(&mut self) {
             self.calls.push(ActionCall::GoBack);
         }
@@ -873,12 +1418,21 @@ mod tests {
             self.calls.push(ActionCall::OpenCommandBar(input.to_string()));
         }
 
+        fn open_history_search(&mut self) {
+            self.calls.push(ActionCall::OpenHistorySearch);
+        }
+
         fn start_find_prompt(&mut self) {
             self.calls.push(ActionCall::StartFindPrompt);
         }
 
-        fn find(&mut self, query: &str, backwards: bool) {
-            self.calls.push(ActionCall::Find(query.to_string(), backwards));
+        fn find(&mut self, query: &str, backwards: bool, case_sensitive: bool, regex: bool) {
+            let call = ActionCall::Find(query.to_string(), backwards, case_sensitive, regex);
+            self.calls.push(call);
+        }
+
+        fn clear_find(&mut self) {
+            self.calls.push(ActionCall::ClearFind);
         }
 
         fn hints_start(&mut self, action: WebHintAction) {
@@ -889,10 +1443,18 @@ mod tests {
             self.calls.push(ActionCall::HintsUpdate(keys.to_string(), action));
         }
 
+        fn hint_preview_changed(&mut self) {
+            self.calls.push(ActionCall::HintPreviewChanged);
+        }
+
         fn hints_cancel(&mut self) {
             self.calls.push(ActionCall::HintsCancel);
         }
 
+        fn hints_open_multiple(&mut self, keys: &[String], action: WebHintAction) {
+            self.calls.push(ActionCall::HintsOpenMultiple(keys.to_vec(), action));
+        }
+
         fn copy_selection(&mut self) {
             self.calls.push(ActionCall::CopySelection);
         }
@@ -909,10 +1471,30 @@ mod tests {
             self.calls.push(ActionCall::VisualMove(direction.to_string(), granularity.to_string()));
         }
 
+        fn select_all(&mut self) {
+            self.calls.push(ActionCall::SelectAll);
+        }
+
+        fn select_element(&mut self) {
+            self.calls.push(ActionCall::SelectElement);
+        }
+
         fn focus_input(&mut self) {
             self.calls.push(ActionCall::FocusInput);
         }
 
+        fn focus_input_index(&mut self, index: u32) {
+            self.calls.push(ActionCall::FocusInputIndex(index));
+        }
+
+        fn focus_next_field(&mut self) {
+            self.calls.push(ActionCall::FocusNextField);
+        }
+
+        fn focus_prev_field(&mut self) {
+            self.calls.push(ActionCall::FocusPrevField);
+        }
+
         fn blur_active_element(&mut self) {
             self.calls.push(ActionCall::BlurActiveElement);
         }
@@ -933,6 +1515,10 @@ mod tests {
             self.calls.push(ActionCall::InsertParagraph);
         }
 
+        fn submit_form(&mut self) {
+            self.calls.push(ActionCall::SubmitForm);
+        }
+
         fn insert_tab(&mut self) {
             self.calls.push(ActionCall::InsertTab);
         }
@@ -941,10 +1527,22 @@ mod tests {
             self.calls.push(ActionCall::CaretMove(direction.to_string(), granularity.to_string()));
         }
 
+        fn start_caret(&mut self) {
+            self.calls.push(ActionCall::StartCaret);
+        }
+
+        fn stop_caret(&mut self) {
+            self.calls.push(ActionCall::StopCaret);
+        }
+
         fn view_source(&mut self) {
             self.calls.push(ActionCall::ViewSource);
         }
 
+        fn toggle_mute(&mut self) {
+            self.calls.push(ActionCall::ToggleMute);
+        }
+
         fn follow_rel(&mut self, rel: &str) {
             self.calls.push(ActionCall::FollowRel(rel.to_string()));
         }
@@ -953,6 +1551,26 @@ mod tests {
             self.calls.push(ActionCall::CopyUrl);
         }
 
+        fn copy_title(&mut self) {
+            self.calls.push(ActionCall::CopyTitle);
+        }
+
+        fn copy_markdown_link(&mut self) {
+            self.calls.push(ActionCall::CopyMarkdownLink);
+        }
+
+        fn copy_domain(&mut self) {
+            self.calls.push(ActionCall::CopyDomain);
+        }
+
+        fn copy_visible_links(&mut self) {
+            self.calls.push(ActionCall::CopyVisibleLinks);
+        }
+
+        fn open_external(&mut self) {
+            self.calls.push(ActionCall::OpenExternal);
+        }
+
         fn open_clipboard(&mut self, new_tab: bool) {
             self.calls.push(ActionCall::OpenClipboard(new_tab));
         }
@@ -973,6 +1591,10 @@ mod tests {
             self.calls.push(ActionCall::RestoreTab);
         }
 
+        fn duplicate_tab(&mut self) {
+            self.calls.push(ActionCall::DuplicateTab);
+        }
+
         fn select_previous_tab(&mut self) {
             self.calls.push(ActionCall::SelectPreviousTab);
         }
@@ -993,6 +1615,10 @@ mod tests {
             self.calls.push(ActionCall::Reload);
         }
 
+        fn hard_reload(&mut self) {
+            self.calls.push(ActionCall::HardReload);
+        }
+
         fn show_help(&mut self) {
             self.calls.push(ActionCall::ShowHelp);
         }
@@ -1034,18 +1660,18 @@ mod tests {
         let mut actions = MockActions::default();
 
         press(&mut state, &mut actions, 'j');
-        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(0.0, WEB_SCROLL_STEP)));
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(0.0, 48.0)));
         press(&mut state, &mut actions, 'k');
-        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(0.0, -WEB_SCROLL_STEP)));
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(0.0, -48.0)));
         press(&mut state, &mut actions, 'h');
-        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(-WEB_SCROLL_STEP, 0.0)));
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(-48.0, 0.0)));
         press(&mut state, &mut actions, 'l');
-        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(WEB_SCROLL_STEP, 0.0)));
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(48.0, 0.0)));
 
         press(&mut state, &mut actions, 'd');
-        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollHalfPage(true)));
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollHalfPage(true, 0.5, false)));
         press(&mut state, &mut actions, 'u');
-        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollHalfPage(false)));
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollHalfPage(false, 0.5, false)));
 
         press(&mut state, &mut actions, 'G');
         assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBottom));
@@ -1062,6 +1688,197 @@ mod tests {
         assert_eq!(actions.last_call(), Some(&ActionCall::ScrollFarRight));
     }
 
+    #[test]
+    fn configured_scroll_step() {
+        let mut config = WebConfig::default();
+        config.scroll_step = 96.0;
+        config.half_page_fraction = 0.8;
+        let mut state = WebCommandState::new(&config);
+        let mut actions = MockActions::default();
+
+        press(&mut state, &mut actions, 'j');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(0.0, 96.0)));
+        press(&mut state, &mut actions, 'h');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(-96.0, 0.0)));
+        press(&mut state, &mut actions, 'u');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollHalfPage(false, 0.8, false)));
+    }
+
+    #[test]
+    fn momentum_half_page_scroll() {
+        let mut config = WebConfig::default();
+        config.momentum = true;
+        let mut state = WebCommandState::new(&config);
+        let mut actions = MockActions::default();
+
+        press(&mut state, &mut actions, 'd');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollHalfPage(true, 0.5, true)));
+        press(&mut state, &mut actions, 'u');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollHalfPage(false, 0.5, true)));
+
+        // Single steps are never animated.
+        press(&mut state, &mut actions, 'j');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(0.0, 48.0)));
+    }
+
+    #[test]
+    fn configured_keybindings() {
+        let mut config = WebConfig::default();
+        config.keybindings.insert("s".into(), WebKeyAction::ScrollDown);
+        config.keybindings.insert("Space".into(), WebKeyAction::ScrollTop);
+        config.keybindings.insert("Enter".into(), WebKeyAction::HalfPageDown);
+        config.keybindings.insert("nope".into(), WebKeyAction::Help);
+        let mut state = WebCommandState::new(&config);
+        let mut actions = MockActions::default();
+
+        press(&mut state, &mut actions, 's');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(0.0, 48.0)));
+        press(&mut state, &mut actions, ' ');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollTop));
+        assert!(handle_key(&mut state, &mut actions, WebKey::Enter, ""));
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollHalfPage(true, 0.5, false)));
+
+        // Unmapped keys keep their default commands.
+        press(&mut state, &mut actions, 'k');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(0.0, -48.0)));
+
+        // Bound keys still complete pending commands.
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 's');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ViewSource));
+    }
+
+    #[test]
+    fn relative_scroll() {
+        let mut state = WebCommandState::default();
+        let mut actions = MockActions::default();
+
+        press(&mut state, &mut actions, 'z');
+        press(&mut state, &mut actions, 'z');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollRelative(WebScrollAnchor::Center)));
+        press(&mut state, &mut actions, 'z');
+        press(&mut state, &mut actions, 't');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollRelative(WebScrollAnchor::Top)));
+        press(&mut state, &mut actions, 'z');
+        press(&mut state, &mut actions, 'b');
+        let call = ActionCall::ScrollRelative(WebScrollAnchor::Bottom);
+        assert_eq!(actions.last_call(), Some(&call));
+        assert_eq!(actions.calls.len(), 3);
+    }
+
+    #[test]
+    fn percent_jumps() {
+        let mut state = WebCommandState::default();
+        let mut actions = MockActions::default();
+
+        press(&mut state, &mut actions, '5');
+        press(&mut state, &mut actions, '0');
+        assert_eq!(actions.last_call(), None);
+        press(&mut state, &mut actions, '%');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollToPercent(50.0)));
+
+        // Counts are clamped to the end of the page.
+        for ch in ['2', '5', '0', '%'] {
+            press(&mut state, &mut actions, ch);
+        }
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollToPercent(100.0)));
+
+        // A leading zero isn't a count.
+        press(&mut state, &mut actions, '0');
+        press(&mut state, &mut actions, '%');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollToPercent(100.0)));
+
+        // Other commands drop the count.
+        press(&mut state, &mut actions, '3');
+        press(&mut state, &mut actions, 'j');
+        press(&mut state, &mut actions, '%');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(0.0, 48.0)));
+    }
+
+    #[test]
+    fn hint_preview_of_unique_prefix() {
+        let mut state = WebCommandState::default();
+        let mut actions = MockActions::default();
+        let url = || Some(String::from("https://example.com/docs"));
+
+        press(&mut state, &mut actions, 'f');
+        press(&mut state, &mut actions, 'a');
+        let update = ActionCall::HintsUpdate(String::from("a"), WebHintAction::Open);
+        assert_eq!(actions.last_call(), Some(&update));
+
+        // The page reports the only link whose label starts with the typed keys.
+        preview_hint(&mut state, &mut actions, "a", url());
+        assert_eq!(actions.last_call(), Some(&ActionCall::HintPreviewChanged));
+        assert_eq!(state.hint_preview(), Some("https://example.com/docs"));
+
+        // Typing further keys drops the preview until the page reports a new match.
+        press(&mut state, &mut actions, 's');
+        assert_eq!(state.hint_preview(), None);
+        assert!(actions.calls.contains(&ActionCall::HintPreviewChanged));
+        actions.calls.clear();
+        preview_hint(&mut state, &mut actions, "a", url());
+        assert_eq!(state.hint_preview(), None);
+        assert!(actions.calls.is_empty());
+
+        // Leaving hint mode discards the preview.
+        preview_hint(&mut state, &mut actions, "as", url());
+        assert_eq!(state.hint_preview(), Some("https://example.com/docs"));
+        press_key(&mut state, &mut actions, WebKey::Escape);
+        assert_eq!(state.hint_preview(), None);
+    }
+
+    #[test]
+    fn queued_hints_open_on_escape() {
+        let mut state = WebCommandState::default();
+        let mut actions = MockActions::default();
+
+        press(&mut state, &mut actions, ';');
+        press(&mut state, &mut actions, 'f');
+        assert_eq!(state.mode, WebMode::Hint);
+        assert_eq!(actions.last_call(), Some(&ActionCall::HintsStart(WebHintAction::Queue)));
+
+        // Each complete label is queued and hint mode stays active for the next one.
+        press(&mut state, &mut actions, 'a');
+        queue_hint(&mut state, &mut actions, "a");
+        let restart = ActionCall::HintsUpdate(String::new(), WebHintAction::Queue);
+        assert_eq!(actions.last_call(), Some(&restart));
+        press(&mut state, &mut actions, 's');
+        press(&mut state, &mut actions, 'd');
+        assert_eq!(
+            actions.last_call(),
+            Some(&ActionCall::HintsUpdate(String::from("sd"), WebHintAction::Queue))
+        );
+        queue_hint(&mut state, &mut actions, "sd");
+        assert_eq!(state.mode, WebMode::Hint);
+
+        // Stale matches of keys which have changed since are ignored.
+        press(&mut state, &mut actions, 'f');
+        queue_hint(&mut state, &mut actions, "sd");
+        let selected = &state.hint.as_ref().unwrap().selected;
+        assert_eq!(selected, &[String::from("a"), String::from("sd")]);
+
+        actions.calls.clear();
+        press_key(&mut state, &mut actions, WebKey::Escape);
+        assert_eq!(state.mode, WebMode::Normal);
+        let keys = vec![String::from("a"), String::from("sd")];
+        let open = ActionCall::HintsOpenMultiple(keys, WebHintAction::OpenBackgroundTab);
+        assert_eq!(actions.calls, vec![open]);
+
+        // Without queued links, Escape only removes the hints.
+        press(&mut state, &mut actions, ';');
+        press(&mut state, &mut actions, 'f');
+        press_key(&mut state, &mut actions, WebKey::Escape);
+        assert_eq!(actions.last_call(), Some(&ActionCall::HintsCancel));
+    }
+
+    #[test]
+    fn hint_match_parsing() {
+        let json = r#"{"url":"https://example.com/","complete":false}"#;
+        let hint_match: WebHintMatch = serde_json::from_str(json).unwrap();
+        let expected = WebHintMatch { url: String::from("https://example.com/"), complete: false };
+        assert_eq!(hint_match, expected);
+    }
+
     #[test]
     fn link_and_input_commands() {
         let mut state = WebCommandState::default();
@@ -1076,6 +1893,13 @@ mod tests {
         assert_eq!(state.mode, WebMode::Hint);
         assert_eq!(actions.last_call(), Some(&ActionCall::HintsStart(WebHintAction::OpenNewTab)));
 
+        state = WebCommandState::default();
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'f');
+        assert_eq!(state.mode, WebMode::Hint);
+        let background = ActionCall::HintsStart(WebHintAction::OpenBackgroundTab);
+        assert_eq!(actions.last_call(), Some(&background));
+
         state = WebCommandState::default();
         press(&mut state, &mut actions, 'y');
         press(&mut state, &mut actions, 'f');
@@ -1087,6 +1911,64 @@ mod tests {
         press(&mut state, &mut actions, 'i');
         assert_eq!(state.mode, WebMode::Insert);
         assert_eq!(actions.last_call(), Some(&ActionCall::FocusInput));
+
+        state = WebCommandState::default();
+        for ch in ['2', 'g', 'i'] {
+            press(&mut state, &mut actions, ch);
+        }
+        assert_eq!(state.mode, WebMode::Insert);
+        assert_eq!(actions.last_call(), Some(&ActionCall::FocusInputIndex(2)));
+    }
+
+    #[test]
+    fn field_cycling_stays_in_normal_mode() {
+        let mut state = WebCommandState::default();
+        let mut actions = MockActions::default();
+
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'n');
+        assert_eq!(state.mode, WebMode::Normal);
+        assert_eq!(actions.last_call(), Some(&ActionCall::FocusNextField));
+
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'p');
+        assert_eq!(state.mode, WebMode::Normal);
+        assert_eq!(actions.last_call(), Some(&ActionCall::FocusPrevField));
+
+        // Insert mode is only entered by `gi`, continuing in the cycled input.
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'i');
+        assert_eq!(state.mode, WebMode::Insert);
+        assert_eq!(actions.last_call(), Some(&ActionCall::FocusInput));
+    }
+
+    #[test]
+    fn insert_enter_by_field_kind() {
+        let mut state = WebCommandState::default();
+        let mut actions = MockActions::default();
+
+        // Enter submits the form of a single-line input.
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'i');
+        field_focused(&mut state, WebFieldKind::SingleLine);
+        assert!(handle_key(&mut state, &mut actions, WebKey::Enter, ""));
+        assert_eq!(actions.last_call(), Some(&ActionCall::SubmitForm));
+
+        // Textareas keep inserting paragraphs.
+        handle_key(&mut state, &mut actions, WebKey::Escape, "");
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'i');
+        field_focused(&mut state, WebFieldKind::MultiLine);
+        assert!(handle_key(&mut state, &mut actions, WebKey::Enter, ""));
+        assert_eq!(actions.last_call(), Some(&ActionCall::InsertParagraph));
+
+        // Fields reported outside of insert mode and stale kinds are ignored.
+        handle_key(&mut state, &mut actions, WebKey::Escape, "");
+        field_focused(&mut state, WebFieldKind::SingleLine);
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'i');
+        assert!(handle_key(&mut state, &mut actions, WebKey::Enter, ""));
+        assert_eq!(actions.last_call(), Some(&ActionCall::InsertParagraph));
     }
 
     #[test]
@@ -1107,9 +1989,13 @@ mod tests {
         state = WebCommandState::default();
         state.last_find = Some(String::from("needle"));
         press(&mut state, &mut actions, 'n');
-        assert_eq!(actions.last_call(), Some(&ActionCall::Find(String::from("needle"), false)));
+        let call = ActionCall::Find(String::from("needle"), false, false, false);
+        assert_eq!(actions.last_call(), Some(&call));
         press(&mut state, &mut actions, 'N');
-        assert_eq!(actions.last_call(), Some(&ActionCall::Find(String::from("needle"), true)));
+        let call = ActionCall::Find(String::from("needle"), true, false, false);
+        assert_eq!(actions.last_call(), Some(&call));
+        press_key(&mut state, &mut actions, WebKey::Escape);
+        assert_eq!(actions.last_call(), Some(&ActionCall::ClearFind));
 
         state = WebCommandState::default();
         press(&mut state, &mut actions, 'v');
@@ -1125,6 +2011,88 @@ mod tests {
         assert_eq!(actions.last_call(), Some(&ActionCall::StartVisualSelection));
     }
 
+    #[test]
+    fn find_options_from_query() {
+        let mut state = WebCommandState::default();
+        let mut actions = MockActions::default();
+
+        find(&mut state, &mut actions, "\\Cneedle", false);
+        let call = ActionCall::Find(String::from("needle"), false, true, false);
+        assert_eq!(actions.last_call(), Some(&call));
+
+        // The options of the last query are kept for the next match.
+        press(&mut state, &mut actions, 'N');
+        let call = ActionCall::Find(String::from("needle"), true, true, false);
+        assert_eq!(actions.last_call(), Some(&call));
+
+        // Smartcase matching, unless overridden.
+        find(&mut state, &mut actions, "Needle", false);
+        let call = ActionCall::Find(String::from("Needle"), false, true, false);
+        assert_eq!(actions.last_call(), Some(&call));
+        find(&mut state, &mut actions, "Needle\\c", false);
+        let call = ActionCall::Find(String::from("Needle"), false, false, false);
+        assert_eq!(actions.last_call(), Some(&call));
+
+        find(&mut state, &mut actions, "\\rne+dle\\d", true);
+        let call = ActionCall::Find(String::from("ne+dle\\d"), true, false, true);
+        assert_eq!(actions.last_call(), Some(&call));
+    }
+
+    #[test]
+    fn caret_commands() {
+        let mut state = WebCommandState::default();
+        let mut actions = MockActions::default();
+
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'c');
+        assert_eq!(state.mode, WebMode::Caret);
+        assert_eq!(state.status_label(), "CARET");
+        assert_eq!(actions.last_call(), Some(&ActionCall::StartCaret));
+
+        press(&mut state, &mut actions, 'l');
+        let forward = ActionCall::CaretMove(String::from("forward"), String::from("character"));
+        assert_eq!(actions.last_call(), Some(&forward));
+        press(&mut state, &mut actions, 'j');
+        let down = ActionCall::CaretMove(String::from("forward"), String::from("line"));
+        assert_eq!(actions.last_call(), Some(&down));
+        press(&mut state, &mut actions, 'b');
+        let word = ActionCall::CaretMove(String::from("backward"), String::from("word"));
+        assert_eq!(actions.last_call(), Some(&word));
+        assert_eq!(state.mode, WebMode::Caret);
+
+        press_key(&mut state, &mut actions, WebKey::Escape);
+        assert_eq!(state.mode, WebMode::Normal);
+        assert_eq!(actions.last_call(), Some(&ActionCall::StopCaret));
+
+        // Selections start from the caret.
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'c');
+        actions.calls.clear();
+        press(&mut state, &mut actions, 'v');
+        assert_eq!(state.mode, WebMode::Visual);
+        assert_eq!(actions.calls, vec![ActionCall::StopCaret, ActionCall::StartVisualSelection]);
+    }
+
+    #[test]
+    fn visual_select_all_and_element() {
+        let mut state = WebCommandState::default();
+        let mut actions = MockActions::default();
+
+        press(&mut state, &mut actions, 'v');
+        press(&mut state, &mut actions, 'a');
+        assert_eq!(actions.last_call(), Some(&ActionCall::SelectAll));
+        assert_eq!(state.mode, WebMode::Visual);
+
+        press(&mut state, &mut actions, 'i');
+        assert_eq!(actions.last_call(), Some(&ActionCall::SelectElement));
+        assert_eq!(state.mode, WebMode::Visual);
+
+        actions.calls.clear();
+        press(&mut state, &mut actions, 'y');
+        assert_eq!(actions.calls, vec![ActionCall::CopySelection, ActionCall::ClearSelection]);
+        assert_eq!(state.mode, WebMode::Normal);
+    }
+
     #[test]
     fn history_and_url_commands() {
         let mut state = WebCommandState::default();
@@ -1138,6 +2106,24 @@ mod tests {
         press(&mut state, &mut actions, 'y');
         press(&mut state, &mut actions, 'y');
         assert_eq!(actions.last_call(), Some(&ActionCall::CopyUrl));
+        press(&mut state, &mut actions, 'y');
+        press(&mut state, &mut actions, 't');
+        assert_eq!(actions.last_call(), Some(&ActionCall::CopyTitle));
+        press(&mut state, &mut actions, 'y');
+        press(&mut state, &mut actions, 'M');
+        assert_eq!(actions.last_call(), Some(&ActionCall::CopyMarkdownLink));
+        press(&mut state, &mut actions, 'y');
+        press(&mut state, &mut actions, 'd');
+        assert_eq!(actions.last_call(), Some(&ActionCall::CopyDomain));
+        press(&mut state, &mut actions, 'y');
+        press(&mut state, &mut actions, 'l');
+        assert_eq!(actions.last_call(), Some(&ActionCall::CopyVisibleLinks));
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'x');
+        assert_eq!(actions.last_call(), Some(&ActionCall::OpenExternal));
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'h');
+        assert_eq!(actions.last_call(), Some(&ActionCall::OpenHistorySearch));
 
         press(&mut state, &mut actions, 'p');
         assert_eq!(actions.last_call(), Some(&ActionCall::OpenClipboard(false)));
@@ -1163,6 +2149,9 @@ mod tests {
         assert_eq!(actions.last_call(), Some(&ActionCall::CloseTab));
         press(&mut state, &mut actions, 'X');
         assert_eq!(actions.last_call(), Some(&ActionCall::RestoreTab));
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'D');
+        assert_eq!(actions.last_call(), Some(&ActionCall::DuplicateTab));
 
         press(&mut state, &mut actions, 'J');
         assert_eq!(actions.last_call(), Some(&ActionCall::SelectPreviousTab));
@@ -1186,6 +2175,9 @@ mod tests {
         assert_eq!(actions.last_call(), Some(&ActionCall::OpenCommandBar(String::from("B "))));
         press(&mut state, &mut actions, 'T');
         assert_eq!(actions.last_call(), Some(&ActionCall::OpenCommandBar(String::from("T "))));
+        press(&mut state, &mut actions, ';');
+        press(&mut state, &mut actions, 'b');
+        assert_eq!(actions.last_call(), Some(&ActionCall::OpenCommandBar(String::from("bm "))));
     }
 
     #[test]
@@ -1195,11 +2187,17 @@ mod tests {
 
         press(&mut state, &mut actions, 'r');
         assert_eq!(actions.last_call(), Some(&ActionCall::Reload));
+        press(&mut state, &mut actions, 'R');
+        assert_eq!(actions.last_call(), Some(&ActionCall::HardReload));
 
         press(&mut state, &mut actions, 'g');
         press(&mut state, &mut actions, 's');
         assert_eq!(actions.last_call(), Some(&ActionCall::ViewSource));
 
+        press(&mut state, &mut actions, 'g');
+        press(&mut state, &mut actions, 'm');
+        assert_eq!(actions.last_call(), Some(&ActionCall::ToggleMute));
+
         press(&mut state, &mut actions, '[');
         press(&mut state, &mut actions, '[');
         assert_eq!(actions.last_call(), Some(&ActionCall::FollowRel(String::from("prev"))));
@@ -1242,4 +2240,85 @@ mod tests {
         assert_eq!(actions.last_call(), Some(&ActionCall::HideHelp));
         assert!(!state.help_visible);
     }
+
+    #[test]
+    fn wheel_scrolling() {
+        let mut actions = MockActions::default();
+
+        scroll_wheel(&mut actions, -30.0, 0.0);
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(30.0, 0.0)));
+
+        scroll_wheel(&mut actions, 0.0, 20.0);
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(0.0, -20.0)));
+
+        scroll_wheel(&mut actions, 12.0, -8.0);
+        assert_eq!(actions.last_call(), Some(&ActionCall::ScrollBy(-12.0, 8.0)));
+
+        let calls = actions.calls.len();
+        scroll_wheel(&mut actions, 0.0, 0.0);
+        assert_eq!(actions.calls.len(), calls);
+    }
+
+    #[test]
+    fn navigation_error_pushes_error() {
+        let mut actions = MockActions::default();
+        let error = WebNavigationError {
+            code: -1202,
+            message: String::from("The certificate for this server is invalid."),
+        };
+
+        navigation_error(&mut actions, &error);
+        assert_eq!(
+            actions.last_call(),
+            Some(&ActionCall::PushError(String::from(
+                "Failed to load page: The certificate for this server is invalid. (-1202)"
+            )))
+        );
+    }
+
+    #[test]
+    fn navigation_error_serialization() {
+        let error = WebNavigationError {
+            code: -1003,
+            message: String::from("A server with the specified hostname could not be found."),
+        };
+
+        let json = serde_json::to_string(&error).unwrap();
+        assert_eq!(
+            json,
+            r#"{"code":-1003,"message":"A server with the specified hostname could not be found."}"#
+        );
+        assert_eq!(serde_json::from_str::<WebNavigationError>(&json).unwrap(), error);
+    }
+
+    #[test]
+    fn visible_links_deduped_and_capped() {
+        let links = ["https://a.com/", "https://b.com/", "https://a.com/", "", "https://c.com/"];
+        let links: Vec<_> = links.into_iter().map(String::from).collect();
+
+        let expected = ["https://a.com/", "https://b.com/", "https://c.com/"];
+        assert_eq!(dedup_links(links.clone(), MAX_YANKED_LINKS), expected);
+
+        // Repeated links don't count towards the cap.
+        assert_eq!(dedup_links(links.clone(), 2), ["https://a.com/", "https://b.com/"]);
+        assert!(dedup_links(links, 0).is_empty());
+
+        let many = (0..MAX_YANKED_LINKS + 10).map(|i| format!("https://example.com/{i}")).collect();
+        let capped = dedup_links(many, MAX_YANKED_LINKS);
+        assert_eq!(capped.len(), MAX_YANKED_LINKS);
+        let last = format!("https://example.com/{}", MAX_YANKED_LINKS - 1);
+        assert_eq!(capped.last(), Some(&last));
+    }
+
+    #[test]
+    fn markdown_link_escaping() {
+        let link = markdown_link("Vec in std::vec - Rust", "https://doc.rust-lang.org/std/vec/");
+        assert_eq!(link, "[Vec in std::vec - Rust](https://doc.rust-lang.org/std/vec/)");
+
+        let link = markdown_link("[RFC] a\\b", "https://example.com/a (b)");
+        assert_eq!(link, "[\\[RFC\\] a\\\\b](https://example.com/a%20%28b%29)");
+
+        let link = markdown_link(" ", "https://example.com");
+        assert_eq!(link, "[https://example.com](https://example.com)");
+    }
 }
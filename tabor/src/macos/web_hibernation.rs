@@ -0,0 +1,198 @@
+//! Unloading of idle background web tabs.
+
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+/// Interval at which background web tabs are checked for hibernation.
+pub const WEB_HIBERNATION_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Script reporting the state saved before a web tab is hibernated.
+pub const WEB_HIBERNATION_JS: &str =
+    "JSON.stringify({ url: location.href, scroll_x: window.scrollX, scroll_y: window.scrollY })";
+
+/// Page of a hibernated web tab, loaded again once the tab is selected.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct HibernatedPage {
+    pub url: String,
+    #[serde(default)]
+    pub scroll_x: f64,
+    #[serde(default)]
+    pub scroll_y: f64,
+
+    /// Page zoom factor of the dropped `WebView`.
+    #[serde(skip)]
+    pub zoom: Option<f64>,
+
+    /// Whether the audio of the dropped `WebView` was muted.
+    #[serde(skip)]
+    pub muted: bool,
+}
+
+impl HibernatedPage {
+    /// Parse the result of [`WEB_HIBERNATION_JS`].
+    pub fn from_json(json: &str) -> Option<Self> {
+        serde_json::from_str::<Self>(json).ok().filter(|page| !page.url.is_empty())
+    }
+}
+
+/// Hibernation state of a web tab.
+#[derive(Debug)]
+pub struct WebHibernation {
+    /// Last time the tab was selected or deselected.
+    last_active: Instant,
+
+    /// Page state was requested to hibernate the tab.
+    pending: bool,
+
+    /// The tab's `WebView` was dropped.
+    hibernated: bool,
+
+    /// Page to restore when the tab wakes up.
+    saved_page: Option<HibernatedPage>,
+}
+
+impl WebHibernation {
+    pub fn new(now: Instant) -> Self {
+        Self { last_active: now, pending: false, hibernated: false, saved_page: None }
+    }
+
+    pub fn hibernated(&self) -> bool {
+        self.hibernated
+    }
+
+    /// Record tab activity, cancelling any hibernation in progress.
+    pub fn touch(&mut self, now: Instant) {
+        self.last_active = now;
+        self.pending = false;
+    }
+
+    /// Whether the tab has been idle for longer than `timeout` and should be hibernated.
+    pub fn is_idle(&self, now: Instant, timeout: Duration) -> bool {
+        let idle = now.saturating_duration_since(self.last_active);
+        !self.hibernated && !self.pending && idle >= timeout
+    }
+
+    /// Mark the page state as requested, so the tab isn't requested again.
+    pub fn request(&mut self) {
+        self.pending = true;
+    }
+
+    /// Save the page of a tab whose state was requested.
+    ///
+    /// Returns `false` when the tab was used since the request, in which case it must be kept.
+    pub fn hibernate(&mut self, page: HibernatedPage) -> bool {
+        if !self.pending {
+            return false;
+        }
+
+        self.pending = false;
+        self.hibernated = true;
+        self.saved_page = Some(page);
+        true
+    }
+
+    /// Whether the saved page is muted, `None` unless the tab is hibernated.
+    pub fn muted(&self) -> Option<bool> {
+        self.saved_page.as_ref().map(|page| page.muted)
+    }
+
+    /// Change whether the saved page is muted once it's loaded again.
+    ///
+    /// Returns `false` when the tab isn't hibernated.
+    pub fn set_muted(&mut self, muted: bool) -> bool {
+        match self.saved_page.as_mut() {
+            Some(page) => {
+                page.muted = muted;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Take the page which has to be loaded again to wake a hibernated tab.
+    pub fn wake(&mut self, now: Instant) -> Option<HibernatedPage> {
+        self.touch(now);
+        if !self.hibernated {
+            return None;
+        }
+
+        self.hibernated = false;
+        self.saved_page.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page() -> HibernatedPage {
+        HibernatedPage {
+            url: String::from("https://example.com/"),
+            scroll_x: 0.,
+            scroll_y: 240.,
+            zoom: None,
+            muted: false,
+        }
+    }
+
+    #[test]
+    fn hibernation_idle_timeout() {
+        let start = Instant::now();
+        let timeout = Duration::from_secs(60);
+        let mut hibernation = WebHibernation::new(start);
+
+        assert!(!hibernation.is_idle(start + Duration::from_secs(59), timeout));
+        assert!(hibernation.is_idle(start + timeout, timeout));
+
+        hibernation.touch(start + timeout);
+        assert!(!hibernation.is_idle(start + timeout, timeout));
+
+        hibernation.request();
+        assert!(!hibernation.is_idle(start + timeout * 3, timeout));
+    }
+
+    #[test]
+    fn hibernation_save_and_restore() {
+        let start = Instant::now();
+        let mut hibernation = WebHibernation::new(start);
+
+        // Tabs can't hibernate without requesting their page first.
+        assert!(!hibernation.hibernate(page()));
+        assert!(!hibernation.hibernated());
+
+        hibernation.request();
+        assert!(hibernation.hibernate(page()));
+        assert!(hibernation.hibernated());
+        assert!(!hibernation.is_idle(start + Duration::from_secs(3600), Duration::ZERO));
+
+        assert_eq!(hibernation.muted(), Some(false));
+        assert!(hibernation.set_muted(true));
+        assert_eq!(hibernation.wake(start), Some(HibernatedPage { muted: true, ..page() }));
+        assert!(!hibernation.hibernated());
+        assert_eq!(hibernation.muted(), None);
+        assert!(!hibernation.set_muted(true));
+        assert_eq!(hibernation.wake(start), None);
+    }
+
+    #[test]
+    fn hibernation_cancelled_by_activity() {
+        let start = Instant::now();
+        let mut hibernation = WebHibernation::new(start);
+
+        hibernation.request();
+        hibernation.touch(start);
+        assert!(!hibernation.hibernate(page()));
+        assert!(!hibernation.hibernated());
+        assert_eq!(hibernation.wake(start), None);
+    }
+
+    #[test]
+    fn hibernated_page_from_json() {
+        let json = r#"{"url":"https://example.com/","scroll_x":0,"scroll_y":240}"#;
+        assert_eq!(HibernatedPage::from_json(json), Some(page()));
+
+        assert_eq!(HibernatedPage::from_json(r#"{"url":""}"#), None);
+        assert_eq!(HibernatedPage::from_json("undefined"), None);
+    }
+}
@@ -1,17 +1,20 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::error::Error;
+use std::ffi::CStr;
+use std::fs;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::ptr;
 use std::ptr::NonNull;
+use std::time::Duration;
 
 use block2::RcBlock;
-use log::debug;
+use log::{debug, warn};
 use objc2::encode::{Encode, Encoding};
-use objc2::ffi::NSInteger;
+use objc2::ffi::{NSInteger, NSUInteger};
 use objc2::rc::Retained;
-use objc2::runtime::AnyObject;
+use objc2::runtime::{AnyClass, AnyObject, Sel};
 use objc2::runtime::NSObject;
 use objc2::runtime::Bool;
 use objc2::{class, define_class, msg_send, sel, DefinedClass, MainThreadMarker, MainThreadOnly};
@@ -25,12 +28,52 @@ use winit::window::WindowId;
 
 use tabor_terminal::grid::Dimensions;
 
+use crate::config::web::{ColorScheme, RunAt, UserScript, WebConfig};
 use crate::display::SizeInfo;
 use crate::display::window::Window;
 use crate::event::{Event, EventType};
+use crate::ipc::InspectorDock;
+use crate::macos::web_commands::WebNavigationError;
 use crate::tabs::TabId;
+use crate::web_url::request_headers;
 use libc::{c_char, c_void};
 
+/// Range accepted by [`WebView::set_page_zoom`].
+const MIN_PAGE_ZOOM: f64 = 0.25;
+const MAX_PAGE_ZOOM: f64 = 5.0;
+
+/// `WKWebView` properties observed to report page loading.
+const LOADING_KEY_PATHS: [&str; 2] = ["estimatedProgress", "loading"];
+
+/// `WKWebsiteDataStore` types removed when clearing caches.
+const CACHE_DATA_TYPES: [&str; 4] = [
+    "WKWebsiteDataTypeDiskCache",
+    "WKWebsiteDataTypeMemoryCache",
+    "WKWebsiteDataTypeFetchCache",
+    "WKWebsiteDataTypeOfflineWebApplicationCache",
+];
+
+/// `WKWebsiteDataStore` types removed when clearing site storage.
+const STORAGE_DATA_TYPES: [&str; 5] = [
+    "WKWebsiteDataTypeLocalStorage",
+    "WKWebsiteDataTypeSessionStorage",
+    "WKWebsiteDataTypeIndexedDBDatabases",
+    "WKWebsiteDataTypeWebSQLDatabases",
+    "WKWebsiteDataTypeServiceWorkerRegistrations",
+];
+
+/// `NSURLErrorCancelled`, reported when a load is replaced by another navigation.
+const URL_ERROR_CANCELLED: NSInteger = -999;
+
+/// `WebKitErrorFrameLoadInterruptedByPolicyChange`, reported for downloads and redirects.
+const FRAME_LOAD_INTERRUPTED: NSInteger = 102;
+
+/// `NSURLSessionAuthChallengeUseCredential`.
+const AUTH_CHALLENGE_USE_CREDENTIAL: NSInteger = 0;
+
+/// `NSURLSessionAuthChallengePerformDefaultHandling`.
+const AUTH_CHALLENGE_DEFAULT_HANDLING: NSInteger = 1;
+
 #[link(name = "WebKit", kind = "framework")]
 unsafe extern "C" {}
 
@@ -72,22 +115,40 @@ unsafe impl Encode for CGRect {
     const ENCODING: Encoding = Encoding::Struct("CGRect", &[CGPoint::ENCODING, CGSize::ENCODING]);
 }
 
+/// Opaque `SecTrustRef` of a TLS authentication challenge.
+#[repr(transparent)]
+struct SecTrustRef(*mut c_void);
+
+// SAFETY: The struct is a transparent pointer to the opaque `__SecTrust` struct.
+unsafe impl Encode for SecTrustRef {
+    const ENCODING: Encoding = Encoding::Pointer(&Encoding::Struct("__SecTrust", &[]));
+}
+
 pub struct WebView {
     view: Retained<AnyObject>,
     last_title: Option<String>,
     last_url: Option<String>,
-    _delegate: Retained<AnyObject>,
+    proxy: EventLoopProxy<Event>,
+    window_id: WindowId,
+    tab_id: TabId,
+    page_zoom: f64,
+    muted: bool,
+    custom_user_agent: Option<String>,
+    extra_headers: HashMap<String, String>,
+    delegate: Retained<AnyObject>,
 }
 
 pub(crate) struct PendingPopup {
     pub(crate) view: Retained<AnyObject>,
     pub(crate) delegate: Retained<AnyObject>,
     pub(crate) url: Option<String>,
+    pub(crate) private: bool,
 }
 
 struct WebViewDelegateIvars {
     proxy: EventLoopProxy<Event>,
     window_id: WindowId,
+    allow_invalid_certs: Cell<bool>,
 }
 
 define_class!(
@@ -108,7 +169,8 @@ define_class!(
             let Some(config) = (unsafe { config.as_ref() }) else {
                 return ptr::null_mut();
             };
-            if let Err(err) = configure_webview_config(config) {
+            // The configuration shares the opener's content controller and its user scripts.
+            if let Err(err) = configure_webview_config(config, ColorScheme::System, &[]) {
                 debug!("Failed to configure popup WebView: {err}");
                 return ptr::null_mut();
             }
@@ -126,7 +188,11 @@ define_class!(
                 return ptr::null_mut();
             }
 
-            let delegate = WebViewDelegate::new(self.ivars().proxy.clone(), self.ivars().window_id);
+            let delegate = WebViewDelegate::new(
+                self.ivars().proxy.clone(),
+                self.ivars().window_id,
+                self.ivars().allow_invalid_certs.get(),
+            );
             let delegate = unsafe { Retained::cast_unchecked(delegate) };
             set_webview_delegate(&view, &delegate);
 
@@ -135,6 +201,11 @@ define_class!(
             }
 
             let url = navigation_action_url(navigation_action);
+            let store: *mut AnyObject = unsafe { msg_send![config, websiteDataStore] };
+            let private = !store.is_null() && {
+                let persistent: Bool = unsafe { msg_send![store, isPersistent] };
+                !persistent.as_bool()
+            };
             let Some(popup_view) = (unsafe {
                 Retained::retain(Retained::as_ptr(&view).cast_mut())
             })
@@ -146,6 +217,7 @@ define_class!(
                 view: popup_view,
                 delegate,
                 url,
+                private,
             });
 
             let event = Event::new(
@@ -169,6 +241,76 @@ define_class!(
             let event = Event::new(EventType::CloseTab(tab_id), self.ivars().window_id);
             let _ = self.ivars().proxy.send_event(event);
         }
+
+        #[unsafe(method(observeValueForKeyPath:ofObject:change:context:))]
+        fn observe_value(
+            &self,
+            _key_path: *mut AnyObject,
+            object: *mut AnyObject,
+            _change: *mut AnyObject,
+            _context: *mut c_void,
+        ) {
+            let Some(webview) = (unsafe { object.as_ref() }) else {
+                return;
+            };
+            let Some(tab_id) = webview_tab_id(webview) else {
+                return;
+            };
+
+            let loading: Bool = unsafe { msg_send![webview, isLoading] };
+            let payload = if loading.as_bool() {
+                let progress: f64 = unsafe { msg_send![webview, estimatedProgress] };
+                EventType::WebLoadProgress { progress }
+            } else {
+                EventType::WebLoadFinished
+            };
+
+            let event = Event::for_tab(payload, self.ivars().window_id, tab_id);
+            let _ = self.ivars().proxy.send_event(event);
+        }
+
+        #[unsafe(method(webView:didFailProvisionalNavigation:withError:))]
+        fn did_fail_provisional_navigation(
+            &self,
+            webview: *mut AnyObject,
+            _navigation: *mut AnyObject,
+            error: *mut AnyObject,
+        ) {
+            self.send_navigation_error(webview, error);
+        }
+
+        #[unsafe(method(webView:didFailNavigation:withError:))]
+        fn did_fail_navigation(
+            &self,
+            webview: *mut AnyObject,
+            _navigation: *mut AnyObject,
+            error: *mut AnyObject,
+        ) {
+            self.send_navigation_error(webview, error);
+        }
+
+        #[unsafe(method(webView:didReceiveAuthenticationChallenge:completionHandler:))]
+        fn did_receive_authentication_challenge(
+            &self,
+            _webview: *mut AnyObject,
+            challenge: *mut AnyObject,
+            completion_handler: &block2::Block<dyn Fn(NSInteger, *mut AnyObject)>,
+        ) {
+            let credential = if self.ivars().allow_invalid_certs.get() {
+                server_trust_credential(challenge)
+            } else {
+                None
+            };
+
+            // Without a credential WebKit validates the certificate and fails the navigation,
+            // which is then reported through `didFailProvisionalNavigation`.
+            match credential {
+                Some(credential) => {
+                    completion_handler.call((AUTH_CHALLENGE_USE_CREDENTIAL, credential))
+                },
+                None => completion_handler.call((AUTH_CHALLENGE_DEFAULT_HANDLING, ptr::null_mut())),
+            }
+        }
     }
 );
 
@@ -186,13 +328,83 @@ thread_local! {
 }
 
 impl WebViewDelegate {
-    fn new(proxy: EventLoopProxy<Event>, window_id: WindowId) -> Retained<Self> {
+    fn new(
+        proxy: EventLoopProxy<Event>,
+        window_id: WindowId,
+        allow_invalid_certs: bool,
+    ) -> Retained<Self> {
         let mtm = MainThreadMarker::new()
             .expect("WebView delegate must be created on the main thread");
+        let allow_invalid_certs = Cell::new(allow_invalid_certs);
         let this = WebViewDelegate::alloc(mtm)
-            .set_ivars(WebViewDelegateIvars { proxy, window_id });
+            .set_ivars(WebViewDelegateIvars { proxy, window_id, allow_invalid_certs });
         unsafe { msg_send![super(this), init] }
     }
+
+    /// Forward a failed page load of `webview` to the event loop.
+    fn send_navigation_error(&self, webview: *mut AnyObject, error: *mut AnyObject) {
+        let Some(webview) = (unsafe { webview.as_ref() }) else {
+            return;
+        };
+        let Some(tab_id) = webview_tab_id(webview) else {
+            return;
+        };
+        let Some(error) = navigation_error(error) else {
+            return;
+        };
+
+        let payload = EventType::WebNavigationError(error);
+        let event = Event::for_tab(payload, self.ivars().window_id, tab_id);
+        let _ = self.ivars().proxy.send_event(event);
+    }
+}
+
+/// Convert an `NSError` of a failed navigation, skipping errors of interrupted loads.
+fn navigation_error(error: *mut AnyObject) -> Option<WebNavigationError> {
+    if error.is_null() {
+        return None;
+    }
+
+    let code: NSInteger = unsafe { msg_send![error, code] };
+    if code == URL_ERROR_CANCELLED || code == FRAME_LOAD_INTERRUPTED {
+        return None;
+    }
+
+    let description: *mut AnyObject = unsafe { msg_send![error, localizedDescription] };
+    let message = if description.is_null() {
+        String::from("Unknown error")
+    } else {
+        unsafe { &*(description as *const NSString) }.to_string()
+    };
+
+    Some(WebNavigationError { code: code as i64, message })
+}
+
+/// Credential trusting the server of a TLS authentication challenge.
+fn server_trust_credential(challenge: *mut AnyObject) -> Option<*mut AnyObject> {
+    if challenge.is_null() {
+        return None;
+    }
+
+    let space: *mut AnyObject = unsafe { msg_send![challenge, protectionSpace] };
+    if space.is_null() {
+        return None;
+    }
+
+    let method: *mut AnyObject = unsafe { msg_send![space, authenticationMethod] };
+    let method = unsafe { method.cast::<NSString>().as_ref() }?.to_string();
+    if method != "NSURLAuthenticationMethodServerTrust" {
+        return None;
+    }
+
+    let trust: SecTrustRef = unsafe { msg_send![space, serverTrust] };
+    if trust.0.is_null() {
+        return None;
+    }
+
+    let credential: *mut AnyObject =
+        unsafe { msg_send![class!(NSURLCredential), credentialForTrust: trust] };
+    (!credential.is_null()).then_some(credential)
 }
 
 fn webview_key(view: &AnyObject) -> usize {
@@ -225,6 +437,11 @@ fn unregister_webview_tab(view: &AnyObject) {
     });
 }
 
+fn webview_tab_id(view: &AnyObject) -> Option<TabId> {
+    let key = webview_key(view);
+    WEBVIEW_TAB_IDS.with(|cell| cell.borrow().get(&key).copied())
+}
+
 fn take_webview_tab_id(view: &AnyObject) -> Option<TabId> {
     let key = webview_key(view);
     WEBVIEW_TAB_IDS.with(|cell| cell.borrow_mut().remove(&key))
@@ -237,6 +454,33 @@ fn set_webview_delegate(view: &AnyObject, delegate: &AnyObject) {
     }
 }
 
+/// Report loading progress of `view` to `delegate` through key-value observing.
+fn observe_loading(view: &AnyObject, delegate: &AnyObject) {
+    // NSKeyValueObservingOptionNew.
+    let options: NSUInteger = 1;
+    for key_path in LOADING_KEY_PATHS {
+        let key_path = NSString::from_str(key_path);
+        unsafe {
+            let _: () = msg_send![
+                view,
+                addObserver: delegate,
+                forKeyPath: &*key_path,
+                options: options,
+                context: ptr::null_mut::<c_void>()
+            ];
+        }
+    }
+}
+
+fn unobserve_loading(view: &AnyObject, delegate: &AnyObject) {
+    for key_path in LOADING_KEY_PATHS {
+        let key_path = NSString::from_str(key_path);
+        unsafe {
+            let _: () = msg_send![view, removeObserver: delegate, forKeyPath: &*key_path];
+        }
+    }
+}
+
 fn safari_user_agent(view: &AnyObject) -> Result<String, Box<dyn Error>> {
     let key = NSString::from_str("userAgent");
     let value: *mut AnyObject = unsafe { msg_send![view, valueForKey: &*key] };
@@ -329,13 +573,241 @@ fn navigation_action_url(navigation_action: *mut AnyObject) -> Option<String> {
     Some(unsafe { &*(absolute as *const NSString) }.to_string())
 }
 
-fn configure_webview_config(config: &AnyObject) -> Result<(), Box<dyn Error>> {
+/// Website data removed by [`clear_website_data`] and [`WebView::clear_site_data`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WebsiteDataTypes {
+    pub cookies: bool,
+    pub cache: bool,
+    pub storage: bool,
+}
+
+impl WebsiteDataTypes {
+    /// Set of `WKWebsiteDataStore` type names, every type when none was selected.
+    fn to_set(self) -> *mut AnyObject {
+        if !self.cookies && !self.cache && !self.storage {
+            return unsafe { msg_send![class!(WKWebsiteDataStore), allWebsiteDataTypes] };
+        }
+
+        let mut names = Vec::new();
+        if self.cookies {
+            names.push("WKWebsiteDataTypeCookies");
+        }
+        if self.cache {
+            names.extend(CACHE_DATA_TYPES);
+        }
+        if self.storage {
+            names.extend(STORAGE_DATA_TYPES);
+        }
+
+        let set: *mut AnyObject = unsafe { msg_send![class!(NSMutableSet), set] };
+        for name in names {
+            let name = NSString::from_str(name);
+            unsafe {
+                let _: () = msg_send![set, addObject: &*name];
+            }
+        }
+        set
+    }
+}
+
+/// Remove website data of all sites modified within the last `since`, or ever.
+///
+/// Only the persistent data store is cleared, private tabs keep their data until closed.
+pub fn clear_website_data<F>(types: WebsiteDataTypes, since: Option<Duration>, callback: F)
+where
+    F: FnOnce() + 'static,
+{
+    let _mtm = MainThreadMarker::new().expect("Website data requires main thread");
+
+    let store: *mut AnyObject = unsafe { msg_send![class!(WKWebsiteDataStore), defaultDataStore] };
+    let date: *mut AnyObject = match since {
+        Some(since) => unsafe {
+            msg_send![class!(NSDate), dateWithTimeIntervalSinceNow: -since.as_secs_f64()]
+        },
+        None => unsafe { msg_send![class!(NSDate), distantPast] },
+    };
+
+    let block = completion_block(callback);
+    unsafe {
+        let _: () = msg_send![
+            store,
+            removeDataOfTypes: types.to_set(),
+            modifiedSince: date,
+            completionHandler: &*block
+        ];
+    }
+}
+
+/// Whether a website data record named `site` holds data of `host`.
+///
+/// Records are named after the registrable domain, like `example.com` for `www.example.com`.
+fn site_matches_host(site: &str, host: &str) -> bool {
+    let site = site.to_ascii_lowercase();
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    host == site || host.strip_suffix(&site).is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Completion handler without arguments which runs `callback` once.
+fn completion_block<F>(callback: F) -> RcBlock<dyn Fn()>
+where
+    F: FnOnce() + 'static,
+{
+    let callback = RefCell::new(Some(callback));
+    RcBlock::new(move || {
+        if let Some(callback) = callback.borrow_mut().take() {
+            callback();
+        }
+    })
+}
+
+fn history_items(items: *mut AnyObject) -> Vec<(String, String)> {
+    if items.is_null() {
+        return Vec::new();
+    }
+
+    let count: NSUInteger = unsafe { msg_send![items, count] };
+    let mut entries = Vec::new();
+    for index in 0..count {
+        let item: *mut AnyObject = unsafe { msg_send![items, objectAtIndex: index] };
+        entries.extend(history_item(item));
+    }
+    entries
+}
+
+fn history_item(item: *mut AnyObject) -> Option<(String, String)> {
+    if item.is_null() {
+        return None;
+    }
+
+    // Entries are kept even without a URL so positions match `itemAtIndex:` offsets.
+    let url: *mut AnyObject = unsafe { msg_send![item, URL] };
+    let absolute: *mut AnyObject =
+        if url.is_null() { url } else { unsafe { msg_send![url, absoluteString] } };
+    let url = if absolute.is_null() {
+        String::new()
+    } else {
+        unsafe { &*(absolute as *const NSString) }.to_string()
+    };
+
+    let title: *mut AnyObject = unsafe { msg_send![item, title] };
+    let title = if title.is_null() {
+        String::new()
+    } else {
+        unsafe { &*(title as *const NSString) }.to_string()
+    };
+
+    Some((url, title))
+}
+
+fn configure_webview_config(
+    config: &AnyObject,
+    color_scheme: ColorScheme,
+    user_scripts: &[UserScript],
+) -> Result<(), Box<dyn Error>> {
     enable_web_authentication(config)?;
     enable_web_inspector(config)?;
     enable_web_popups(config)?;
+
+    let controller: *mut AnyObject = unsafe { msg_send![config, userContentController] };
+    if let Some(controller) = unsafe { controller.as_ref() } {
+        if let Some(source) = color_scheme.injection_source() {
+            add_controller_script(controller, &source, false);
+        }
+
+        for script in user_scripts {
+            if let Some(source) = user_script_source(script) {
+                add_controller_script(controller, &source, script.run_at == RunAt::DocumentEnd);
+            }
+        }
+    }
+
     Ok(())
 }
 
+/// Register a script with a `WKUserContentController`.
+///
+/// Scripts with the same injection time run in the order they were added.
+fn add_controller_script(controller: &AnyObject, source: &str, at_end: bool) {
+    // WKUserScriptInjectionTimeAtDocumentStart/End.
+    let injection_time: NSInteger = if at_end { 1 } else { 0 };
+
+    let source = NSString::from_str(source);
+    let script: *mut AnyObject = unsafe { msg_send![class!(WKUserScript), alloc] };
+    let script: *mut AnyObject = unsafe {
+        msg_send![
+            script,
+            initWithSource: &*source,
+            injectionTime: injection_time,
+            forMainFrameOnly: Bool::YES
+        ]
+    };
+    let Some(script) = (unsafe { Retained::from_raw(script) }) else {
+        return;
+    };
+
+    unsafe {
+        let _: () = msg_send![controller, addUserScript: &*script];
+    }
+}
+
+/// Override the appearance WebKit reports to pages through `prefers-color-scheme`.
+fn set_view_color_scheme(view: &AnyObject, scheme: ColorScheme) {
+    let name = match scheme {
+        ColorScheme::System => None,
+        ColorScheme::Light => Some(NSString::from_str("NSAppearanceNameAqua")),
+        ColorScheme::Dark => Some(NSString::from_str("NSAppearanceNameDarkAqua")),
+    };
+
+    let appearance: *mut AnyObject = match name {
+        Some(name) => unsafe { msg_send![class!(NSAppearance), appearanceNamed: &*name] },
+        None => ptr::null_mut(),
+    };
+    unsafe {
+        let _: () = msg_send![view, setAppearance: appearance];
+    }
+}
+
+/// Load a configured user script, guarded so it only runs on matching URLs.
+fn user_script_source(script: &UserScript) -> Option<String> {
+    let mut path = script.path.clone();
+    if let (Ok(stripped), Some(home_dir)) = (path.strip_prefix("~/"), home::home_dir()) {
+        path = home_dir.join(stripped);
+    }
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            warn!("Unable to read user script {path:?}: {err}");
+            return None;
+        },
+    };
+
+    let body = if path.extension().is_some_and(|extension| extension == "css") {
+        let css = serde_json::to_string(&contents).ok()?;
+        format!(
+            "const style = document.createElement('style');\n\
+             style.textContent = {css};\n\
+             (document.head || document.documentElement).appendChild(style);"
+        )
+    } else {
+        contents
+    };
+
+    let patterns = script
+        .matches
+        .iter()
+        .filter_map(|glob| serde_json::to_string(&glob.to_js_regex()).ok())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Some(format!(
+        "(() => {{\n\
+         if (![{patterns}].some((pattern) => new RegExp(pattern).test(location.href))) return;\n\
+         {body}\n\
+         }})();"
+    ))
+}
+
 fn install_mouse_monitor() -> Result<(), Box<dyn Error>> {
     MOUSE_MONITOR.with(|cell| {
         if cell.borrow().is_some() {
@@ -379,6 +851,8 @@ impl WebView {
         size_info: &SizeInfo,
         tab_id: TabId,
         url: &str,
+        private: bool,
+        web_config: &WebConfig,
         proxy: &EventLoopProxy<Event>,
     ) -> Result<Self, Box<dyn Error>> {
         let _mtm = MainThreadMarker::new().ok_or_else(|| {
@@ -399,9 +873,16 @@ impl WebView {
                     "Failed to allocate WKWebViewConfiguration",
                 )
             })?;
-            configure_webview_config(&*config)?;
-            let store: *mut AnyObject =
-                unsafe { msg_send![class!(WKWebsiteDataStore), defaultDataStore] };
+            configure_webview_config(
+                &*config,
+                web_config.force_color_scheme,
+                &web_config.user_scripts,
+            )?;
+            let store: *mut AnyObject = if private {
+                unsafe { msg_send![class!(WKWebsiteDataStore), nonPersistentDataStore] }
+            } else {
+                unsafe { msg_send![class!(WKWebsiteDataStore), defaultDataStore] }
+            };
             unsafe {
                 let _: () = msg_send![&*config, setWebsiteDataStore: store];
             }
@@ -418,17 +899,28 @@ impl WebView {
                 let _: () = msg_send![parent, addSubview: &*view];
             }
 
-            let delegate = WebViewDelegate::new(proxy.clone(), window.id());
+            let delegate =
+                WebViewDelegate::new(proxy.clone(), window.id(), web_config.allow_invalid_certs);
             let delegate = unsafe { Retained::cast_unchecked(delegate) };
             set_webview_delegate(&view, &delegate);
             register_webview_tab(&view, tab_id);
             apply_safari_user_agent(&view)?;
+            observe_loading(&view, &delegate);
+
+            set_view_color_scheme(&view, web_config.force_color_scheme);
 
             let mut web_view = Self {
                 view,
                 last_title: None,
                 last_url: None,
-                _delegate: delegate,
+                proxy: proxy.clone(),
+                window_id: window.id(),
+                tab_id,
+                page_zoom: 1.0,
+                muted: false,
+                custom_user_agent: None,
+                extra_headers: HashMap::new(),
+                delegate,
             };
             let initial_url = if url.is_empty() { "about:blank" } else { url };
             web_view.load_url(initial_url);
@@ -448,6 +940,7 @@ impl WebView {
         tab_id: TabId,
         view: Retained<AnyObject>,
         delegate: Retained<AnyObject>,
+        proxy: &EventLoopProxy<Event>,
     ) -> Result<Self, Box<dyn Error>> {
         let _mtm = MainThreadMarker::new().ok_or_else(|| {
             std::io::Error::new(
@@ -467,7 +960,9 @@ impl WebView {
                     "WKWebView has no configuration",
                 )
             })?;
-            configure_webview_config(config)?;
+            // Keep the data store and user scripts WebKit copied from the opener, so popups of
+            // private tabs stay private.
+            configure_webview_config(config, ColorScheme::System, &[])?;
 
             unsafe {
                 let _: () = msg_send![parent, addSubview: &*view];
@@ -482,12 +977,20 @@ impl WebView {
             set_webview_delegate(&view, &delegate);
             register_webview_tab(&view, tab_id);
             apply_safari_user_agent(&view)?;
+            observe_loading(&view, &delegate);
 
             Ok(Self {
                 view,
                 last_title: None,
                 last_url: None,
-                _delegate: delegate,
+                proxy: proxy.clone(),
+                window_id: window.id(),
+                tab_id,
+                page_zoom: 1.0,
+                muted: false,
+                custom_user_agent: None,
+                extra_headers: HashMap::new(),
+                delegate,
             })
         })();
 
@@ -503,6 +1006,7 @@ impl WebView {
             let _: () = msg_send![&*self.view, setHidden: !visible];
         }
         if visible {
+            self.apply_page_zoom();
             self.invalidate_cursor_rects();
         }
     }
@@ -512,9 +1016,81 @@ impl WebView {
         unsafe {
             let _: () = msg_send![&*self.view, setFrame: frame];
         }
+        self.apply_page_zoom();
         self.invalidate_cursor_rects();
     }
 
+    /// Height of the page viewport in CSS pixels.
+    pub fn viewport_height(&self) -> f64 {
+        let frame: CGRect = unsafe { msg_send![&*self.view, frame] };
+        frame.size.height as f64 / self.page_zoom
+    }
+
+    /// Current page zoom factor of this tab.
+    pub fn page_zoom(&self) -> f64 {
+        self.page_zoom
+    }
+
+    /// Set the page zoom factor, clamped to a usable range.
+    ///
+    /// The value is remembered so it can be restored after resizes and tab switches.
+    pub fn set_page_zoom(&mut self, zoom: f64) {
+        self.page_zoom = zoom.clamp(MIN_PAGE_ZOOM, MAX_PAGE_ZOOM);
+        self.apply_page_zoom();
+    }
+
+    fn apply_page_zoom(&mut self) {
+        let zoom = self.page_zoom as CGFloat;
+
+        let responds: Bool =
+            unsafe { msg_send![&*self.view, respondsToSelector: sel!(setPageZoom:)] };
+        if responds.as_bool() {
+            let current: CGFloat = unsafe { msg_send![&*self.view, pageZoom] };
+            if current != zoom {
+                unsafe {
+                    let _: () = msg_send![&*self.view, setPageZoom: zoom];
+                }
+            }
+            return;
+        }
+
+        // WebKit before macOS 11 only supports magnifying the whole view.
+        let current: CGFloat = unsafe { msg_send![&*self.view, magnification] };
+        if current != zoom {
+            let center = CGPoint { x: 0.0, y: 0.0 };
+            unsafe {
+                let _: () = msg_send![&*self.view, setMagnification: zoom, centeredAtPoint: center];
+            }
+        }
+    }
+
+    /// Whether audio of this tab is muted.
+    pub fn muted(&self) -> bool {
+        self.muted
+    }
+
+    /// Mute or unmute all audio played by the page.
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+
+        let responds: Bool =
+            unsafe { msg_send![&*self.view, respondsToSelector: sel!(_setPageMuted:)] };
+        if responds.as_bool() {
+            // _WKMediaAudioMuted.
+            let state: NSUInteger = if muted { 1 } else { 0 };
+            unsafe {
+                let _: () = msg_send![&*self.view, _setPageMuted: state];
+            }
+            return;
+        }
+
+        // Without the private API only media elements already in the document can be muted.
+        let script = format!(
+            "document.querySelectorAll('audio,video').forEach(m => m.muted = {muted});"
+        );
+        self.exec_js(&script);
+    }
+
     pub fn invalidate_cursor_rects(&self) {
         let window: *mut AnyObject = unsafe { msg_send![&*self.view, window] };
         if window.is_null() {
@@ -549,17 +1125,58 @@ impl WebView {
         }
 
         let request: *mut AnyObject =
-            unsafe { msg_send![class!(NSURLRequest), requestWithURL: ns_url] };
+            unsafe { msg_send![class!(NSMutableURLRequest), requestWithURL: ns_url] };
+        for (name, value) in request_headers(&self.extra_headers).unwrap_or_default() {
+            let name = NSString::from_str(&name);
+            let value = NSString::from_str(&value);
+            unsafe {
+                let _: () = msg_send![request, setValue: &*value, forHTTPHeaderField: &*name];
+            }
+        }
         let _: *mut AnyObject = unsafe { msg_send![&*self.view, loadRequest: request] };
         true
     }
 
+    /// Replace the user agent, an empty agent restores the Safari user agent.
+    pub fn set_custom_user_agent(&mut self, user_agent: &str) -> Result<(), Box<dyn Error>> {
+        if user_agent.is_empty() {
+            apply_safari_user_agent(&self.view)?;
+            self.custom_user_agent = None;
+            return Ok(());
+        }
+
+        let agent = NSString::from_str(user_agent);
+        unsafe {
+            let _: () = msg_send![&*self.view, setCustomUserAgent: &*agent];
+        }
+        self.custom_user_agent = Some(user_agent.into());
+
+        Ok(())
+    }
+
+    /// Replace the headers added to pages loaded through [`Self::load_url`].
+    pub fn set_extra_headers(&mut self, headers: HashMap<String, String>) {
+        self.extra_headers = headers;
+    }
+
+    /// Whether the user agent or request headers were overridden.
+    pub fn has_request_overrides(&self) -> bool {
+        self.custom_user_agent.is_some() || !self.extra_headers.is_empty()
+    }
+
     pub fn reload(&mut self) {
         unsafe {
             let _: () = msg_send![&*self.view, reload];
         }
     }
 
+    /// Reload the page, revalidating cached resources with the server.
+    pub fn reload_ignoring_cache(&mut self) {
+        unsafe {
+            let _: () = msg_send![&*self.view, reloadFromOrigin];
+        }
+    }
+
     pub fn go_back(&mut self) {
         unsafe {
             let _: () = msg_send![&*self.view, goBack];
@@ -572,6 +1189,66 @@ impl WebView {
         }
     }
 
+    /// Back-forward list as `(url, title)` pairs, oldest entry first.
+    ///
+    /// Titles may be empty for pages that are still loading.
+    pub fn back_forward_list(&self) -> Vec<(String, String)> {
+        let list: *mut AnyObject = unsafe { msg_send![&*self.view, backForwardList] };
+        if list.is_null() {
+            return Vec::new();
+        }
+
+        let back: *mut AnyObject = unsafe { msg_send![list, backList] };
+        let current: *mut AnyObject = unsafe { msg_send![list, currentItem] };
+        let forward: *mut AnyObject = unsafe { msg_send![list, forwardList] };
+
+        let mut entries = history_items(back);
+        entries.extend(history_item(current));
+        entries.extend(history_items(forward));
+        entries
+    }
+
+    /// Position of the current page in [`WebView::back_forward_list`].
+    pub fn back_forward_index(&self) -> Option<usize> {
+        let list: *mut AnyObject = unsafe { msg_send![&*self.view, backForwardList] };
+        if list.is_null() {
+            return None;
+        }
+
+        let current: *mut AnyObject = unsafe { msg_send![list, currentItem] };
+        if current.is_null() {
+            return None;
+        }
+
+        let back: *mut AnyObject = unsafe { msg_send![list, backList] };
+        if back.is_null() {
+            return Some(0);
+        }
+        let count: NSUInteger = unsafe { msg_send![back, count] };
+        Some(count as usize)
+    }
+
+    /// Navigate to an entry of [`WebView::back_forward_list`].
+    ///
+    /// Returns `false` if there is no entry at `index`.
+    pub fn go_to_index(&mut self, index: usize) -> bool {
+        let Some(current) = self.back_forward_index() else {
+            return false;
+        };
+
+        let list: *mut AnyObject = unsafe { msg_send![&*self.view, backForwardList] };
+        let offset = index as NSInteger - current as NSInteger;
+        let item: *mut AnyObject = unsafe { msg_send![list, itemAtIndex: offset] };
+        if item.is_null() {
+            return false;
+        }
+
+        unsafe {
+            let _: *mut AnyObject = msg_send![&*self.view, goToBackForwardListItem: item];
+        }
+        true
+    }
+
     pub fn handle_mouse_input(
         &mut self,
         window: &Window,
@@ -695,6 +1372,68 @@ impl WebView {
         self.eval_js_string(script, |_| {});
     }
 
+    /// Scroll the page to an absolute position in CSS pixels.
+    pub fn scroll_to(&mut self, x: f64, y: f64) {
+        self.exec_js(&format!("window.scrollTo({x}, {y});"));
+    }
+
+    /// Read the page's scroll offset in CSS pixels.
+    ///
+    /// The callback receives `None` when the page could not run the script.
+    pub fn scroll_position<F>(&mut self, callback: F)
+    where
+        F: FnOnce(Option<(f64, f64)>) + 'static,
+    {
+        let script = "JSON.stringify([window.scrollX, window.scrollY])";
+        self.eval_js_string(script, move |result| {
+            let position = result.and_then(|result| serde_json::from_str(&result).ok());
+            callback(position);
+        });
+    }
+
+    /// Inject a script into every document loaded after this call.
+    ///
+    /// Document start scripts run before any page script, document end scripts run once the DOM
+    /// is parsed. Scripts with the same injection time run in the order they were added.
+    pub fn add_user_script(&mut self, source: &str, at_end: bool) {
+        let config: *mut AnyObject = unsafe { msg_send![&*self.view, configuration] };
+        let controller: *mut AnyObject = unsafe { msg_send![config, userContentController] };
+        if let Some(controller) = unsafe { controller.as_ref() } {
+            add_controller_script(controller, source, at_end);
+        }
+    }
+
+    /// Replace all injected scripts with the configured color scheme and user scripts.
+    pub fn set_user_scripts(&mut self, web_config: &WebConfig) {
+        let config: *mut AnyObject = unsafe { msg_send![&*self.view, configuration] };
+        let controller: *mut AnyObject = unsafe { msg_send![config, userContentController] };
+        unsafe {
+            let _: () = msg_send![controller, removeAllUserScripts];
+        }
+
+        if let Some(source) = web_config.force_color_scheme.injection_source() {
+            self.add_user_script(&source, false);
+        }
+
+        for script in &web_config.user_scripts {
+            if let Some(source) = user_script_source(script) {
+                self.add_user_script(&source, script.run_at == RunAt::DocumentEnd);
+            }
+        }
+    }
+
+    /// Render pages with a fixed `prefers-color-scheme`, or follow the system appearance.
+    pub fn set_color_scheme(&mut self, scheme: ColorScheme) {
+        set_view_color_scheme(&self.view, scheme);
+    }
+
+    /// Accept or reject TLS certificates which fail validation on future navigations.
+    pub fn set_allow_invalid_certs(&mut self, allow_invalid_certs: bool) {
+        // SAFETY: Delegates of tab WebViews are always created by `WebViewDelegate::new`.
+        let delegate = unsafe { &*Retained::as_ptr(&self.delegate).cast::<WebViewDelegate>() };
+        delegate.ivars().allow_invalid_certs.set(allow_invalid_certs);
+    }
+
     pub fn eval_js_string<F>(&mut self, script: &str, callback: F)
     where
         F: FnOnce(Option<String>) + 'static,
@@ -741,13 +1480,123 @@ impl WebView {
         }
     }
 
-    pub fn poll_title(&mut self) -> Option<String> {
+    /// Capture the visible page as PNG data.
+    ///
+    /// The callback receives `None` when the page has not finished loading or the snapshot
+    /// could not be encoded.
+    pub fn snapshot<F>(&mut self, callback: F)
+    where
+        F: FnOnce(Option<Vec<u8>>) + 'static,
+    {
+        let _mtm = MainThreadMarker::new().expect("WebView snapshot requires main thread");
+
+        let url: *mut AnyObject = unsafe { msg_send![&*self.view, URL] };
+        let loading: Bool = unsafe { msg_send![&*self.view, isLoading] };
+        if url.is_null() || loading.as_bool() {
+            callback(None);
+            return;
+        }
+
+        let callback = Rc::new(RefCell::new(Some(callback)));
+        let block = RcBlock::new({
+            let callback = Rc::clone(&callback);
+            move |image: *mut AnyObject, error: *mut AnyObject| {
+                let Some(callback) = callback.borrow_mut().take() else {
+                    return;
+                };
+
+                if !error.is_null() || image.is_null() {
+                    callback(None);
+                    return;
+                }
+
+                callback(png_from_image(image));
+            }
+        });
+
+        unsafe {
+            let _: () = msg_send![
+                &*self.view,
+                takeSnapshotWithConfiguration: ptr::null_mut::<AnyObject>(),
+                completionHandler: &*block
+            ];
+        }
+    }
+
+    /// Search the page using WebKit's native find.
+    ///
+    /// The match result is reported asynchronously through [`EventType::WebFindResult`]. Returns
+    /// `false` without searching when the running WebKit has no native find support.
+    pub fn find(&mut self, query: &str, backwards: bool, wrap: bool, case_sensitive: bool) -> bool {
+        let _mtm = MainThreadMarker::new().expect("WebView find requires main thread");
+
+        let selector = sel!(findString:withConfiguration:completionHandler:);
+        let responds: Bool = unsafe { msg_send![&*self.view, respondsToSelector: selector] };
+        if !responds.as_bool() {
+            return false;
+        }
+
+        let class_name = CStr::from_bytes_with_nul(b"WKFindConfiguration\0").expect("static CStr");
+        let Some(config_class) = AnyClass::get(class_name) else {
+            return false;
+        };
+
+        let config: *mut AnyObject = unsafe { msg_send![config_class, new] };
+        let Some(config) = (unsafe { Retained::from_raw(config) }) else {
+            return false;
+        };
+
+        unsafe {
+            let _: () = msg_send![&*config, setBackwards: Bool::new(backwards)];
+            let _: () = msg_send![&*config, setWraps: Bool::new(wrap)];
+            let _: () = msg_send![&*config, setCaseSensitive: Bool::new(case_sensitive)];
+        }
+
+        let proxy = self.proxy.clone();
+        let window_id = self.window_id;
+        let tab_id = self.tab_id;
+        let block = RcBlock::new(move |result: *mut AnyObject| {
+            let matched = !result.is_null() && {
+                let found: Bool = unsafe { msg_send![result, matchFound] };
+                found.as_bool()
+            };
+
+            let event = Event::for_tab(EventType::WebFindResult { matched }, window_id, tab_id);
+            let _ = proxy.send_event(event);
+        });
+
+        let query = NSString::from_str(query);
+        unsafe {
+            let _: () = msg_send![
+                &*self.view,
+                findString: &*query,
+                withConfiguration: &*config,
+                completionHandler: &*block
+            ];
+        }
+
+        true
+    }
+
+    /// Remove the highlight left behind by [`WebView::find`].
+    ///
+    /// Native find marks the match by selecting it, so dropping the selection clears it.
+    pub fn clear_find(&mut self) {
+        self.exec_js("window.getSelection().removeAllRanges();");
+    }
+
+    /// Current title of the page.
+    pub fn title(&self) -> Option<String> {
         let title: *mut AnyObject = unsafe { msg_send![&*self.view, title] };
         if title.is_null() {
             return None;
         }
 
-        let title = unsafe { &*(title as *const NSString) }.to_string();
+        Some(unsafe { &*(title as *const NSString) }.to_string())
+    }
+
+    pub fn poll_title(&mut self) -> Option<String> {
+        let title = self.title()?;
         if self.last_title.as_deref() == Some(&title) {
             return None;
         }
@@ -776,6 +1625,75 @@ impl WebView {
         Some(url)
     }
 
+    /// Remove website data of the current page's site from the tab's data store.
+    ///
+    /// The callback receives `false` without removing anything when the page has no host.
+    pub fn clear_site_data<F>(&mut self, types: WebsiteDataTypes, callback: F)
+    where
+        F: FnOnce(bool) + 'static,
+    {
+        let _mtm = MainThreadMarker::new().expect("Website data requires main thread");
+
+        let url: *mut AnyObject = unsafe { msg_send![&*self.view, URL] };
+        let host: *mut AnyObject =
+            if url.is_null() { ptr::null_mut() } else { unsafe { msg_send![url, host] } };
+        let Some(host) = (unsafe { host.cast::<NSString>().as_ref() }).map(|host| host.to_string())
+        else {
+            return callback(false);
+        };
+
+        let config: *mut AnyObject = unsafe { msg_send![&*self.view, configuration] };
+        let store: *mut AnyObject = unsafe { msg_send![config, websiteDataStore] };
+        let Some(store) = (unsafe { Retained::retain(store) }) else {
+            return callback(false);
+        };
+
+        let callback = RefCell::new(Some(callback));
+        let block = RcBlock::new({
+            let store = store.clone();
+            move |records: *mut AnyObject| {
+                let Some(callback) = callback.borrow_mut().take() else {
+                    return;
+                };
+
+                let matching: *mut AnyObject = unsafe { msg_send![class!(NSMutableArray), array] };
+                let count: NSUInteger =
+                    if records.is_null() { 0 } else { unsafe { msg_send![records, count] } };
+                for index in 0..count {
+                    let record: *mut AnyObject = unsafe { msg_send![records, objectAtIndex: index] };
+                    let name: *mut AnyObject = unsafe { msg_send![record, displayName] };
+                    let Some(name) = (unsafe { name.cast::<NSString>().as_ref() }) else {
+                        continue;
+                    };
+
+                    if site_matches_host(&name.to_string(), &host) {
+                        unsafe {
+                            let _: () = msg_send![matching, addObject: record];
+                        }
+                    }
+                }
+
+                let block = completion_block(move || callback(true));
+                unsafe {
+                    let _: () = msg_send![
+                        &*store,
+                        removeDataOfTypes: types.to_set(),
+                        forDataRecords: matching,
+                        completionHandler: &*block
+                    ];
+                }
+            }
+        });
+
+        unsafe {
+            let _: () = msg_send![
+                &*store,
+                fetchDataRecordsOfTypes: types.to_set(),
+                completionHandler: &*block
+            ];
+        }
+    }
+
     pub fn current_url(&self) -> Option<String> {
         let url: *mut AnyObject = unsafe { msg_send![&*self.view, URL] };
         if url.is_null() {
@@ -791,8 +1709,36 @@ impl WebView {
     }
 
     pub fn show_inspector(&mut self) -> bool {
-        let inspector: *mut AnyObject = unsafe { msg_send![&*self.view, _inspector] };
-        if inspector.is_null() {
+        let Some(inspector) = self.inspector() else {
+            return false;
+        };
+
+        unsafe {
+            let _: () = msg_send![inspector, show];
+        }
+
+        true
+    }
+
+    /// Whether the Web Inspector of this page is open.
+    pub fn inspector_is_visible(&self) -> bool {
+        let Some(inspector) = self.inspector() else {
+            return false;
+        };
+        if !responds_to(inspector, sel!(isVisible)) {
+            return false;
+        }
+
+        let visible: Bool = unsafe { msg_send![inspector, isVisible] };
+        visible.as_bool()
+    }
+
+    /// Show the Web Inspector docked to a side of the page, or in a window of its own.
+    pub fn set_inspector_docked(&mut self, dock: InspectorDock) -> bool {
+        let Some(inspector) = self.inspector() else {
+            return false;
+        };
+        if !responds_to(inspector, sel!(attach)) || !responds_to(inspector, sel!(detach)) {
             return false;
         }
 
@@ -800,8 +1746,55 @@ impl WebView {
             let _: () = msg_send![inspector, show];
         }
 
+        let side: NSUInteger = match dock {
+            InspectorDock::Detached => {
+                unsafe {
+                    let _: () = msg_send![inspector, detach];
+                }
+                return true;
+            },
+            // WebKit's `InspectorAttachmentSide`.
+            InspectorDock::Bottom => 0,
+            InspectorDock::Right => 1,
+            InspectorDock::Left => 2,
+        };
+
+        let config: *mut AnyObject = unsafe { msg_send![&*self.view, configuration] };
+        let prefs: *mut AnyObject = match unsafe { config.as_ref() } {
+            Some(config) => unsafe { msg_send![config, preferences] },
+            None => ptr::null_mut(),
+        };
+        let prefs = unsafe { prefs.as_ref() };
+        match prefs.filter(|prefs| responds_to(prefs, sel!(_setInspectorAttachmentSide:))) {
+            Some(prefs) => unsafe {
+                // The side only applies when attaching, so move an attached inspector over.
+                let _: () = msg_send![inspector, detach];
+                let _: () = msg_send![prefs, _setInspectorAttachmentSide: side];
+            },
+            None => debug!("WebKit does not support choosing the Web Inspector side"),
+        }
+
+        unsafe {
+            let _: () = msg_send![inspector, attach];
+        }
+
         true
     }
+
+    /// The private `_WKInspector` of this page.
+    fn inspector(&self) -> Option<&AnyObject> {
+        if !responds_to(&self.view, sel!(_inspector)) {
+            return None;
+        }
+
+        let inspector: *mut AnyObject = unsafe { msg_send![&*self.view, _inspector] };
+        unsafe { inspector.as_ref() }
+    }
+}
+
+fn responds_to(object: &AnyObject, selector: Sel) -> bool {
+    let responds: Bool = unsafe { msg_send![object, respondsToSelector: selector] };
+    responds.as_bool()
 }
 
 fn enable_web_inspector(config: &AnyObject) -> Result<(), Box<dyn Error>> {
@@ -894,6 +1887,7 @@ fn enable_web_authentication(config: &AnyObject) -> Result<(), Box<dyn Error>> {
 
 impl Drop for WebView {
     fn drop(&mut self) {
+        unobserve_loading(&self.view, &self.delegate);
         unregister_webview_tab(&self.view);
         unsafe {
             let _: () = msg_send![&*self.view, removeFromSuperview];
@@ -902,6 +1896,38 @@ impl Drop for WebView {
     }
 }
 
+/// Encode an `NSImage` as PNG.
+fn png_from_image(image: *mut AnyObject) -> Option<Vec<u8>> {
+    // NSBitmapImageFileTypePNG.
+    const PNG_FILE_TYPE: NSUInteger = 4;
+
+    let tiff: *mut AnyObject = unsafe { msg_send![image, TIFFRepresentation] };
+    if tiff.is_null() {
+        return None;
+    }
+
+    let rep: *mut AnyObject = unsafe { msg_send![class!(NSBitmapImageRep), imageRepWithData: tiff] };
+    if rep.is_null() {
+        return None;
+    }
+
+    let properties: *mut AnyObject = unsafe { msg_send![class!(NSDictionary), dictionary] };
+    let png: *mut AnyObject = unsafe {
+        msg_send![rep, representationUsingType: PNG_FILE_TYPE, properties: properties]
+    };
+    if png.is_null() {
+        return None;
+    }
+
+    let length: NSUInteger = unsafe { msg_send![png, length] };
+    let bytes: *const c_void = unsafe { msg_send![png, bytes] };
+    if bytes.is_null() || length == 0 {
+        return None;
+    }
+
+    Some(unsafe { std::slice::from_raw_parts(bytes as *const u8, length) }.to_vec())
+}
+
 fn ns_view(window: &Window) -> Result<*mut AnyObject, Box<dyn Error>> {
     match window.raw_window_handle() {
         RawWindowHandle::AppKit(handle) => Ok(handle.ns_view.as_ptr() as *mut AnyObject),
@@ -929,3 +1955,18 @@ fn webview_frame(window: &Window, size_info: &SizeInfo) -> CGRect {
         size: CGSize { width, height },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn website_data_site_matches_host() {
+        assert!(site_matches_host("example.com", "example.com"));
+        assert!(site_matches_host("example.com", "www.Example.com."));
+        assert!(site_matches_host("Example.com", "a.b.example.com"));
+        assert!(!site_matches_host("example.com", "badexample.com"));
+        assert!(!site_matches_host("example.com", "example.com.evil.net"));
+        assert!(!site_matches_host("www.example.com", "example.com"));
+    }
+}
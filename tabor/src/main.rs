@@ -33,8 +33,11 @@ mod config;
 mod daemon;
 mod display;
 mod event;
+mod history;
 mod input;
 #[cfg(unix)]
+mod inspector;
+#[cfg(unix)]
 mod ipc;
 mod logging;
 #[cfg(target_os = "macos")]
@@ -45,8 +48,12 @@ mod migrate;
 mod panic;
 mod renderer;
 mod scheduler;
+mod session;
+#[cfg(unix)]
+mod session_script;
 mod string;
 mod tab_panel;
+mod tab_title;
 mod tabs;
 mod web_url;
 mod window_kind;
@@ -59,10 +66,16 @@ mod gl {
 
 #[cfg(unix)]
 use crate::cli::{
-    MessageOptions, MsgCloseTab, MsgCreateGroup, MsgCreateTab, MsgDispatchAction, MsgGetTabState,
-    MsgInspector, MsgInspectorAttach, MsgInspectorDetach, MsgInspectorPoll, MsgInspectorSend,
-    MsgMoveTab, MsgOpenInspector, MsgOpenUrl, MsgReloadWeb, MsgRunCommandBar, MsgSelectTab,
-    MsgSendInput, MsgSetGroupName, MsgSetTabPanel, MsgSetTabTitle, MsgSetWebUrl, TabIdArg,
+    MessageOptions, MsgClearWebData, MsgCloseTab, MsgCreateGroup, MsgCreateTab, MsgCreateWindow,
+    MsgDispatchAction, MsgDuplicateTab, MsgFocusWindow, MsgGetRenderStats, MsgGetSelection,
+    MsgGetTabContents, MsgGetTabState, MsgGetWebHistory, MsgGetWebScroll, MsgGoToHistoryIndex,
+    MsgHibernateGroup, MsgInspector, MsgInspectorAttach, MsgInspectorDetach, MsgInspectorPoll,
+    MsgInspectorSend, MsgMoveTab, MsgOpenExternal, MsgOpenInspector, MsgOpenUrl, MsgReloadWeb,
+    MsgRestoreClosedTab, MsgRunBookmarklet, MsgRunCommandBar, MsgSelectTab, MsgSendInput,
+    MsgSetGroupColor, MsgSetGroupDefaults, MsgSetGroupMuted, MsgSetGroupName, MsgSetInspectorDock,
+    MsgSetTabPanel, MsgSetTabTitle, MsgSetTabTitleTemplate, MsgSetWebHeaders, MsgSetWebMuted,
+    MsgSetWebScroll, MsgSetWebUrl, MsgSetWebZoom, MsgSnapshotTab, MsgSubscribe, MsgWaitTab,
+    TabIdArg,
 };
 #[cfg(unix)]
 use crate::cli::WindowOptions;
@@ -94,59 +107,134 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     match options.subcommands {
         #[cfg(unix)]
-        Some(Subcommands::Msg(options)) => msg(options)?,
+        Some(Subcommands::Msg(options)) => {
+            if let Err(err) = msg(*options) {
+                eprintln!("Error: {err}");
+                std::process::exit(err.exit_code());
+            }
+        },
         Some(Subcommands::Migrate(options)) => migrate::migrate(options),
+        Some(Subcommands::CheckConfig(options)) => config::check_config(options),
         None => tabor(options)?,
     }
 
     Ok(())
 }
 
+/// Failure of the `msg` subcommand.
+#[cfg(unix)]
+#[derive(Debug)]
+enum MsgError {
+    /// The request failed, either locally or with an IPC error reply.
+    Failed(String),
+    /// No Tabor socket could be reached.
+    Connection(io::Error),
+    /// The reply was missing or didn't answer the request.
+    UnexpectedReply(String),
+}
+
+#[cfg(unix)]
+impl MsgError {
+    /// Exit code of `tabor msg` for this failure.
+    fn exit_code(&self) -> i32 {
+        match self {
+            Self::Failed(_) => 1,
+            Self::Connection(_) => 2,
+            Self::UnexpectedReply(_) => 3,
+        }
+    }
+}
+
+#[cfg(unix)]
+impl std::fmt::Display for MsgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Failed(message) | Self::UnexpectedReply(message) => f.write_str(message),
+            Self::Connection(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl From<io::Error> for MsgError {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::InvalidData => Self::UnexpectedReply(err.to_string()),
+            _ => Self::Connection(err),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl From<serde_json::Error> for MsgError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Failed(err.to_string())
+    }
+}
+
+/// Check that a reply successfully answers a request.
+///
+/// Replies to raw messages which aren't a known request are only checked for errors.
+#[cfg(unix)]
+fn check_reply(
+    request: Option<&ipc::IpcRequest>,
+    reply: Option<&ipc::SocketReply>,
+) -> Result<(), MsgError> {
+    match reply {
+        None => Err(MsgError::UnexpectedReply(String::from("No reply received"))),
+        Some(ipc::SocketReply::Error { error }) => Err(MsgError::Failed(error.message.clone())),
+        Some(reply) if request.is_some_and(|request| !request.accepts_reply(reply)) => {
+            Err(MsgError::UnexpectedReply(format!("Unexpected reply: {reply:?}")))
+        },
+        Some(_) => Ok(()),
+    }
+}
+
 /// `msg` subcommand entrypoint.
 #[cfg(unix)]
 #[allow(unused_mut)]
-fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
+fn msg(mut options: MessageOptions) -> Result<(), MsgError> {
     fn ipc_tab_id(tab_id: TabIdArg) -> ipc::IpcTabId {
         ipc::IpcTabId { index: tab_id.index, generation: tab_id.generation }
     }
 
-    fn print_reply(reply: Option<ipc::SocketReply>) -> Result<(), Box<dyn Error>> {
-        if let Some(reply) = reply {
-            println!("{}", serde_json::to_string(&reply)?);
-            if let ipc::SocketReply::Error { error } = reply {
-                return Err(error.message.into());
-            }
+    fn print_reply(
+        request: Option<&ipc::IpcRequest>,
+        reply: Option<ipc::SocketReply>,
+    ) -> Result<(), MsgError> {
+        if let Some(reply) = &reply {
+            println!("{}", serde_json::to_string(reply)?);
         }
-        Ok(())
+        check_reply(request, reply.as_ref())
     }
 
-    fn send_request(
-        socket: &Option<PathBuf>,
-        request: ipc::IpcRequest,
-    ) -> Result<(), Box<dyn Error>> {
-        let reply = ipc::send_message(socket.clone(), request)?;
-        print_reply(reply)
+    fn send_request(socket: &Option<PathBuf>, request: ipc::IpcRequest) -> Result<(), MsgError> {
+        let reply = ipc::send_message(socket.clone(), request.clone())?;
+        print_reply(Some(&request), reply)
     }
 
     let socket = options.socket.clone();
+    let json = options.json;
 
     match options.message {
         crate::cli::MessageCommand::Config(config) => {
-            let reply = ipc::send_message(socket.clone(), ipc::IpcRequest::SetConfig(config))?;
-            if let Some(ipc::SocketReply::Error { error }) = reply {
-                return Err(error.message.into());
+            let request = ipc::IpcRequest::SetConfig(config);
+            let reply = ipc::send_message(socket.clone(), request.clone())?;
+            if json {
+                print_reply(Some(&request), reply)?;
+            } else {
+                check_reply(Some(&request), reply.as_ref())?;
             }
         },
         crate::cli::MessageCommand::GetConfig(config) => {
-            let reply = ipc::send_message(socket.clone(), ipc::IpcRequest::GetConfig(config))?;
+            let request = ipc::IpcRequest::GetConfig(config);
+            let reply = ipc::send_message(socket.clone(), request.clone())?;
             match reply {
+                reply if json => print_reply(Some(&request), reply)?,
                 Some(ipc::SocketReply::Config { config }) => {
                     println!("{}", serde_json::to_string(&config)?);
                 },
-                Some(ipc::SocketReply::Error { error }) => {
-                    return Err(error.message.into());
-                },
-                _ => (),
+                reply => check_reply(Some(&request), reply.as_ref())?,
             }
         },
         crate::cli::MessageCommand::Ping => {
@@ -155,9 +243,29 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
         crate::cli::MessageCommand::GetCapabilities => {
             send_request(&socket, ipc::IpcRequest::GetCapabilities)?;
         },
+        crate::cli::MessageCommand::Subscribe(MsgSubscribe { events }) => {
+            let request = ipc::IpcRequest::Subscribe { events: events.clone() };
+            for reply in ipc::subscribe(socket.clone(), events)? {
+                print_reply(Some(&request), Some(reply?))?;
+            }
+        },
         crate::cli::MessageCommand::ListTabs => {
             send_request(&socket, ipc::IpcRequest::ListTabs)?;
         },
+        crate::cli::MessageCommand::ExportSession => {
+            let request = ipc::IpcRequest::ListTabs;
+            let reply = ipc::send_message(socket.clone(), request.clone())?;
+            match reply {
+                reply if json => print_reply(Some(&request), reply)?,
+                Some(ipc::SocketReply::TabList { groups }) => {
+                    print!("{}", session_script::session_script(&groups));
+                },
+                reply => check_reply(Some(&request), reply.as_ref())?,
+            }
+        },
+        crate::cli::MessageCommand::ListWindows => {
+            send_request(&socket, ipc::IpcRequest::ListWindows)?;
+        },
         crate::cli::MessageCommand::GetTabState(MsgGetTabState { tab_id }) => {
             send_request(
                 &socket,
@@ -168,8 +276,10 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
         },
         crate::cli::MessageCommand::CreateTab(MsgCreateTab {
             web,
+            private,
             group_id,
             group_name,
+            background,
             terminal_options,
             window_identity,
         }) => {
@@ -177,21 +287,46 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
             tab_options.terminal_options = terminal_options;
             tab_options.window_identity = window_identity;
             tab_options.window_kind = match web {
-                Some(url) => WindowKind::Web { url },
+                Some(url) => WindowKind::Web { url, private },
                 None => WindowKind::Terminal,
             };
             send_request(
                 &socket,
                 ipc::IpcRequest::CreateTab {
-                    options: tab_options,
+                    options: Box::new(tab_options),
                     group_id,
                     group_name,
+                    focus: !background,
                 },
             )?;
         },
+        crate::cli::MessageCommand::CreateWindow(MsgCreateWindow {
+            web,
+            private,
+            window_options: mut options,
+        }) => {
+            if let Some(url) = web {
+                options.window_kind = WindowKind::Web { url, private };
+            }
+            send_request(&socket, ipc::IpcRequest::CreateWindow { options: Box::new(options) })?;
+        },
+        crate::cli::MessageCommand::FocusWindow(MsgFocusWindow { window_id }) => {
+            send_request(&socket, ipc::IpcRequest::FocusWindow { window_id })?;
+        },
+        crate::cli::MessageCommand::GetRenderStats(MsgGetRenderStats { window_id }) => {
+            send_request(&socket, ipc::IpcRequest::GetRenderStats { window_id })?;
+        },
         crate::cli::MessageCommand::CreateGroup(MsgCreateGroup { name }) => {
             send_request(&socket, ipc::IpcRequest::CreateGroup { name })?;
         },
+        crate::cli::MessageCommand::DuplicateTab(MsgDuplicateTab { tab_id }) => {
+            send_request(
+                &socket,
+                ipc::IpcRequest::DuplicateTab {
+                    tab_id: tab_id.map(ipc_tab_id),
+                },
+            )?;
+        },
         crate::cli::MessageCommand::CloseTab(MsgCloseTab { tab_id }) => {
             send_request(
                 &socket,
@@ -207,13 +342,15 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
             last,
             index,
             tab_id,
+            no_wrap,
         }) => {
+            let wrap = !no_wrap;
             let selection = if active {
                 ipc::TabSelection::Active
             } else if next {
-                ipc::TabSelection::Next
+                ipc::TabSelection::Next { wrap }
             } else if previous {
-                ipc::TabSelection::Previous
+                ipc::TabSelection::Previous { wrap }
             } else if last {
                 ipc::TabSelection::Last
             } else if let Some(index) = index {
@@ -227,6 +364,8 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
             tab_id,
             target_group_id,
             target_index,
+            before,
+            after,
         }) => {
             send_request(
                 &socket,
@@ -234,6 +373,8 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
                     tab_id: ipc_tab_id(tab_id),
                     target_group_id,
                     target_index,
+                    before: before.map(ipc_tab_id),
+                    after: after.map(ipc_tab_id),
                 },
             )?;
         },
@@ -247,6 +388,17 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
                 },
             )?;
         },
+        crate::cli::MessageCommand::SetTabTitleTemplate(MsgSetTabTitleTemplate {
+            tab_id,
+            template,
+            clear,
+        }) => {
+            let template = if clear { None } else { template };
+            send_request(
+                &socket,
+                ipc::IpcRequest::SetTabTitleTemplate { tab_id: tab_id.map(ipc_tab_id), template },
+            )?;
+        },
         crate::cli::MessageCommand::SetGroupName(MsgSetGroupName { group_id, name, clear }) => {
             let name = if clear { None } else { name };
             send_request(
@@ -254,8 +406,22 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
                 ipc::IpcRequest::SetGroupName { group_id, name },
             )?;
         },
-        crate::cli::MessageCommand::RestoreClosedTab => {
-            send_request(&socket, ipc::IpcRequest::RestoreClosedTab)?;
+        crate::cli::MessageCommand::SetGroupColor(MsgSetGroupColor { group_id, color, clear }) => {
+            let color = if clear { None } else { color };
+            send_request(&socket, ipc::IpcRequest::SetGroupColor { group_id, color })?;
+        },
+        crate::cli::MessageCommand::SetGroupDefaults(MsgSetGroupDefaults {
+            group_id,
+            cwd,
+            homepage,
+        }) => {
+            send_request(&socket, ipc::IpcRequest::SetGroupDefaults { group_id, cwd, homepage })?;
+        },
+        crate::cli::MessageCommand::RestoreClosedTab(MsgRestoreClosedTab { count }) => {
+            send_request(&socket, ipc::IpcRequest::RestoreClosedTab { count: Some(count) })?;
+        },
+        crate::cli::MessageCommand::ListClosedTabs => {
+            send_request(&socket, ipc::IpcRequest::ListClosedTabs)?;
         },
         crate::cli::MessageCommand::OpenUrl(MsgOpenUrl { url, new_tab, tab_id }) => {
             let target = if new_tab {
@@ -276,11 +442,129 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
                 },
             )?;
         },
-        crate::cli::MessageCommand::ReloadWeb(MsgReloadWeb { tab_id }) => {
+        crate::cli::MessageCommand::ReloadWeb(MsgReloadWeb { tab_id, ignore_cache }) => {
+            send_request(
+                &socket,
+                ipc::IpcRequest::ReloadWeb { tab_id: tab_id.map(ipc_tab_id), ignore_cache },
+            )?;
+        },
+        crate::cli::MessageCommand::OpenExternal(MsgOpenExternal { tab_id }) => {
+            send_request(
+                &socket,
+                ipc::IpcRequest::OpenExternal {
+                    tab_id: tab_id.map(ipc_tab_id),
+                },
+            )?;
+        },
+        crate::cli::MessageCommand::RunBookmarklet(MsgRunBookmarklet { name, tab_id }) => {
+            send_request(
+                &socket,
+                ipc::IpcRequest::RunBookmarklet { tab_id: tab_id.map(ipc_tab_id), name },
+            )?;
+        },
+        crate::cli::MessageCommand::SetWebZoom(MsgSetWebZoom { zoom, tab_id }) => {
+            send_request(
+                &socket,
+                ipc::IpcRequest::SetWebZoom {
+                    tab_id: tab_id.map(ipc_tab_id),
+                    zoom,
+                },
+            )?;
+        },
+        crate::cli::MessageCommand::SetWebMuted(MsgSetWebMuted { muted, tab_id }) => {
+            send_request(
+                &socket,
+                ipc::IpcRequest::SetWebMuted {
+                    tab_id: tab_id.map(ipc_tab_id),
+                    muted,
+                },
+            )?;
+        },
+        crate::cli::MessageCommand::SetGroupMuted(MsgSetGroupMuted { muted, group_id }) => {
+            send_request(&socket, ipc::IpcRequest::SetGroupMuted { group_id, muted })?;
+        },
+        crate::cli::MessageCommand::HibernateGroup(MsgHibernateGroup { group_id }) => {
+            send_request(&socket, ipc::IpcRequest::HibernateGroup { group_id })?;
+        },
+        crate::cli::MessageCommand::SetWebHeaders(MsgSetWebHeaders {
+            user_agent,
+            headers,
+            clear_headers,
+            tab_id,
+        }) => {
+            let headers = (clear_headers || !headers.is_empty())
+                .then(|| headers.into_iter().collect());
+            send_request(
+                &socket,
+                ipc::IpcRequest::SetWebHeaders {
+                    tab_id: tab_id.map(ipc_tab_id),
+                    user_agent,
+                    headers,
+                },
+            )?;
+        },
+        crate::cli::MessageCommand::ClearWebData(MsgClearWebData {
+            cookies,
+            cache,
+            storage,
+            since,
+            tab_id,
+        }) => {
+            send_request(
+                &socket,
+                ipc::IpcRequest::ClearWebData {
+                    tab_id: tab_id.map(ipc_tab_id),
+                    cookies,
+                    cache,
+                    storage,
+                    since_seconds: since,
+                },
+            )?;
+        },
+        crate::cli::MessageCommand::GetWebHistory(MsgGetWebHistory { tab_id }) => {
+            send_request(
+                &socket,
+                ipc::IpcRequest::GetWebHistory {
+                    tab_id: tab_id.map(ipc_tab_id),
+                },
+            )?;
+        },
+        crate::cli::MessageCommand::GetWebScroll(MsgGetWebScroll { tab_id }) => {
+            send_request(
+                &socket,
+                ipc::IpcRequest::GetWebScroll {
+                    tab_id: tab_id.map(ipc_tab_id),
+                },
+            )?;
+        },
+        crate::cli::MessageCommand::SetWebScroll(MsgSetWebScroll { x, y, tab_id }) => {
+            send_request(
+                &socket,
+                ipc::IpcRequest::SetWebScroll {
+                    tab_id: tab_id.map(ipc_tab_id),
+                    x,
+                    y,
+                },
+            )?;
+        },
+        crate::cli::MessageCommand::GoToHistoryIndex(MsgGoToHistoryIndex { index, tab_id }) => {
             send_request(
                 &socket,
-                ipc::IpcRequest::ReloadWeb {
+                ipc::IpcRequest::GoToHistoryIndex {
                     tab_id: tab_id.map(ipc_tab_id),
+                    index,
+                },
+            )?;
+        },
+        crate::cli::MessageCommand::SnapshotTab(MsgSnapshotTab { path, tab_id }) => {
+            // The file is written by the Tabor process, which may have a different cwd.
+            let path =
+                std::path::absolute(path).map_err(|err| MsgError::Failed(err.to_string()))?;
+            send_request(
+                &socket,
+                ipc::IpcRequest::SnapshotTab {
+                    tab_id: tab_id.map(ipc_tab_id),
+                    path,
                 },
             )?;
         },
@@ -292,6 +576,12 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
                 },
             )?;
         },
+        crate::cli::MessageCommand::SetInspectorDock(MsgSetInspectorDock { side, tab_id }) => {
+            send_request(
+                &socket,
+                ipc::IpcRequest::SetInspectorDock { tab_id: tab_id.map(ipc_tab_id), side },
+            )?;
+        },
         crate::cli::MessageCommand::GetTabPanel => {
             send_request(&socket, ipc::IpcRequest::GetTabPanel)?;
         },
@@ -318,6 +608,9 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
             mouse_action,
             esc,
             command,
+            scroll,
+            scroll_to_top,
+            scroll_to_bottom,
         }) => {
             let action = if let Some(name) = action {
                 ipc::IpcAction::Action { name }
@@ -342,8 +635,14 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
                     }
                 };
                 ipc::IpcAction::Command { program }
+            } else if let Some(lines) = scroll {
+                ipc::IpcAction::Scroll { lines }
+            } else if scroll_to_top {
+                ipc::IpcAction::ScrollToTop
+            } else if scroll_to_bottom {
+                ipc::IpcAction::ScrollToBottom
             } else {
-                return Err("No action provided".into());
+                return Err(MsgError::Failed(String::from("No action provided")));
             };
             send_request(
                 &socket,
@@ -353,15 +652,33 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
                 },
             )?;
         },
-        crate::cli::MessageCommand::SendInput(MsgSendInput { text, tab_id }) => {
+        crate::cli::MessageCommand::SendInput(MsgSendInput { text, escapes, tab_id }) => {
             send_request(
                 &socket,
                 ipc::IpcRequest::SendInput {
                     tab_id: tab_id.map(ipc_tab_id),
                     text,
+                    interpret_escapes: escapes,
+                },
+            )?;
+        },
+        crate::cli::MessageCommand::GetTabContents(MsgGetTabContents { scrollback, tab_id }) => {
+            send_request(
+                &socket,
+                ipc::IpcRequest::GetTabContents {
+                    tab_id: tab_id.map(ipc_tab_id),
+                    scrollback,
                 },
             )?;
         },
+        crate::cli::MessageCommand::GetSelection(MsgGetSelection { tab_id }) => {
+            let request = ipc::IpcRequest::GetSelection { tab_id: tab_id.map(ipc_tab_id) };
+            send_request(&socket, request)?;
+        },
+        crate::cli::MessageCommand::WaitTab(MsgWaitTab { tab_id, timeout }) => {
+            let tab_id = tab_id.map(ipc_tab_id);
+            send_request(&socket, ipc::IpcRequest::WaitTab { tab_id, timeout_ms: timeout })?;
+        },
         crate::cli::MessageCommand::RunCommandBar(MsgRunCommandBar { input, tab_id }) => {
             send_request(
                 &socket,
@@ -400,11 +717,13 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
                 )?;
             },
         },
-        crate::cli::MessageCommand::Send { json } => {
-            let reply = ipc::send_raw_message(socket, &json)?;
-            if let Some(reply) = reply {
-                println!("{}", serde_json::to_string(&reply)?);
-            }
+        crate::cli::MessageCommand::Send { message } => {
+            let request = serde_json::from_str::<ipc::IpcRequest>(&message).ok();
+            let reply = ipc::send_raw_message(socket, &message)?;
+            print_reply(request.as_ref(), reply)?;
+        },
+        crate::cli::MessageCommand::ListRequests if json => {
+            println!("{}", serde_json::to_string(&ipc::IpcRequestList::default())?);
         },
         crate::cli::MessageCommand::ListRequests => {
             println!("Available IPC request types:");
@@ -506,7 +825,8 @@ fn tabor(mut options: Options) -> Result<(), Box<dyn Error>> {
     // Create the IPC socket listener.
     #[cfg(unix)]
     let socket_path = if config.ipc_socket() {
-        match ipc::spawn_ipc_socket(&options, window_event_loop.create_proxy()) {
+        let token = config.general.ipc_token.clone();
+        match ipc::spawn_ipc_socket(&options, token, window_event_loop.create_proxy()) {
             Ok(path) => Some(path),
             Err(err) if options.daemon => return Err(err.into()),
             Err(err) => {
@@ -517,6 +837,23 @@ fn tabor(mut options: Options) -> Result<(), Box<dyn Error>> {
     } else {
         None
     };
+    #[cfg(unix)]
+    if socket_path.is_none() {
+        ipc::clear_socket_env();
+    }
+
+    // Create the IPC TCP listener.
+    #[cfg(unix)]
+    if let Some(spec) = &config.general.ipc_tcp {
+        let token = config.general.ipc_token.clone();
+        let proxy = window_event_loop.create_proxy();
+        let listener = ipc::tcp_listen_addr(spec, config.general.ipc_tcp_allow_remote)
+            .and_then(|addr| ipc::spawn_ipc_tcp_listener(addr, token, proxy));
+        match listener {
+            Ok(addr) => log::info!("Listening for IPC on tcp://{addr}"),
+            Err(err) => log::warn!("Unable to create IPC TCP listener: {err}"),
+        }
+    }
 
     // Setup automatic RAII cleanup for our files.
     let log_cleanup = log_file.filter(|_| !config.debug.persistent_logging);
@@ -574,3 +911,47 @@ fn log_config_path(config: &UiConfig) {
 
     info!("{msg}");
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use crate::ipc::{IpcError, IpcErrorCode, IpcRequest, SocketReply};
+
+    fn exit_code(request: Option<&IpcRequest>, reply: Option<&SocketReply>) -> i32 {
+        check_reply(request, reply).map_or_else(|err| err.exit_code(), |_| 0)
+    }
+
+    #[test]
+    fn msg_reply_exit_codes() {
+        let ping = IpcRequest::Ping;
+        assert_eq!(exit_code(Some(&ping), Some(&SocketReply::Pong)), 0);
+        assert_eq!(exit_code(Some(&ping), Some(&SocketReply::Ok)), 3);
+        assert_eq!(exit_code(Some(&ping), None), 3);
+
+        let error = IpcError::new(IpcErrorCode::NotFound, "Tab not found");
+        let reply = SocketReply::Error { error };
+        assert_eq!(exit_code(Some(&ping), Some(&reply)), 1);
+        assert_eq!(exit_code(None, Some(&reply)), 1);
+
+        let list_tabs = IpcRequest::ListTabs;
+        assert_eq!(exit_code(Some(&list_tabs), Some(&SocketReply::TabList { groups: vec![] })), 0);
+        assert_eq!(exit_code(Some(&list_tabs), Some(&SocketReply::Ok)), 3);
+
+        // Raw messages which aren't a known request accept any successful reply.
+        assert_eq!(exit_code(None, Some(&SocketReply::Ok)), 0);
+        assert_eq!(exit_code(None, None), 3);
+    }
+
+    #[test]
+    fn msg_io_error_exit_codes() {
+        let refused = io::Error::from(io::ErrorKind::ConnectionRefused);
+        assert_eq!(MsgError::from(refused).exit_code(), 2);
+
+        let not_found = io::Error::new(io::ErrorKind::NotFound, "no socket found");
+        assert_eq!(MsgError::from(not_found).exit_code(), 2);
+
+        let invalid = io::Error::new(io::ErrorKind::InvalidData, "Invalid IPC format");
+        assert_eq!(MsgError::from(invalid).exit_code(), 3);
+    }
+}
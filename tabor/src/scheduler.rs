@@ -28,9 +28,21 @@ pub enum Topic {
     DelayedSearch,
     BlinkCursor,
     BlinkTimeout,
+    Resize,
     ForegroundProcess,
     TabActivityTick,
+    SaveSession,
     WebCursor,
+    #[cfg(unix)]
+    TabWaitTimeout,
+    #[cfg(target_os = "macos")]
+    WebHibernation,
+    #[cfg(target_os = "macos")]
+    WebStatePoll,
+    #[cfg(target_os = "macos")]
+    TabPanelTooltip,
+    #[cfg(target_os = "macos")]
+    TabPanelDragScroll,
     Frame,
 }
 
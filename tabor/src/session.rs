@@ -0,0 +1,223 @@
+//! Tabs and groups persisted across restarts.
+
+#[cfg(windows)]
+use std::env;
+use std::fs;
+use std::io::{Error as IoError, Result as IoResult};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::WindowOptions;
+use crate::window_kind::WindowKind;
+
+/// Session file name inside the state directory.
+const SESSION_FILE: &str = "session.json";
+
+/// Serialized session file content.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+struct Session {
+    groups: Vec<SessionGroup>,
+}
+
+/// Tab group of a saved session.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct SessionGroup {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub tabs: Vec<SessionTab>,
+}
+
+/// Tab of a saved session.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SessionTab {
+    /// Title reported by the shell or page when the session was saved.
+    #[serde(default)]
+    pub title: String,
+
+    /// Title set by the user, which takes precedence over `title`.
+    #[serde(default)]
+    pub custom_title: Option<String>,
+
+    #[serde(flatten)]
+    pub kind: SessionTabKind,
+}
+
+impl SessionTab {
+    /// Whether this tab can be opened on the current platform.
+    fn is_supported(&self) -> bool {
+        cfg!(target_os = "macos") || matches!(self.kind, SessionTabKind::Terminal { .. })
+    }
+
+    /// Options for spawning the tab again.
+    pub fn window_options(&self) -> WindowOptions {
+        let mut options = WindowOptions::default();
+        self.apply(&mut options);
+        options
+    }
+
+    /// Replace the tab kind and working directory of `options` with this tab's.
+    pub fn apply(&self, options: &mut WindowOptions) {
+        match &self.kind {
+            SessionTabKind::Terminal { working_directory } => {
+                options.window_kind = WindowKind::Terminal;
+                options.terminal_options.working_directory =
+                    working_directory.clone().filter(|directory| directory.is_dir());
+            },
+            SessionTabKind::Web { url } => {
+                options.window_kind = WindowKind::Web { url: url.clone(), private: false };
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SessionTabKind {
+    Terminal {
+        #[serde(default)]
+        working_directory: Option<PathBuf>,
+    },
+    Web {
+        url: String,
+    },
+}
+
+/// Default location of the session file.
+#[cfg(not(windows))]
+pub fn session_path() -> Option<PathBuf> {
+    xdg::BaseDirectories::with_prefix("tabor").get_state_file(SESSION_FILE)
+}
+
+/// Default location of the session file.
+#[cfg(windows)]
+pub fn session_path() -> Option<PathBuf> {
+    let app_data = env::var_os("APPDATA")?;
+    Some(PathBuf::from(app_data).join("tabor").join(SESSION_FILE))
+}
+
+/// Write the session file, replacing any previous session.
+pub fn save_session(path: &Path, groups: &[SessionGroup]) -> IoResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let session = Session { groups: groups.to_vec() };
+    let json = serde_json::to_string_pretty(&session).map_err(IoError::other)?;
+
+    // Write through a temporary file, so a crash never leaves a truncated session behind.
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, json)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Read the groups of a session file.
+///
+/// Tabs which can't be opened on this platform and groups left without tabs are skipped.
+pub fn load_session(path: &Path) -> IoResult<Vec<SessionGroup>> {
+    let json = fs::read_to_string(path)?;
+    let session: Session = serde_json::from_str(&json).map_err(IoError::other)?;
+    let mut groups = session.groups;
+    for group in &mut groups {
+        group.tabs.retain(SessionTab::is_supported);
+    }
+    groups.retain(|group| !group.tabs.is_empty());
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_groups() -> Vec<SessionGroup> {
+        vec![
+            SessionGroup {
+                name: Some(String::from("work")),
+                tabs: vec![
+                    SessionTab {
+                        title: String::from("zsh"),
+                        custom_title: None,
+                        kind: SessionTabKind::Terminal {
+                            working_directory: Some(PathBuf::from("/tmp")),
+                        },
+                    },
+                    SessionTab {
+                        title: String::from("Example"),
+                        custom_title: Some(String::from("docs")),
+                        kind: SessionTabKind::Web { url: String::from("https://example.com/") },
+                    },
+                ],
+            },
+            SessionGroup {
+                name: None,
+                tabs: vec![SessionTab {
+                    title: String::new(),
+                    custom_title: None,
+                    kind: SessionTabKind::Terminal { working_directory: None },
+                }],
+            },
+        ]
+    }
+
+    #[test]
+    fn session_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join(SESSION_FILE);
+
+        let mut groups = session_groups();
+        for group in &mut groups {
+            group.tabs.retain(SessionTab::is_supported);
+        }
+        save_session(&path, &groups).unwrap();
+        assert_eq!(load_session(&path).unwrap(), groups);
+        assert!(!path.with_extension("json.tmp").exists());
+
+        save_session(&path, &groups[1..]).unwrap();
+        assert_eq!(load_session(&path).unwrap(), groups[1..]);
+    }
+
+    #[test]
+    fn session_skips_empty_groups() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SESSION_FILE);
+
+        let mut groups = session_groups();
+        groups.insert(0, SessionGroup { name: Some(String::from("empty")), tabs: Vec::new() });
+        save_session(&path, &groups).unwrap();
+        assert_eq!(load_session(&path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn session_tab_window_options() {
+        let groups = session_groups();
+
+        let options = groups[0].tabs[0].window_options();
+        assert_eq!(options.window_kind, WindowKind::Terminal);
+        assert_eq!(options.terminal_options.working_directory, Some(PathBuf::from("/tmp")));
+
+        let options = groups[0].tabs[1].window_options();
+        let url = String::from("https://example.com/");
+        assert_eq!(options.window_kind, WindowKind::Web { url, private: false });
+
+        let tab = SessionTab {
+            title: String::new(),
+            custom_title: None,
+            kind: SessionTabKind::Terminal {
+                working_directory: Some(PathBuf::from("/nonexistent/tabor/session")),
+            },
+        };
+        assert_eq!(tab.window_options().terminal_options.working_directory, None);
+    }
+
+    #[test]
+    fn session_tab_format() {
+        let json =
+            r#"{"groups":[{"tabs":[{"kind":"web","url":"https://a.b/"},{"kind":"terminal"}]}]}"#;
+        let session: Session = serde_json::from_str(json).unwrap();
+        let tabs = &session.groups[0].tabs;
+        assert_eq!(session.groups[0].name, None);
+        assert_eq!(tabs[0].kind, SessionTabKind::Web { url: String::from("https://a.b/") });
+        assert_eq!(tabs[1].kind, SessionTabKind::Terminal { working_directory: None });
+        assert_eq!(tabs[1].title, "");
+    }
+}
@@ -0,0 +1,154 @@
+//! Shell scripts recreating the tab layout of a window through `tabor msg`.
+
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use crate::ipc::{IpcTabGroup, IpcTabKind};
+
+/// Command extracting the group id from the reply of `tabor msg create-group`.
+const GROUP_ID_FILTER: &str = r#"sed -n 's/.*"group_id":\([0-9]*\).*/\1/p'"#;
+
+/// Format a script which recreates the groups and tabs of a window.
+///
+/// New tabs are focused, so custom titles are applied to the active tab right after creating it.
+/// Values are attached to their flags with `=`, so values starting with `-` aren't read as flags.
+pub fn session_script(groups: &[IpcTabGroup]) -> String {
+    let mut script = String::from("#!/bin/sh\n");
+    script.push_str("# Tab layout exported by `tabor msg export-session`.\n");
+    script.push_str("set -e\n");
+
+    for group in groups.iter().filter(|group| !group.tabs.is_empty()) {
+        script.push_str("\ngroup=$(tabor msg create-group");
+        if let Some(name) = &group.name {
+            let _ = write!(script, " --name={}", shell_quote(name));
+        }
+        let _ = writeln!(script, " | {GROUP_ID_FILTER})");
+
+        for tab in &group.tabs {
+            script.push_str("tabor msg create-tab --group-id \"$group\"");
+            match &tab.kind {
+                IpcTabKind::Terminal => {
+                    if let Some(cwd) = &tab.cwd {
+                        let cwd = cwd.to_string_lossy();
+                        let _ = write!(script, " --working-directory={}", shell_quote(&cwd));
+                    }
+                },
+                IpcTabKind::Web { url } => {
+                    let _ = write!(script, " --web={}", shell_quote(url));
+                },
+            }
+            script.push('\n');
+
+            if let Some(title) = &tab.custom_title {
+                let _ = writeln!(script, "tabor msg set-tab-title --title={}", shell_quote(title));
+            }
+        }
+    }
+
+    script
+}
+
+/// Quote text as a single shell word.
+fn shell_quote(text: &str) -> Cow<'_, str> {
+    let is_plain = |c: char| c.is_ascii_alphanumeric() || "_-+=.,:/@%".contains(c);
+    if !text.is_empty() && text.chars().all(is_plain) {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(format!("'{}'", text.replace('\'', r"'\''")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    use crate::ipc::{IpcTabId, IpcTabState};
+
+    fn tab(kind: IpcTabKind, custom_title: Option<&str>, cwd: Option<&str>) -> IpcTabState {
+        IpcTabState {
+            tab_id: IpcTabId { index: 1, generation: 0 },
+            group_id: 1,
+            index: 0,
+            is_active: false,
+            title: String::new(),
+            custom_title: custom_title.map(String::from),
+            program_name: String::new(),
+            kind,
+            activity: None,
+            load_progress: None,
+            busy: false,
+            foreground_program: None,
+            zoom: None,
+            scroll: None,
+            cwd: cwd.map(PathBuf::from),
+            inspector_visible: None,
+        }
+    }
+
+    #[test]
+    fn quote_shell_words() {
+        assert_eq!(shell_quote("https://example.com/a?b=c"), "'https://example.com/a?b=c'");
+        assert_eq!(shell_quote("/home/user/src"), "/home/user/src");
+        assert_eq!(shell_quote("my tab"), "'my tab'");
+        assert_eq!(shell_quote(r#"it's "quoted""#), r#"'it'\''s "quoted"'"#);
+        assert_eq!(shell_quote("$HOME `id`"), "'$HOME `id`'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn script_recreates_groups() {
+        let groups = vec![
+            IpcTabGroup {
+                id: 1,
+                name: Some(String::from("dev work")),
+                color: None,
+                default_cwd: None,
+                homepage: None,
+                tabs: vec![
+                    tab(IpcTabKind::Terminal, Some(r#"build "main""#), Some("/src")),
+                    tab(IpcTabKind::Web { url: String::from("https://example.com/") }, None, None),
+                ],
+            },
+            IpcTabGroup {
+                id: 2,
+                name: None,
+                color: None,
+                default_cwd: None,
+                homepage: None,
+                tabs: vec![tab(IpcTabKind::Terminal, None, None)],
+            },
+        ];
+
+        let script = session_script(&groups);
+        let commands: Vec<_> = script.lines().filter(|line| line.starts_with("tabor")).collect();
+        assert_eq!(commands, [
+            r#"tabor msg create-tab --group-id "$group" --working-directory=/src"#,
+            r#"tabor msg set-tab-title --title='build "main"'"#,
+            r#"tabor msg create-tab --group-id "$group" --web=https://example.com/"#,
+            r#"tabor msg create-tab --group-id "$group""#,
+        ]);
+        assert!(script.contains("group=$(tabor msg create-group --name='dev work' | sed"));
+        assert!(script.contains("group=$(tabor msg create-group | sed"));
+    }
+    #[test]
+    fn script_keeps_dash_values() {
+        let groups = vec![IpcTabGroup {
+            id: 1,
+            name: Some(String::from("-g")),
+            color: None,
+            default_cwd: None,
+            homepage: None,
+            tabs: vec![tab(IpcTabKind::Terminal, Some("--help"), Some("-dir"))],
+        }];
+
+        let script = session_script(&groups);
+        let commands: Vec<_> = script.lines().filter(|line| line.starts_with("tabor")).collect();
+        assert_eq!(commands, [
+            r#"tabor msg create-tab --group-id "$group" --working-directory=-dir"#,
+            "tabor msg set-tab-title --title=--help",
+        ]);
+        assert!(script.contains("group=$(tabor msg create-group --name=-g | sed"));
+    }
+}
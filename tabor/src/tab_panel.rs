@@ -1,26 +1,25 @@
 use std::time::{Duration, Instant};
 
+use crate::display::color::Rgb;
 use crate::tabs::TabId;
 use crate::window_kind::TabKind;
 
-#[cfg(target_os = "macos")]
-use std::sync::Arc;
-
-#[cfg(target_os = "macos")]
-use crate::macos::favicon::FaviconImage;
-
+/// Favicon of a web tab, whose image is kept in the tab panel's favicon cache.
 #[cfg(target_os = "macos")]
 #[derive(Clone, Debug)]
 pub struct TabFavicon {
     id: u64,
     pub character: char,
-    pub image: Arc<FaviconImage>,
 }
 
 #[cfg(target_os = "macos")]
 impl TabFavicon {
-    pub fn new(id: u64, character: char, image: Arc<FaviconImage>) -> Self {
-        Self { id, character, image }
+    pub fn new(id: u64, character: char) -> Self {
+        Self { id, character }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
     }
 }
 
@@ -59,28 +58,41 @@ impl TabActivity {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TabPanelTab {
     pub tab_id: TabId,
     pub title: String,
     pub is_active: bool,
     pub kind: TabKind,
     pub activity: Option<TabActivity>,
+    /// A job other than the shell runs in the foreground.
+    pub busy: bool,
     #[cfg(target_os = "macos")]
     pub favicon: Option<TabFavicon>,
+    /// Progress of the page load in flight, from 0 to 1.
+    #[cfg(target_os = "macos")]
+    pub load_progress: Option<f32>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TabPanelGroup {
     pub id: usize,
     pub label: String,
+    /// Color tinting the group header and the edge of its tabs.
+    pub color: Option<Rgb>,
+    /// All loaded web tabs of the group are muted.
+    pub muted: bool,
     pub tabs: Vec<TabPanelTab>,
 }
 
 #[derive(Clone, Debug)]
 pub enum TabPanelCommand {
     Focus(TabId),
+    /// Focus the last focused tab of a group.
+    FocusGroup(usize),
     Close(TabId),
+    /// Close every tab of a group.
+    CloseGroup(usize),
     Move {
         tab_id: TabId,
         target_group_id: Option<usize>,
@@ -92,4 +104,9 @@ pub enum TabPanelCommand {
     },
     RenameTab(TabId),
     RenameGroup(usize),
+    /// Create a tab in a group.
+    NewTab {
+        group_id: usize,
+    },
+    NewGroup,
 }
@@ -0,0 +1,111 @@
+//! Terminal tab titles derived from a template.
+
+use std::path::Path;
+
+/// Values available to tab title templates.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct TitleContext<'a> {
+    /// Title reported by the terminal.
+    pub title: &'a str,
+
+    /// Name of the foreground process.
+    pub program: &'a str,
+
+    /// Working directory of the foreground process.
+    pub cwd: Option<&'a Path>,
+}
+
+/// Render a tab title template.
+///
+/// The placeholders `{title}`, `{program}`, `{cwd}` and `{cwd_basename}` are replaced with the
+/// values of `context`, `{{` and `}}` are escaped braces. Unknown placeholders are kept as-is.
+pub fn render_title_template(template: &str, context: &TitleContext<'_>) -> String {
+    let mut title = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find(['{', '}']) {
+        title.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            title.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+
+        let placeholder = rest.strip_prefix('{').and_then(|rest| Some(&rest[..rest.find('}')?]));
+        let value = placeholder.and_then(|placeholder| placeholder_value(placeholder, context));
+        match (placeholder, value) {
+            (Some(placeholder), Some(value)) => {
+                title.push_str(&value);
+                rest = &rest[placeholder.len() + 2..];
+            },
+            _ => {
+                title.push_str(&rest[..1]);
+                rest = &rest[1..];
+            },
+        }
+    }
+
+    title.push_str(rest);
+    title
+}
+
+fn placeholder_value(placeholder: &str, context: &TitleContext<'_>) -> Option<String> {
+    let value = match placeholder {
+        "title" => context.title.to_owned(),
+        "program" => context.program.to_owned(),
+        "cwd" => context.cwd.map(|cwd| cwd.display().to_string()).unwrap_or_default(),
+        "cwd_basename" => context
+            .cwd
+            .map(|cwd| match cwd.file_name() {
+                Some(name) => name.to_string_lossy().into_owned(),
+                None => cwd.display().to_string(),
+            })
+            .unwrap_or_default(),
+        _ => return None,
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(cwd: Option<&Path>) -> TitleContext<'_> {
+        TitleContext { title: "user@host: ~/src", program: "vim", cwd }
+    }
+
+    #[test]
+    fn title_template_placeholders() {
+        let cwd = Path::new("/home/user/src/tabor");
+        let context = context(Some(cwd));
+
+        assert_eq!(render_title_template("{cwd_basename}", &context), "tabor");
+        assert_eq!(render_title_template("{program}", &context), "vim");
+        assert_eq!(render_title_template("{title}", &context), "user@host: ~/src");
+        assert_eq!(
+            render_title_template("{program} in {cwd}", &context),
+            "vim in /home/user/src/tabor"
+        );
+        assert_eq!(render_title_template("plain", &context), "plain");
+    }
+
+    #[test]
+    fn title_template_without_cwd() {
+        let context = context(None);
+        assert_eq!(render_title_template("{program}:{cwd_basename}", &context), "vim:");
+
+        // The root directory has no basename.
+        let context = TitleContext { cwd: Some(Path::new("/")), ..context };
+        assert_eq!(render_title_template("{cwd_basename}", &context), "/");
+    }
+
+    #[test]
+    fn title_template_escapes_and_unknown() {
+        let context = context(None);
+        assert_eq!(render_title_template("{{program}}", &context), "{program}");
+        assert_eq!(render_title_template("{unknown} {program", &context), "{unknown} {program");
+        assert_eq!(render_title_template("}{program}{", &context), "}vim{");
+    }
+}
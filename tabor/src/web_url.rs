@@ -1,3 +1,97 @@
+use std::collections::HashMap;
+
+use url::form_urlencoded::byte_serialize;
+#[cfg(unix)]
+use url::Url;
+
+/// Program opening URLs in the default browser.
+#[cfg(target_os = "macos")]
+pub const EXTERNAL_OPENER: &str = "open";
+#[cfg(all(unix, not(target_os = "macos")))]
+pub const EXTERNAL_OPENER: &str = "xdg-open";
+
+/// Headers WebKit sets on every request, which can't be overridden.
+const RESERVED_HEADERS: [&str; 4] = ["connection", "content-length", "host", "user-agent"];
+
+/// Page opened for text typed or pasted as a location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OmniTarget {
+    /// Normalized URL of the page.
+    Url(String),
+    /// Text which isn't a URL, to be searched for.
+    Search(String),
+}
+
+impl OmniTarget {
+    /// URL of the page, searching with `search_url` when the input wasn't a URL.
+    pub fn into_url(self, search_url: &str) -> String {
+        match self {
+            Self::Url(url) => url,
+            Self::Search(query) => expand_search_url(search_url, &query),
+        }
+    }
+}
+
+/// Resolve the location passed to the command bar's open commands.
+///
+/// Input starting with a keyword of `engines` searches for the rest of it with that engine,
+/// anything else is treated like omnibar input.
+pub fn parse_command_bar(input: &str, engines: &HashMap<String, String>) -> OmniTarget {
+    let input = input.trim();
+    let keyword_search = input.split_once(char::is_whitespace).and_then(|(keyword, query)| {
+        let engine = engines.get(keyword)?;
+        let query = query.split_whitespace().collect::<Vec<_>>().join(" ");
+        Some(expand_search_url(engine, &query))
+    });
+
+    match keyword_search {
+        Some(url) => OmniTarget::Url(url),
+        None => normalize_omnibar_input(input),
+    }
+}
+
+fn expand_search_url(search_url: &str, query: &str) -> String {
+    let query: String = byte_serialize(query.as_bytes()).collect();
+    search_url.replace("{}", &query)
+}
+
+/// Resolve text typed or pasted as a location to a page.
+///
+/// URLs broken across several lines are joined, bare domains get a scheme and anything else
+/// becomes a search query.
+pub fn normalize_omnibar_input(input: &str) -> OmniTarget {
+    let joined: String = input.lines().map(str::trim).collect();
+    if !joined.is_empty() && !joined.contains(char::is_whitespace) && looks_like_url(&joined) {
+        return OmniTarget::Url(normalize_web_url(&joined));
+    }
+
+    OmniTarget::Search(input.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn looks_like_url(input: &str) -> bool {
+    let schemes = ["about:", "file:", "data:"];
+    if input.contains("://") || schemes.iter().any(|scheme| input.starts_with(scheme)) {
+        return true;
+    }
+
+    if is_local_host(input) {
+        return true;
+    }
+
+    // Hosts need a dot followed by a top-level domain, which excludes most words and sentences.
+    let end = input.find(['/', '?', '#']).unwrap_or(input.len());
+    let host = input[..end].rsplit_once('@').map_or(&input[..end], |(_, host)| host);
+    let host = host.rsplit_once(':').map_or(host, |(host, _)| host);
+    let Some((name, tld)) = host.rsplit_once('.') else {
+        return false;
+    };
+
+    let is_ipv4 = host.split('.').count() == 4
+        && host.split('.').all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+    let is_domain = !name.is_empty() && !tld.is_empty() && tld.chars().all(char::is_alphabetic);
+    is_ipv4 || (is_domain && !name.split('.').any(str::is_empty))
+}
+
 pub fn normalize_web_url(input: &str) -> String {
     let trimmed = input.trim();
     if trimmed.is_empty() {
@@ -45,3 +139,188 @@ fn is_local_host(input: &str) -> bool {
 
     host.bytes().all(|b| b.is_ascii_digit() || b == b'.') && host.starts_with("127.")
 }
+
+/// Validate extra headers for page loads, returning them sorted by name.
+///
+/// Header names are case-insensitive, so names which only differ by case are rejected.
+pub fn request_headers(headers: &HashMap<String, String>) -> Result<Vec<(String, String)>, String> {
+    let mut merged: Vec<(String, String)> = Vec::with_capacity(headers.len());
+    for (name, value) in headers {
+        let is_token = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+        if name.is_empty() || !name.chars().all(is_token) {
+            return Err(format!("Invalid header name: {name:?}"));
+        }
+        if value.contains(['\r', '\n']) {
+            return Err(format!("Invalid value for header {name}"));
+        }
+
+        let lowercase = name.to_ascii_lowercase();
+        if lowercase == "user-agent" {
+            return Err(String::from("Set the user agent through user_agent instead"));
+        } else if RESERVED_HEADERS.contains(&lowercase.as_str()) {
+            return Err(format!("Header {name} can't be overridden"));
+        } else if merged.iter().any(|(other, _)| other.eq_ignore_ascii_case(name)) {
+            return Err(format!("Duplicate header: {name}"));
+        }
+
+        merged.push((name.clone(), value.trim().to_string()));
+    }
+
+    merged.sort_by_key(|(name, _)| name.to_ascii_lowercase());
+    Ok(merged)
+}
+
+/// URL of a page which can be handed to an external browser.
+///
+/// Only absolute `http` and `https` URLs are accepted, so no local files or programs are opened.
+#[cfg(unix)]
+pub fn external_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    let url = url.strip_prefix("view-source:").unwrap_or(url);
+    let parsed = Url::parse(url).ok()?;
+    let is_web = matches!(parsed.scheme(), "http" | "https") && parsed.host_str().is_some();
+    is_web.then(|| parsed.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn request_headers_sorted() {
+        let headers = headers(&[("X-Team", " tools "), ("Authorization", "Bearer abc")]);
+        let merged = request_headers(&headers).unwrap();
+        assert_eq!(merged, vec![
+            (String::from("Authorization"), String::from("Bearer abc")),
+            (String::from("X-Team"), String::from("tools")),
+        ]);
+
+        assert_eq!(request_headers(&HashMap::new()).unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn request_headers_rejected() {
+        assert!(request_headers(&headers(&[("X-Id", "1"), ("x-id", "2")])).is_err());
+        assert!(request_headers(&headers(&[("User-Agent", "curl")])).is_err());
+        assert!(request_headers(&headers(&[("Host", "example.com")])).is_err());
+        assert!(request_headers(&headers(&[("X Id", "1")])).is_err());
+        assert!(request_headers(&headers(&[("", "1")])).is_err());
+        assert!(request_headers(&headers(&[("X-Id", "1\r\nHost: a")])).is_err());
+    }
+
+    #[test]
+    fn omnibar_domains() {
+        let url = |url: &str| OmniTarget::Url(String::from(url));
+        assert_eq!(normalize_omnibar_input("example.com"), url("https://example.com"));
+        assert_eq!(normalize_omnibar_input("  docs.rs/tokio\n"), url("https://docs.rs/tokio"));
+        assert_eq!(normalize_omnibar_input("localhost:3000"), url("http://localhost:3000"));
+        assert_eq!(normalize_omnibar_input("user@10.0.0.1:22"), url("https://user@10.0.0.1:22"));
+
+        // URLs wrapped across lines are joined.
+        let wrapped = "https://example.com/a/\n  long/path?q=1\n";
+        assert_eq!(normalize_omnibar_input(wrapped), url("https://example.com/a/long/path?q=1"));
+    }
+
+    #[test]
+    fn omnibar_full_urls() {
+        let url = |url: &str| OmniTarget::Url(String::from(url));
+        let full = "http://example.com/search?q=a+b";
+        assert_eq!(normalize_omnibar_input(full), url(full));
+        assert_eq!(normalize_omnibar_input("about:blank"), url("about:blank"));
+        assert_eq!(normalize_omnibar_input("file:///tmp/a.html"), url("file:///tmp/a.html"));
+    }
+
+    #[test]
+    fn omnibar_search_fallback() {
+        let search = |query: &str| OmniTarget::Search(String::from(query));
+        assert_eq!(normalize_omnibar_input("rust tokio"), search("rust tokio"));
+        assert_eq!(normalize_omnibar_input("tokio"), search("tokio"));
+        assert_eq!(normalize_omnibar_input("1.5"), search("1.5"));
+        assert_eq!(normalize_omnibar_input("what is 1.5"), search("what is 1.5"));
+        assert_eq!(normalize_omnibar_input("end of sentence."), search("end of sentence."));
+        assert_eq!(normalize_omnibar_input("line one\nline two"), search("line one line two"));
+
+        let url = normalize_omnibar_input("c++ & rust").into_url("https://duckduckgo.com/?q={}");
+        assert_eq!(url, "https://duckduckgo.com/?q=c%2B%2B+%26+rust");
+        let url = OmniTarget::Url(String::from("https://a.b/")).into_url("https://s/?q={}");
+        assert_eq!(url, "https://a.b/");
+    }
+
+    fn engines() -> HashMap<String, String> {
+        let engines = [
+            ("g", "https://www.google.com/search?q={}"),
+            ("gh", "https://github.com/search?q={}&type=code"),
+        ];
+        engines.iter().map(|(keyword, url)| (keyword.to_string(), url.to_string())).collect()
+    }
+
+    #[test]
+    fn command_bar_keywords() {
+        let url = |url: &str| OmniTarget::Url(String::from(url));
+        let engines = engines();
+
+        let google = url("https://www.google.com/search?q=rust+tokio");
+        assert_eq!(parse_command_bar("g rust tokio", &engines), google);
+        assert_eq!(parse_command_bar(" g  rust\ttokio ", &engines), google);
+        let github = url("https://github.com/search?q=fn+main&type=code");
+        assert_eq!(parse_command_bar("gh fn main", &engines), github);
+
+        // Queries which look like URLs are still searched for.
+        let query = url("https://www.google.com/search?q=example.com");
+        assert_eq!(parse_command_bar("g example.com", &engines), query);
+
+        // Keywords are case-sensitive and need a query.
+        let search = |query: &str| OmniTarget::Search(String::from(query));
+        assert_eq!(parse_command_bar("G rust", &engines), search("G rust"));
+        assert_eq!(parse_command_bar("g", &engines), search("g"));
+        assert_eq!(parse_command_bar("g rust", &HashMap::new()), search("g rust"));
+    }
+
+    #[test]
+    fn command_bar_urls() {
+        let url = |url: &str| OmniTarget::Url(String::from(url));
+        let engines = engines();
+
+        assert_eq!(parse_command_bar("example.com", &engines), url("https://example.com"));
+        assert_eq!(parse_command_bar("g.co/maps", &engines), url("https://g.co/maps"));
+        assert_eq!(parse_command_bar("localhost:8080", &engines), url("http://localhost:8080"));
+        let full = "https://example.com/?q={}";
+        assert_eq!(parse_command_bar(full, &engines), url(full));
+    }
+
+    #[test]
+    fn command_bar_default_search() {
+        let engines = engines();
+        let default = "https://duckduckgo.com/?q={}";
+
+        let target = parse_command_bar("rust tokio", &engines);
+        assert_eq!(target, OmniTarget::Search(String::from("rust tokio")));
+        assert_eq!(target.into_url(default), "https://duckduckgo.com/?q=rust+tokio");
+
+        let target = parse_command_bar("why is 0.1 + 0.2", &engines);
+        assert_eq!(target.into_url(default), "https://duckduckgo.com/?q=why+is+0.1+%2B+0.2");
+        let url = parse_command_bar("tokio", &engines).into_url(default);
+        assert_eq!(url, "https://duckduckgo.com/?q=tokio");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn external_url_schemes() {
+        let url = external_url(" https://example.com/a?b=c ");
+        assert_eq!(url.as_deref(), Some("https://example.com/a?b=c"));
+        let url = external_url("view-source:http://localhost:8080");
+        assert_eq!(url.as_deref(), Some("http://localhost:8080/"));
+
+        assert_eq!(external_url(""), None);
+        assert_eq!(external_url("example.com"), None);
+        assert_eq!(external_url("about:blank"), None);
+        assert_eq!(external_url("file:///etc/passwd"), None);
+        assert_eq!(external_url("javascript:alert(1)"), None);
+        assert_eq!(external_url("data:text/html,hi"), None);
+        assert_eq!(external_url("ftp://example.com/"), None);
+    }
+}
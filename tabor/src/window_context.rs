@@ -1,39 +1,48 @@
 //! Terminal window context.
 
+use std::collections::HashMap;
+#[cfg(unix)]
+use std::collections::VecDeque;
 use std::error::Error;
+#[cfg(target_os = "macos")]
+use std::fs;
 use std::fs::File;
 use std::io::Write;
 use std::mem;
 #[cfg(not(windows))]
 use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
+#[cfg(unix)]
+use std::time::Duration;
 use std::time::Instant;
 
 use glutin::config::Config as GlutinConfig;
 use glutin::display::GetGlDisplay;
 #[cfg(all(feature = "x11", not(any(target_os = "macos", windows))))]
 use glutin::platform::x11::X11GlConfigExt;
-use log::info;
+use log::{info, warn};
 #[cfg(target_os = "macos")]
 use serde::Deserialize;
 use serde_json as json;
 use winit::event::{Event as WinitEvent, Ime, Modifiers, WindowEvent};
 use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
 use winit::raw_window_handle::HasDisplayHandle;
-use winit::window::WindowId;
 #[cfg(target_os = "macos")]
 use winit::window::CursorIcon;
+use winit::window::WindowId;
 
 use tabor_terminal::event::{Event as TerminalEvent, Notify, OnResize};
 use tabor_terminal::event_loop::{EventLoop as PtyEventLoop, Msg, Notifier};
 use tabor_terminal::grid::{Dimensions, Scroll};
-use tabor_terminal::index::Direction;
+use tabor_terminal::index::{Column, Direction, Line};
 use tabor_terminal::sync::FairMutex;
-use tabor_terminal::term::test::TermSize;
-use tabor_terminal::term::{Term, TermMode};
 #[cfg(target_os = "macos")]
 use tabor_terminal::term::MIN_COLUMNS;
+use tabor_terminal::term::cell::Flags;
+use tabor_terminal::term::test::TermSize;
+use tabor_terminal::term::{Term, TermMode};
 use tabor_terminal::tty;
 use tabor_terminal::vte::ansi::NamedColor;
 
@@ -42,56 +51,123 @@ use crate::clipboard::Clipboard;
 #[cfg(unix)]
 use crate::config::Action;
 use crate::config::UiConfig;
+use crate::config::tabs::NewTabPosition;
+use crate::config::ui_config::config_diff;
 #[cfg(not(windows))]
-use crate::daemon::foreground_process_name;
+use crate::daemon::{
+    foreground_process_busy, foreground_process_name, foreground_process_path, spawn_daemon,
+};
 use crate::display::Display;
 use crate::display::color::Rgb;
 use crate::display::window::Window;
 #[cfg(target_os = "macos")]
-use crate::display::{TabPanelEditOutcome, TabPanelEditTarget};
-use crate::event::{
-    request_web_cursor_update, ActionContext, CommandHistory, CommandState, Event, EventProxy,
-    EventType, InlineSearchState, Mouse, SearchState, TouchPurpose,
-};
+use crate::display::{DRAG_SCROLL_INTERVAL, TabPanelEditOutcome, TabPanelEditTarget};
 #[cfg(target_os = "macos")]
 use crate::event::WebCommand;
-#[cfg(unix)]
-use crate::logging::LOG_TARGET_IPC_CONFIG;
-use crate::message_bar::MessageBuffer;
+use crate::event::{
+    ActionContext, CommandState, Event, EventProxy, EventType, InlineSearchState, Mouse,
+    SearchState, TouchPurpose, request_web_cursor_update,
+};
+use crate::history::{self, UrlHistory};
 #[cfg(unix)]
 use crate::ipc::{
-    IpcError, IpcErrorCode, IpcInspectorMessage, IpcInspectorSession, IpcInspectorTarget,
-    IpcTabActivity, IpcTabGroup, IpcTabKind, IpcTabPanelState, IpcTabState, TabSelection,
+    InspectorDock, IpcClosedTab, IpcError, IpcErrorCode, IpcEvent, IpcInspectorMessage,
+    IpcInspectorSession, IpcInspectorTarget, IpcScrollPosition, IpcTabActivity, IpcTabGroup,
+    IpcTabKind, IpcTabPanelState, IpcTabState, IpcWebHistory, SocketReply, TabSelection,
+    TabWaiters, reply_error,
 };
+#[cfg(unix)]
+use crate::logging::LOG_TARGET_IPC_CONFIG;
+use crate::message_bar::MessageBuffer;
 use crate::scheduler::Scheduler;
+#[cfg(unix)]
+use crate::scheduler::{TimerId, Topic};
+use crate::session::{SessionGroup, SessionTab, SessionTabKind};
 use crate::tab_panel::TabActivity;
+#[cfg(target_os = "macos")]
+use crate::tab_panel::TabPanelCommand;
+use crate::tab_title::{TitleContext, render_title_template};
 use crate::tabs::TabId;
+#[cfg(unix)]
+use crate::web_url::{EXTERNAL_OPENER, external_url};
 use crate::window_kind::WindowKind;
 use crate::{input, renderer};
 
 #[cfg(target_os = "macos")]
-use crate::macos::web_commands::WebCommandState;
+use crate::ipc::IpcWebHistoryEntry;
 #[cfg(target_os = "macos")]
-use crate::macos::favicon::{fetch_favicon, resolve_favicon_url, FaviconImage};
+use crate::macos::favicon::{FaviconImage, fetch_favicon, resolve_favicon_url};
 #[cfg(target_os = "macos")]
-use crate::macos::webview::{take_pending_popup, PendingPopup, WebView};
+use crate::macos::web_commands::WebCommandState;
+#[cfg(target_os = "macos")]
+use crate::macos::webview::{PendingPopup, WebView, WebsiteDataTypes, take_pending_popup};
 #[cfg(not(target_os = "macos"))]
 type PendingPopup = ();
 #[cfg(target_os = "macos")]
+use crate::inspector::map_inspector_error;
+#[cfg(unix)]
+use crate::inspector::{InspectorSession, PlatformInspector};
+#[cfg(target_os = "macos")]
 use crate::macos::remote_inspector::{
-    match_tab_for_target, match_target_for_tab, InspectorError, InspectorTabInfo,
-    RemoteInspectorClient,
+    InspectorTabInfo, match_tab_for_target, match_target_for_tab,
+};
+#[cfg(target_os = "macos")]
+use crate::macos::web_hibernation::{
+    HibernatedPage, WEB_HIBERNATION_CHECK_INTERVAL, WEB_HIBERNATION_JS, WebHibernation,
 };
 #[cfg(target_os = "macos")]
 use crate::tab_panel::TabFavicon;
 
+/// Title of a tab, as reported by its shell or page and as set by the user.
+struct TabTitle {
+    /// Title reported by the shell or page, updated even while a custom title is set.
+    reported: String,
+    /// Title set by the user, which pins the tab label until it is cleared.
+    custom: Option<String>,
+}
+
+impl TabTitle {
+    fn new(reported: String) -> Self {
+        Self { reported, custom: None }
+    }
+
+    /// Title shown for the tab, the custom title taking precedence.
+    fn current(&self) -> &str {
+        self.custom.as_deref().unwrap_or(&self.reported)
+    }
+
+    fn set_reported(&mut self, title: String) -> bool {
+        if self.reported == title {
+            return false;
+        }
+
+        self.reported = title;
+        true
+    }
+
+    fn set_custom(&mut self, title: Option<String>) -> bool {
+        if self.custom == title {
+            return false;
+        }
+
+        self.custom = title;
+        true
+    }
+}
+
 struct TabState {
     id: TabId,
-    title: String,
-    custom_title: Option<String>,
+    title: TabTitle,
+    /// Title template overriding `tab_panel.title_template` for this tab.
+    title_template: Option<String>,
     program_name: String,
+    /// A job other than the shell runs in the foreground.
+    busy: bool,
+    /// Working directory of the foreground process, only tracked for title templates.
+    cwd: Option<PathBuf>,
+    /// Working directory reported by the shell through OSC 7.
+    reported_cwd: Option<PathBuf>,
     kind: WindowKind,
-    activity: TabActivity,
     terminal: Arc<FairMutex<Term<EventProxy>>>,
     notifier: Notifier,
     search_state: SearchState,
@@ -101,6 +177,9 @@ struct TabState {
     touch: TouchPurpose,
     cursor_blink_timed_out: bool,
     prev_bell_cmd: Option<Instant>,
+    /// Exit status of the tab's command once it exited, `None` if it was killed by a signal.
+    #[cfg(unix)]
+    exit_status: Option<Option<i32>>,
     #[cfg(target_os = "macos")]
     web_view: Option<WebView>,
     #[cfg(target_os = "macos")]
@@ -109,15 +188,62 @@ struct TabState {
     favicon: Option<TabFavicon>,
     #[cfg(target_os = "macos")]
     favicon_pending: bool,
+    /// Progress of the page load in flight, from 0 to 1.
+    #[cfg(target_os = "macos")]
+    load_progress: Option<f64>,
+    #[cfg(target_os = "macos")]
+    hibernation: WebHibernation,
+    /// Scroll offset of the page as of its last poll.
+    #[cfg(target_os = "macos")]
+    web_scroll: Option<IpcScrollPosition>,
     #[cfg(not(windows))]
     master_fd: RawFd,
     #[cfg(not(windows))]
     shell_pid: u32,
 }
 
-#[cfg(target_os = "macos")]
+/// Tab which was closed and can be restored.
+#[cfg(unix)]
 struct ClosedTab {
     kind: WindowKind,
+    title: String,
+    custom_title: Option<String>,
+    working_directory: Option<PathBuf>,
+    /// Tabs before the closed tab in its group, nearest first.
+    before: Vec<TabId>,
+    /// Tabs after the closed tab in its group, nearest first.
+    after: Vec<TabId>,
+}
+
+/// Recently closed tabs, most recent last.
+#[cfg(unix)]
+#[derive(Default)]
+struct ClosedTabs {
+    tabs: VecDeque<ClosedTab>,
+}
+
+#[cfg(unix)]
+impl ClosedTabs {
+    /// Remember a closed tab, forgetting the oldest ones beyond `limit`.
+    fn push(&mut self, tab: ClosedTab, limit: usize) {
+        self.tabs.push_back(tab);
+        self.truncate(limit);
+    }
+
+    fn pop(&mut self) -> Option<ClosedTab> {
+        self.tabs.pop_back()
+    }
+
+    /// Forget the oldest tabs beyond `limit`.
+    fn truncate(&mut self, limit: usize) {
+        let excess = self.tabs.len().saturating_sub(limit);
+        self.tabs.drain(..excess);
+    }
+
+    /// Closed tabs, most recent first.
+    fn iter(&self) -> impl Iterator<Item = &ClosedTab> {
+        self.tabs.iter().rev()
+    }
 }
 
 #[cfg(target_os = "macos")]
@@ -131,6 +257,13 @@ const WEB_FAVICON_JS: &str = r#"
 })()
 "#;
 
+/// Interval of polling the scroll offset of web pages, reported by the IPC tab state.
+#[cfg(target_os = "macos")]
+const WEB_STATE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[cfg(target_os = "macos")]
+const WEB_SELECTION_JS: &str = "String(window.getSelection() || '')";
+
 #[cfg(target_os = "macos")]
 #[derive(Deserialize)]
 struct WebFaviconHint {
@@ -176,21 +309,146 @@ fn select_favicon_base(page_url: &str, base_uri: &str, referrer: &str) -> String
 }
 
 impl TabState {
-    fn panel_title(&self) -> String {
-        if let Some(custom_title) = &self.custom_title {
+    fn panel_title(&self, default_template: Option<&str>) -> String {
+        if let Some(custom_title) = &self.title.custom {
             return custom_title.clone();
         }
 
         if self.kind.is_web() {
-            return self.title.clone();
+            return self.title.reported.clone();
+        }
+
+        if let Some(template) = self.title_template.as_deref().or(default_template) {
+            let context = TitleContext {
+                title: &self.title.reported,
+                program: &self.program_name,
+                cwd: self.reported_cwd.as_deref().or(self.cwd.as_deref()),
+            };
+            return render_title_template(template, &context);
         }
 
         if self.program_name.is_empty() {
-            return self.title.clone();
+            return self.title.reported.clone();
         }
 
         self.program_name.clone()
     }
+
+    /// Name of the job running in the foreground of a terminal instead of the shell.
+    #[cfg(unix)]
+    fn foreground_job(&self) -> Option<String> {
+        let busy = !self.kind.is_web() && foreground_process_busy(self.master_fd, self.shell_pid);
+        busy.then(|| foreground_process_name(self.master_fd, self.shell_pid).unwrap_or_default())
+    }
+
+    /// Saved state of this tab, private web tabs are never saved.
+    fn session_tab(&self) -> Option<SessionTab> {
+        let kind = match &self.kind {
+            WindowKind::Terminal => {
+                SessionTabKind::Terminal { working_directory: self.working_directory() }
+            },
+            WindowKind::Web { private: true, .. } => return None,
+            WindowKind::Web { url, .. } => SessionTabKind::Web { url: url.clone() },
+        };
+
+        let custom_title = self.title.custom.clone();
+        Some(SessionTab { title: self.title.reported.clone(), custom_title, kind })
+    }
+
+    /// Working directory of the shell, preferring the one it reported through OSC 7.
+    #[cfg(not(windows))]
+    fn working_directory(&self) -> Option<PathBuf> {
+        let cwd = self.reported_cwd.clone();
+        cwd.or_else(|| foreground_process_path(self.master_fd, self.shell_pid).ok())
+    }
+
+    #[cfg(windows)]
+    fn working_directory(&self) -> Option<PathBuf> {
+        self.reported_cwd.clone()
+    }
+
+    #[cfg(target_os = "macos")]
+    fn load_progress(&self) -> Option<f64> {
+        self.load_progress
+    }
+
+    #[cfg(target_os = "macos")]
+    fn web_zoom(&self) -> Option<f64> {
+        self.web_view.as_ref().map(WebView::page_zoom)
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn web_zoom(&self) -> Option<f64> {
+        None
+    }
+
+    #[cfg(target_os = "macos")]
+    fn web_scroll(&self) -> Option<IpcScrollPosition> {
+        self.web_scroll
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn web_scroll(&self) -> Option<IpcScrollPosition> {
+        None
+    }
+
+    #[cfg(target_os = "macos")]
+    fn web_inspector_visible(&self) -> Option<bool> {
+        self.web_view.as_ref().map(WebView::inspector_is_visible)
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn web_inspector_visible(&self) -> Option<bool> {
+        None
+    }
+
+    /// Whether the audio of this tab is muted, `None` without a loaded or hibernated page.
+    #[cfg(target_os = "macos")]
+    fn web_muted(&self) -> Option<bool> {
+        self.web_view.as_ref().map(WebView::muted).or_else(|| self.hibernation.muted())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn web_muted(&self) -> Option<bool> {
+        None
+    }
+
+    /// Request the page state of this web tab, so it can be hibernated once it arrives.
+    #[cfg(target_os = "macos")]
+    fn request_hibernation(&mut self, window_id: WindowId, event_proxy: &EventLoopProxy<Event>) {
+        // Private tabs would lose their cookies and storage with the view.
+        if self.kind.is_private() {
+            return;
+        }
+        // Request overrides are not restored with the page.
+        let Some(web_view) = self.web_view.as_mut().filter(|view| !view.has_request_overrides())
+        else {
+            return;
+        };
+
+        self.hibernation.request();
+        let (tab_id, proxy) = (self.id, event_proxy.clone());
+        web_view.eval_js_string(WEB_HIBERNATION_JS, move |result| {
+            let page = result.as_deref().and_then(HibernatedPage::from_json);
+            let event = Event::for_tab(EventType::WebHibernate(page), window_id, tab_id);
+            let _ = proxy.send_event(event);
+        });
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn load_progress(&self) -> Option<f64> {
+        None
+    }
+
+    #[cfg(target_os = "macos")]
+    fn hint_preview(&self) -> Option<&str> {
+        self.web_command_state.hint_preview()
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn hint_preview(&self) -> Option<&str> {
+        None
+    }
 }
 
 struct TabSlot {
@@ -201,7 +459,14 @@ struct TabSlot {
 struct TabGroup {
     id: usize,
     name: Option<String>,
+    color: Option<Rgb>,
     tabs: Vec<TabId>,
+    /// Tab which was focused last within the group.
+    last_active: Option<TabId>,
+    /// Working directory of new terminal tabs.
+    default_cwd: Option<PathBuf>,
+    /// Page loaded by new blank web tabs.
+    homepage: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -211,10 +476,90 @@ enum DrawMode {
 }
 
 fn draw_mode(kind: &WindowKind) -> DrawMode {
-    if kind.is_web() {
-        DrawMode::Web
+    if kind.is_web() { DrawMode::Web } else { DrawMode::Terminal }
+}
+
+/// Text of the terminal viewport, or the entire scrollback, with one string per grid line.
+fn terminal_text_lines<T>(terminal: &Term<T>, scrollback: bool) -> Vec<String> {
+    let grid = terminal.grid();
+    let lines = if scrollback {
+        grid.topmost_line().0..=grid.bottommost_line().0
     } else {
-        DrawMode::Terminal
+        let display_offset = grid.display_offset() as i32;
+        -display_offset..=grid.screen_lines() as i32 - 1 - display_offset
+    };
+
+    lines
+        .map(|line| {
+            let row = &grid[Line(line)];
+            let mut text = String::new();
+            for column in 0..grid.columns() {
+                let cell = &row[Column(column)];
+                if cell.flags.intersects(Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER)
+                {
+                    continue;
+                }
+
+                text.push(cell.c);
+                for c in cell.zerowidth().into_iter().flatten() {
+                    text.push(*c);
+                }
+            }
+            text.truncate(text.trim_end().len());
+            text
+        })
+        .collect()
+}
+
+/// Error of web IPC requests on platforms without web tabs.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn web_tabs_unsupported() -> IpcError {
+    IpcError::new(IpcErrorCode::Unsupported, "Web tabs are only supported on macOS")
+}
+
+/// Read the URL history of previous runs.
+fn load_url_history() -> UrlHistory {
+    let Some(path) = history::history_path() else {
+        return UrlHistory::default();
+    };
+
+    match UrlHistory::load(&path) {
+        Ok(history) => history,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => UrlHistory::default(),
+        Err(err) => {
+            warn!("Unable to load URL history from {path:?}: {err}");
+            UrlHistory::default()
+        },
+    }
+}
+
+/// Whether a new web tab's URL leaves the page to load up to the tab's defaults.
+fn is_blank_url(url: &str) -> bool {
+    url.is_empty() || url == "about:blank"
+}
+
+/// Tab next to `active` in `tabs`, either wrapping around or stopping at the first and last tab.
+fn adjacent_tab(tabs: &[TabId], active: TabId, forward: bool, wrap: bool) -> Option<TabId> {
+    let pos = tabs.iter().position(|id| *id == active)?;
+    let target = match (forward, wrap) {
+        (true, true) => (pos + 1) % tabs.len(),
+        (true, false) => (pos + 1).min(tabs.len() - 1),
+        (false, true) => pos.checked_sub(1).unwrap_or(tabs.len() - 1),
+        (false, false) => pos.saturating_sub(1),
+    };
+    tabs.get(target).copied()
+}
+
+/// Index of a new tab within the other `tabs` of its group.
+///
+/// Tabs are only placed after the `current` tab if it is part of the same group.
+fn new_tab_index(position: NewTabPosition, tabs: &[TabId], current: Option<TabId>) -> usize {
+    match position {
+        NewTabPosition::End => tabs.len(),
+        NewTabPosition::Start => 0,
+        NewTabPosition::AfterCurrent => current
+            .and_then(|current| tabs.iter().position(|id| *id == current))
+            .map_or(tabs.len(), |index| index + 1),
     }
 }
 
@@ -224,6 +569,14 @@ struct TabManager {
     active: Option<TabId>,
     groups: Vec<TabGroup>,
     next_group_id: usize,
+    /// Tabs, groups or their titles changed since the session was last saved.
+    session_dirty: bool,
+    /// Default title template of terminal tabs.
+    title_template: Option<String>,
+    /// Position of new tabs within their group.
+    new_tab_position: NewTabPosition,
+    /// Output activity of terminal tabs.
+    activity: HashMap<TabId, TabActivity>,
 }
 
 impl TabManager {
@@ -234,6 +587,10 @@ impl TabManager {
             active: None,
             groups: Vec::new(),
             next_group_id: 1,
+            session_dirty: false,
+            title_template: None,
+            new_tab_position: NewTabPosition::default(),
+            activity: HashMap::new(),
         }
     }
 
@@ -256,15 +613,25 @@ impl TabManager {
         group_name: Option<String>,
     ) -> Result<(), String> {
         if self.slots.len() <= tab_id.slot_index() {
-            self.slots.resize_with(tab_id.slot_index() + 1, || TabSlot {
-                generation: 0,
-                tab: None,
-            });
+            self.slots
+                .resize_with(tab_id.slot_index() + 1, || TabSlot { generation: 0, tab: None });
         }
 
         let slot = &mut self.slots[tab_id.slot_index()];
         slot.tab = Some(tab);
 
+        self.attach(tab_id, group_id, group_name)
+    }
+
+    /// Add a tab to a group, without focusing it unless no tab is active yet.
+    ///
+    /// Without `group_id` or `group_name`, the tab joins the group of the active tab.
+    fn attach(
+        &mut self,
+        tab_id: TabId,
+        group_id: Option<usize>,
+        group_name: Option<String>,
+    ) -> Result<(), String> {
         if self.groups.is_empty() {
             let group = self.new_group();
             self.groups.push(group);
@@ -277,7 +644,8 @@ impl TabManager {
                 .position(|group| group.id == group_id)
                 .ok_or_else(|| String::from("Group not found"))?
         } else if let Some(name) = group_name {
-            if let Some(index) = self.groups.iter().position(|group| group.name.as_deref() == Some(&name))
+            if let Some(index) =
+                self.groups.iter().position(|group| group.name.as_deref() == Some(&name))
             {
                 index
             } else {
@@ -288,7 +656,9 @@ impl TabManager {
             }
         } else {
             self.active
-                .and_then(|active| self.groups.iter().position(|group| group.tabs.contains(&active)))
+                .and_then(|active| {
+                    self.groups.iter().position(|group| group.tabs.contains(&active))
+                })
                 .unwrap_or(0)
         };
 
@@ -297,8 +667,9 @@ impl TabManager {
         }
 
         if self.active.is_none() {
-            self.active = Some(tab_id);
+            self.activate(tab_id);
         }
+        self.session_dirty = true;
         Ok(())
     }
 
@@ -336,10 +707,50 @@ impl TabManager {
             return false;
         }
 
-        self.active = Some(tab_id);
+        self.activate(tab_id);
         true
     }
 
+    /// Make a tab active and remember it as the last focused tab of its group.
+    ///
+    /// Output of the tab is considered seen once it is focused.
+    fn activate(&mut self, tab_id: TabId) {
+        self.active = Some(tab_id);
+        if let Some(activity) = self.activity.get_mut(&tab_id) {
+            activity.mark_seen();
+        }
+        if let Some(group) = self.groups.iter_mut().find(|group| group.tabs.contains(&tab_id)) {
+            group.last_active = Some(tab_id);
+        }
+    }
+
+    /// Output activity of a terminal tab.
+    fn activity(&self, tab_id: TabId) -> TabActivity {
+        self.activity.get(&tab_id).cloned().unwrap_or_default()
+    }
+
+    fn note_output(&mut self, tab_id: TabId, now: Instant, seen: bool) {
+        self.activity.entry(tab_id).or_default().note_output(now, seen);
+    }
+
+    /// Whether any terminal tab produced output recently.
+    fn has_active_output(&self, now: Instant) -> bool {
+        self.activity.values().any(|activity| activity.is_active(now))
+    }
+
+    /// Tab to focus when switching to a group.
+    ///
+    /// This is the tab focused last within the group, or its first tab if that was closed
+    /// or moved to another group.
+    #[cfg(any(target_os = "macos", test))]
+    fn group_focus_target(&self, group_id: usize) -> Option<TabId> {
+        let group = self.groups.iter().find(|group| group.id == group_id)?;
+        group
+            .last_active
+            .filter(|tab_id| group.tabs.contains(tab_id))
+            .or_else(|| group.tabs.first().copied())
+    }
+
     fn iter(&self) -> impl Iterator<Item = &TabState> {
         self.slots.iter().filter_map(|slot| slot.tab.as_ref())
     }
@@ -350,12 +761,36 @@ impl TabManager {
 
     fn prune_empty_groups(&mut self) {
         self.groups.retain(|group| !group.tabs.is_empty());
+        self.renumber_groups();
+    }
+
+    /// Remove a group which has no tabs.
+    fn remove_empty_group(&mut self, group_id: usize) {
+        let count = self.groups.len();
+        self.groups.retain(|group| group.id != group_id || !group.tabs.is_empty());
+        if self.groups.len() != count {
+            self.renumber_groups();
+        }
+    }
+
+    fn renumber_groups(&mut self) {
         for (index, group) in self.groups.iter_mut().enumerate() {
             group.id = index + 1;
         }
         self.next_group_id = self.groups.len() + 1;
     }
 
+    /// Take a tab out of its group, removing the group once it's empty.
+    fn detach(&mut self, tab_id: TabId) {
+        for group in &mut self.groups {
+            group.tabs.retain(|id| *id != tab_id);
+            if group.last_active == Some(tab_id) {
+                group.last_active = None;
+            }
+        }
+        self.prune_empty_groups();
+    }
+
     fn remove(&mut self, tab_id: TabId) -> Option<TabState> {
         let slot = self.slots.get_mut(tab_id.slot_index())?;
         if slot.generation != tab_id.generation {
@@ -365,15 +800,17 @@ impl TabManager {
         let tab = slot.tab.take()?;
         slot.generation = slot.generation.wrapping_add(1);
         self.free.push(tab_id.slot_index());
+        self.activity.remove(&tab_id);
 
-        for group in &mut self.groups {
-            group.tabs.retain(|id| *id != tab_id);
-        }
-        self.prune_empty_groups();
+        self.detach(tab_id);
 
         if self.active == Some(tab_id) {
-            self.active = self.ordered_tabs().first().copied();
+            self.active = None;
+            if let Some(&next) = self.ordered_tabs().first() {
+                self.activate(next);
+            }
         }
+        self.session_dirty = true;
 
         Some(tab)
     }
@@ -388,6 +825,18 @@ impl TabManager {
             return false;
         }
 
+        self.move_tab_id(tab_id, target_group_id, target_index)
+    }
+
+    /// Move a tab to `target_index` of a group, as it was before the tab was taken out.
+    ///
+    /// Without a target group the tab is moved into a new group.
+    fn move_tab_id(
+        &mut self,
+        tab_id: TabId,
+        target_group_id: Option<usize>,
+        target_index: Option<usize>,
+    ) -> bool {
         let mut origin_group_id = None;
         let mut origin_index = None;
         let mut origin_len = 0;
@@ -418,10 +867,7 @@ impl TabManager {
             }
         }
 
-        for group in &mut self.groups {
-            group.tabs.retain(|id| *id != tab_id);
-        }
-        self.prune_empty_groups();
+        self.detach(tab_id);
 
         let mut target_index = target_index;
         let group_index = if let Some(group_id) = target_group_id {
@@ -436,8 +882,9 @@ impl TabManager {
             self.groups.len() - 1
         });
 
+        // Indices within the origin group were computed with the moved tab still present.
         let group_id = self.groups[group_index].id;
-        if Some(group_id) == Some(origin_group_id) {
+        if !origin_group_removed && group_id == origin_group_id {
             if let (Some(origin_index), Some(target_index_value)) = (origin_index, target_index) {
                 if target_index_value > origin_index {
                     target_index = Some(target_index_value.saturating_sub(1));
@@ -448,6 +895,10 @@ impl TabManager {
         let group = &mut self.groups[group_index];
         let insert_index = target_index.unwrap_or(group.tabs.len()).min(group.tabs.len());
         group.tabs.insert(insert_index, tab_id);
+        if self.active == Some(tab_id) {
+            group.last_active = Some(tab_id);
+        }
+        self.session_dirty = true;
         true
     }
 
@@ -466,9 +917,19 @@ impl TabManager {
 
         let group = self.groups.remove(from_index);
         self.groups.insert(insert_index, group);
+        self.session_dirty = true;
         true
     }
 
+    #[cfg(unix)]
+    fn group_tab_ids(&self, group_id: usize) -> Vec<TabId> {
+        self.groups
+            .iter()
+            .find(|group| group.id == group_id)
+            .map(|group| group.tabs.clone())
+            .unwrap_or_default()
+    }
+
     fn ordered_tabs(&self) -> Vec<TabId> {
         self.groups
             .iter()
@@ -482,12 +943,8 @@ impl TabManager {
             return false;
         };
 
-        if tab.title == title {
-            return false;
-        }
-
-        tab.title = title;
-        true
+        // Automatic titles change constantly, so they are saved with the next other change.
+        tab.title.set_reported(title)
     }
 
     fn set_custom_title(&mut self, tab_id: TabId, title: Option<String>) -> bool {
@@ -495,20 +952,20 @@ impl TabManager {
             return false;
         };
 
-        if tab.custom_title.as_deref() == title.as_deref() {
+        if !tab.title.set_custom(title) {
             return false;
         }
 
-        tab.custom_title = title;
+        self.session_dirty = true;
         true
     }
 
     fn custom_title(&self, tab_id: TabId) -> Option<&str> {
-        self.get(tab_id).and_then(|tab| tab.custom_title.as_deref())
+        self.get(tab_id).and_then(|tab| tab.title.custom.as_deref())
     }
 
     fn tab_label(&self, tab_id: TabId) -> Option<String> {
-        self.get(tab_id).map(|tab| tab.panel_title())
+        self.get(tab_id).map(|tab| tab.panel_title(self.title_template.as_deref()))
     }
 
     fn set_group_name(&mut self, group_id: usize, name: Option<String>) -> bool {
@@ -521,40 +978,201 @@ impl TabManager {
         }
 
         group.name = name;
+        self.session_dirty = true;
         true
     }
 
-    fn group_name(&self, group_id: usize) -> Option<&str> {
-        self.groups
-            .iter()
-            .find(|group| group.id == group_id)
-            .and_then(|group| group.name.as_deref())
-    }
+    fn set_group_color(&mut self, group_id: usize, color: Option<Rgb>) -> bool {
+        let Some(group) = self.groups.iter_mut().find(|group| group.id == group_id) else {
+            return false;
+        };
 
-    fn group_for_tab(&self, tab_id: TabId) -> Option<(usize, usize)> {
-        for group in &self.groups {
-            if let Some(index) = group.tabs.iter().position(|id| *id == tab_id) {
-                return Some((group.id, index));
-            }
-        }
-        None
+        group.color = color;
+        true
     }
 
-    fn set_program_name(&mut self, tab_id: TabId, program_name: String) -> bool {
-        let Some(tab) = self.get_mut(tab_id) else {
+    #[cfg(unix)]
+    fn set_group_defaults(
+        &mut self,
+        group_id: usize,
+        default_cwd: Option<PathBuf>,
+        homepage: Option<String>,
+    ) -> bool {
+        let Some(group) = self.groups.iter_mut().find(|group| group.id == group_id) else {
             return false;
         };
 
-        if tab.program_name == program_name {
-            return false;
-        }
-
-        tab.program_name = program_name;
+        group.default_cwd = default_cwd;
+        group.homepage = homepage;
         true
     }
 
-    fn panel_groups(&self) -> Vec<crate::tab_panel::TabPanelGroup> {
-        let active = self.active;
+    /// Fill in the working directory or page a new tab's options omit from its group's defaults.
+    ///
+    /// Like [`Self::insert`], the tab's group is `group_id`, the group named `group_name` or the
+    /// group of the active tab.
+    fn apply_group_defaults(
+        &self,
+        options: &mut WindowOptions,
+        group_id: Option<usize>,
+        group_name: Option<&str>,
+    ) {
+        let group = if let Some(group_id) = group_id {
+            self.groups.iter().find(|group| group.id == group_id)
+        } else if let Some(name) = group_name.filter(|name| !name.is_empty()) {
+            self.groups.iter().find(|group| group.name.as_deref() == Some(name))
+        } else {
+            let active = self.active;
+            let active_group = self
+                .groups
+                .iter()
+                .find(|group| active.is_some_and(|active| group.tabs.contains(&active)));
+            active_group.or(self.groups.first())
+        };
+        let Some(group) = group else {
+            return;
+        };
+
+        let command_input = options.command_input.is_some();
+        match &mut options.window_kind {
+            WindowKind::Terminal => {
+                let working_directory = &mut options.terminal_options.working_directory;
+                if working_directory.is_none() {
+                    *working_directory = group.default_cwd.clone();
+                }
+            },
+            // Tabs opening the omnibar have no page to replace.
+            WindowKind::Web { url, .. } if !command_input && is_blank_url(url) => {
+                if let Some(homepage) = &group.homepage {
+                    *url = homepage.clone();
+                }
+            },
+            WindowKind::Web { .. } => (),
+        }
+    }
+
+    fn group_name(&self, group_id: usize) -> Option<&str> {
+        self.groups
+            .iter()
+            .find(|group| group.id == group_id)
+            .and_then(|group| group.name.as_deref())
+    }
+
+    fn group_for_tab(&self, tab_id: TabId) -> Option<(usize, usize)> {
+        for group in &self.groups {
+            if let Some(index) = group.tabs.iter().position(|id| *id == tab_id) {
+                return Some((group.id, index));
+            }
+        }
+        None
+    }
+
+    /// Group and index restoring a closed tab next to the closest of its former neighbors.
+    #[cfg(unix)]
+    fn closed_tab_position(&self, tab: &ClosedTab) -> Option<(usize, usize)> {
+        let before = tab.before.iter().find_map(|tab_id| self.group_for_tab(*tab_id));
+        let before = before.map(|(group_id, index)| (group_id, index + 1));
+        before.or_else(|| tab.after.iter().find_map(|tab_id| self.group_for_tab(*tab_id)))
+    }
+
+    /// Move a tab right after `anchor`, into the anchor's group.
+    fn place_after(&mut self, tab_id: TabId, anchor: TabId) -> bool {
+        let Some((group_id, index)) = self.group_for_tab(anchor) else {
+            return false;
+        };
+        self.move_tab_id(tab_id, Some(group_id), Some(index + 1))
+    }
+
+    /// Move a new tab within its group according to the new tab position.
+    fn place_new_tab(&mut self, tab_id: TabId, current: Option<TabId>) -> bool {
+        let Some((group_id, index)) = self.group_for_tab(tab_id) else {
+            return false;
+        };
+        let Some(group) = self.groups.iter().find(|group| group.id == group_id) else {
+            return false;
+        };
+
+        let tabs: Vec<TabId> = group.tabs.iter().copied().filter(|id| *id != tab_id).collect();
+        let target = new_tab_index(self.new_tab_position, &tabs, current);
+        if target == index {
+            return false;
+        }
+
+        match target.checked_sub(1) {
+            Some(anchor) => self.place_after(tab_id, tabs[anchor]),
+            None => self.move_tab_id(tab_id, Some(group_id), Some(0)),
+        }
+    }
+
+    fn set_title_template(&mut self, tab_id: TabId, template: Option<String>) -> bool {
+        let Some(tab) = self.get_mut(tab_id) else {
+            return false;
+        };
+
+        if tab.title_template == template {
+            return false;
+        }
+
+        tab.title_template = template;
+        true
+    }
+
+    /// Whether the title of a tab is derived from a template.
+    fn uses_title_template(&self, tab_id: TabId) -> bool {
+        self.get(tab_id).is_some_and(|tab| {
+            !tab.kind.is_web() && (tab.title_template.is_some() || self.title_template.is_some())
+        })
+    }
+
+    fn set_cwd(&mut self, tab_id: TabId, cwd: Option<PathBuf>) -> bool {
+        let Some(tab) = self.get_mut(tab_id) else {
+            return false;
+        };
+
+        if tab.cwd == cwd {
+            return false;
+        }
+
+        tab.cwd = cwd;
+        true
+    }
+
+    fn set_reported_cwd(&mut self, tab_id: TabId, cwd: PathBuf) -> bool {
+        let Some(tab) = self.get_mut(tab_id) else {
+            return false;
+        };
+
+        if tab.reported_cwd.as_ref() == Some(&cwd) {
+            return false;
+        }
+
+        tab.reported_cwd = Some(cwd);
+        true
+    }
+
+    fn set_program_name(&mut self, tab_id: TabId, program_name: String) -> bool {
+        let Some(tab) = self.get_mut(tab_id) else {
+            return false;
+        };
+
+        if tab.program_name == program_name {
+            return false;
+        }
+
+        tab.program_name = program_name;
+        true
+    }
+
+    fn set_busy(&mut self, tab_id: TabId, busy: bool) -> bool {
+        let Some(tab) = self.get_mut(tab_id) else {
+            return false;
+        };
+
+        mem::replace(&mut tab.busy, busy) != busy
+    }
+
+    fn panel_groups(&self) -> Vec<crate::tab_panel::TabPanelGroup> {
+        let active = self.active;
         self.groups
             .iter()
             .map(|group| crate::tab_panel::TabPanelGroup {
@@ -563,22 +1181,27 @@ impl TabManager {
                     Some(name) if !name.is_empty() => name.to_string(),
                     _ => format!("group {}", group.id),
                 },
+                color: group.color,
+                muted: self.group_muted(group),
                 tabs: group
                     .tabs
                     .iter()
                     .filter_map(|tab_id| {
                         self.get(*tab_id).map(|tab| crate::tab_panel::TabPanelTab {
                             tab_id: *tab_id,
-                            title: tab.panel_title(),
+                            title: tab.panel_title(self.title_template.as_deref()),
                             is_active: Some(*tab_id) == active,
                             kind: crate::window_kind::TabKind::from(&tab.kind),
                             activity: if tab.kind.is_web() {
                                 None
                             } else {
-                                Some(tab.activity.clone())
+                                Some(self.activity(*tab_id))
                             },
+                            busy: tab.busy,
                             #[cfg(target_os = "macos")]
                             favicon: tab.favicon.clone(),
+                            #[cfg(target_os = "macos")]
+                            load_progress: tab.load_progress.map(|progress| progress as f32),
                         })
                     })
                     .collect(),
@@ -586,24 +1209,24 @@ impl TabManager {
             .collect()
     }
 
+    /// Whether the group has loaded web tabs and all of them are muted.
+    fn group_muted(&self, group: &TabGroup) -> bool {
+        let mut muted =
+            group.tabs.iter().filter_map(|tab_id| self.get(*tab_id)?.web_muted()).peekable();
+        muted.peek().is_some() && muted.all(|muted| muted)
+    }
+
     fn select_by_index(&self, index: usize) -> Option<TabId> {
         let tabs = self.ordered_tabs();
         tabs.get(index).copied()
     }
 
-    fn select_next(&self) -> Option<TabId> {
-        let tabs = self.ordered_tabs();
-        let active = self.active?;
-        let pos = tabs.iter().position(|id| *id == active)?;
-        tabs.get((pos + 1) % tabs.len()).copied()
+    fn select_next(&self, wrap: bool) -> Option<TabId> {
+        adjacent_tab(&self.ordered_tabs(), self.active?, true, wrap)
     }
 
-    fn select_previous(&self) -> Option<TabId> {
-        let tabs = self.ordered_tabs();
-        let active = self.active?;
-        let pos = tabs.iter().position(|id| *id == active)?;
-        let prev = if pos == 0 { tabs.len() - 1 } else { pos - 1 };
-        tabs.get(prev).copied()
+    fn select_previous(&self, wrap: bool) -> Option<TabId> {
+        adjacent_tab(&self.ordered_tabs(), self.active?, false, wrap)
     }
 
     fn select_last(&self) -> Option<TabId> {
@@ -614,7 +1237,15 @@ impl TabManager {
     fn new_group(&mut self) -> TabGroup {
         let id = self.next_group_id;
         self.next_group_id += 1;
-        TabGroup { id, name: None, tabs: Vec::new() }
+        TabGroup {
+            id,
+            name: None,
+            color: None,
+            tabs: Vec::new(),
+            last_active: None,
+            default_cwd: None,
+            homepage: None,
+        }
     }
 
     fn create_group(&mut self, name: Option<String>) -> usize {
@@ -635,17 +1266,22 @@ pub struct WindowContext {
     pub message_buffer: MessageBuffer,
     pub display: Display,
     pub dirty: bool,
-    command_history: CommandHistory,
+    command_history: UrlHistory,
     event_queue: Vec<WinitEvent<Event>>,
     tabs: TabManager,
-    #[cfg(target_os = "macos")]
-    closed_tabs: Vec<ClosedTab>,
+    #[cfg(unix)]
+    closed_tabs: ClosedTabs,
     #[cfg(target_os = "macos")]
     next_favicon_id: u64,
     #[cfg(target_os = "macos")]
     next_favicon_char: u32,
-    #[cfg(target_os = "macos")]
-    remote_inspector: Option<RemoteInspectorClient>,
+    #[cfg(unix)]
+    inspector: Option<PlatformInspector>,
+    #[cfg(unix)]
+    ipc_events: Vec<IpcEvent>,
+    /// IPC clients waiting for the command of a tab to exit.
+    #[cfg(unix)]
+    tab_waiters: TabWaiters,
     modifiers: Modifiers,
     occluded: bool,
     window_focused: bool,
@@ -767,6 +1403,8 @@ impl WindowContext {
             None
         };
         let mut tabs = TabManager::new();
+        tabs.title_template = config.window.tab_panel.title_template.clone();
+        tabs.new_tab_position = config.tabs.new_tab_position;
         let mut pty_config = config.pty_config();
         options.terminal_options.override_pty_config(&mut pty_config);
         let first_tab = Self::spawn_tab(
@@ -787,21 +1425,25 @@ impl WindowContext {
             display,
             config,
             message_buffer: Default::default(),
-            command_history: Default::default(),
+            command_history: load_url_history(),
             window_config: Default::default(),
             event_queue: Default::default(),
             modifiers: Default::default(),
             occluded: Default::default(),
             window_focused: Default::default(),
             tabs,
-            #[cfg(target_os = "macos")]
+            #[cfg(unix)]
             closed_tabs: Default::default(),
             #[cfg(target_os = "macos")]
             next_favicon_id: 0,
             #[cfg(target_os = "macos")]
             next_favicon_char: 0xE000,
-            #[cfg(target_os = "macos")]
-            remote_inspector: None,
+            #[cfg(unix)]
+            inspector: None,
+            #[cfg(unix)]
+            ipc_events: vec![IpcEvent::TabCreated { tab_id: first_tab.into() }],
+            #[cfg(unix)]
+            tab_waiters: Default::default(),
             dirty: Default::default(),
         };
 
@@ -864,20 +1506,27 @@ impl WindowContext {
 
         #[cfg(target_os = "macos")]
         let web_view = match (&window_kind, pending_popup) {
-            (WindowKind::Web { url }, None) => Some(WebView::new(
+            (WindowKind::Web { url, private }, None) => Some(WebView::new(
                 &display.window,
                 &display.size_info,
                 tab_id,
                 url,
+                *private,
+                &config.web,
                 proxy,
             )?),
-            (WindowKind::Web { .. }, Some(popup)) => Some(WebView::from_existing(
-                &display.window,
-                &display.size_info,
-                tab_id,
-                popup.view,
-                popup.delegate,
-            )?),
+            (WindowKind::Web { .. }, Some(popup)) => {
+                let mut web_view = WebView::from_existing(
+                    &display.window,
+                    &display.size_info,
+                    tab_id,
+                    popup.view,
+                    popup.delegate,
+                    proxy,
+                )?;
+                web_view.set_color_scheme(config.web.force_color_scheme);
+                Some(web_view)
+            },
             (WindowKind::Terminal, None) => None,
             (WindowKind::Terminal, Some(_)) => {
                 return Err(std::io::Error::new(
@@ -890,7 +1539,7 @@ impl WindowContext {
 
         let title = match &window_kind {
             WindowKind::Terminal => config.window.identity.title.clone(),
-            WindowKind::Web { url } => {
+            WindowKind::Web { url, .. } => {
                 if url.is_empty() {
                     String::from("Browser")
                 } else {
@@ -901,11 +1550,13 @@ impl WindowContext {
 
         let tab = TabState {
             id: tab_id,
-            title,
-            custom_title: None,
+            title: TabTitle::new(title),
+            title_template: None,
             program_name: String::new(),
+            busy: false,
+            cwd: None,
+            reported_cwd: None,
             kind: window_kind,
-            activity: TabActivity::default(),
             terminal,
             notifier: Notifier(loop_tx),
             search_state: Default::default(),
@@ -915,14 +1566,22 @@ impl WindowContext {
             touch: Default::default(),
             cursor_blink_timed_out: Default::default(),
             prev_bell_cmd: Default::default(),
+            #[cfg(unix)]
+            exit_status: None,
             #[cfg(target_os = "macos")]
             web_view,
             #[cfg(target_os = "macos")]
-            web_command_state: Default::default(),
+            web_command_state: WebCommandState::new(&config.web),
             #[cfg(target_os = "macos")]
             favicon: None,
             #[cfg(target_os = "macos")]
             favicon_pending: false,
+            #[cfg(target_os = "macos")]
+            load_progress: None,
+            #[cfg(target_os = "macos")]
+            hibernation: WebHibernation::new(Instant::now()),
+            #[cfg(target_os = "macos")]
+            web_scroll: None,
             #[cfg(not(windows))]
             master_fd,
             #[cfg(not(windows))]
@@ -954,22 +1613,16 @@ impl WindowContext {
     fn refresh_tab_panel(&mut self) {}
 
     pub(crate) fn note_terminal_output(&mut self, tab_id: TabId, is_active: bool) {
-        let Some(tab) = self.tabs.get_mut(tab_id) else {
-            return;
-        };
-
-        if tab.kind.is_web() {
+        if self.tabs.get(tab_id).is_none_or(|tab| tab.kind.is_web()) {
             return;
         }
 
-        tab.activity.note_output(Instant::now(), is_active);
+        self.tabs.note_output(tab_id, Instant::now(), is_active);
         self.refresh_tab_panel();
     }
 
     pub(crate) fn has_active_terminal_output(&self, now: Instant) -> bool {
-        self.tabs
-            .iter()
-            .any(|tab| !tab.kind.is_web() && tab.activity.is_active(now))
+        self.tabs.has_active_output(now)
     }
 
     #[cfg(target_os = "macos")]
@@ -1058,8 +1711,7 @@ impl WindowContext {
         }
 
         let option = format!("window.tab_panel.width={target_logical}");
-        let parsed = toml::from_str(&option)
-            .expect("failed to parse tab panel width override");
+        let parsed = toml::from_str(&option).expect("failed to parse tab panel width override");
 
         if let Some(existing) = self
             .window_config
@@ -1092,61 +1744,58 @@ impl WindowContext {
         }
     }
 
-    fn update_active_web_title(&mut self, event_proxy: &EventLoopProxy<Event>) {
+    /// Update the title of the active web tab and the URLs of all loaded web tabs.
+    fn update_web_pages(&mut self, event_proxy: &EventLoopProxy<Event>) {
         #[cfg(target_os = "macos")]
         {
-            let mut pending_scroll = None;
-            let mut url_update = None;
-            let mut favicon_request = None;
-            let mut favicon_cleared = false;
-            let title = {
-                let Some(active_tab) = self.tabs.active_mut() else {
-                    return;
-                };
+            let title = self.tabs.active_mut().and_then(|active_tab| {
+                let title = active_tab.web_view.as_mut()?.poll_title()?;
+                Some((active_tab.id, title))
+            });
+            if let Some((tab_id, title)) = title {
+                self.update_tab_title(tab_id, title);
+            }
 
-                let Some(web_view) = active_tab.web_view.as_mut() else {
-                    return;
+            // Background tabs navigate too, through redirects or scripts.
+            let mut url_updates = Vec::new();
+            for tab in self.tabs.iter_mut() {
+                let Some(web_view) = tab.web_view.as_mut() else {
+                    continue;
+                };
+                let Some(url) = web_view.poll_url() else {
+                    continue;
                 };
 
-                let title = web_view.poll_title().map(|title| (active_tab.id, title));
-                if let Some(url) = web_view.poll_url() {
-                    if let WindowKind::Web { url: current_url } = &mut active_tab.kind {
-                        *current_url = url.clone();
-                    }
-                    active_tab.web_command_state.set_cursor_bootstrapped(false);
-                    active_tab.web_command_state.clear_last_cursor_request();
-                    active_tab.favicon = None;
-                    active_tab.favicon_pending = false;
-                    favicon_cleared = true;
-                    favicon_request = Some((active_tab.id, url.clone()));
-                    pending_scroll = active_tab.web_command_state.take_pending_scroll(&url);
-                    url_update = Some(url);
+                if let WindowKind::Web { url: current_url, .. } = &mut tab.kind {
+                    *current_url = url.clone();
                 }
+                tab.web_command_state.set_cursor_bootstrapped(false);
+                tab.web_command_state.clear_last_cursor_request();
+                tab.favicon = None;
+                tab.favicon_pending = false;
 
-                title
-            };
+                // Scroll back once a restored page has been loaded.
+                if let Some((x, y)) = tab.web_command_state.take_pending_scroll(&url) {
+                    web_view.scroll_to(x, y);
+                }
 
-            if let Some((tab_id, title)) = title {
-                self.update_tab_title(tab_id, title);
+                url_updates.push((tab.id, url, tab.kind.is_private()));
             }
 
-            if let Some(url) = url_update.clone() {
-                self.command_history.record_url(url);
+            if url_updates.is_empty() {
+                return;
             }
+            self.refresh_tab_panel();
 
-            if let Some((scroll_x, scroll_y)) = pending_scroll {
-                if let Some(active_tab) = self.tabs.active_mut() {
-                    if let Some(web_view) = active_tab.web_view.as_mut() {
-                        web_view.exec_js(&format!("window.scrollTo({scroll_x}, {scroll_y});"));
-                    }
-                }
-            }
+            for (tab_id, url, private) in url_updates {
+                let event = IpcEvent::UrlChanged { tab_id: tab_id.into(), url: url.clone() };
+                self.ipc_events.push(event);
 
-            if favicon_cleared {
-                self.refresh_tab_panel();
-            }
+                if !private {
+                    self.command_history.add(url.clone());
+                    self.tabs.session_dirty = true;
+                }
 
-            if let Some((tab_id, url)) = favicon_request {
                 self.request_web_favicon(tab_id, url, event_proxy);
             }
         }
@@ -1173,6 +1822,7 @@ impl WindowContext {
 
         let proxy = event_proxy.clone();
         let window_id = self.display.window.id();
+        let config = self.config.web.favicon.clone();
         web_view.eval_js_string(WEB_FAVICON_JS, move |result| {
             let hint = parse_web_favicon_hint(&result.unwrap_or_default());
             let base_url = select_favicon_base(&page_url, &hint.base_uri, &hint.referrer);
@@ -1181,7 +1831,7 @@ impl WindowContext {
             match icon_url {
                 Some(icon_url) => {
                     std::thread::spawn(move || {
-                        let icon = fetch_favicon(&icon_url);
+                        let icon = fetch_favicon(&icon_url, &config);
                         let event = Event::for_tab(
                             EventType::WebFavicon { page_url, icon },
                             window_id,
@@ -1202,17 +1852,25 @@ impl WindowContext {
         });
     }
 
+    /// Fetch the favicons again whose image was evicted before their glyph was rasterized.
     #[cfg(target_os = "macos")]
-    fn handle_web_favicon(
-        &mut self,
-        tab_id: TabId,
-        page_url: String,
-        icon: Option<FaviconImage>,
-    ) {
+    fn refetch_evicted_favicons(&mut self, event_proxy: &EventLoopProxy<Event>) {
+        for tab_id in self.display.tab_panel.take_evicted_favicons() {
+            let Some(WindowKind::Web { url, .. }) = self.tabs.get(tab_id).map(|tab| &tab.kind)
+            else {
+                continue;
+            };
+            let url = url.clone();
+            self.request_web_favicon(tab_id, url, event_proxy);
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn handle_web_favicon(&mut self, tab_id: TabId, page_url: String, icon: Option<FaviconImage>) {
         let Some(tab) = self.tabs.get(tab_id) else {
             return;
         };
-        let WindowKind::Web { url } = &tab.kind else {
+        let WindowKind::Web { url, .. } = &tab.kind else {
             return;
         };
         if url != &page_url {
@@ -1220,20 +1878,30 @@ impl WindowContext {
         }
 
         let Some(icon) = icon else {
-            if let Some(tab) = self.tabs.get_mut(tab_id) {
+            // Tabs whose evicted favicon can't be fetched again fall back to their kind glyph.
+            let cleared = self.tabs.get_mut(tab_id).is_some_and(|tab| {
                 tab.favicon_pending = false;
+                tab.favicon.take().is_some()
+            });
+            if cleared {
+                self.refresh_tab_panel();
             }
             return;
         };
 
-        let id = self.next_favicon_id;
-        self.next_favicon_id = self.next_favicon_id.wrapping_add(1);
-        let character = self.allocate_favicon_char();
+        // Favicons fetched again after their eviction keep their id and glyph.
+        let existing = self.tabs.get(tab_id).and_then(|tab| tab.favicon.clone());
+        let favicon = existing.unwrap_or_else(|| {
+            let id = self.next_favicon_id;
+            self.next_favicon_id = self.next_favicon_id.wrapping_add(1);
+            TabFavicon::new(id, self.allocate_favicon_char())
+        });
+        self.display.tab_panel.cache_favicon(favicon.id(), Arc::new(icon));
         let Some(tab) = self.tabs.get_mut(tab_id) else {
             return;
         };
         tab.favicon_pending = false;
-        tab.favicon = Some(TabFavicon::new(id, character, Arc::new(icon)));
+        tab.favicon = Some(favicon);
         self.refresh_tab_panel();
         self.dirty = true;
     }
@@ -1301,93 +1969,326 @@ impl WindowContext {
     }
 
     #[cfg(target_os = "macos")]
-    fn allocate_favicon_char(&mut self) -> char {
-        const BMP_END: u32 = 0xF8FF;
-        const SUP_START: u32 = 0xF0000;
-        const SUP_END: u32 = 0xFFFFD;
-
-        if self.next_favicon_char == BMP_END + 1 {
-            self.next_favicon_char = SUP_START;
-        }
-        if self.next_favicon_char > SUP_END {
-            panic!("Ran out of favicon glyph slots");
+    fn handle_web_find_result(&mut self, tab_id: TabId, matched: bool) {
+        if matched || Some(tab_id) != self.tabs.active_id() {
+            return;
         }
 
-        let value = self.next_favicon_char;
-        self.next_favicon_char = self.next_favicon_char.saturating_add(1);
-        char::from_u32(value).expect("Invalid favicon glyph")
+        self.message_buffer.push(crate::message_bar::Message::new(
+            String::from("Pattern not found"),
+            crate::message_bar::MessageType::Warning,
+        ));
+        self.display.pending_update.dirty = true;
+        self.dirty = true;
     }
 
-    fn set_active_tab(&mut self, tab_id: TabId) {
-        let previous = self.tabs.active_id();
-        if self.tabs.get(tab_id).is_none() {
+    #[cfg(target_os = "macos")]
+    fn handle_web_load_progress(&mut self, tab_id: TabId, progress: Option<f64>) {
+        let Some(tab) = self.tabs.get_mut(tab_id) else {
             return;
-        }
-
-        let changed = self.tabs.set_active(tab_id);
+        };
 
-        if changed {
-            self.update_tab_program_name(tab_id);
+        let progress = progress.map(|progress| progress.clamp(0., 1.));
+        if tab.load_progress == progress {
+            return;
         }
+        tab.load_progress = progress;
+        self.refresh_tab_panel();
 
-        if changed {
-            if let Some(prev_id) = previous {
-                if let Some(prev_tab) = self.tabs.get_mut(prev_id) {
-                    if !prev_tab.kind.is_web() {
-                        prev_tab.terminal.lock().is_focused = false;
-                    }
-                }
+        if Some(tab_id) == self.tabs.active_id() {
+            self.dirty = true;
+            if self.display.window.has_frame {
+                self.display.window.request_redraw();
             }
         }
+    }
 
-        if let Some(active_tab) = self.tabs.get_mut(tab_id) {
-            if !active_tab.kind.is_web() {
-                active_tab.terminal.lock().is_focused = self.window_focused;
-                active_tab.activity.mark_seen();
-            } else {
-                #[cfg(target_os = "macos")]
-                {
-                    self.display.window.set_mouse_cursor(CursorIcon::Default);
-                    active_tab.web_command_state.set_last_cursor(CursorIcon::Default);
-                    active_tab.web_command_state.set_cursor_pending(false);
+    /// Redraw the tab panel once the tooltip of the hovered tab is due.
+    #[cfg(target_os = "macos")]
+    fn schedule_tab_panel_tooltip(&self, scheduler: &mut Scheduler) {
+        let timer_id = TimerId::new(Topic::TabPanelTooltip, self.id());
+        let now = Instant::now();
+        match self.display.tab_panel.tooltip_deadline() {
+            Some(deadline) if deadline > now => {
+                if !scheduler.scheduled(timer_id) {
+                    let event = Event::new(EventType::TabPanelTooltip, self.id());
+                    scheduler.schedule(event, deadline - now, false, timer_id);
                 }
-            }
-            if !self.preserve_title && self.config.window.dynamic_title {
-                let title = active_tab.custom_title.clone().unwrap_or_else(|| active_tab.title.clone());
-                self.display.window.set_title(title);
-            }
+            },
+            _ => scheduler.unschedule(timer_id),
         }
+    }
 
-        if changed {
-            if let Some(previous_id) = previous {
-                if let Some(previous_tab) = self.tabs.get_mut(previous_id) {
-                    previous_tab.command_state.cancel();
-                    #[cfg(target_os = "macos")]
-                    previous_tab.web_command_state.reset_mode();
-                }
-            }
-            if let Some(active_tab) = self.tabs.active_mut() {
-                active_tab.command_state.cancel();
-                #[cfg(target_os = "macos")]
-                active_tab.web_command_state.reset_mode();
-            }
-            self.display.tab_panel.cancel_edit();
-            self.update_webview_visibility();
-            self.display.pending_update.dirty = true;
-            self.display.damage_tracker.frame().mark_fully_damaged();
-            self.refresh_tab_panel();
-            self.dirty = true;
+    /// Keep scrolling the tab panel while a dragged item is held near one of its edges.
+    #[cfg(target_os = "macos")]
+    fn schedule_tab_panel_drag_scroll(&self, scheduler: &mut Scheduler) {
+        let timer_id = TimerId::new(Topic::TabPanelDragScroll, self.id());
+        if !self.display.tab_panel.drag_scroll_pending(&self.display.size_info) {
+            scheduler.unschedule(timer_id);
+        } else if !scheduler.scheduled(timer_id) {
+            let event = Event::new(EventType::TabPanelDragScroll, self.id());
+            scheduler.schedule(event, DRAG_SCROLL_INTERVAL, true, timer_id);
         }
     }
 
-    pub(crate) fn create_tab(
+    /// Wake the active web tab if it's hibernated and request the page state of idle tabs.
+    #[cfg(target_os = "macos")]
+    fn update_web_hibernation(
         &mut self,
-        options: WindowOptions,
-        proxy: &EventLoopProxy<Event>,
-    ) -> Result<TabId, Box<dyn Error>> {
-        self.create_tab_with_popup(options, proxy, None, None, None)
-    }
-
+        event_proxy: &EventLoopProxy<Event>,
+        scheduler: &mut Scheduler,
+    ) {
+        self.wake_active_web_tab(event_proxy);
+
+        let timer_id = TimerId::new(Topic::WebHibernation, self.id());
+        let Some(timeout) = self.config.web.hibernate_after() else {
+            scheduler.unschedule(timer_id);
+            return;
+        };
+
+        if !scheduler.scheduled(timer_id) {
+            let event = Event::new(EventType::WebHibernationTick, self.id());
+            scheduler.schedule(event, WEB_HIBERNATION_CHECK_INTERVAL, true, timer_id);
+        }
+
+        let now = Instant::now();
+        let window_id = self.id();
+        let active_id = self.tabs.active_id();
+        for tab in self.tabs.iter_mut() {
+            if Some(tab.id) != active_id && tab.hibernation.is_idle(now, timeout) {
+                tab.request_hibernation(window_id, event_proxy);
+            }
+        }
+    }
+
+    /// Keep polling the page state of web tabs while the window has any.
+    #[cfg(target_os = "macos")]
+    fn update_web_state_poll(&mut self, scheduler: &mut Scheduler) {
+        let timer_id = TimerId::new(Topic::WebStatePoll, self.id());
+        if !self.tabs.iter().any(|tab| tab.kind.is_web()) {
+            scheduler.unschedule(timer_id);
+        } else if !scheduler.scheduled(timer_id) {
+            let event = Event::new(EventType::WebStatePollTick, self.id());
+            scheduler.schedule(event, WEB_STATE_POLL_INTERVAL, true, timer_id);
+        }
+    }
+
+    /// Request the scroll offset of all loaded web pages.
+    #[cfg(target_os = "macos")]
+    fn poll_web_state(&mut self, event_proxy: &EventLoopProxy<Event>) {
+        let window_id = self.id();
+        for tab in self.tabs.iter_mut() {
+            let Some(web_view) = tab.web_view.as_mut() else {
+                continue;
+            };
+
+            let (tab_id, proxy) = (tab.id, event_proxy.clone());
+            web_view.scroll_position(move |position| {
+                let Some((x, y)) = position else {
+                    return;
+                };
+                let event = Event::for_tab(EventType::WebScrollPolled { x, y }, window_id, tab_id);
+                let _ = proxy.send_event(event);
+            });
+        }
+    }
+
+    /// Drop the `WebView` of a background tab, keeping its page to restore it later.
+    #[cfg(target_os = "macos")]
+    fn hibernate_web_tab(&mut self, tab_id: TabId, page: Option<HibernatedPage>) {
+        let active = Some(tab_id) == self.tabs.active_id();
+        let Some(tab) = self.tabs.get_mut(tab_id) else {
+            return;
+        };
+
+        // Keep the tab for now if its page state is unavailable, it's retried after the timeout.
+        let (Some(mut page), Some(web_view)) = (page.filter(|_| !active), tab.web_view.as_ref())
+        else {
+            tab.hibernation.touch(Instant::now());
+            return;
+        };
+
+        // Per-tab overrides are restored together with the page.
+        page.zoom = Some(web_view.page_zoom());
+        page.muted = web_view.muted();
+
+        if tab.hibernation.hibernate(page) {
+            tab.web_view = None;
+            tab.load_progress = None;
+        }
+    }
+
+    /// Load the saved page of the active tab again, if it was hibernated.
+    #[cfg(target_os = "macos")]
+    fn wake_active_web_tab(&mut self, event_proxy: &EventLoopProxy<Event>) {
+        let Some(tab) = self.tabs.active_mut() else {
+            return;
+        };
+        let Some(page) = tab.hibernation.wake(Instant::now()) else {
+            return;
+        };
+
+        let private = tab.kind.is_private();
+        let web_view = WebView::new(
+            &self.display.window,
+            &self.display.size_info,
+            tab.id,
+            &page.url,
+            private,
+            &self.config.web,
+            event_proxy,
+        );
+
+        match web_view {
+            Ok(mut web_view) => {
+                if let Some(zoom) = page.zoom {
+                    web_view.set_page_zoom(zoom);
+                }
+                if page.muted {
+                    web_view.set_muted(true);
+                }
+
+                // Scroll back once the page has been loaded.
+                tab.web_command_state.set_pending_scroll(page.url, page.scroll_x, page.scroll_y);
+                tab.web_command_state.set_cursor_bootstrapped(false);
+                tab.web_view = Some(web_view);
+            },
+            Err(err) => {
+                warn!("Could not restore hibernated web tab: {err}");
+                return;
+            },
+        }
+
+        self.update_webview_visibility();
+        self.dirty = true;
+    }
+
+    #[cfg(target_os = "macos")]
+    fn allocate_favicon_char(&mut self) -> char {
+        const BMP_END: u32 = 0xF8FF;
+        const SUP_START: u32 = 0xF0000;
+        const SUP_END: u32 = 0xFFFFD;
+
+        if self.next_favicon_char == BMP_END + 1 {
+            self.next_favicon_char = SUP_START;
+        }
+        if self.next_favicon_char > SUP_END {
+            panic!("Ran out of favicon glyph slots");
+        }
+
+        let value = self.next_favicon_char;
+        self.next_favicon_char = self.next_favicon_char.saturating_add(1);
+        char::from_u32(value).expect("Invalid favicon glyph")
+    }
+
+    fn set_active_tab(&mut self, tab_id: TabId) {
+        let previous = self.tabs.active_id();
+        if self.tabs.get(tab_id).is_none() {
+            return;
+        }
+
+        let changed = self.tabs.set_active(tab_id);
+
+        if changed {
+            self.update_tab_program_name(tab_id);
+        }
+
+        if changed {
+            if let Some(prev_id) = previous {
+                if let Some(prev_tab) = self.tabs.get_mut(prev_id) {
+                    if !prev_tab.kind.is_web() {
+                        prev_tab.terminal.lock().is_focused = false;
+                    }
+                }
+            }
+        }
+
+        if let Some(active_tab) = self.tabs.get_mut(tab_id) {
+            if !active_tab.kind.is_web() {
+                active_tab.terminal.lock().is_focused = self.window_focused;
+            } else {
+                #[cfg(target_os = "macos")]
+                {
+                    self.display.window.set_mouse_cursor(CursorIcon::Default);
+                    active_tab.web_command_state.set_last_cursor(CursorIcon::Default);
+                    active_tab.web_command_state.set_cursor_pending(false);
+                }
+            }
+            if !self.preserve_title && self.config.window.dynamic_title {
+                self.display.window.set_title(active_tab.title.current().to_owned());
+            }
+        }
+
+        if changed {
+            if let Some(previous_id) = previous {
+                if let Some(previous_tab) = self.tabs.get_mut(previous_id) {
+                    previous_tab.command_state.cancel();
+                    #[cfg(target_os = "macos")]
+                    {
+                        previous_tab.web_command_state.reset_mode();
+                        previous_tab.hibernation.touch(Instant::now());
+                    }
+                }
+            }
+            if let Some(active_tab) = self.tabs.active_mut() {
+                active_tab.command_state.cancel();
+                #[cfg(target_os = "macos")]
+                {
+                    active_tab.web_command_state.reset_mode();
+                    active_tab.hibernation.touch(Instant::now());
+                }
+            }
+            self.display.tab_panel.cancel_edit();
+            self.update_webview_visibility();
+            self.display.pending_update.dirty = true;
+            self.display.damage_tracker.frame().mark_fully_damaged();
+            self.refresh_tab_panel();
+            self.dirty = true;
+        }
+    }
+
+    pub(crate) fn create_tab(
+        &mut self,
+        options: WindowOptions,
+        proxy: &EventLoopProxy<Event>,
+    ) -> Result<TabId, Box<dyn Error>> {
+        let current = self.tabs.active_id();
+        let tab_id = self.create_tab_with_popup(options, proxy, None, None, None, true)?;
+        self.place_new_tab(tab_id, current);
+        Ok(tab_id)
+    }
+
+    /// Move a new tab within its group according to `tabs.new_tab_position`.
+    fn place_new_tab(&mut self, tab_id: TabId, current: Option<TabId>) {
+        if self.tabs.place_new_tab(tab_id, current) {
+            self.refresh_tab_panel();
+        }
+    }
+
+    /// Create a tab requested by a binding, using the defaults of the active group.
+    ///
+    /// Terminals without a working directory from their options or group start in the directory
+    /// of the `source` tab.
+    pub(crate) fn create_new_tab(
+        &mut self,
+        mut options: WindowOptions,
+        source: Option<TabId>,
+        proxy: &EventLoopProxy<Event>,
+    ) -> Result<TabId, Box<dyn Error>> {
+        self.tabs.apply_group_defaults(&mut options, None, None);
+
+        let terminal_options = &mut options.terminal_options;
+        if matches!(options.window_kind, WindowKind::Terminal)
+            && terminal_options.working_directory.is_none()
+        {
+            let source = source.or(self.tabs.active_id()).and_then(|id| self.tabs.get(id));
+            let source = source.filter(|tab| !tab.kind.is_web());
+            terminal_options.working_directory = source.and_then(TabState::working_directory);
+        }
+
+        self.create_tab(options, proxy)
+    }
+
     pub(crate) fn create_tab_in_group(
         &mut self,
         options: WindowOptions,
@@ -1395,7 +2296,7 @@ impl WindowContext {
         group_name: Option<String>,
         proxy: &EventLoopProxy<Event>,
     ) -> Result<TabId, Box<dyn Error>> {
-        self.create_tab_with_popup(options, proxy, None, group_id, group_name)
+        self.create_tab_with_popup(options, proxy, None, group_id, group_name, true)
     }
 
     fn create_tab_with_popup(
@@ -1405,6 +2306,7 @@ impl WindowContext {
         pending_popup: Option<PendingPopup>,
         group_id: Option<usize>,
         group_name: Option<String>,
+        focus: bool,
     ) -> Result<TabId, Box<dyn Error>> {
         let terminal_command_input = if matches!(&options.window_kind, WindowKind::Terminal) {
             options.terminal_options.command_input()
@@ -1425,11 +2327,19 @@ impl WindowContext {
             group_id,
             group_name,
         )?;
-        self.set_active_tab(tab_id);
+        #[cfg(unix)]
+        self.ipc_events.push(IpcEvent::TabCreated { tab_id: tab_id.into() });
+        if focus || self.tabs.active_id() == Some(tab_id) {
+            self.set_active_tab(tab_id);
+        } else {
+            // Keep web views of background tabs hidden behind the active tab.
+            self.update_webview_visibility();
+            self.refresh_tab_panel();
+        }
         self.send_startup_input(tab_id, terminal_command_input);
         if let Some(input) = command_input.as_deref() {
-            if let Some(active_tab) = self.tabs.active_mut() {
-                active_tab.command_state.start_with_input(':', input);
+            if let Some(new_tab) = self.tabs.get_mut(tab_id) {
+                new_tab.command_state.start_with_input(':', input);
                 self.display.pending_update.dirty = true;
                 self.display.damage_tracker.frame().mark_fully_damaged();
                 self.dirty = true;
@@ -1458,25 +2368,22 @@ impl WindowContext {
         proxy: &EventLoopProxy<Event>,
     ) -> Result<TabId, Box<dyn Error>> {
         let Some(popup) = take_pending_popup(popup_id) else {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Popup WebView not found",
-            )
-            .into());
+            return Err(
+                std::io::Error::new(std::io::ErrorKind::Other, "Popup WebView not found").into()
+            );
         };
 
         let mut options = WindowOptions::default();
-        options.window_kind = WindowKind::Web {
-            url: popup.url.clone().unwrap_or_default(),
-        };
+        options.window_kind =
+            WindowKind::Web { url: popup.url.clone().unwrap_or_default(), private: popup.private };
 
-        self.create_tab_with_popup(options, proxy, Some(popup), None, None)
+        self.create_tab_with_popup(options, proxy, Some(popup), None, None, true)
     }
 
     pub(crate) fn handle_tab_command(&mut self, command: crate::tabs::TabCommand) {
         let target = match command {
-            crate::tabs::TabCommand::SelectNext => self.tabs.select_next(),
-            crate::tabs::TabCommand::SelectPrevious => self.tabs.select_previous(),
+            crate::tabs::TabCommand::SelectNext => self.tabs.select_next(true),
+            crate::tabs::TabCommand::SelectPrevious => self.tabs.select_previous(true),
             crate::tabs::TabCommand::SelectIndex(index) => self.tabs.select_by_index(index),
             crate::tabs::TabCommand::SelectLast => self.tabs.select_last(),
         };
@@ -1490,29 +2397,144 @@ impl WindowContext {
         self.tabs.active_id()
     }
 
+    /// Take the events queued for IPC subscribers.
+    #[cfg(unix)]
+    pub(crate) fn take_ipc_events(&mut self) -> Vec<IpcEvent> {
+        mem::take(&mut self.ipc_events)
+    }
+
+    /// Take the queued IPC events, followed by a close event for every remaining tab.
+    #[cfg(unix)]
+    pub(crate) fn take_closing_ipc_events(&mut self) -> Vec<IpcEvent> {
+        let mut events = self.take_ipc_events();
+        for tab in self.tabs.iter() {
+            self.tab_waiters.exited(tab.id.into(), tab.exit_status.flatten());
+            events.push(IpcEvent::TabClosed { tab_id: tab.id.into() });
+        }
+        events
+    }
+
+    /// Take whether tabs changed since the session was last saved.
+    pub(crate) fn take_session_dirty(&mut self) -> bool {
+        mem::take(&mut self.tabs.session_dirty)
+    }
+
+    /// Write the URL history, if pages were visited since it was last written.
+    ///
+    /// URLs saved by other windows in the meantime are merged into this window's history.
+    pub(crate) fn save_url_history(&mut self) {
+        let Some(path) = history::history_path() else {
+            return;
+        };
+
+        if let Err(err) = self.command_history.save_merged(&path) {
+            warn!("Unable to save URL history to {path:?}: {err}");
+        }
+    }
+
+    /// Tabs and groups of this window for the session file.
+    pub(crate) fn session_groups(&self) -> Vec<SessionGroup> {
+        self.tabs
+            .groups
+            .iter()
+            .map(|group| SessionGroup {
+                name: group.name.clone(),
+                tabs: group
+                    .tabs
+                    .iter()
+                    .filter_map(|tab_id| self.tabs.get(*tab_id))
+                    .filter_map(TabState::session_tab)
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Open the tabs of a saved session.
+    ///
+    /// The active tab is reused for the first session tab, so it must have been created from
+    /// that tab's options.
+    pub(crate) fn restore_session(
+        &mut self,
+        groups: Vec<SessionGroup>,
+        proxy: &EventLoopProxy<Event>,
+    ) {
+        let mut initial_tab = self.tabs.active_id();
+        let mut first_tab = None;
+        for group in groups {
+            let mut group_id = None;
+            for tab in group.tabs {
+                let tab_id = match initial_tab.take() {
+                    Some(tab_id) => tab_id,
+                    None => {
+                        let target = *group_id.get_or_insert_with(|| self.tabs.create_group(None));
+                        let options = tab.window_options();
+                        match self.create_tab_in_group(options, Some(target), None, proxy) {
+                            Ok(tab_id) => tab_id,
+                            Err(err) => {
+                                warn!("Unable to restore tab: {err}");
+                                continue;
+                            },
+                        }
+                    },
+                };
+
+                group_id = self.tabs.group_for_tab(tab_id).map(|(group_id, _)| group_id);
+                first_tab.get_or_insert(tab_id);
+                self.tabs.set_title(tab_id, tab.title);
+                self.tabs.set_custom_title(tab_id, tab.custom_title);
+            }
+
+            if let Some(group_id) = group_id {
+                self.tabs.set_group_name(group_id, group.name);
+            }
+        }
+
+        self.tabs.prune_empty_groups();
+        if let Some(tab_id) = first_tab {
+            self.set_active_tab(tab_id);
+        }
+        self.refresh_tab_panel();
+    }
+
     pub(crate) fn tab_kind(&self, tab_id: TabId) -> Option<&WindowKind> {
         self.tabs.get(tab_id).map(|tab| &tab.kind)
     }
 
     pub(crate) fn close_tab(&mut self, tab_id: TabId) -> bool {
         let was_active = self.tabs.active_id() == Some(tab_id);
+        #[cfg(unix)]
+        let neighbors = self.tabs.group_for_tab(tab_id).map(|(group_id, index)| {
+            let group_tabs = self.tabs.group_tab_ids(group_id);
+            let before = group_tabs[..index].iter().rev().copied().collect();
+            (before, group_tabs[index + 1..].to_vec())
+        });
         let Some(tab) = self.tabs.remove(tab_id) else {
             return false;
         };
 
-        #[cfg(target_os = "macos")]
-        if tab.kind.is_web() {
-            self.closed_tabs.push(ClosedTab {
+        #[cfg(unix)]
+        if self.config.general.closed_tab_history > 0 {
+            let (before, after) = neighbors.unwrap_or_default();
+            let working_directory = if tab.kind.is_web() { None } else { tab.working_directory() };
+            let closed = ClosedTab {
                 kind: tab.kind.clone(),
-            });
-            const MAX_CLOSED_TABS: usize = 10;
-            if self.closed_tabs.len() > MAX_CLOSED_TABS {
-                self.closed_tabs.remove(0);
-            }
+                title: tab.title.reported.clone(),
+                custom_title: tab.title.custom.clone(),
+                working_directory,
+                before,
+                after,
+            };
+            self.closed_tabs.push(closed, self.config.general.closed_tab_history);
         }
 
         let _ = tab.notifier.0.send(Msg::Shutdown);
 
+        #[cfg(unix)]
+        {
+            self.tab_waiters.exited(tab_id.into(), tab.exit_status.flatten());
+            self.ipc_events.push(IpcEvent::TabClosed { tab_id: tab_id.into() });
+        }
+
         if was_active {
             if let Some(active_id) = self.tabs.active_id() {
                 self.set_active_tab(active_id);
@@ -1525,36 +2547,74 @@ impl WindowContext {
         self.tabs.active_id().is_none()
     }
 
-    #[cfg(target_os = "macos")]
+    /// Reopen the most recently closed tab next to the tabs it was closed from.
+    #[cfg(unix)]
     pub(crate) fn restore_closed_tab(
         &mut self,
         proxy: &EventLoopProxy<Event>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<Option<TabId>, Box<dyn Error>> {
         let Some(closed) = self.closed_tabs.pop() else {
-            return Ok(());
+            return Ok(None);
         };
 
+        // Without any of its former neighbors, the tab is reopened in the active group.
+        let position = self.tabs.closed_tab_position(&closed);
+
         let mut options = WindowOptions::default();
         options.window_kind = closed.kind;
-        let _ = self.create_tab(options, proxy)?;
-        Ok(())
+        options.terminal_options.working_directory = closed.working_directory;
+        let group_id = position.map(|(group_id, _)| group_id);
+        let tab_id = self.create_tab_in_group(options, group_id, None, proxy)?;
+
+        if let Some((group_id, index)) = position {
+            self.tabs.move_tab_id(tab_id, Some(group_id), Some(index));
+        }
+        self.tabs.set_title(tab_id, closed.title);
+        self.tabs.set_custom_title(tab_id, closed.custom_title);
+        self.refresh_tab_panel();
+
+        Ok(Some(tab_id))
     }
 
-    #[cfg(target_os = "macos")]
-    pub(crate) fn open_web_url_in_tab(
+    /// Open a copy of a tab in its group, at the same page or working directory.
+    ///
+    /// The copy is placed like other new tabs, with the source tab taking the place of the
+    /// current tab.
+    #[cfg(unix)]
+    pub(crate) fn duplicate_tab(
         &mut self,
         tab_id: TabId,
-        url: String,
-    ) -> Result<(), String> {
+        proxy: &EventLoopProxy<Event>,
+    ) -> Result<TabId, Box<dyn Error>> {
+        let Some(tab) = self.tabs.get(tab_id) else {
+            return Err(String::from("Tab not found").into());
+        };
+
+        let mut options = WindowOptions::default();
+        options.window_kind = tab.kind.clone();
+        if !tab.kind.is_web() {
+            options.terminal_options.working_directory = tab.working_directory();
+        }
+
+        let group_id = self.tabs.group_for_tab(tab_id).map(|(group_id, _)| group_id);
+        let duplicate_id = self.create_tab_in_group(options, group_id, None, proxy)?;
+        self.place_new_tab(duplicate_id, Some(tab_id));
+        Ok(duplicate_id)
+    }
+
+    #[cfg(target_os = "macos")]
+    pub(crate) fn open_web_url_in_tab(&mut self, tab_id: TabId, url: String) -> Result<(), String> {
         let Some(tab) = self.tabs.get_mut(tab_id) else {
             return Err(String::from("Tab not found"));
         };
 
-        if let WindowKind::Web { url: current_url } = &mut tab.kind {
+        if let WindowKind::Web { url: current_url, private } = &mut tab.kind {
             *current_url = url.clone();
             if let Some(web_view) = tab.web_view.as_mut() {
                 if web_view.load_url(&url) {
-                    self.command_history.record_url(url.clone());
+                    if !*private {
+                        self.command_history.add(url.clone());
+                    }
                     self.update_tab_title(tab_id, url);
                     return Ok(());
                 }
@@ -1572,9 +2632,25 @@ impl WindowContext {
         proxy: &EventLoopProxy<Event>,
     ) -> Result<(), Box<dyn Error>> {
         let mut options = WindowOptions::default();
-        options.window_kind = WindowKind::Web { url: url.clone() };
+        options.window_kind = WindowKind::Web { url: url.clone(), private: false };
         let _ = self.create_tab(options, proxy)?;
-        self.command_history.record_url(url);
+        self.command_history.add(url);
+        Ok(())
+    }
+
+    /// Open a URL in a new web tab, keeping the current tab focused.
+    #[cfg(target_os = "macos")]
+    pub(crate) fn open_web_url_background_tab(
+        &mut self,
+        url: String,
+        proxy: &EventLoopProxy<Event>,
+    ) -> Result<(), Box<dyn Error>> {
+        let current = self.tabs.active_id();
+        let mut options = WindowOptions::default();
+        options.window_kind = WindowKind::Web { url: url.clone(), private: false };
+        let tab_id = self.create_tab_with_popup(options, proxy, None, None, None, false)?;
+        self.place_new_tab(tab_id, current);
+        self.command_history.add(url);
         Ok(())
     }
 
@@ -1604,23 +2680,38 @@ impl WindowContext {
                         let activity = if tab.kind.is_web() {
                             None
                         } else {
-                            Some(Self::ipc_activity(&tab.activity, now))
+                            Some(Self::ipc_activity(&self.tabs.activity(*tab_id), now))
                         };
+                        let foreground_job = tab.foreground_job();
                         Some(IpcTabState {
                             tab_id: (*tab_id).into(),
                             group_id: group.id,
                             index,
                             is_active: Some(*tab_id) == active,
-                            title: tab.title.clone(),
-                            custom_title: tab.custom_title.clone(),
+                            title: tab.title.reported.clone(),
+                            custom_title: tab.title.custom.clone(),
                             program_name: tab.program_name.clone(),
                             kind: IpcTabKind::from(&tab.kind),
                             activity,
+                            load_progress: tab.load_progress(),
+                            busy: foreground_job.is_some(),
+                            foreground_program: foreground_job.filter(|name| !name.is_empty()),
+                            zoom: tab.web_zoom(),
+                            scroll: tab.web_scroll(),
+                            cwd: if tab.kind.is_web() { None } else { tab.working_directory() },
+                            inspector_visible: tab.web_inspector_visible(),
                         })
                     })
                     .collect();
 
-                IpcTabGroup { id: group.id, name: group.name.clone(), tabs }
+                IpcTabGroup {
+                    id: group.id,
+                    name: group.name.clone(),
+                    color: group.color,
+                    default_cwd: group.default_cwd.clone(),
+                    homepage: group.homepage.clone(),
+                    tabs,
+                }
             })
             .collect()
     }
@@ -1632,18 +2723,26 @@ impl WindowContext {
         let activity = if tab.kind.is_web() {
             None
         } else {
-            Some(Self::ipc_activity(&tab.activity, now))
+            Some(Self::ipc_activity(&self.tabs.activity(tab_id), now))
         };
+        let foreground_job = tab.foreground_job();
         Some(IpcTabState {
             tab_id: tab_id.into(),
             group_id,
             index,
             is_active: Some(tab_id) == self.tabs.active_id(),
-            title: tab.title.clone(),
-            custom_title: tab.custom_title.clone(),
+            title: tab.title.reported.clone(),
+            custom_title: tab.title.custom.clone(),
             program_name: tab.program_name.clone(),
             kind: IpcTabKind::from(&tab.kind),
             activity,
+            load_progress: tab.load_progress(),
+            busy: foreground_job.is_some(),
+            foreground_program: foreground_job.filter(|name| !name.is_empty()),
+            zoom: tab.web_zoom(),
+            scroll: tab.web_scroll(),
+            cwd: if tab.kind.is_web() { None } else { tab.working_directory() },
+            inspector_visible: tab.web_inspector_visible(),
         })
     }
 
@@ -1655,16 +2754,97 @@ impl WindowContext {
     #[cfg(unix)]
     pub(crate) fn ipc_create_tab(
         &mut self,
-        options: WindowOptions,
+        mut options: WindowOptions,
         group_id: Option<usize>,
         group_name: Option<String>,
+        focus: bool,
         proxy: &EventLoopProxy<Event>,
     ) -> Result<TabId, IpcError> {
-        self.create_tab_in_group(options, group_id, group_name, proxy).map_err(|err| {
-            IpcError::new(IpcErrorCode::Internal, format!("Could not create tab: {err}"))
+        self.tabs.apply_group_defaults(&mut options, group_id, group_name.as_deref());
+
+        // Start new terminals in the directory of the active terminal, like new tab bindings.
+        if matches!(options.window_kind, WindowKind::Terminal) {
+            let active = self.tabs.active().filter(|tab| !tab.kind.is_web());
+            options
+                .terminal_options
+                .inherit_working_directory(|| active.and_then(TabState::working_directory));
+        }
+
+        let current = self.tabs.active_id();
+        let tab_id =
+            self.create_tab_with_popup(options, proxy, None, group_id, group_name, focus).map_err(
+                |err| IpcError::new(IpcErrorCode::Internal, format!("Could not create tab: {err}")),
+            )?;
+        self.place_new_tab(tab_id, current);
+        Ok(tab_id)
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_duplicate_tab(
+        &mut self,
+        tab_id: TabId,
+        proxy: &EventLoopProxy<Event>,
+    ) -> Result<TabId, IpcError> {
+        if self.tabs.get(tab_id).is_none() {
+            return Err(IpcError::new(IpcErrorCode::NotFound, "Tab not found"));
+        }
+
+        self.duplicate_tab(tab_id, proxy).map_err(|err| {
+            IpcError::new(IpcErrorCode::Internal, format!("Could not duplicate tab: {err}"))
         })
     }
 
+    /// Notify IPC clients that the command of a terminal tab exited.
+    #[cfg(unix)]
+    pub(crate) fn note_child_exit(&mut self, tab_id: TabId, status: Option<i32>) {
+        let Some(tab) = self.tabs.get_mut(tab_id).filter(|tab| tab.exit_status.is_none()) else {
+            return;
+        };
+        tab.exit_status = Some(status);
+
+        self.ipc_events.push(IpcEvent::ChildExited { tab_id: tab_id.into(), status });
+        self.tab_waiters.exited(tab_id.into(), status);
+    }
+
+    /// Reply once the command of a terminal tab exited, or with an error after `timeout`.
+    #[cfg(unix)]
+    pub(crate) fn ipc_wait_tab<F>(
+        &mut self,
+        tab_id: Option<TabId>,
+        timeout: Option<Duration>,
+        reply: F,
+    ) where
+        F: FnOnce(SocketReply) + 'static,
+    {
+        let Some(tab) = tab_id.or(self.tabs.active_id()).and_then(|id| self.tabs.get(id)) else {
+            return reply(reply_error(IpcErrorCode::NotFound, "Tab not found"));
+        };
+
+        if tab.kind.is_web() {
+            return reply(reply_error(IpcErrorCode::InvalidRequest, "Not a terminal tab"));
+        }
+
+        // Tabs kept open by `--hold` reply right away.
+        if let Some(status) = tab.exit_status {
+            return reply(SocketReply::TabExited { tab_id: tab.id.into(), status });
+        }
+
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        self.tab_waiters.wait(tab.id.into(), deadline, Box::new(reply));
+    }
+
+    /// Schedule the earliest timeout of IPC clients waiting for a tab.
+    #[cfg(unix)]
+    fn update_tab_wait_timeout(&mut self, scheduler: &mut Scheduler) {
+        let timer_id = TimerId::new(Topic::TabWaitTimeout, self.id());
+        scheduler.unschedule(timer_id);
+        if let Some(deadline) = self.tab_waiters.next_deadline() {
+            let event = Event::new(EventType::TabWaitTimeout, self.id());
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            scheduler.schedule(event, timeout, false, timer_id);
+        }
+    }
+
     #[cfg(unix)]
     pub(crate) fn ipc_create_group(&mut self, name: Option<String>) -> Result<usize, IpcError> {
         let group_id = self.tabs.create_group(name);
@@ -1687,17 +2867,13 @@ impl WindowContext {
     pub(crate) fn ipc_select_tab(&mut self, selection: TabSelection) -> Result<(), IpcError> {
         let target = match selection {
             TabSelection::Active => return Ok(()),
-            TabSelection::Next => self.tabs.select_next(),
-            TabSelection::Previous => self.tabs.select_previous(),
+            TabSelection::Next { wrap } => self.tabs.select_next(wrap),
+            TabSelection::Previous { wrap } => self.tabs.select_previous(wrap),
             TabSelection::Last => self.tabs.select_last(),
             TabSelection::ByIndex { index } => self.tabs.select_by_index(index),
             TabSelection::ById { tab_id } => {
                 let tab_id = tab_id.into();
-                if self.tabs.get(tab_id).is_some() {
-                    Some(tab_id)
-                } else {
-                    None
-                }
+                if self.tabs.get(tab_id).is_some() { Some(tab_id) } else { None }
             },
         };
 
@@ -1705,128 +2881,549 @@ impl WindowContext {
             return Err(IpcError::new(IpcErrorCode::NotFound, "Tab not found"));
         };
 
-        self.set_active_tab(tab_id);
-        Ok(())
+        self.set_active_tab(tab_id);
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_move_tab(
+        &mut self,
+        tab_id: TabId,
+        target_group_id: Option<usize>,
+        target_index: Option<usize>,
+    ) -> Result<(), IpcError> {
+        if !self.tabs.move_tab(tab_id, target_group_id, target_index) {
+            return Err(IpcError::new(IpcErrorCode::NotFound, "Tab not found"));
+        }
+        self.refresh_tab_panel();
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_set_tab_title(
+        &mut self,
+        tab_id: TabId,
+        title: Option<String>,
+    ) -> Result<(), IpcError> {
+        if self.tabs.get(tab_id).is_none() {
+            return Err(IpcError::new(IpcErrorCode::NotFound, "Tab not found"));
+        }
+        if self.tabs.set_custom_title(tab_id, title) {
+            self.refresh_tab_panel();
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_set_tab_title_template(
+        &mut self,
+        tab_id: TabId,
+        template: Option<String>,
+    ) -> Result<(), IpcError> {
+        let Some(tab) = self.tabs.get(tab_id) else {
+            return Err(IpcError::new(IpcErrorCode::NotFound, "Tab not found"));
+        };
+        if tab.kind.is_web() {
+            let message = "Title templates only apply to terminal tabs";
+            return Err(IpcError::new(IpcErrorCode::Unsupported, message));
+        }
+        if self.tabs.set_title_template(tab_id, template) {
+            self.update_tab_program_name(tab_id);
+            self.refresh_tab_panel();
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_set_group_name(
+        &mut self,
+        group_id: usize,
+        name: Option<String>,
+    ) -> Result<(), IpcError> {
+        if !self.tabs.set_group_name(group_id, name) {
+            return Err(IpcError::new(IpcErrorCode::NotFound, "Group not found"));
+        }
+        self.refresh_tab_panel();
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_set_group_color(
+        &mut self,
+        group_id: usize,
+        color: Option<Rgb>,
+    ) -> Result<(), IpcError> {
+        if !self.tabs.set_group_color(group_id, color) {
+            return Err(IpcError::new(IpcErrorCode::NotFound, "Group not found"));
+        }
+        self.refresh_tab_panel();
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_set_group_defaults(
+        &mut self,
+        group_id: usize,
+        cwd: Option<PathBuf>,
+        homepage: Option<String>,
+    ) -> Result<(), IpcError> {
+        if !self.tabs.set_group_defaults(group_id, cwd, homepage) {
+            return Err(IpcError::new(IpcErrorCode::NotFound, "Group not found"));
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_restore_closed_tab(
+        &mut self,
+        count: usize,
+        proxy: &EventLoopProxy<Event>,
+    ) -> Result<(), IpcError> {
+        for _ in 0..count {
+            match self.restore_closed_tab(proxy) {
+                Ok(Some(_)) => (),
+                Ok(None) => break,
+                Err(err) => return Err(IpcError::new(IpcErrorCode::Internal, err.to_string())),
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_closed_tabs(&self) -> Vec<IpcClosedTab> {
+        self.closed_tabs
+            .iter()
+            .map(|tab| IpcClosedTab {
+                title: tab.title.clone(),
+                custom_title: tab.custom_title.clone(),
+                kind: IpcTabKind::from(&tab.kind),
+                group_id: self.tabs.closed_tab_position(tab).map(|(group_id, _)| group_id),
+            })
+            .collect()
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_open_url_in_tab(
+        &mut self,
+        tab_id: TabId,
+        url: String,
+        proxy: &EventLoopProxy<Event>,
+    ) -> Result<(), IpcError> {
+        #[cfg(target_os = "macos")]
+        {
+            let _ = proxy;
+            return self
+                .open_web_url_in_tab(tab_id, url)
+                .map_err(|err| IpcError::new(IpcErrorCode::InvalidRequest, err));
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (tab_id, url, proxy);
+            Err(web_tabs_unsupported())
+        }
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_open_url_new_tab(
+        &mut self,
+        url: String,
+        proxy: &EventLoopProxy<Event>,
+    ) -> Result<TabId, IpcError> {
+        #[cfg(target_os = "macos")]
+        {
+            let mut options = WindowOptions::default();
+            options.window_kind = WindowKind::Web { url: url.clone(), private: false };
+            let tab_id = self
+                .create_tab(options, proxy)
+                .map_err(|err| IpcError::new(IpcErrorCode::Internal, err.to_string()))?;
+            self.command_history.add(url);
+            return Ok(tab_id);
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (url, proxy);
+            Err(web_tabs_unsupported())
+        }
+    }
+
+    #[cfg(unix)]
+    /// Open the page of a web tab in the default external browser.
+    #[cfg(unix)]
+    pub(crate) fn ipc_open_external(&mut self, tab_id: TabId) -> Result<(), IpcError> {
+        let tab = self
+            .tabs
+            .get(tab_id)
+            .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Tab not found"))?;
+        let WindowKind::Web { url, .. } = &tab.kind else {
+            return Err(IpcError::new(IpcErrorCode::InvalidRequest, "Not a web tab"));
+        };
+
+        let url = external_url(url).ok_or_else(|| {
+            IpcError::new(
+                IpcErrorCode::InvalidRequest,
+                "Only http(s) URLs can be opened externally",
+            )
+        })?;
+        spawn_daemon(EXTERNAL_OPENER, &[url], tab.master_fd, tab.shell_pid).map_err(|err| {
+            let message = format!("Unable to launch {EXTERNAL_OPENER}: {err}");
+            IpcError::new(IpcErrorCode::Internal, message)
+        })
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_run_bookmarklet(
+        &mut self,
+        tab_id: TabId,
+        name: &str,
+    ) -> Result<(), IpcError> {
+        #[cfg(target_os = "macos")]
+        {
+            let config = self.config.clone();
+            let bookmarklet = config
+                .web
+                .bookmarklet(name)
+                .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Bookmarklet not found"))?;
+            let web_view = self.web_view_mut(tab_id)?;
+
+            let url = web_view.current_url().unwrap_or_default();
+            if !bookmarklet.allowed_on(&url) {
+                return Err(IpcError::new(
+                    IpcErrorCode::PermissionDenied,
+                    "Bookmarklet is not allowed on this page",
+                ));
+            }
+
+            web_view.exec_js(bookmarklet.source());
+            Ok(())
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (tab_id, name);
+            Err(web_tabs_unsupported())
+        }
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_reload_web(
+        &mut self,
+        tab_id: TabId,
+        ignore_cache: bool,
+        event_loop: &ActiveEventLoop,
+        event_proxy: &EventLoopProxy<Event>,
+        clipboard: &mut Clipboard,
+        scheduler: &mut Scheduler,
+    ) -> Result<(), IpcError> {
+        #[cfg(target_os = "macos")]
+        {
+            return self.with_action_context(
+                tab_id,
+                event_loop,
+                event_proxy,
+                clipboard,
+                scheduler,
+                |ctx| {
+                    ctx.reload_web(ignore_cache);
+                },
+            );
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (tab_id, ignore_cache, event_loop, event_proxy, clipboard, scheduler);
+            Err(web_tabs_unsupported())
+        }
+    }
+
+    /// Loaded page of the web tab targeted by an IPC request.
+    #[cfg(target_os = "macos")]
+    fn web_view_mut(&mut self, tab_id: TabId) -> Result<&mut WebView, IpcError> {
+        let tab = self
+            .tabs
+            .get_mut(tab_id)
+            .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Tab not found"))?;
+        tab.web_view
+            .as_mut()
+            .ok_or_else(|| IpcError::new(IpcErrorCode::InvalidRequest, "Not a web tab"))
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn ipc_set_web_zoom(&mut self, tab_id: TabId, zoom: f64) -> Result<(), IpcError> {
+        #[cfg(target_os = "macos")]
+        {
+            self.web_view_mut(tab_id)?.set_page_zoom(zoom);
+            return Ok(());
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (tab_id, zoom);
+            Err(web_tabs_unsupported())
+        }
+    }
+
+    /// Read the scroll offset of a web tab, then pass the reply to `reply`.
+    #[cfg(target_os = "macos")]
+    pub(crate) fn ipc_web_scroll<F>(&mut self, tab_id: Option<TabId>, reply: F)
+    where
+        F: FnOnce(SocketReply) + 'static,
+    {
+        let Some(tab_id) = tab_id.or_else(|| self.active_tab_id()) else {
+            return reply(reply_error(IpcErrorCode::NotFound, "No active tab"));
+        };
+        let web_view = match self.web_view_mut(tab_id) {
+            Ok(web_view) => web_view,
+            Err(error) => return reply(SocketReply::Error { error }),
+        };
+
+        web_view.scroll_position(move |position| {
+            let response = match position {
+                Some((x, y)) => SocketReply::WebScroll { x, y },
+                None => reply_error(IpcErrorCode::Internal, "Failed to read the scroll position"),
+            };
+            reply(response);
+        });
+    }
+
+    /// Read the selected text of a tab, then pass the reply to `reply`.
+    #[cfg(unix)]
+    pub(crate) fn ipc_selection<F>(&mut self, tab_id: Option<TabId>, reply: F)
+    where
+        F: FnOnce(SocketReply) + 'static,
+    {
+        let Some(tab_id) = tab_id.or_else(|| self.active_tab_id()) else {
+            return reply(reply_error(IpcErrorCode::NotFound, "No active tab"));
+        };
+        let Some(tab) = self.tabs.get_mut(tab_id) else {
+            return reply(reply_error(IpcErrorCode::NotFound, "Tab not found"));
+        };
+
+        if !tab.kind.is_web() {
+            let text = tab.terminal.lock().selection_to_string().filter(|text| !text.is_empty());
+            return reply(SocketReply::Selection { text });
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            // Hibernated tabs have no page to select text in.
+            let Some(web_view) = tab.web_view.as_mut() else {
+                return reply(SocketReply::Selection { text: None });
+            };
+            web_view.eval_js_string(WEB_SELECTION_JS, move |text| {
+                let text = text.filter(|text| !text.is_empty());
+                reply(SocketReply::Selection { text });
+            });
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        reply(SocketReply::Error { error: web_tabs_unsupported() });
     }
 
     #[cfg(unix)]
-    pub(crate) fn ipc_move_tab(
+    pub(crate) fn ipc_set_web_scroll(
         &mut self,
         tab_id: TabId,
-        target_group_id: Option<usize>,
-        target_index: Option<usize>,
+        x: f64,
+        y: f64,
     ) -> Result<(), IpcError> {
-        if !self.tabs.move_tab(tab_id, target_group_id, target_index) {
-            return Err(IpcError::new(IpcErrorCode::NotFound, "Tab not found"));
+        #[cfg(target_os = "macos")]
+        {
+            self.web_view_mut(tab_id)?.scroll_to(x, y);
+
+            // Scripted scrolls win over positions restored once a navigation finishes.
+            if let Some(tab) = self.tabs.get_mut(tab_id) {
+                tab.web_command_state.clear_pending_scroll();
+                tab.web_scroll = Some(IpcScrollPosition { x, y });
+            }
+            return Ok(());
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (tab_id, x, y);
+            Err(web_tabs_unsupported())
         }
-        self.refresh_tab_panel();
-        Ok(())
     }
 
     #[cfg(unix)]
-    pub(crate) fn ipc_set_tab_title(
-        &mut self,
-        tab_id: TabId,
-        title: Option<String>,
-    ) -> Result<(), IpcError> {
-        if self.tabs.get(tab_id).is_none() {
-            return Err(IpcError::new(IpcErrorCode::NotFound, "Tab not found"));
+    pub(crate) fn ipc_set_web_muted(&mut self, tab_id: TabId, muted: bool) -> Result<(), IpcError> {
+        #[cfg(target_os = "macos")]
+        {
+            // Hibernated tabs are muted once their page is loaded again.
+            if self.tabs.get_mut(tab_id).is_some_and(|tab| tab.hibernation.set_muted(muted)) {
+                return Ok(());
+            }
+            self.web_view_mut(tab_id)?.set_muted(muted);
+            return Ok(());
         }
-        if self.tabs.set_custom_title(tab_id, title) {
-            self.refresh_tab_panel();
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (tab_id, muted);
+            Err(web_tabs_unsupported())
         }
-        Ok(())
     }
 
     #[cfg(unix)]
-    pub(crate) fn ipc_set_group_name(
+    pub(crate) fn ipc_hibernate_web_tab(
         &mut self,
-        group_id: usize,
-        name: Option<String>,
+        tab_id: TabId,
+        event_proxy: &EventLoopProxy<Event>,
     ) -> Result<(), IpcError> {
-        if !self.tabs.set_group_name(group_id, name) {
-            return Err(IpcError::new(IpcErrorCode::NotFound, "Group not found"));
+        #[cfg(target_os = "macos")]
+        {
+            let window_id = self.id();
+            let active = Some(tab_id) == self.tabs.active_id();
+            let tab = self
+                .tabs
+                .get_mut(tab_id)
+                .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Tab not found"))?;
+            if !tab.kind.is_web() {
+                return Err(IpcError::new(IpcErrorCode::InvalidRequest, "Not a web tab"));
+            }
+
+            // The active tab is kept loaded, like with the idle timeout.
+            if !active && tab.hibernation.is_idle(Instant::now(), Duration::ZERO) {
+                tab.request_hibernation(window_id, event_proxy);
+            }
+            return Ok(());
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (tab_id, event_proxy);
+            Err(web_tabs_unsupported())
         }
-        self.refresh_tab_panel();
-        Ok(())
     }
 
     #[cfg(unix)]
-    pub(crate) fn ipc_restore_closed_tab(
+    pub(crate) fn ipc_set_web_headers(
         &mut self,
-        proxy: &EventLoopProxy<Event>,
+        tab_id: TabId,
+        user_agent: Option<String>,
+        headers: Option<HashMap<String, String>>,
     ) -> Result<(), IpcError> {
         #[cfg(target_os = "macos")]
         {
-            return self
-                .restore_closed_tab(proxy)
-                .map_err(|err| IpcError::new(IpcErrorCode::Internal, err.to_string()));
+            let web_view = self.web_view_mut(tab_id)?;
+            if let Some(user_agent) = user_agent {
+                web_view
+                    .set_custom_user_agent(&user_agent)
+                    .map_err(|err| IpcError::new(IpcErrorCode::Internal, err.to_string()))?;
+            }
+            if let Some(headers) = headers {
+                web_view.set_extra_headers(headers);
+            }
+            return Ok(());
         }
 
         #[cfg(not(target_os = "macos"))]
         {
-            let _ = proxy;
-            Err(IpcError::new(
-                IpcErrorCode::Unsupported,
-                "Restore closed tabs is only available on macOS",
-            ))
+            let _ = (tab_id, user_agent, headers);
+            Err(web_tabs_unsupported())
         }
     }
 
     #[cfg(unix)]
-    pub(crate) fn ipc_open_url_in_tab(
-        &mut self,
-        tab_id: TabId,
-        url: String,
-        proxy: &EventLoopProxy<Event>,
-    ) -> Result<(), IpcError> {
+    pub(crate) fn ipc_web_history(&mut self, tab_id: TabId) -> Result<IpcWebHistory, IpcError> {
         #[cfg(target_os = "macos")]
         {
-            let _ = proxy;
-            return self
-                .open_web_url_in_tab(tab_id, url)
-                .map_err(|err| IpcError::new(IpcErrorCode::InvalidRequest, err));
+            let web_view = self.web_view_mut(tab_id)?;
+            let entries = web_view
+                .back_forward_list()
+                .into_iter()
+                .map(|(url, title)| IpcWebHistoryEntry { url, title })
+                .collect();
+            return Ok(IpcWebHistory { entries, current: web_view.back_forward_index() });
         }
 
         #[cfg(not(target_os = "macos"))]
         {
-            let _ = (tab_id, url, proxy);
-            Err(IpcError::new(
-                IpcErrorCode::Unsupported,
-                "Web tabs are only supported on macOS",
-            ))
+            let _ = tab_id;
+            Err(web_tabs_unsupported())
         }
     }
 
     #[cfg(unix)]
-    pub(crate) fn ipc_open_url_new_tab(
+    pub(crate) fn ipc_go_to_history_index(
         &mut self,
-        url: String,
-        proxy: &EventLoopProxy<Event>,
-    ) -> Result<TabId, IpcError> {
+        tab_id: TabId,
+        index: usize,
+    ) -> Result<(), IpcError> {
         #[cfg(target_os = "macos")]
         {
-            let mut options = WindowOptions::default();
-            options.window_kind = WindowKind::Web { url: url.clone() };
-            let tab_id = self
-                .create_tab(options, proxy)
-                .map_err(|err| IpcError::new(IpcErrorCode::Internal, err.to_string()))?;
-            self.command_history.record_url(url);
-            return Ok(tab_id);
+            if !self.web_view_mut(tab_id)?.go_to_index(index) {
+                return Err(IpcError::new(
+                    IpcErrorCode::InvalidRequest,
+                    "No history entry at index",
+                ));
+            }
+            return Ok(());
         }
 
         #[cfg(not(target_os = "macos"))]
         {
-            let _ = (url, proxy);
-            Err(IpcError::new(
-                IpcErrorCode::Unsupported,
-                "Web tabs are only supported on macOS",
-            ))
+            let _ = (tab_id, index);
+            Err(web_tabs_unsupported())
         }
     }
 
+    /// Write a PNG snapshot of a web tab, then pass the reply to `reply`.
+    #[cfg(target_os = "macos")]
+    pub(crate) fn ipc_snapshot_tab<F>(&mut self, tab_id: Option<TabId>, path: PathBuf, reply: F)
+    where
+        F: FnOnce(SocketReply) + 'static,
+    {
+        let Some(tab_id) = tab_id.or_else(|| self.active_tab_id()) else {
+            return reply(reply_error(IpcErrorCode::NotFound, "No active tab"));
+        };
+        let web_view = match self.web_view_mut(tab_id) {
+            Ok(web_view) => web_view,
+            Err(error) => return reply(SocketReply::Error { error }),
+        };
+
+        web_view.snapshot(move |png| {
+            let response = match png {
+                Some(png) => match fs::write(&path, png) {
+                    Ok(()) => SocketReply::Snapshot { path },
+                    Err(err) => reply_error(
+                        IpcErrorCode::Internal,
+                        format!("Failed to write snapshot: {err}"),
+                    ),
+                },
+                None => reply_error(
+                    IpcErrorCode::InvalidRequest,
+                    "Web page is not ready for a snapshot",
+                ),
+            };
+            reply(response);
+        });
+    }
+
+    /// Remove website data of a web tab's site, then pass the reply to `reply`.
+    #[cfg(target_os = "macos")]
+    pub(crate) fn ipc_clear_web_data<F>(&mut self, tab_id: TabId, types: WebsiteDataTypes, reply: F)
+    where
+        F: FnOnce(SocketReply) + 'static,
+    {
+        let web_view = match self.web_view_mut(tab_id) {
+            Ok(web_view) => web_view,
+            Err(error) => return reply(SocketReply::Error { error }),
+        };
+
+        web_view.clear_site_data(types, move |cleared| {
+            reply(if cleared {
+                SocketReply::Ok
+            } else {
+                reply_error(IpcErrorCode::InvalidRequest, "Web page has no site to clear")
+            });
+        });
+    }
+
     #[cfg(unix)]
-    pub(crate) fn ipc_reload_web(
+    pub(crate) fn ipc_open_inspector(
         &mut self,
         tab_id: TabId,
         event_loop: &ActiveEventLoop,
@@ -1843,7 +3440,7 @@ impl WindowContext {
                 clipboard,
                 scheduler,
                 |ctx| {
-                    ctx.reload_web();
+                    ctx.open_web_inspector();
                 },
             );
         }
@@ -1851,43 +3448,31 @@ impl WindowContext {
         #[cfg(not(target_os = "macos"))]
         {
             let _ = (tab_id, event_loop, event_proxy, clipboard, scheduler);
-            Err(IpcError::new(
-                IpcErrorCode::Unsupported,
-                "Web tabs are only supported on macOS",
-            ))
+            Err(web_tabs_unsupported())
         }
     }
 
     #[cfg(unix)]
-    pub(crate) fn ipc_open_inspector(
+    pub(crate) fn ipc_set_inspector_dock(
         &mut self,
         tab_id: TabId,
-        event_loop: &ActiveEventLoop,
-        event_proxy: &EventLoopProxy<Event>,
-        clipboard: &mut Clipboard,
-        scheduler: &mut Scheduler,
+        side: InspectorDock,
     ) -> Result<(), IpcError> {
         #[cfg(target_os = "macos")]
         {
-            return self.with_action_context(
-                tab_id,
-                event_loop,
-                event_proxy,
-                clipboard,
-                scheduler,
-                |ctx| {
-                    ctx.open_web_inspector();
-                },
-            );
+            if !self.web_view_mut(tab_id)?.set_inspector_docked(side) {
+                return Err(IpcError::new(
+                    IpcErrorCode::Unsupported,
+                    "Web Inspector docking is not supported by this WebKit version",
+                ));
+            }
+            return Ok(());
         }
 
         #[cfg(not(target_os = "macos"))]
         {
-            let _ = (tab_id, event_loop, event_proxy, clipboard, scheduler);
-            Err(IpcError::new(
-                IpcErrorCode::Unsupported,
-                "Web tabs are only supported on macOS",
-            ))
+            let _ = (tab_id, side);
+            Err(web_tabs_unsupported())
         }
     }
 
@@ -1940,6 +3525,23 @@ impl WindowContext {
         })
     }
 
+    #[cfg(unix)]
+    pub(crate) fn ipc_tab_contents(
+        &self,
+        tab_id: TabId,
+        scrollback: bool,
+    ) -> Result<Vec<String>, IpcError> {
+        let tab = self
+            .tabs
+            .get(tab_id)
+            .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Tab not found"))?;
+        if tab.kind.is_web() {
+            return Err(IpcError::new(IpcErrorCode::InvalidRequest, "Not a terminal tab"));
+        }
+
+        Ok(terminal_text_lines(&tab.terminal.lock(), scrollback))
+    }
+
     #[cfg(unix)]
     pub(crate) fn ipc_send_input(&mut self, tab_id: TabId, text: String) -> Result<(), IpcError> {
         if self.tabs.get(tab_id).is_none() {
@@ -1976,21 +3578,12 @@ impl WindowContext {
     ) -> Result<Vec<IpcInspectorTarget>, IpcError> {
         #[cfg(not(target_os = "macos"))]
         {
-            Err(IpcError::new(
-                IpcErrorCode::Unsupported,
-                "Remote inspector is only supported on macOS",
-            ))
+            Err(crate::inspector::unsupported())
         }
 
         #[cfg(target_os = "macos")]
         {
-            self.ensure_remote_inspector()?;
-            let targets = self
-                .remote_inspector
-                .as_ref()
-                .expect("remote inspector should be initialized")
-                .list_targets()
-                .map_err(map_inspector_error)?;
+            let targets = self.inspector()?.list_targets().map_err(map_inspector_error)?;
             let tabs = self.inspector_tabs();
             let pid = std::process::id();
             let mapped = targets
@@ -2018,93 +3611,21 @@ impl WindowContext {
         tab_id: Option<TabId>,
         target_id: Option<u64>,
     ) -> Result<IpcInspectorSession, IpcError> {
-        #[cfg(not(target_os = "macos"))]
-        {
-            let _ = (tab_id, target_id);
-            Err(IpcError::new(
-                IpcErrorCode::Unsupported,
-                "Remote inspector is only supported on macOS",
-            ))
+        if tab_id.is_none() && target_id.is_none() {
+            return Err(IpcError::new(
+                IpcErrorCode::InvalidRequest,
+                "tab_id or target_id must be provided",
+            ));
         }
 
-        #[cfg(target_os = "macos")]
-        {
-            if tab_id.is_none() && target_id.is_none() {
-                return Err(IpcError::new(
-                    IpcErrorCode::InvalidRequest,
-                    "tab_id or target_id must be provided",
-                ));
-            }
-
-            self.ensure_remote_inspector()?;
-            let targets = self
-                .remote_inspector
-                .as_ref()
-                .expect("remote inspector should be initialized")
-                .list_targets()
-                .map_err(map_inspector_error)?;
-            let resolved_target = if let Some(target_id) = target_id {
-                target_id
-            } else {
-                let tab_id =
-                    tab_id.ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Tab not found"))?;
-                let tab_info = self.inspector_tab_info(tab_id)?;
-                match_target_for_tab(&targets, &tab_info, std::process::id())
-                    .map_err(map_inspector_error)?
-            };
-
-            let resolved_tab_id = if let Some(tab_id) = tab_id {
-                tab_id
-            } else {
-                let target = targets
-                    .iter()
-                    .find(|target| target.target_id == resolved_target)
-                    .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Target not found"))?;
-                match_tab_for_target(target, &self.inspector_tabs(), std::process::id())
-                    .ok_or_else(|| {
-                        IpcError::new(
-                            IpcErrorCode::Ambiguous,
-                            "Target does not map to a web tab",
-                        )
-                    })?
-            };
-
-            let session = self
-                .remote_inspector
-                .as_ref()
-                .expect("remote inspector should be initialized")
-                .attach(resolved_tab_id, resolved_target)
-                .map_err(map_inspector_error)?;
-
-            Ok(IpcInspectorSession {
-                session_id: session.session_id,
-                target_id: session.target_id,
-                tab_id: session.tab_id.into(),
-            })
-        }
+        // Call through the trait, the macOS client has inherent methods of the same name.
+        let (tab_id, target_id) = self.resolve_inspector_target(tab_id, target_id)?;
+        InspectorSession::attach(self.inspector()?, tab_id, target_id)
     }
 
     #[cfg(unix)]
     pub(crate) fn ipc_detach_inspector(&mut self, session_id: String) -> Result<(), IpcError> {
-        #[cfg(not(target_os = "macos"))]
-        {
-            let _ = session_id;
-            Err(IpcError::new(
-                IpcErrorCode::Unsupported,
-                "Remote inspector is only supported on macOS",
-            ))
-        }
-
-        #[cfg(target_os = "macos")]
-        {
-            self.ensure_remote_inspector()?;
-            self.remote_inspector
-                .as_ref()
-                .expect("remote inspector should be initialized")
-                .detach(&session_id)
-                .map_err(map_inspector_error)?;
-            Ok(())
-        }
+        InspectorSession::detach(self.inspector()?, &session_id)
     }
 
     #[cfg(unix)]
@@ -2113,25 +3634,7 @@ impl WindowContext {
         session_id: String,
         message: String,
     ) -> Result<(), IpcError> {
-        #[cfg(not(target_os = "macos"))]
-        {
-            let _ = (session_id, message);
-            Err(IpcError::new(
-                IpcErrorCode::Unsupported,
-                "Remote inspector is only supported on macOS",
-            ))
-        }
-
-        #[cfg(target_os = "macos")]
-        {
-            self.ensure_remote_inspector()?;
-            self.remote_inspector
-                .as_ref()
-                .expect("remote inspector should be initialized")
-                .send_message(&session_id, &message)
-                .map_err(map_inspector_error)?;
-            Ok(())
-        }
+        self.inspector()?.send(&session_id, &message)
     }
 
     #[cfg(unix)]
@@ -2140,48 +3643,68 @@ impl WindowContext {
         session_id: String,
         max: Option<usize>,
     ) -> Result<Vec<IpcInspectorMessage>, IpcError> {
-        #[cfg(not(target_os = "macos"))]
-        {
-            let _ = (session_id, max);
-            Err(IpcError::new(
-                IpcErrorCode::Unsupported,
-                "Remote inspector is only supported on macOS",
-            ))
-        }
-
-        #[cfg(target_os = "macos")]
-        {
-            self.ensure_remote_inspector()?;
-            let messages = self
-                .remote_inspector
-                .as_ref()
-                .expect("remote inspector should be initialized")
-                .poll_messages(&session_id, max)
-                .map_err(map_inspector_error)?;
-            let mapped = messages
-                .into_iter()
-                .map(|message| IpcInspectorMessage {
-                    session_id: message.session_id,
-                    payload: message.payload,
-                })
-                .collect();
-            Ok(mapped)
-        }
+        self.inspector()?.poll(&session_id, max)
     }
 
     #[cfg(unix)]
     pub(crate) fn has_inspector_session(&self, session_id: &str) -> bool {
-        #[cfg(not(target_os = "macos"))]
-        {
-            let _ = session_id;
-            false
+        self.inspector
+            .as_ref()
+            .is_some_and(|inspector| InspectorSession::has_session(inspector, session_id))
+    }
+
+    /// Inspector connection of this window, connecting on first use.
+    #[cfg(unix)]
+    fn inspector(&mut self) -> Result<&PlatformInspector, IpcError> {
+        if self.inspector.is_none() {
+            self.inspector = Some(<PlatformInspector as InspectorSession>::connect()?);
         }
+        Ok(self.inspector.as_ref().expect("inspector should be connected"))
+    }
 
-        #[cfg(target_os = "macos")]
-        {
-            self.remote_inspector
-                .as_ref()
-                .is_some_and(|inspector| inspector.has_session(session_id))
+    /// Resolve the tab and inspector target of an attach request.
+    #[cfg(target_os = "macos")]
+    fn resolve_inspector_target(
+        &mut self,
+        tab_id: Option<TabId>,
+        target_id: Option<u64>,
+    ) -> Result<(TabId, u64), IpcError> {
+        let targets = self.inspector()?.list_targets().map_err(map_inspector_error)?;
+        let resolved_target = if let Some(target_id) = target_id {
+            target_id
+        } else {
+            let tab_id =
+                tab_id.ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Tab not found"))?;
+            let tab_info = self.inspector_tab_info(tab_id)?;
+            match_target_for_tab(&targets, &tab_info, std::process::id())
+                .map_err(map_inspector_error)?
+        };
+
+        let resolved_tab_id = if let Some(tab_id) = tab_id {
+            tab_id
+        } else {
+            let target = targets
+                .iter()
+                .find(|target| target.target_id == resolved_target)
+                .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Target not found"))?;
+            match_tab_for_target(target, &self.inspector_tabs(), std::process::id()).ok_or_else(
+                || IpcError::new(IpcErrorCode::Ambiguous, "Target does not map to a web tab"),
+            )?
+        };
+
+        Ok((resolved_tab_id, resolved_target))
+    }
+
+    /// Resolve the tab and inspector target of an attach request.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn resolve_inspector_target(
+        &mut self,
+        tab_id: Option<TabId>,
+        target_id: Option<u64>,
+    ) -> Result<(TabId, u64), IpcError> {
+        match (tab_id, target_id) {
+            (Some(tab_id), Some(target_id)) => Ok((tab_id, target_id)),
+            _ => Err(crate::inspector::unsupported()),
         }
     }
 
@@ -2206,10 +3729,8 @@ impl WindowContext {
             self.set_active_tab(tab_id);
         }
 
-        let old_is_searching = self
-            .tabs
-            .active()
-            .is_some_and(|tab| tab.search_state.history_index.is_some());
+        let old_is_searching =
+            self.tabs.active().is_some_and(|tab| tab.search_state.history_index.is_some());
 
         {
             let Some(active_tab) = self.tabs.active_mut() else {
@@ -2259,28 +3780,19 @@ impl WindowContext {
         Ok(())
     }
 
-    #[cfg(target_os = "macos")]
-    fn ensure_remote_inspector(&mut self) -> Result<(), IpcError> {
-        if self.remote_inspector.is_none() {
-            let inspector = RemoteInspectorClient::connect().map_err(map_inspector_error)?;
-            self.remote_inspector = Some(inspector);
-        }
-        Ok(())
-    }
-
     #[cfg(target_os = "macos")]
     fn inspector_tabs(&self) -> Vec<InspectorTabInfo> {
         self.tabs
             .iter()
             .filter_map(|tab| {
-                let WindowKind::Web { url } = &tab.kind else {
+                let WindowKind::Web { url, .. } = &tab.kind else {
                     return None;
                 };
                 Some(InspectorTabInfo {
                     tab_id: tab.id,
                     url: if url.is_empty() { None } else { Some(url.clone()) },
-                    title: if tab.title.is_empty() { None } else { Some(tab.title.clone()) },
-                    override_name: tab.custom_title.clone(),
+                    title: Some(tab.title.reported.clone()).filter(|title| !title.is_empty()),
+                    override_name: tab.title.custom.clone(),
                 })
             })
             .collect()
@@ -2292,21 +3804,17 @@ impl WindowContext {
             .tabs
             .get(tab_id)
             .ok_or_else(|| IpcError::new(IpcErrorCode::NotFound, "Tab not found"))?;
-        let WindowKind::Web { url } = &tab.kind else {
-            return Err(IpcError::new(
-                IpcErrorCode::InvalidRequest,
-                "Tab is not a web tab",
-            ));
+        let WindowKind::Web { url, .. } = &tab.kind else {
+            return Err(IpcError::new(IpcErrorCode::InvalidRequest, "Tab is not a web tab"));
         };
         Ok(InspectorTabInfo {
             tab_id: tab.id,
             url: if url.is_empty() { None } else { Some(url.clone()) },
-            title: if tab.title.is_empty() { None } else { Some(tab.title.clone()) },
-            override_name: tab.custom_title.clone(),
+            title: Some(tab.title.reported.clone()).filter(|title| !title.is_empty()),
+            override_name: tab.title.custom.clone(),
         })
     }
 
-
     #[cfg(unix)]
     fn apply_ipc_display_update(&mut self, old_is_searching: bool) {
         if self.display.pending_update.dirty {
@@ -2343,17 +3851,13 @@ impl WindowContext {
 
         let needle = query.to_lowercase();
         let match_id = self.tabs.iter().find_map(|tab| {
-            let title = tab.title.to_lowercase();
+            let title = tab.title.reported.to_lowercase();
             let url_match = match &tab.kind {
-                WindowKind::Web { url } => url.to_lowercase().contains(&needle),
+                WindowKind::Web { url, .. } => url.to_lowercase().contains(&needle),
                 WindowKind::Terminal => false,
             };
 
-            if title.contains(&needle) || url_match {
-                Some(tab.id)
-            } else {
-                None
-            }
+            if title.contains(&needle) || url_match { Some(tab.id) } else { None }
         });
 
         if let Some(tab_id) = match_id {
@@ -2370,6 +3874,9 @@ impl WindowContext {
     fn update_tab_title(&mut self, tab_id: TabId, title: String) {
         let custom_title = self.tabs.custom_title(tab_id).map(str::to_string);
         if self.tabs.set_title(tab_id, title.clone()) {
+            #[cfg(unix)]
+            self.ipc_events
+                .push(IpcEvent::TitleChanged { tab_id: tab_id.into(), title: title.clone() });
             if Some(tab_id) == self.tabs.active_id()
                 && !self.preserve_title
                 && self.config.window.dynamic_title
@@ -2394,7 +3901,9 @@ impl WindowContext {
         {
             let title = match name {
                 Some(title) => title,
-                None => self.tabs.get(tab_id).map(|tab| tab.title.clone()).unwrap_or_default(),
+                None => {
+                    self.tabs.get(tab_id).map(|tab| tab.title.reported.clone()).unwrap_or_default()
+                },
             };
             self.display.window.set_title(title);
         }
@@ -2409,11 +3918,7 @@ impl WindowContext {
                 return None;
             }
 
-            if trimmed == format!("group {group_id}") {
-                None
-            } else {
-                Some(trimmed.to_string())
-            }
+            if trimmed == format!("group {group_id}") { None } else { Some(trimmed.to_string()) }
         });
 
         if self.tabs.set_group_name(group_id, name) {
@@ -2431,18 +3936,59 @@ impl WindowContext {
             return false;
         }
 
-        let Ok(program_name) = foreground_process_name(tab.master_fd, tab.shell_pid) else {
+        // The process directory is only a fallback for shells which don't report it.
+        let cwd = if self.tabs.uses_title_template(tab_id) && tab.reported_cwd.is_none() {
+            foreground_process_path(tab.master_fd, tab.shell_pid).ok()
+        } else {
+            None
+        };
+        let Ok(program_name) = foreground_process_name(tab.master_fd, tab.shell_pid) else {
+            return false;
+        };
+
+        let cwd_changed = self.tabs.set_cwd(tab_id, cwd);
+        let busy_changed = self.update_tab_busy(tab_id);
+        self.tabs.set_program_name(tab_id, program_name) || cwd_changed || busy_changed
+    }
+
+    #[cfg(windows)]
+    fn update_tab_program_name(&mut self, _tab_id: TabId) -> bool {
+        false
+    }
+
+    #[cfg(not(windows))]
+    fn update_tab_busy(&mut self, tab_id: TabId) -> bool {
+        let Some(tab) = self.tabs.get(tab_id).filter(|tab| !tab.kind.is_web()) else {
             return false;
         };
 
-        self.tabs.set_program_name(tab_id, program_name)
+        let busy = foreground_process_busy(tab.master_fd, tab.shell_pid);
+        self.tabs.set_busy(tab_id, busy)
     }
 
-    #[cfg(windows)]
-    fn update_tab_program_name(&mut self, _tab_id: TabId) -> bool {
-        false
+    /// Refresh the busy state of all terminal tabs.
+    ///
+    /// Program names of busy tabs are updated as well, since only the active tab's name is
+    /// refreshed on output.
+    #[cfg(not(windows))]
+    pub(crate) fn update_busy_tabs(&mut self) {
+        let tab_ids: Vec<_> = self.tabs.iter().map(|tab| tab.id).collect();
+        let mut changed = false;
+        for tab_id in tab_ids {
+            if self.update_tab_busy(tab_id) {
+                self.update_tab_program_name(tab_id);
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.refresh_tab_panel();
+        }
     }
 
+    #[cfg(windows)]
+    pub(crate) fn update_busy_tabs(&mut self) {}
+
     /// Update the terminal window to the latest config.
     pub fn update_config(&mut self, new_config: Rc<UiConfig>) {
         let old_config = mem::replace(&mut self.config, new_config);
@@ -2450,9 +3996,17 @@ impl WindowContext {
         // Apply ipc config if there are overrides.
         self.config = self.window_config.override_config_rc(self.config.clone());
 
-        self.display.update_config(&self.config);
-        for tab in self.tabs.iter_mut() {
-            tab.terminal.lock().set_options(self.config.term_options());
+        // Only apply the sections which have changed, to preserve runtime state.
+        let changed = config_diff(&old_config, &self.config);
+
+        if changed.colors || changed.window || changed.other {
+            self.display.update_config(&self.config);
+        }
+
+        if changed.other {
+            for tab in self.tabs.iter_mut() {
+                tab.terminal.lock().set_options(self.config.term_options());
+            }
         }
 
         // Reload cursor if its thickness has changed.
@@ -2460,7 +4014,7 @@ impl WindowContext {
             self.display.pending_update.set_cursor_dirty();
         }
 
-        if old_config.font != self.config.font {
+        if changed.font {
             let scale_factor = self.display.window.scale_factor as f32;
             // Do not update font size if it has been changed at runtime.
             if self.display.font_size == old_config.font.size().scale(scale_factor) {
@@ -2471,6 +4025,20 @@ impl WindowContext {
             self.display.pending_update.set_font(font);
         }
 
+        let title_template = &self.config.window.tab_panel.title_template;
+        if self.tabs.title_template != *title_template {
+            self.tabs.title_template = title_template.clone();
+            let tab_ids: Vec<_> = self.tabs.iter().map(|tab| tab.id).collect();
+            for tab_id in tab_ids {
+                self.update_tab_program_name(tab_id);
+            }
+            self.refresh_tab_panel();
+        }
+        self.tabs.new_tab_position = self.config.tabs.new_tab_position;
+
+        #[cfg(unix)]
+        self.closed_tabs.truncate(self.config.general.closed_tab_history);
+
         // Always reload the theme to account for auto-theme switching.
         self.display.window.set_theme(self.config.window.theme());
 
@@ -2491,32 +4059,48 @@ impl WindowContext {
         // │ N  │       Y       │              Y              ││     Y     │
         // │ N  │       Y       │              N              ││     N     │
         // │ N  │       N       │              _              ││     Y     │
-        if !self.preserve_title
+        if changed.window
+            && !self.preserve_title
             && (!self.config.window.dynamic_title
                 || self.display.window.title() == old_config.window.identity.title)
         {
             self.display.window.set_title(self.config.window.identity.title.clone());
         }
 
-        let opaque = self.config.window_opacity() >= 1.;
+        if changed.window {
+            let opaque = self.config.window_opacity() >= 1.;
 
-        // Disable shadows for transparent windows on macOS.
-        #[cfg(target_os = "macos")]
-        self.display.window.set_has_shadow(opaque);
+            // Disable shadows for transparent windows on macOS.
+            #[cfg(target_os = "macos")]
+            self.display.window.set_has_shadow(opaque);
 
-        #[cfg(target_os = "macos")]
-        self.display.window.set_option_as_alt(self.config.window.option_as_alt());
+            #[cfg(target_os = "macos")]
+            self.display.window.set_option_as_alt(self.config.window.option_as_alt());
+
+            // Change opacity and blur state.
+            self.display.window.set_transparent(!opaque);
+            self.display.window.set_blur(self.config.window.blur);
+        }
 
-        // Change opacity and blur state.
-        self.display.window.set_transparent(!opaque);
-        self.display.window.set_blur(self.config.window.blur);
+        // Reinstall user scripts and the certificate policy, they apply from the next page load.
+        // The color scheme applies immediately.
+        #[cfg(target_os = "macos")]
+        if old_config.web != self.config.web {
+            for web_view in self.tabs.iter_mut().filter_map(|tab| tab.web_view.as_mut()) {
+                web_view.set_user_scripts(&self.config.web);
+                web_view.set_color_scheme(self.config.web.force_color_scheme);
+                web_view.set_allow_invalid_certs(self.config.web.allow_invalid_certs);
+            }
+        }
 
-        // Update hint keys.
-        self.display.hint_state.update_alphabet(self.config.hints.alphabet());
+        if changed.other {
+            // Update hint keys.
+            self.display.hint_state.update_alphabet(self.config.hints.alphabet());
 
-        // Update cursor blinking.
-        let event = Event::new(TerminalEvent::CursorBlinkingChange.into(), None);
-        self.event_queue.push(event.into());
+            // Update cursor blinking.
+            let event = Event::new(TerminalEvent::CursorBlinkingChange.into(), None);
+            self.event_queue.push(event.into());
+        }
 
         self.dirty = true;
     }
@@ -2587,6 +4171,8 @@ impl WindowContext {
                     &self.message_buffer,
                     &self.config,
                     &tab.command_state,
+                    tab.load_progress(),
+                    tab.hint_preview(),
                 );
             },
             DrawMode::Terminal => {
@@ -2601,6 +4187,16 @@ impl WindowContext {
                 );
             },
         }
+
+        // Keep drawing until all favicons in the tab panel are rasterized.
+        #[cfg(target_os = "macos")]
+        if self.display.tab_panel.favicons_pending() {
+            if self.display.window.has_frame {
+                self.display.window.request_redraw();
+            } else {
+                self.dirty = true;
+            }
+        }
     }
 
     /// Process events for this terminal window.
@@ -2613,13 +4209,24 @@ impl WindowContext {
         event: WinitEvent<Event>,
     ) {
         #[cfg(target_os = "macos")]
-        if self.handle_tab_panel_event(&event, event_proxy) {
+        if self.handle_tab_panel_event(&event, event_proxy, clipboard) {
             return;
         }
 
         match event {
             WinitEvent::AboutToWait
             | WinitEvent::WindowEvent { event: WindowEvent::RedrawRequested, .. } => {
+                #[cfg(unix)]
+                self.update_tab_wait_timeout(scheduler);
+                #[cfg(target_os = "macos")]
+                {
+                    self.update_web_hibernation(event_proxy, scheduler);
+                    self.update_web_state_poll(scheduler);
+                    self.refetch_evicted_favicons(event_proxy);
+                    self.schedule_tab_panel_tooltip(scheduler);
+                    self.schedule_tab_panel_drag_scroll(scheduler);
+                }
+
                 // Skip further event handling with no staged updates.
                 if self.event_queue.is_empty() {
                     return;
@@ -2638,7 +4245,8 @@ impl WindowContext {
         let events: Vec<_> = self.event_queue.drain(..).collect();
 
         for event in events {
-            if let WinitEvent::WindowEvent { event: WindowEvent::Focused(is_focused), .. } = &event {
+            if let WinitEvent::WindowEvent { event: WindowEvent::Focused(is_focused), .. } = &event
+            {
                 self.window_focused = *is_focused;
             }
 
@@ -2673,27 +4281,109 @@ impl WindowContext {
                         self.handle_web_cursor_request(tab_id, event_proxy, scheduler);
                         continue;
                     },
+                    #[cfg(target_os = "macos")]
+                    EventType::WebLoadProgress { progress } => {
+                        let Some(tab_id) = event.tab_id() else {
+                            continue;
+                        };
+                        self.handle_web_load_progress(tab_id, Some(*progress));
+                        continue;
+                    },
+                    #[cfg(target_os = "macos")]
+                    EventType::WebLoadFinished => {
+                        let Some(tab_id) = event.tab_id() else {
+                            continue;
+                        };
+                        self.handle_web_load_progress(tab_id, None);
+                        continue;
+                    },
+                    #[cfg(target_os = "macos")]
+                    EventType::WebFindResult { matched } => {
+                        let Some(tab_id) = event.tab_id() else {
+                            continue;
+                        };
+                        self.handle_web_find_result(tab_id, *matched);
+                        continue;
+                    },
+                    #[cfg(target_os = "macos")]
+                    EventType::WebHibernationTick => continue,
+                    #[cfg(unix)]
+                    EventType::TabWaitTimeout => {
+                        self.tab_waiters.expire(Instant::now());
+                        continue;
+                    },
+                    #[cfg(target_os = "macos")]
+                    EventType::WebStatePollTick => {
+                        self.poll_web_state(event_proxy);
+                        continue;
+                    },
+                    #[cfg(target_os = "macos")]
+                    EventType::WebScrollPolled { x, y } => {
+                        let tab = event.tab_id().and_then(|tab_id| self.tabs.get_mut(tab_id));
+                        if let Some(tab) = tab {
+                            tab.web_scroll = Some(IpcScrollPosition { x: *x, y: *y });
+                        }
+                        continue;
+                    },
+                    #[cfg(target_os = "macos")]
+                    EventType::TabPanelTooltip => {
+                        self.dirty = true;
+                        continue;
+                    },
+                    #[cfg(target_os = "macos")]
+                    EventType::TabPanelDragScroll => {
+                        if self.display.tab_panel.drag_scroll(&self.display.size_info) {
+                            self.dirty = true;
+                        }
+                        continue;
+                    },
+                    #[cfg(target_os = "macos")]
+                    EventType::WebHibernate(page) => {
+                        let Some(tab_id) = event.tab_id() else {
+                            continue;
+                        };
+                        self.hibernate_web_tab(tab_id, page.clone());
+                        continue;
+                    },
+                    // Errors, hint previews and focused fields only concern the active tab.
+                    #[cfg(target_os = "macos")]
+                    EventType::WebNavigationError(_)
+                    | EventType::WebHintPreview { .. }
+                    | EventType::WebHintQueued { .. }
+                    | EventType::WebFieldFocused { .. }
+                        if event.tab_id() != active_id =>
+                    {
+                        continue;
+                    },
                     EventType::Terminal(term_event) => {
                         let Some(tab_id) = event.tab_id() else {
                             continue;
                         };
 
-                        if self
-                            .tabs
-                            .get(tab_id)
-                            .is_some_and(|tab| tab.kind.is_web())
-                        {
+                        if self.tabs.get(tab_id).is_some_and(|tab| tab.kind.is_web()) {
                             continue;
                         }
 
                         match term_event {
                             TerminalEvent::Title(title) => {
+                                // Titles can signal a directory change of template titles.
+                                if self.tabs.uses_title_template(tab_id)
+                                    && self.update_tab_program_name(tab_id)
+                                {
+                                    self.refresh_tab_panel();
+                                }
                                 self.update_tab_title(tab_id, title.clone());
                             },
                             TerminalEvent::ResetTitle => {
                                 let title = self.config.window.identity.title.clone();
                                 self.update_tab_title(tab_id, title);
                             },
+                            TerminalEvent::WorkingDirectory(cwd) => {
+                                let changed = self.tabs.set_reported_cwd(tab_id, cwd.clone());
+                                if changed && self.tabs.uses_title_template(tab_id) {
+                                    self.refresh_tab_panel();
+                                }
+                            },
                             _ => (),
                         }
 
@@ -2719,10 +4409,8 @@ impl WindowContext {
             pending_events.push(event);
         }
 
-        let old_is_searching = self
-            .tabs
-            .active()
-            .is_some_and(|tab| tab.search_state.history_index.is_some());
+        let old_is_searching =
+            self.tabs.active().is_some_and(|tab| tab.search_state.history_index.is_some());
 
         {
             let Some(active_tab) = self.tabs.active_mut() else {
@@ -2803,7 +4491,7 @@ impl WindowContext {
             active_tab.mouse.hint_highlight_dirty = false;
         }
 
-        self.update_active_web_title(event_proxy);
+        self.update_web_pages(event_proxy);
 
         // Don't call `request_redraw` when event is `RedrawRequested` since the `dirty` flag
         // represents the current frame, but redraw is for the next frame.
@@ -2821,6 +4509,7 @@ impl WindowContext {
         &mut self,
         event: &WinitEvent<Event>,
         event_proxy: &EventLoopProxy<Event>,
+        clipboard: &mut Clipboard,
     ) -> bool {
         if !self.display.tab_panel.is_enabled() {
             return false;
@@ -2828,10 +4517,10 @@ impl WindowContext {
 
         match event {
             WinitEvent::WindowEvent {
-                event: WindowEvent::CursorMoved { position, .. },
-                ..
+                event: WindowEvent::CursorMoved { position, .. }, ..
             } => {
-                let update = self.display.tab_panel.cursor_moved(*position, &self.display.size_info);
+                let update =
+                    self.display.tab_panel.cursor_moved(*position, &self.display.size_info);
                 if let Some(width_px) = update.resize_width {
                     self.set_tab_panel_width_px(width_px);
                 }
@@ -2856,36 +4545,7 @@ impl WindowContext {
                     self.display.tab_panel.mouse_input(*state, *button, &self.display.size_info);
 
                 if let Some(command) = update.command {
-                    match command {
-                        crate::tab_panel::TabPanelCommand::Focus(tab_id) => {
-                            self.set_active_tab(tab_id);
-                        },
-                        crate::tab_panel::TabPanelCommand::Close(tab_id) => {
-                            let event =
-                                Event::new(EventType::CloseTab(tab_id), self.display.window.id());
-                            let _ = event_proxy.send_event(event);
-                        },
-                        crate::tab_panel::TabPanelCommand::Move {
-                            tab_id,
-                            target_group_id,
-                            target_index,
-                        } => {
-                            if self.tabs.move_tab(tab_id, target_group_id, target_index) {
-                                self.refresh_tab_panel();
-                            }
-                        },
-                        crate::tab_panel::TabPanelCommand::MoveGroup { group_id, target_index } => {
-                            if self.tabs.move_group(group_id, target_index) {
-                                self.refresh_tab_panel();
-                            }
-                        },
-                        crate::tab_panel::TabPanelCommand::RenameTab(tab_id) => {
-                            self.begin_tab_rename(tab_id);
-                        },
-                        crate::tab_panel::TabPanelCommand::RenameGroup(group_id) => {
-                            self.begin_group_rename(group_id);
-                        },
-                    }
+                    self.apply_tab_panel_command(command, event_proxy);
                 }
 
                 if update.capture {
@@ -2905,10 +4565,25 @@ impl WindowContext {
                 ..
             } => {
                 if !self.display.tab_panel.is_editing() {
-                    return false;
+                    let update = self.display.tab_panel.handle_panel_key(event);
+                    if let Some(command) = update.command {
+                        self.apply_tab_panel_command(command, event_proxy);
+                    }
+                    if update.needs_redraw {
+                        self.dirty = true;
+                        if self.display.window.has_frame {
+                            self.display.window.request_redraw();
+                        }
+                    }
+                    return update.capture;
                 }
 
-                let outcome = self.display.tab_panel.handle_key_event(event);
+                let mods = self.modifiers.state();
+                let mut outcome = self.display.tab_panel.handle_key_event(event, mods);
+                if let TabPanelEditOutcome::Paste = outcome {
+                    let text = clipboard.load(tabor_terminal::term::ClipboardType::Clipboard);
+                    outcome = self.display.tab_panel.paste(&text);
+                }
                 let needs_redraw = self.apply_tab_panel_edit_outcome(outcome);
                 if needs_redraw {
                     self.dirty = true;
@@ -2925,7 +4600,7 @@ impl WindowContext {
 
                 let outcome = match ime {
                     Ime::Commit(text) => self.display.tab_panel.handle_ime_commit(text),
-                    Ime::Preedit(_, _) | Ime::Enabled | Ime::Disabled => TabPanelEditOutcome::None,
+                    Ime::Preedit(..) | Ime::Enabled | Ime::Disabled => TabPanelEditOutcome::None,
                 };
                 let needs_redraw = self.apply_tab_panel_edit_outcome(outcome);
                 if needs_redraw {
@@ -2936,26 +4611,101 @@ impl WindowContext {
                 }
                 true
             },
-            WinitEvent::WindowEvent {
-                event: WindowEvent::MouseWheel { .. },
-                ..
-            } => self.display.tab_panel.should_capture_last(),
+            WinitEvent::WindowEvent { event: WindowEvent::MouseWheel { delta, .. }, .. } => {
+                if !self.display.tab_panel.should_capture_last() {
+                    return false;
+                }
+
+                if self.display.tab_panel.scroll(*delta, &self.display.size_info) {
+                    self.dirty = true;
+                    if self.display.window.has_frame {
+                        self.display.window.request_redraw();
+                    }
+                }
+                true
+            },
             _ => false,
         }
     }
 
+    #[cfg(target_os = "macos")]
+    fn apply_tab_panel_command(
+        &mut self,
+        command: TabPanelCommand,
+        event_proxy: &EventLoopProxy<Event>,
+    ) {
+        match command {
+            TabPanelCommand::Focus(tab_id) => {
+                self.set_active_tab(tab_id);
+            },
+            TabPanelCommand::FocusGroup(group_id) => {
+                if let Some(tab_id) = self.tabs.group_focus_target(group_id) {
+                    self.set_active_tab(tab_id);
+                }
+            },
+            TabPanelCommand::Close(tab_id) => {
+                let event = Event::new(EventType::CloseTab(tab_id), self.display.window.id());
+                let _ = event_proxy.send_event(event);
+            },
+            TabPanelCommand::CloseGroup(group_id) => {
+                let window_id = self.display.window.id();
+                for tab_id in self.tabs.group_tab_ids(group_id) {
+                    let event = Event::new(EventType::CloseTab(tab_id), window_id);
+                    let _ = event_proxy.send_event(event);
+                }
+            },
+            TabPanelCommand::Move { tab_id, target_group_id, target_index } => {
+                if self.tabs.move_tab(tab_id, target_group_id, target_index) {
+                    self.refresh_tab_panel();
+                }
+            },
+            TabPanelCommand::MoveGroup { group_id, target_index } => {
+                if self.tabs.move_group(group_id, target_index) {
+                    self.refresh_tab_panel();
+                }
+            },
+            TabPanelCommand::RenameTab(tab_id) => {
+                self.begin_tab_rename(tab_id);
+            },
+            TabPanelCommand::RenameGroup(group_id) => {
+                self.begin_group_rename(group_id);
+            },
+            TabPanelCommand::NewTab { group_id } => {
+                self.create_panel_tab(group_id, event_proxy);
+            },
+            TabPanelCommand::NewGroup => {
+                let group_id = self.tabs.create_group(None);
+                if !self.create_panel_tab(group_id, event_proxy) {
+                    self.tabs.remove_empty_group(group_id);
+                    self.refresh_tab_panel();
+                }
+            },
+        }
+    }
+
+    /// Create a terminal tab in a group for the buttons of the tab panel.
+    ///
+    /// Returns `false` when the tab could not be created.
+    #[cfg(target_os = "macos")]
+    fn create_panel_tab(&mut self, group_id: usize, event_proxy: &EventLoopProxy<Event>) -> bool {
+        let options = WindowOptions::default();
+        match self.ipc_create_tab(options, Some(group_id), None, true, event_proxy) {
+            Ok(_) => true,
+            Err(err) => {
+                warn!("Could not create tab: {}", err.message);
+                false
+            },
+        }
+    }
+
     #[cfg(target_os = "macos")]
     fn apply_tab_panel_edit_outcome(&mut self, outcome: TabPanelEditOutcome) -> bool {
         match outcome {
-            TabPanelEditOutcome::None => false,
+            TabPanelEditOutcome::None | TabPanelEditOutcome::Paste => false,
             TabPanelEditOutcome::Changed | TabPanelEditOutcome::Cancelled => true,
             TabPanelEditOutcome::Commit(commit) => {
                 let trimmed = commit.text.trim();
-                let name = if trimmed.is_empty() {
-                    None
-                } else {
-                    Some(trimmed.to_string())
-                };
+                let name = if trimmed.is_empty() { None } else { Some(trimmed.to_string()) };
 
                 match commit.target {
                     TabPanelEditTarget::Tab(tab_id) => self.rename_tab(tab_id, name),
@@ -3013,18 +4763,26 @@ impl WindowContext {
                     tab.web_command_state.reset_mode();
                 }
             },
-            WebCommand::SetMark {
-                name,
-                url,
-                scroll_x,
-                scroll_y,
-            } => {
+            WebCommand::OpenUrlInBackground { url } => {
+                if let Err(err) = self.open_web_url_background_tab(url.clone(), event_proxy) {
+                    self.message_buffer.push(crate::message_bar::Message::new(
+                        format!("Failed to open URL: {err}"),
+                        crate::message_bar::MessageType::Error,
+                    ));
+                    self.display.pending_update.dirty = true;
+                }
+                if let Some(tab_id) = event.tab_id().or(self.tabs.active_id()) {
+                    if let Some(tab) = self.tabs.get_mut(tab_id) {
+                        tab.web_command_state.reset_mode();
+                    }
+                }
+            },
+            WebCommand::SetMark { name, url, scroll_x, scroll_y } => {
                 let Some(tab_id) = event.tab_id().or(self.tabs.active_id()) else {
                     return;
                 };
                 if let Some(tab) = self.tabs.get_mut(tab_id) {
-                    tab.web_command_state
-                        .set_mark(*name, url.clone(), *scroll_x, *scroll_y);
+                    tab.web_command_state.set_mark(*name, url.clone(), *scroll_x, *scroll_y);
                 }
             },
         }
@@ -3150,7 +4908,8 @@ impl WindowContext {
 
             let new_is_searching = active_tab.search_state.history_index.is_some();
             if !old_is_searching && new_is_searching {
-                // Scroll on search start to make sure origin is visible with minimal viewport motion.
+                // Scroll on search start to make sure origin is visible with minimal viewport
+                // motion.
                 let display_offset = terminal.grid().display_offset();
                 if display_offset == 0 && cursor_at_bottom && !origin_at_bottom {
                     terminal.scroll_display(Scroll::Delta(1));
@@ -3184,26 +4943,6 @@ impl WindowContext {
     }
 }
 
-#[cfg(target_os = "macos")]
-fn map_inspector_error(error: InspectorError) -> IpcError {
-    match error {
-        InspectorError::PermissionDenied => {
-            IpcError::new(IpcErrorCode::PermissionDenied, "Inspector permission denied")
-        },
-        InspectorError::ConnectionFailed(message) => {
-            IpcError::new(IpcErrorCode::Internal, message)
-        },
-        InspectorError::Timeout => {
-            IpcError::new(IpcErrorCode::Timeout, "Inspector request timed out")
-        },
-        InspectorError::NotFound(message) => IpcError::new(IpcErrorCode::NotFound, message),
-        InspectorError::Ambiguous(message) => IpcError::new(IpcErrorCode::Ambiguous, message),
-        InspectorError::InvalidMessage(message) => {
-            IpcError::new(IpcErrorCode::InvalidRequest, message)
-        },
-    }
-}
-
 impl Drop for WindowContext {
     fn drop(&mut self) {
         // Shutdown each tab's PTY.
@@ -3215,11 +4954,13 @@ impl Drop for WindowContext {
 
 #[cfg(test)]
 mod tests {
+    use tabor_terminal::event::VoidListener;
+
     use super::*;
 
     #[test]
     fn draw_mode_selects_web() {
-        let mode = draw_mode(&WindowKind::Web { url: String::from("about:blank") });
+        let mode = draw_mode(&WindowKind::Web { url: String::from("about:blank"), private: false });
         assert_eq!(mode, DrawMode::Web);
     }
 
@@ -3228,4 +4969,351 @@ mod tests {
         let mode = draw_mode(&WindowKind::Terminal);
         assert_eq!(mode, DrawMode::Terminal);
     }
+
+    fn tab_manager(groups: &[&[u32]]) -> TabManager {
+        let mut tabs = TabManager::new();
+        for group_tabs in groups {
+            let mut group = tabs.new_group();
+            group.tabs = group_tabs.iter().map(|index| TabId::new(*index, 0)).collect();
+            tabs.groups.push(group);
+        }
+        tabs
+    }
+
+    fn group_tabs(tabs: &TabManager) -> Vec<Vec<u32>> {
+        tabs.groups.iter().map(|group| group.tabs.iter().map(|id| id.index).collect()).collect()
+    }
+
+    #[test]
+    fn move_tab_between_tabs_in_group() {
+        // Drop the first tab between the second and third one.
+        let mut tabs = tab_manager(&[&[0, 1, 2, 3]]);
+        assert!(tabs.move_tab_id(TabId::new(0, 0), Some(1), Some(2)));
+        assert_eq!(group_tabs(&tabs), vec![vec![1, 0, 2, 3]]);
+
+        // Drop the last tab between the first and second one.
+        assert!(tabs.move_tab_id(TabId::new(3, 0), Some(1), Some(1)));
+        assert_eq!(group_tabs(&tabs), vec![vec![1, 3, 0, 2]]);
+    }
+
+    #[test]
+    fn move_tab_between_tabs_of_other_group() {
+        let mut tabs = tab_manager(&[&[0, 1], &[2, 3]]);
+        assert!(tabs.move_tab_id(TabId::new(0, 0), Some(2), Some(1)));
+        assert_eq!(group_tabs(&tabs), vec![vec![1], vec![2, 0, 3]]);
+
+        // The emptied origin group is removed, shifting the target group's id.
+        let mut tabs = tab_manager(&[&[0], &[1, 2]]);
+        assert!(tabs.move_tab_id(TabId::new(0, 0), Some(2), Some(1)));
+        assert_eq!(group_tabs(&tabs), vec![vec![1, 0, 2]]);
+    }
+
+    #[test]
+    fn place_duplicate_after_source() {
+        // New tabs are appended to the group of the source tab.
+        let mut tabs = tab_manager(&[&[0, 1, 2, 4], &[3]]);
+        assert!(tabs.place_after(TabId::new(4, 0), TabId::new(0, 0)));
+        assert_eq!(group_tabs(&tabs), vec![vec![0, 4, 1, 2], vec![3]]);
+
+        // Duplicating the last tab keeps it in place.
+        let mut tabs = tab_manager(&[&[0], &[1, 2]]);
+        tabs.groups[1].tabs.push(TabId::new(3, 0));
+        assert!(tabs.place_after(TabId::new(3, 0), TabId::new(2, 0)));
+        assert_eq!(group_tabs(&tabs), vec![vec![0], vec![1, 2, 3]]);
+
+        // Tabs created in another group are moved next to the source.
+        let mut tabs = tab_manager(&[&[0, 1], &[2], &[3]]);
+        assert!(tabs.place_after(TabId::new(3, 0), TabId::new(2, 0)));
+        assert_eq!(group_tabs(&tabs), vec![vec![0, 1], vec![2, 3]]);
+
+        assert!(!tabs.place_after(TabId::new(3, 0), TabId::new(9, 0)));
+    }
+
+    #[test]
+    fn new_tab_index_policies() {
+        let tabs = [TabId::new(0, 0), TabId::new(1, 0), TabId::new(2, 0), TabId::new(3, 0)];
+        let current = Some(tabs[1]);
+        assert_eq!(new_tab_index(NewTabPosition::End, &tabs, current), 4);
+        assert_eq!(new_tab_index(NewTabPosition::AfterCurrent, &tabs, current), 2);
+        assert_eq!(new_tab_index(NewTabPosition::Start, &tabs, current), 0);
+
+        // Current tabs of another group don't affect the placement.
+        let other = Some(TabId::new(7, 0));
+        assert_eq!(new_tab_index(NewTabPosition::AfterCurrent, &tabs, other), 4);
+        assert_eq!(new_tab_index(NewTabPosition::AfterCurrent, &tabs, None), 4);
+    }
+
+    #[test]
+    fn place_new_tab_in_group() {
+        let current = Some(TabId::new(1, 0));
+        let new_tab = TabId::new(4, 0);
+
+        let mut tabs = tab_manager(&[&[0, 1, 2, 4], &[3]]);
+        assert!(!tabs.place_new_tab(new_tab, current));
+        assert_eq!(group_tabs(&tabs), vec![vec![0, 1, 2, 4], vec![3]]);
+
+        let mut tabs = tab_manager(&[&[0, 1, 2, 4], &[3]]);
+        tabs.new_tab_position = NewTabPosition::AfterCurrent;
+        assert!(tabs.place_new_tab(new_tab, current));
+        assert_eq!(group_tabs(&tabs), vec![vec![0, 1, 4, 2], vec![3]]);
+
+        let mut tabs = tab_manager(&[&[0, 1, 2, 4], &[3]]);
+        tabs.new_tab_position = NewTabPosition::Start;
+        assert!(tabs.place_new_tab(new_tab, current));
+        assert_eq!(group_tabs(&tabs), vec![vec![4, 0, 1, 2], vec![3]]);
+
+        // Tabs created in another group stay at its end.
+        let mut tabs = tab_manager(&[&[0, 1, 2], &[3, 4]]);
+        tabs.new_tab_position = NewTabPosition::AfterCurrent;
+        assert!(!tabs.place_new_tab(new_tab, current));
+        assert_eq!(group_tabs(&tabs), vec![vec![0, 1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn adjacent_tab_wraps_at_edges() {
+        let tabs = [TabId::new(0, 0), TabId::new(1, 0), TabId::new(2, 0)];
+        assert_eq!(adjacent_tab(&tabs, tabs[0], true, true), Some(tabs[1]));
+        assert_eq!(adjacent_tab(&tabs, tabs[2], true, true), Some(tabs[0]));
+        assert_eq!(adjacent_tab(&tabs, tabs[0], false, true), Some(tabs[2]));
+        assert_eq!(adjacent_tab(&tabs, tabs[1], false, true), Some(tabs[0]));
+        assert_eq!(adjacent_tab(&tabs, TabId::new(3, 0), true, true), None);
+    }
+
+    #[test]
+    fn adjacent_tab_stops_at_edges() {
+        let tabs = [TabId::new(0, 0), TabId::new(1, 0), TabId::new(2, 0)];
+        assert_eq!(adjacent_tab(&tabs, tabs[1], true, false), Some(tabs[2]));
+        assert_eq!(adjacent_tab(&tabs, tabs[2], true, false), Some(tabs[2]));
+        assert_eq!(adjacent_tab(&tabs, tabs[1], false, false), Some(tabs[0]));
+        assert_eq!(adjacent_tab(&tabs, tabs[0], false, false), Some(tabs[0]));
+    }
+
+    #[test]
+    fn group_focus_restores_last_active_tab() {
+        let mut tabs = tab_manager(&[&[0, 1, 2], &[3, 4]]);
+        assert_eq!(tabs.group_focus_target(1), Some(TabId::new(0, 0)));
+
+        // Focus away from the first group and back again.
+        tabs.activate(TabId::new(1, 0));
+        tabs.activate(TabId::new(4, 0));
+        assert_eq!(tabs.group_focus_target(1), Some(TabId::new(1, 0)));
+        assert_eq!(tabs.group_focus_target(2), Some(TabId::new(4, 0)));
+
+        // Moving the active tab makes it the focus target of its new group.
+        assert!(tabs.move_tab_id(TabId::new(4, 0), Some(1), Some(0)));
+        assert_eq!(tabs.group_focus_target(1), Some(TabId::new(4, 0)));
+        assert_eq!(tabs.group_focus_target(2), Some(TabId::new(3, 0)));
+        assert_eq!(tabs.group_focus_target(3), None);
+    }
+
+    #[test]
+    fn focus_clears_unseen_output() {
+        let mut tabs = tab_manager(&[&[0, 1, 2], &[3]]);
+        let (focused, background) = (TabId::new(1, 0), TabId::new(3, 0));
+        tabs.activate(focused);
+
+        let now = Instant::now();
+        tabs.note_output(focused, now, true);
+        tabs.note_output(background, now, false);
+        assert!(!tabs.activity(focused).has_unseen_output);
+        assert!(tabs.activity(background).has_unseen_output);
+
+        // Focusing the tab marks its output as seen and makes it its group's focus target.
+        tabs.activate(background);
+        let activity = tabs.activity(background);
+        assert!(!activity.has_unseen_output);
+        assert_eq!(activity.last_output, Some(now));
+        assert_eq!(tabs.group_focus_target(2), Some(background));
+
+        // Output of tabs in the background is unseen until they are focused again.
+        tabs.note_output(focused, now, false);
+        assert!(tabs.activity(focused).has_unseen_output);
+        tabs.activate(focused);
+        assert!(!tabs.activity(focused).has_unseen_output);
+        assert!(tabs.has_active_output(now));
+    }
+
+    #[test]
+    fn remove_empty_group() {
+        let mut tabs = tab_manager(&[&[0], &[1]]);
+        let group_id = tabs.create_group(None);
+        assert_eq!(group_id, 3);
+
+        // Only groups without tabs are removed.
+        tabs.remove_empty_group(1);
+        tabs.remove_empty_group(group_id);
+        assert_eq!(group_tabs(&tabs), vec![vec![0], vec![1]]);
+        assert_eq!(tabs.create_group(None), 3);
+    }
+
+    #[test]
+    fn attach_background_tab() {
+        let mut tabs = tab_manager(&[&[0, 1], &[2, 3]]);
+        tabs.activate(TabId::new(3, 0));
+        tabs.activate(TabId::new(1, 0));
+
+        // Background tabs neither take focus nor become the last focused tab of their group.
+        tabs.attach(TabId::new(4, 0), Some(2), None).unwrap();
+        assert_eq!(tabs.active_id(), Some(TabId::new(1, 0)));
+        assert_eq!(tabs.group_focus_target(2), Some(TabId::new(3, 0)));
+        assert_eq!(group_tabs(&tabs), vec![vec![0, 1], vec![2, 3, 4]]);
+
+        // Tabs without a group join the group of the active tab.
+        tabs.attach(TabId::new(5, 0), None, None).unwrap();
+        assert_eq!(group_tabs(&tabs), vec![vec![0, 1, 5], vec![2, 3, 4]]);
+        assert!(tabs.attach(TabId::new(6, 0), Some(9), None).is_err());
+
+        // The first tab of a window is always focused.
+        let mut tabs = TabManager::new();
+        tabs.attach(TabId::new(0, 0), None, None).unwrap();
+        assert_eq!(tabs.active_id(), Some(TabId::new(0, 0)));
+    }
+
+    #[test]
+    fn group_focus_after_closing_last_active_tab() {
+        let mut tabs = tab_manager(&[&[0, 1, 2], &[3]]);
+        tabs.activate(TabId::new(2, 0));
+        tabs.activate(TabId::new(3, 0));
+
+        // Fall back to the group's first tab.
+        tabs.detach(TabId::new(2, 0));
+        assert_eq!(tabs.group_focus_target(1), Some(TabId::new(0, 0)));
+
+        tabs.detach(TabId::new(0, 0));
+        assert_eq!(tabs.group_focus_target(1), Some(TabId::new(1, 0)));
+    }
+
+    #[test]
+    fn custom_title_pins_tab_title() {
+        let mut title = TabTitle::new(String::from("zsh"));
+        assert!(title.set_custom(Some(String::from("Build"))));
+
+        // Titles reported by the shell are kept, but the custom title stays current.
+        assert!(title.set_reported(String::from("vim")));
+        assert_eq!(title.reported, "vim");
+        assert_eq!(title.current(), "Build");
+
+        // Clearing the custom title brings back the automatic one.
+        assert!(title.set_custom(None));
+        assert_eq!(title.current(), "vim");
+        assert!(!title.set_custom(None));
+        assert!(!title.set_reported(String::from("vim")));
+    }
+
+    fn closed_tab(title: &str, before: &[u32], after: &[u32]) -> ClosedTab {
+        let tab_ids = |indices: &[u32]| indices.iter().map(|index| TabId::new(*index, 0)).collect();
+        ClosedTab {
+            kind: WindowKind::Terminal,
+            title: title.into(),
+            custom_title: None,
+            working_directory: None,
+            before: tab_ids(before),
+            after: tab_ids(after),
+        }
+    }
+
+    #[test]
+    fn closed_tabs_bounded() {
+        let mut closed_tabs = ClosedTabs::default();
+        for title in ["a", "b", "c"] {
+            closed_tabs.push(closed_tab(title, &[], &[]), 2);
+        }
+        let titles: Vec<_> = closed_tabs.iter().map(|tab| tab.title.as_str()).collect();
+        assert_eq!(titles, ["c", "b"]);
+
+        closed_tabs.truncate(1);
+        assert_eq!(closed_tabs.pop().map(|tab| tab.title), Some(String::from("c")));
+        assert!(closed_tabs.pop().is_none());
+
+        closed_tabs.push(closed_tab("d", &[], &[]), 0);
+        assert!(closed_tabs.pop().is_none());
+    }
+
+    #[test]
+    fn closed_tab_rejoins_neighbors() {
+        // Tab 2 was closed from between tabs 1 and 3 of the second group.
+        let closed = closed_tab("closed", &[1, 0], &[3]);
+        let mut tabs = tab_manager(&[&[4], &[0, 1, 3]]);
+        assert_eq!(tabs.closed_tab_position(&closed), Some((2, 2)));
+
+        // Fall back to the next neighbors once the closest ones are gone.
+        tabs.detach(TabId::new(1, 0));
+        assert_eq!(tabs.closed_tab_position(&closed), Some((2, 1)));
+        tabs.detach(TabId::new(0, 0));
+        assert_eq!(tabs.closed_tab_position(&closed), Some((2, 0)));
+
+        // Neighbors moved to another group take the tab with them.
+        tabs.move_tab_id(TabId::new(3, 0), Some(1), None);
+        assert_eq!(tabs.closed_tab_position(&closed), Some((1, 1)));
+
+        // Without any neighbor left, the group no longer exists.
+        tabs.detach(TabId::new(3, 0));
+        assert_eq!(tabs.closed_tab_position(&closed), None);
+    }
+
+    #[test]
+    fn group_defaults_precedence() {
+        let mut tabs = tab_manager(&[&[0], &[1]]);
+        tabs.active = Some(TabId::new(1, 0));
+        let homepage = String::from("https://example.com/");
+        assert!(tabs.set_group_defaults(2, Some(PathBuf::from("/srv")), Some(homepage.clone())));
+        assert!(!tabs.set_group_defaults(3, None, None));
+
+        let web = |url: &str| {
+            let mut options = WindowOptions::default();
+            options.window_kind = WindowKind::Web { url: url.into(), private: false };
+            options
+        };
+        let web_url = |options: WindowOptions| match options.window_kind {
+            WindowKind::Web { url, .. } => url,
+            WindowKind::Terminal => unreachable!(),
+        };
+
+        // Explicit working directories take precedence over the group default.
+        let mut options = WindowOptions::default();
+        options.terminal_options.working_directory = Some(PathBuf::from("/tmp"));
+        tabs.apply_group_defaults(&mut options, None, None);
+        assert_eq!(options.terminal_options.working_directory, Some(PathBuf::from("/tmp")));
+
+        // Tabs of the active group start in its default directory.
+        let mut options = WindowOptions::default();
+        tabs.apply_group_defaults(&mut options, None, None);
+        assert_eq!(options.terminal_options.working_directory, Some(PathBuf::from("/srv")));
+
+        // Groups without defaults leave the global fallback in place.
+        let mut options = WindowOptions::default();
+        tabs.apply_group_defaults(&mut options, Some(1), None);
+        assert_eq!(options.terminal_options.working_directory, None);
+
+        // Only blank web tabs load the homepage.
+        let mut options = web("about:blank");
+        tabs.apply_group_defaults(&mut options, Some(2), None);
+        assert_eq!(web_url(options), homepage);
+        let mut options = web("https://tabor.dev/");
+        tabs.apply_group_defaults(&mut options, Some(2), None);
+        assert_eq!(web_url(options), "https://tabor.dev/");
+
+        // Tabs opening the omnibar stay blank.
+        let mut options = web("");
+        options.command_input = Some(String::new());
+        tabs.apply_group_defaults(&mut options, Some(2), None);
+        assert_eq!(web_url(options), "");
+    }
+
+    #[test]
+    fn terminal_text_lines_trim_cells() {
+        let size = TermSize::new(5, 2);
+        let mut term = Term::new(Default::default(), &size, VoidListener);
+        for (column, c) in "ab".chars().enumerate() {
+            term.grid_mut()[Line(0)][Column(column)].c = c;
+        }
+        term.grid_mut()[Line(1)][Column(2)].c = 'c';
+
+        assert_eq!(terminal_text_lines(&term, false), vec!["ab", "  c"]);
+
+        term.grid_mut().scroll_up(&(Line(0)..Line(2)), 1);
+        term.grid_mut()[Line(1)][Column(0)].c = 'd';
+        assert_eq!(terminal_text_lines(&term, false), vec!["  c", "d"]);
+        assert_eq!(terminal_text_lines(&term, true), vec!["ab", "  c", "d"]);
+    }
 }
@@ -4,7 +4,12 @@ use serde::{Deserialize, Serialize};
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum WindowKind {
     Terminal,
-    Web { url: String },
+    Web {
+        url: String,
+        /// Use a non-persistent data store, keeping cookies and storage out of other tabs.
+        #[serde(default)]
+        private: bool,
+    },
 }
 
 impl Default for WindowKind {
@@ -17,29 +22,34 @@ impl WindowKind {
     pub fn is_web(&self) -> bool {
         matches!(self, Self::Web { .. })
     }
+
+    pub fn is_private(&self) -> bool {
+        matches!(self, Self::Web { private: true, .. })
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TabKind {
     Terminal,
-    Web { url: String },
+    Web { url: String, private: bool },
 }
 
 impl From<&WindowKind> for TabKind {
     fn from(kind: &WindowKind) -> Self {
         match kind {
             WindowKind::Terminal => Self::Terminal,
-            WindowKind::Web { url } => Self::Web { url: url.clone() },
+            WindowKind::Web { url, private } => Self::Web { url: url.clone(), private: *private },
         }
     }
 }
 
 impl TabKind {
+    /// Glyph shown in front of tab titles which have no favicon.
     #[allow(dead_code)]
-    pub fn indicator(&self) -> &'static str {
+    pub fn glyph(&self) -> char {
         match self {
-            Self::Terminal => "T",
-            Self::Web { .. } => "W",
+            Self::Terminal => '>',
+            Self::Web { .. } => '@',
         }
     }
 }
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::fmt::{self, Debug, Formatter};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::term::ClipboardType;
@@ -20,6 +21,9 @@ pub enum Event {
     /// Reset to the default window title.
     ResetTitle,
 
+    /// Working directory reported by the shell through OSC 7.
+    WorkingDirectory(PathBuf),
+
     /// Request to store a text string in the clipboard.
     ClipboardStore(ClipboardType, String),
 
@@ -69,6 +73,7 @@ impl Debug for Event {
             Event::CursorBlinkingChange => write!(f, "CursorBlinkingChange"),
             Event::MouseCursorDirty => write!(f, "MouseCursorDirty"),
             Event::ResetTitle => write!(f, "ResetTitle"),
+            Event::WorkingDirectory(cwd) => write!(f, "WorkingDirectory({})", cwd.display()),
             Event::Wakeup => write!(f, "Wakeup"),
             Event::Bell => write!(f, "Bell"),
             Event::Exit => write!(f, "Exit"),
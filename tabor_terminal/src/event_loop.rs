@@ -15,6 +15,7 @@ use log::error;
 use polling::{Event as PollingEvent, Events, PollMode};
 
 use crate::event::{self, Event, EventListener, WindowSize};
+use crate::osc7::Osc7Scanner;
 use crate::sync::FairMutex;
 use crate::term::Term;
 use crate::{thread, tty};
@@ -152,6 +153,9 @@ where
 
             // Parse the incoming bytes.
             state.parser.advance(&mut **terminal, &buf[..unprocessed]);
+            if let Some(cwd) = state.osc7.advance(&buf[..unprocessed]) {
+                self.event_proxy.send_event(Event::WorkingDirectory(cwd));
+            }
 
             processed += unprocessed;
             unprocessed = 0;
@@ -401,6 +405,7 @@ pub struct State {
     write_list: VecDeque<Cow<'static, [u8]>>,
     writing: Option<Writing>,
     parser: ansi::Processor,
+    osc7: Osc7Scanner,
 }
 
 impl State {
@@ -8,6 +8,7 @@ pub mod event;
 pub mod event_loop;
 pub mod grid;
 pub mod index;
+mod osc7;
 pub mod selection;
 pub mod sync;
 pub mod term;
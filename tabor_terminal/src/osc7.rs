@@ -0,0 +1,174 @@
+//! Working directory reports of the OSC 7 escape.
+//!
+//! The VTE parser ignores OSC 7, so PTY output is scanned for it separately.
+
+use std::path::PathBuf;
+
+/// Longest OSC 7 URL which is still parsed.
+const MAX_URL_LEN: usize = 4096;
+
+const ESC: u8 = 0x1b;
+const BEL: u8 = 0x07;
+const CAN: u8 = 0x18;
+const SUB: u8 = 0x1a;
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+enum ScanState {
+    #[default]
+    Ground,
+    Escape,
+    OscStart,
+    OscSeven,
+    Url,
+    UrlEscape,
+}
+
+/// Scanner for OSC 7 sequences split across any number of PTY reads.
+#[derive(Default, Debug)]
+pub struct Osc7Scanner {
+    state: ScanState,
+    url: Vec<u8>,
+}
+
+impl Osc7Scanner {
+    /// Scan PTY output, returning the last working directory reported in it.
+    pub fn advance(&mut self, bytes: &[u8]) -> Option<PathBuf> {
+        let mut cwd = None;
+        let mut bytes = bytes;
+
+        while !bytes.is_empty() {
+            // Skip to the next escape, since nothing else can start a sequence.
+            if self.state == ScanState::Ground {
+                match bytes.iter().position(|byte| *byte == ESC) {
+                    Some(start) => bytes = &bytes[start..],
+                    None => break,
+                }
+            }
+
+            if let Some(path) = self.advance_byte(bytes[0]) {
+                cwd = Some(path);
+            }
+            bytes = &bytes[1..];
+        }
+
+        cwd
+    }
+
+    fn advance_byte(&mut self, byte: u8) -> Option<PathBuf> {
+        self.state = match (self.state, byte) {
+            (ScanState::Url, BEL) | (ScanState::UrlEscape, b'\\') => {
+                self.state = ScanState::Ground;
+                let url = String::from_utf8_lossy(&self.url);
+                return parse_osc7(&url);
+            },
+            (ScanState::Url, ESC) => ScanState::UrlEscape,
+            (ScanState::Url, CAN | SUB) => ScanState::Ground,
+            (ScanState::Url, _) if self.url.len() < MAX_URL_LEN => {
+                self.url.push(byte);
+                ScanState::Url
+            },
+            (ScanState::Url, _) => ScanState::Ground,
+            (_, ESC) => ScanState::Escape,
+            (ScanState::Escape | ScanState::UrlEscape, b']') => ScanState::OscStart,
+            (ScanState::OscStart, b'7') => ScanState::OscSeven,
+            (ScanState::OscSeven, b';') => {
+                self.url.clear();
+                ScanState::Url
+            },
+            _ => ScanState::Ground,
+        };
+
+        None
+    }
+}
+
+/// Local path of an OSC 7 `file://host/path` URL.
+///
+/// The host is not checked, so directories of remote shells are reported as they are.
+pub fn parse_osc7(url: &str) -> Option<PathBuf> {
+    let rest = url.strip_prefix("file://")?;
+    let path = &rest[rest.find('/')?..];
+    let path = percent_decode(path);
+
+    #[cfg(unix)]
+    {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        Some(PathBuf::from(OsString::from_vec(path)))
+    }
+
+    #[cfg(not(unix))]
+    Some(PathBuf::from(String::from_utf8_lossy(&path).into_owned()))
+}
+
+fn percent_decode(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            },
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            },
+        }
+    }
+
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_file_urls() {
+        assert_eq!(parse_osc7("file://host/home/user"), Some(PathBuf::from("/home/user")));
+        assert_eq!(parse_osc7("file:///tmp"), Some(PathBuf::from("/tmp")));
+        assert_eq!(parse_osc7("file://localhost/"), Some(PathBuf::from("/")));
+        assert_eq!(parse_osc7("file://host/my%20dir/%zz"), Some(PathBuf::from("/my dir/%zz")));
+        assert_eq!(parse_osc7("file://host"), None);
+        assert_eq!(parse_osc7("https://host/path"), None);
+    }
+
+    #[test]
+    fn scan_terminators() {
+        let mut scanner = Osc7Scanner::default();
+        let cwd = scanner.advance(b"ls\x1b]7;file://host/tmp\x07\r\n");
+        assert_eq!(cwd, Some(PathBuf::from("/tmp")));
+
+        let cwd = scanner.advance(b"\x1b]7;file://host/a\x1b\\\x1b]7;file://host/b\x1b\\");
+        assert_eq!(cwd, Some(PathBuf::from("/b")));
+    }
+
+    #[test]
+    fn scan_across_reads() {
+        let mut scanner = Osc7Scanner::default();
+        assert_eq!(scanner.advance(b"\x1b]7;file://ho"), None);
+        assert_eq!(scanner.advance(b"st/sr"), None);
+        assert_eq!(scanner.advance(b"c\x1b"), None);
+        assert_eq!(scanner.advance(b"\\"), Some(PathBuf::from("/src")));
+    }
+
+    #[test]
+    fn scan_ignores_other_sequences() {
+        let mut scanner = Osc7Scanner::default();
+        assert_eq!(scanner.advance(b"\x1b]0;file://host/title\x07"), None);
+        assert_eq!(scanner.advance(b"\x1b]77;file://host/tmp\x07"), None);
+        assert_eq!(scanner.advance(b"\x1b]7;file://host/tmp\x18\x07"), None);
+
+        // An escape inside the URL starts a new sequence.
+        let cwd = scanner.advance(b"\x1b]7;file://host/a\x1b]7;file://host/b\x07");
+        assert_eq!(cwd, Some(PathBuf::from("/b")));
+    }
+}